@@ -0,0 +1,109 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ logger: { error: vi.fn() } }));
+
+const mockGet = vi.fn();
+vi.mock('axios', () => ({
+    default: { create: vi.fn(() => ({ get: mockGet, post: vi.fn() })) },
+}));
+
+import { validateDeployment } from '../../src/accounts/doctor';
+
+function runtime(settings: Record<string, string>, accounts?: Record<string, unknown>) {
+    return {
+        getSetting: (key: string) => settings[key] ?? null,
+        character: { settings: accounts ? { whatsapp: { accounts } } : {} },
+    } as any;
+}
+
+describe('validateDeployment', () => {
+    beforeEach(() => {
+        mockGet.mockReset();
+    });
+
+    it('should pass a complete Cloud API account', async () => {
+        const report = await validateDeployment(runtime({
+            WHATSAPP_ACCESS_TOKEN: 'token',
+            WHATSAPP_PHONE_NUMBER_ID: '106540352242922',
+            WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'verify',
+        }));
+
+        expect(report).toEqual({ ok: true, accounts: ['default'], issues: [] });
+    });
+
+    it('should report every problem with the account it belongs to', async () => {
+        const report = await validateDeployment(runtime(
+            { WHATSAPP_GROUPS: JSON.stringify({ '*': {}, 'Family chat': {} }) },
+            {
+                sales: { accessToken: 'a', phoneNumberId: '+1 650 555 1234', webhookVerifyToken: 'v' },
+                support: { accessToken: 'b', phoneNumberId: '222', webhookVerifyToken: 'v' },
+                billing: { accessToken: 'c', phoneNumberId: '222' },
+                draft: { phoneNumberId: '333' },
+            }
+        ));
+
+        expect(report.ok).toBe(false);
+        const codes = report.issues.map((i) => `${i.accountId}:${i.code}`);
+        expect(codes).toEqual(expect.arrayContaining([
+            'sales:INVALID_PHONE_NUMBER_ID',
+            'billing:DUPLICATE_PHONE_NUMBER_ID',
+            'billing:MISSING_WEBHOOK_TOKEN',
+            'draft:MISSING_ACCESS_TOKEN',
+            'support:UNKNOWN_GROUP',
+        ]));
+        expect(codes).not.toContain('support:DUPLICATE_PHONE_NUMBER_ID');
+        expect(report.issues.find((i) => i.code === 'MISSING_WEBHOOK_TOKEN').severity).toBe('warning');
+    });
+
+    it('should report fallback accounts that are unknown or the account itself', async () => {
+        const report = await validateDeployment(runtime({}, {
+            sales: { accessToken: 'a', phoneNumberId: '111', webhookVerifyToken: 'v', fallbackAccount: 'support' },
+            support: { accessToken: 'b', phoneNumberId: '222', webhookVerifyToken: 'v', fallbackAccount: 'support' },
+            billing: { accessToken: 'c', phoneNumberId: '333', webhookVerifyToken: 'v', fallbackAccount: 'missing' },
+        }));
+
+        const codes = report.issues.map((i) => `${i.accountId}:${i.code}`);
+        expect(codes).toEqual(['billing:UNKNOWN_FALLBACK_ACCOUNT', 'support:UNKNOWN_FALLBACK_ACCOUNT']);
+    });
+
+    it('should report a deployment without accounts', async () => {
+        const report = await validateDeployment(runtime({}));
+
+        expect(report.ok).toBe(false);
+        expect(report.issues).toEqual([
+            { severity: 'error', code: 'NO_ACCOUNTS', message: 'No WhatsApp account is configured' },
+        ]);
+    });
+
+    it('should report content policy patterns that are not regular expressions', async () => {
+        const report = await validateDeployment(runtime({
+            WHATSAPP_ACCESS_TOKEN: 'token',
+            WHATSAPP_PHONE_NUMBER_ID: '106540352242922',
+            WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'verify',
+            WHATSAPP_CONTENT_POLICY: JSON.stringify({ bannedPatterns: ['crypto\\w*', '(unclosed'] }),
+        }));
+
+        expect(report.ok).toBe(false);
+        expect(report.issues).toEqual([expect.objectContaining({ code: 'INVALID_POLICY_PATTERN', accountId: 'default' })]);
+    });
+
+    it('should check tokens against the Graph API when live', async () => {
+        mockGet.mockRejectedValue({
+            response: { status: 401, data: { error: { code: 190, message: 'Error validating access token' } } },
+        });
+
+        const report = await validateDeployment(runtime({
+            WHATSAPP_ACCESS_TOKEN: 'expired',
+            WHATSAPP_PHONE_NUMBER_ID: '106540352242922',
+            WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'verify',
+        }), { live: true });
+
+        expect(mockGet).toHaveBeenCalledWith('/106540352242922', expect.anything());
+        expect(report.issues).toEqual([{
+            severity: 'error',
+            code: 'INVALID_ACCESS_TOKEN',
+            message: 'Error validating access token',
+            accountId: 'default',
+        }]);
+    });
+});
@@ -0,0 +1,97 @@
+import { describe, it, expect, vi } from 'vitest';
+import { checkHealth, HealthMonitor } from '../../src/accounts/health';
+import { GraphApiError } from '../../src/errors';
+import { ManualClock } from '../../src/utils/clock';
+
+describe('checkHealth', () => {
+    it('should pass a connected number in good standing', async () => {
+        const phoneNumber = vi.fn().mockResolvedValue({ id: '1', quality_rating: 'GREEN', status: 'CONNECTED' });
+
+        expect(await checkHealth({ connection: 'open', phoneNumber })).toEqual([]);
+    });
+
+    it('should report a rejected token', async () => {
+        const phoneNumber = vi.fn().mockRejectedValue(
+            new GraphApiError('Error validating access token', { status: 401, code: 190 })
+        );
+
+        const problems = await checkHealth({ connection: 'open', phoneNumber });
+
+        expect(problems).toEqual([{ code: 'INVALID_ACCESS_TOKEN', message: 'Error validating access token' }]);
+    });
+
+    it('should report flagged and low quality numbers', async () => {
+        const phoneNumber = vi.fn().mockResolvedValue({ id: '1', quality_rating: 'RED', status: 'FLAGGED' });
+
+        const problems = await checkHealth({ connection: 'open', phoneNumber });
+
+        expect(problems.map((p) => p.code)).toEqual(['NUMBER_FLAGGED', 'LOW_QUALITY']);
+    });
+
+    it('should report webhooks that stopped arriving', async () => {
+        const phoneNumber = vi.fn().mockResolvedValue({ id: '1' });
+        const probe = { connection: 'open' as const, phoneNumber, lastWebhookAt: 0 };
+
+        expect(await checkHealth(probe, { webhookSilenceMs: 60_000 }, 30_000)).toEqual([]);
+        expect(await checkHealth(probe, { webhookSilenceMs: 60_000 }, 120_000)).toEqual([
+            { code: 'WEBHOOK_SILENT', message: 'No webhook received for 2 minute(s)' },
+        ]);
+    });
+
+    it('should report a closed Baileys connection', async () => {
+        const problems = await checkHealth({ connection: 'close' }, { webhookSilenceMs: 1 });
+
+        expect(problems.map((p) => p.code)).toEqual(['DISCONNECTED']);
+    });
+});
+
+describe('HealthMonitor', () => {
+    it('should emit degraded on changes and recovered once healthy', async () => {
+        const problem = { code: 'LOW_QUALITY', message: 'The phone number quality rating is RED' };
+        const check = vi.fn()
+            .mockResolvedValueOnce([problem])
+            .mockResolvedValueOnce([problem])
+            .mockResolvedValueOnce([]);
+        const monitor = new HealthMonitor(check);
+        const degraded = vi.fn();
+        const recovered = vi.fn();
+        monitor.on('degraded', degraded);
+        monitor.on('recovered', recovered);
+
+        await monitor.run();
+        await monitor.run();
+        await monitor.run();
+
+        expect(degraded).toHaveBeenCalledTimes(1);
+        expect(degraded).toHaveBeenCalledWith([problem]);
+        expect(recovered).toHaveBeenCalledTimes(1);
+        expect(monitor.problems).toEqual([]);
+    });
+
+    it('should run the check on its interval until stopped', async () => {
+        vi.useFakeTimers();
+        const check = vi.fn().mockResolvedValue([]);
+        const monitor = new HealthMonitor(check, 1000);
+
+        monitor.start();
+        await vi.advanceTimersByTimeAsync(2000);
+        monitor.stop();
+        await vi.advanceTimersByTimeAsync(2000);
+
+        expect(check).toHaveBeenCalledTimes(2);
+        vi.useRealTimers();
+    });
+
+    it('should run on the injected clock', () => {
+        const clock = new ManualClock();
+        const check = vi.fn().mockResolvedValue([]);
+        const monitor = new HealthMonitor(check, 1000, clock);
+
+        monitor.start();
+        clock.advance(3000);
+        monitor.stop();
+        clock.advance(3000);
+
+        expect(check).toHaveBeenCalledTimes(3);
+    });
+});
@@ -0,0 +1,132 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ logger: { error: vi.fn(), warn: vi.fn() } }));
+
+import { logger } from '@elizaos/core';
+
+import {
+    listEnabledWhatsAppAccounts,
+    listWhatsAppAccountIds,
+    resolveWhatsAppAccount,
+} from '../../src/accounts/resolve';
+
+function runtime(settings: Record<string, string>, accounts?: Record<string, unknown>) {
+    return {
+        getSetting: (key: string) => settings[key] ?? null,
+        character: { settings: accounts ? { whatsapp: { accounts } } : {} },
+    } as any;
+}
+
+describe('WhatsApp account resolution', () => {
+    it('should build the default account from WHATSAPP_* settings', () => {
+        const rt = runtime({
+            WHATSAPP_ACCESS_TOKEN: 'token',
+            WHATSAPP_PHONE_NUMBER_ID: '111',
+            WHATSAPP_API_VERSION: 'v20.0',
+            WHATSAPP_SKILLS: 'REPLY, BLOCK_WHATSAPP_USER',
+        });
+
+        expect(listWhatsAppAccountIds(rt)).toEqual(['default']);
+        expect(resolveWhatsAppAccount(rt, 'default')).toMatchObject({
+            accountId: 'default',
+            enabled: true,
+            accessToken: 'token',
+            phoneNumberId: '111',
+            apiVersion: 'v20.0',
+            skills: ['REPLY', 'BLOCK_WHATSAPP_USER'],
+        });
+    });
+
+    it('should merge character accounts over shared policies', () => {
+        const rt = runtime(
+            { WHATSAPP_SKILLS: 'REPLY', WHATSAPP_RATE_LIMIT: '{"maxMessages":5,"windowMs":60000}' },
+            {
+                sales: { accessToken: 'sales-token', phoneNumberId: '222', skills: ['REPLY', 'TAG_WHATSAPP_CONTACT'] },
+                support: { authDir: './auth/support' },
+            }
+        );
+
+        const sales = resolveWhatsAppAccount(rt, 'sales');
+        expect(sales.skills).toEqual(['REPLY', 'TAG_WHATSAPP_CONTACT']);
+        expect(sales.rateLimit).toEqual({ maxMessages: 5, windowMs: 60000 });
+        expect(resolveWhatsAppAccount(rt, 'support').authDir).toBe('./auth/support');
+        expect(resolveWhatsAppAccount(rt, 'missing')).toBeNull();
+    });
+
+    it('should read accounts from WHATSAPP_ACCOUNTS with per-account variable overrides', () => {
+        const rt = runtime(
+            {
+                WHATSAPP_ACCOUNTS: JSON.stringify({
+                    sales: { phoneNumberId: '222', skills: ['REPLY'] },
+                    'support-eu': { authDir: './auth/support' },
+                }),
+                WHATSAPP_SALES_ACCESS_TOKEN: 'sales-token',
+                WHATSAPP_SUPPORT_EU_AUTH_DIR: './auth/support-eu',
+            },
+            { sales: { phoneNumberId: '333' } }
+        );
+
+        expect(listWhatsAppAccountIds(rt)).toEqual(['sales', 'support-eu']);
+        expect(resolveWhatsAppAccount(rt, 'sales')).toMatchObject({
+            accessToken: 'sales-token',
+            phoneNumberId: '333',
+            skills: ['REPLY'],
+        });
+        expect(resolveWhatsAppAccount(rt, 'support-eu').authDir).toBe('./auth/support-eu');
+    });
+
+    it('should accept the deprecated WHATSAPP_WEBHOOK_TOKEN name with a warning', () => {
+        const credentials = { WHATSAPP_ACCESS_TOKEN: 'token', WHATSAPP_PHONE_NUMBER_ID: '111' };
+
+        expect(resolveWhatsAppAccount(runtime({ ...credentials, WHATSAPP_WEBHOOK_TOKEN: 'old' }), 'default').webhookVerifyToken)
+            .toBe('old');
+        expect(logger.warn).toHaveBeenCalledWith(expect.stringContaining('WHATSAPP_WEBHOOK_TOKEN is deprecated'));
+
+        const both = runtime({ ...credentials, WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'new', WHATSAPP_WEBHOOK_TOKEN: 'old' });
+        expect(resolveWhatsAppAccount(both, 'default').webhookVerifyToken).toBe('new');
+
+        const sales = runtime({ WHATSAPP_SALES_WEBHOOK_TOKEN: 'sales-old' }, { sales: { authDir: './auth' } });
+        expect(resolveWhatsAppAccount(sales, 'sales').webhookVerifyToken).toBe('sales-old');
+    });
+
+    it('should fall back to the shared verify token and app secret for every account', () => {
+        const rt = runtime(
+            {
+                WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'shared-token',
+                WHATSAPP_APP_SECRET: 'shared-secret',
+                WHATSAPP_SUPPORT_APP_SECRET: 'support-secret',
+            },
+            {
+                sales: { accessToken: 'a', phoneNumberId: '222' },
+                support: { accessToken: 'b', phoneNumberId: '333', webhookVerifyToken: 'support-token' },
+            }
+        );
+
+        expect(resolveWhatsAppAccount(rt, 'sales')).toMatchObject({
+            webhookVerifyToken: 'shared-token',
+            appSecret: 'shared-secret',
+        });
+        expect(resolveWhatsAppAccount(rt, 'support')).toMatchObject({
+            webhookVerifyToken: 'support-token',
+            appSecret: 'support-secret',
+        });
+    });
+
+    it('should ignore a WHATSAPP_ACCOUNTS value that is not an object', () => {
+        expect(listWhatsAppAccountIds(runtime({ WHATSAPP_ACCOUNTS: '["sales"]' }))).toEqual([]);
+        expect(listWhatsAppAccountIds(runtime({ WHATSAPP_ACCOUNTS: '{not json' }))).toEqual([]);
+    });
+
+    it('should skip disabled accounts and accounts without credentials', () => {
+        const rt = runtime(
+            { WHATSAPP_AUTH_DIR: './auth' },
+            {
+                sales: { accessToken: 'sales-token', phoneNumberId: '222', enabled: false },
+                draft: { skills: ['REPLY'] },
+            }
+        );
+
+        expect(listWhatsAppAccountIds(rt)).toEqual(['default', 'draft', 'sales']);
+        expect(listEnabledWhatsAppAccounts(rt).map((a) => a.accountId)).toEqual(['default']);
+    });
+});
@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest';
+import { StickyRoutes } from '../../src/accounts/sticky';
+
+describe('StickyRoutes', () => {
+    it('should route a chat to the account it last used', () => {
+        const routes = new StickyRoutes();
+
+        routes.remember('+1 (555) 123-4567', 'sales');
+        routes.remember('15551234567@s.whatsapp.net', 'support');
+
+        expect(routes.get('15551234567')).toBe('support');
+        expect(routes.get('15559999999')).toBeUndefined();
+    });
+
+    it('should keep group chats apart from users', () => {
+        const routes = new StickyRoutes();
+
+        routes.remember('120363041234567890@g.us', 'support');
+
+        expect(routes.get('120363041234567890@g.us')).toBe('support');
+        expect(routes.size).toBe(1);
+    });
+
+    it('should forget the least recently used chats beyond the limit', () => {
+        const routes = new StickyRoutes(2);
+
+        routes.remember('15550000001', 'sales');
+        routes.remember('15550000002', 'sales');
+        routes.remember('15550000001', 'support');
+        routes.remember('15550000003', 'sales');
+
+        expect(routes.get('15550000002')).toBeUndefined();
+        expect(routes.get('15550000001')).toBe('support');
+        expect(routes.size).toBe(2);
+    });
+});
@@ -0,0 +1,59 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ logger: { error: vi.fn(), warn: vi.fn() } }));
+vi.mock('../../src/service', () => ({ WhatsAppConnectorService: { serviceType: 'whatsapp_connector' } }));
+
+import { actionAccount } from '../../src/actions/account';
+import { tagContactAction } from '../../src/actions/tag-contact';
+import { sendOtpAction } from '../../src/actions/send-otp';
+
+const runtime = {
+    getSetting: () => null,
+    character: {
+        settings: {
+            whatsapp: {
+                accounts: {
+                    sales: { accessToken: 'token', phoneNumberId: '222' },
+                    support: { authDir: './auth/support', enabled: false },
+                },
+            },
+        },
+    },
+} as any;
+const memory = { content: { source: 'whatsapp', whatsappAccount: 'support' } } as any;
+
+describe('actionAccount', () => {
+    it('should default to the account the message arrived on', () => {
+        expect(actionAccount(runtime, memory)).toEqual({ accountId: 'support' });
+    });
+
+    it('should send from a configured account named in the options', () => {
+        expect(actionAccount(runtime, memory, { accountId: 'sales' })).toEqual({ accountId: 'sales' });
+    });
+
+    it('should refuse accounts that are unknown or disabled', () => {
+        expect(actionAccount(runtime, memory, { accountId: 'marketing' })).toBe('No WhatsApp account named marketing');
+        expect(actionAccount(runtime, memory, { accountId: 'support' })).toBe('WhatsApp account support is disabled');
+    });
+});
+
+describe('account-aware actions', () => {
+    const services = () => ({ tagContact: vi.fn(async () => ['vip']), untagContact: vi.fn(async () => []), verifyOtp: vi.fn(async () => 'valid') });
+    const withService = (service: ReturnType<typeof services>) => ({ ...runtime, getService: () => service });
+
+    it('should tag contacts on the account the message arrived on', async () => {
+        const service = services();
+
+        await tagContactAction.handler(withService(service), { content: { ...memory.content, whatsappSender: '1234567890' } } as any, undefined, { tags: 'vip' });
+
+        expect(service.tagContact).toHaveBeenCalledWith('1234567890', ['vip'], 'support');
+    });
+
+    it('should verify codes on the named account', async () => {
+        const service = services();
+
+        await sendOtpAction.handler(withService(service), memory, undefined, { target: '1234567890', code: '123456', accountId: 'sales' });
+
+        expect(service.verifyOtp).toHaveBeenCalledWith('1234567890', '123456', 'sales');
+    });
+});
@@ -0,0 +1,30 @@
+import { describe, it, expect } from 'vitest';
+import { inboundRef } from '../../src/actions/inbound-ref';
+
+const memory = (content) => ({ content: { source: 'whatsapp', ...content } });
+
+describe('inboundRef', () => {
+    it('should identify a direct message by id and chat', () => {
+        expect(inboundRef(memory({
+            whatsappMessageId: 'wamid.1',
+            channelId: '1234567890',
+            whatsappSender: '1234567890',
+        }))).toEqual({ id: 'wamid.1', from: '1234567890' });
+    });
+
+    it('should include the participant for group messages', () => {
+        expect(inboundRef(memory({
+            whatsappMessageId: 'wamid.2',
+            channelId: '120363042000000000@g.us',
+            whatsappSender: '1234567890@s.whatsapp.net',
+        }))).toEqual({
+            id: 'wamid.2',
+            from: '120363042000000000@g.us',
+            participant: '1234567890@s.whatsapp.net',
+        });
+    });
+
+    it('should return undefined for memories without a WhatsApp message id', () => {
+        expect(inboundRef(memory({ channelId: '1234567890' }))).toBeUndefined();
+    });
+});
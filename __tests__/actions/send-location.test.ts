@@ -0,0 +1,26 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('../../src/service', () => ({ WhatsAppConnectorService: { serviceType: 'whatsapp_connector' } }));
+
+import { parseLocation } from '../../src/actions/send-location';
+
+describe('parseLocation', () => {
+    it('should accept numeric and string coordinates', () => {
+        expect(parseLocation({ latitude: 52.52, longitude: 13.405, name: 'Office' })).toEqual({
+            latitude: 52.52,
+            longitude: 13.405,
+            name: 'Office',
+        });
+        expect(parseLocation({ lat: '-33.86', lon: '151.2', address: '' })).toEqual({
+            latitude: -33.86,
+            longitude: 151.2,
+        });
+    });
+
+    it('should reject missing or out-of-range coordinates', () => {
+        expect(parseLocation({ latitude: 10 })).toBe('Latitude and longitude are required');
+        expect(parseLocation({ latitude: 'north', longitude: 5 })).toBe('Latitude and longitude are required');
+        expect(parseLocation({ latitude: 91, longitude: 0 })).toContain('outside -90..90');
+        expect(parseLocation({ latitude: 0, longitude: -180.5 })).toContain('outside -180..180');
+    });
+});
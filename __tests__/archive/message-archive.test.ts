@@ -0,0 +1,83 @@
+import { describe, it, expect } from 'vitest';
+import { InMemoryMessageArchive, RuntimeMessageArchive } from '../../src/archive/message-archive';
+
+const entry = (id, timestamp, chatId = '1234567890') => ({
+    id,
+    chatId,
+    direction: 'inbound',
+    sender: chatId,
+    type: 'text',
+    text: `message ${id}`,
+    timestamp,
+});
+
+describe('InMemoryMessageArchive', () => {
+    it('should return the most recent messages per chat, oldest first', async () => {
+        const archive = new InMemoryMessageArchive();
+        const now = Date.now();
+
+        await archive.append(entry('1', now - 3));
+        await archive.append(entry('2', now - 2));
+        await archive.append(entry('other', now - 2, '5550000000'));
+        await archive.append(entry('3', now - 1));
+
+        expect((await archive.recent('1234567890', 2)).map((m) => m.id)).toEqual(['2', '3']);
+    });
+
+    it('should apply age and size retention', async () => {
+        const archive = new InMemoryMessageArchive({ maxAgeMs: 1000, maxPerChat: 2 });
+        const now = Date.now();
+
+        await archive.append(entry('expired', now - 5000));
+        await archive.append(entry('1', now));
+        await archive.append(entry('2', now));
+        await archive.append(entry('3', now));
+
+        expect((await archive.recent('1234567890', 10)).map((m) => m.id)).toEqual(['2', '3']);
+    });
+});
+
+describe('RuntimeMessageArchive', () => {
+    // A runtime cache whose reads and writes each take a turn of the event loop
+    function runtime() {
+        const cache = new Map();
+        const tick = () => new Promise((resolve) => setTimeout(resolve, 0));
+        return {
+            cache,
+            getCache: async (key) => { await tick(); return cache.get(key); },
+            setCache: async (key, value) => { await tick(); cache.set(key, value); return true; },
+        };
+    }
+
+    it('should keep every message when appends to a chat overlap', async () => {
+        const archive = new RuntimeMessageArchive(runtime(), 'default');
+        const now = Date.now();
+
+        await Promise.all(['1', '2', '3'].map((id) => archive.append(entry(id, now))));
+
+        expect((await archive.recent('1234567890', 10)).map((m) => m.id)).toEqual(['1', '2', '3']);
+    });
+
+    it('should keep each account\'s history apart', async () => {
+        const shared = runtime();
+        const sales = new RuntimeMessageArchive(shared, 'sales');
+        const support = new RuntimeMessageArchive(shared, 'support');
+
+        await sales.append(entry('1', Date.now()));
+
+        expect(await support.recent('1234567890', 10)).toEqual([]);
+        expect([...shared.cache.keys()]).toEqual(['whatsapp-archive-sales-1234567890']);
+    });
+
+    it('should read history stored before it was kept per account', async () => {
+        const shared = runtime();
+        const now = Date.now();
+        shared.cache.set('whatsapp-archive-1234567890', [entry('old', now - 1)]);
+        const archive = new RuntimeMessageArchive(shared, 'sales');
+
+        expect((await archive.recent('1234567890', 10)).map((m) => m.id)).toEqual(['old']);
+        await archive.append(entry('new', now));
+
+        expect(shared.cache.get('whatsapp-archive-sales-1234567890').map((m) => m.id)).toEqual(['old', 'new']);
+    });
+});
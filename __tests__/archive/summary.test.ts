@@ -0,0 +1,104 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { IAgentRuntime } from '@elizaos/core';
+import { ConversationSummaries, RuntimeSummaryStore } from '../../src/archive/summary';
+import { WhatsAppConnectorService } from '../../src/service';
+import { WhatsAppPlugin } from '../../src/index';
+
+const turns = (count: number, from = 1) =>
+    Array.from({ length: count }, (_, i) => ({
+        id: `m${from + i}`,
+        chatId: '1234567890',
+        direction: 'inbound' as const,
+        sender: '1234567890',
+        type: 'text',
+        text: `message ${from + i}`,
+        timestamp: 1000 + from + i,
+    }));
+
+describe('ConversationSummaries', () => {
+    it('should show the last turns verbatim without a summarizer', async () => {
+        const summaries = new ConversationSummaries({ keep: 3 });
+
+        const context = await summaries.context('1234567890', turns(8));
+
+        expect(context.summary).toBeUndefined();
+        expect(context.recent.map((m) => m.id)).toEqual(['m6', 'm7', 'm8']);
+    });
+
+    it('should fold older turns into a rolling summary once the threshold is passed', async () => {
+        const summarizer = vi.fn(async ({ previous, messages }) =>
+            `${previous ? `${previous} + ` : ''}${messages[0].id}..${messages[messages.length - 1].id}`
+        );
+        const summaries = new ConversationSummaries({ threshold: 5, keep: 2 });
+        summaries.useSummarizer(summarizer);
+
+        const short = await summaries.context('1234567890', turns(5));
+        expect(short.summary).toBeUndefined();
+        expect(short.recent).toHaveLength(5);
+
+        const first = await summaries.context('1234567890', turns(6));
+        expect(first.summary).toMatchObject({ text: 'm1..m4', throughId: 'm4', turns: 4 });
+        expect(first.recent.map((m) => m.id)).toEqual(['m5', 'm6']);
+
+        // Until the threshold is passed again the summary is reused and new turns stay verbatim
+        const reused = await summaries.context('1234567890', turns(9));
+        expect(reused.summary?.text).toBe('m1..m4');
+        expect(reused.recent.map((m) => m.id)).toEqual(['m5', 'm6', 'm7', 'm8', 'm9']);
+        expect(summarizer).toHaveBeenCalledTimes(1);
+
+        const rolled = await summaries.context('1234567890', turns(10));
+        expect(rolled.summary).toMatchObject({ text: 'm1..m4 + m5..m8', turns: 8 });
+        expect(rolled.recent.map((m) => m.id)).toEqual(['m9', 'm10']);
+        expect(summarizer).toHaveBeenLastCalledWith(expect.objectContaining({ previous: 'm1..m4' }));
+    });
+
+    it('should share one summarizer call between concurrent requests', async () => {
+        const summarizer = vi.fn(async () => 'summary');
+        const summaries = new ConversationSummaries({ threshold: 3, keep: 1 });
+        summaries.useSummarizer(summarizer);
+
+        await Promise.all([summaries.context('1234567890', turns(4)), summaries.context('1234567890', turns(4))]);
+
+        expect(summarizer).toHaveBeenCalledTimes(1);
+    });
+});
+
+describe('RuntimeSummaryStore', () => {
+    it('should keep each account\'s summary apart and read ones stored before', async () => {
+        const cache = new Map<string, unknown>();
+        const runtime = {
+            getCache: async (key: string) => cache.get(key),
+            setCache: async (key: string, value: unknown) => { cache.set(key, value); return true; },
+        } as unknown as IAgentRuntime;
+        const summary = (text: string) => ({ text, throughId: 'm1', throughTimestamp: 1, turns: 1, updatedAt: 1 });
+        cache.set('whatsapp-summary-1234567890', summary('older'));
+        const sales = new RuntimeSummaryStore(runtime, 'sales');
+        const support = new RuntimeSummaryStore(runtime, 'support');
+
+        await sales.set('1234567890', summary('sales'));
+
+        expect((await sales.get('1234567890'))?.text).toBe('sales');
+        expect((await support.get('1234567890'))?.text).toBe('older');
+    });
+});
+
+describe('getConversationContext', () => {
+    it('should share one archive read between the providers of a turn', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123' });
+        const read = vi.spyOn(plugin, 'getRecentMessages').mockResolvedValue(turns(3));
+        const connector = new WhatsAppConnectorService({} as IAgentRuntime);
+        connector['accounts'].set('default', {
+            plugin,
+            config: { accountId: 'default', enabled: true, accessToken: 'token', phoneNumberId: '123' },
+            enabled: true,
+        });
+
+        const [summary, recent] = await Promise.all([
+            connector.getConversationContext('1234567890', 'default'),
+            connector.getConversationContext('1234567890', 'default'),
+        ]);
+
+        expect(read).toHaveBeenCalledTimes(1);
+        expect(summary).toEqual(recent);
+    });
+});
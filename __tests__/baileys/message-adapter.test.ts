@@ -0,0 +1,19 @@
+import { describe, it, expect } from 'vitest';
+import { MessageAdapter } from '../../src/baileys/message-adapter';
+
+describe('MessageAdapter message keys', () => {
+    it('should mark quotes and reactions on our own messages as fromMe', () => {
+        const adapter = new MessageAdapter();
+        adapter.rememberOwn('OWN1');
+
+        expect(adapter.sendOptions({ type: 'text', to: '1234567890', content: 'Yes', replyTo: 'OWN1' })?.quoted.key).toEqual({
+            remoteJid: '1234567890@s.whatsapp.net',
+            id: 'OWN1',
+            fromMe: true,
+        });
+        expect(adapter.sendOptions({ type: 'text', to: '1234567890', content: 'Yes', replyTo: 'THEIRS1' })?.quoted.key?.fromMe).toBe(false);
+        expect(adapter.toBaileys({ type: 'reaction', to: '1234567890', content: { messageId: 'OWN1', emoji: '👍' } })).toMatchObject({
+            react: { key: { id: 'OWN1', fromMe: true } },
+        });
+    });
+});
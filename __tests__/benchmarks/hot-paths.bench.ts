@@ -0,0 +1,58 @@
+import { bench, describe } from 'vitest';
+import { chunkText } from '../../src/utils/chunk';
+import { normalizeE164, normalizeWhatsAppTarget } from '../../src/utils/normalize';
+import { toCloudPayload } from '../../src/clients/cloud-payload';
+import { ButtonMessageBuilder } from '../../src/interactive/builders';
+import { buildTemplate } from '../../src/templates/builder';
+
+// Roughly what a verbose LLM reply looks like: paragraphs of prose, some long unbroken tokens
+const paragraph = `${'The quick brown fox jumps over the lazy dog. '.repeat(40)}\n\n`;
+const reply500k = `${paragraph.repeat(250)}${'x'.repeat(50_000)}`;
+const reply4k = paragraph.repeat(2);
+
+describe('chunkText', () => {
+    bench('4 KB reply', () => {
+        chunkText(reply4k);
+    });
+
+    bench('500 KB reply', () => {
+        chunkText(reply500k);
+    });
+});
+
+describe('normalization', () => {
+    bench('normalizeE164', () => {
+        normalizeE164('+1 (555) 123-4567');
+    });
+
+    bench('normalizeWhatsAppTarget, JID with device', () => {
+        normalizeWhatsAppTarget('15551234567:12@s.whatsapp.net');
+    });
+
+    bench('normalizeWhatsAppTarget, distinct targets', () => {
+        normalizeWhatsAppTarget(`1555${Math.floor(Math.random() * 1e7)}@s.whatsapp.net`);
+    });
+});
+
+describe('payload building', () => {
+    const buttons = new ButtonMessageBuilder('Pick one')
+        .button('yes', 'Yes')
+        .button('no', 'No')
+        .build();
+
+    bench('text', () => {
+        toCloudPayload({ type: 'text', to: '15551234567', content: 'Hello there' });
+    });
+
+    bench('interactive buttons', () => {
+        toCloudPayload({ type: 'interactive', to: '15551234567', content: buttons });
+    });
+
+    bench('template with parameters', () => {
+        toCloudPayload({
+            type: 'template',
+            to: '15551234567',
+            content: buildTemplate('order_update', 'en_US', { body: ['Ada', 'A1234'] }),
+        });
+    });
+});
@@ -0,0 +1,91 @@
+import { describe, it, expect } from 'vitest';
+import { ConversationCostTracker } from '../../src/billing';
+import type { MessageStatusUpdate } from '../../src/events';
+import { ManualClock } from '../../src/utils/clock';
+
+const DAY = 86_400_000;
+
+function status(messageId: string, overrides: Partial<MessageStatusUpdate> = {}): MessageStatusUpdate {
+    return {
+        messageId,
+        status: 'sent',
+        recipient: '15551234567',
+        timestamp: 1_700_000_000,
+        pricing: { billable: true, category: 'marketing', model: 'CBP' },
+        conversation: { id: 'CONV_1', origin: 'marketing' },
+        ...overrides,
+    };
+}
+
+describe('ConversationCostTracker', () => {
+    it('should count a conversation once across its statuses and messages', () => {
+        const tracker = new ConversationCostTracker({ rates: { marketing: 0.025 } }, new ManualClock(1_800_000_000_000));
+
+        tracker.record('default', status('wamid.1'));
+        tracker.record('default', status('wamid.1', { status: 'delivered' }));
+        tracker.record('default', status('wamid.2'));
+
+        expect(tracker.summarize().total).toEqual({ conversations: 1, billable: 1, cost: 0.025 });
+    });
+
+    it('should charge every message under per-message pricing', () => {
+        const tracker = new ConversationCostTracker({ rates: { utility: 0.004 } }, new ManualClock(1_800_000_000_000));
+        const pricing = { billable: true, category: 'utility', model: 'PMP' };
+
+        tracker.record('default', status('wamid.1', { pricing }));
+        tracker.record('default', status('wamid.1', { pricing, status: 'read' }));
+        tracker.record('default', status('wamid.2', { pricing }));
+
+        expect(tracker.summarize().byCategory.utility).toEqual({ conversations: 2, billable: 2, cost: 0.008 });
+    });
+
+    it('should break costs down by account and category and skip free conversations', () => {
+        const tracker = new ConversationCostTracker(
+            { currency: 'EUR', rates: { marketing: 0.05, service: 0.01 } },
+            new ManualClock(1_800_000_000_000)
+        );
+
+        tracker.record('sales', status('wamid.1'));
+        tracker.record('support', status('wamid.2', {
+            conversation: { id: 'CONV_2' },
+            pricing: { billable: false, category: 'service' },
+        }));
+        tracker.record('support', status('wamid.3'));
+        tracker.record('support', status('wamid.4', { pricing: undefined, conversation: undefined }));
+
+        const report = tracker.summarize();
+        expect(report.currency).toBe('EUR');
+        expect(report.byAccount.sales).toMatchObject({ conversations: 1, billable: 1, cost: 0.05 });
+        expect(report.byAccount.support).toMatchObject({
+            conversations: 2,
+            billable: 1,
+            byCategory: { service: { conversations: 1, billable: 0, cost: 0 } },
+        });
+        expect(report.total).toMatchObject({ conversations: 3, billable: 2 });
+    });
+
+    it('should only report charges within the range', () => {
+        const tracker = new ConversationCostTracker({}, new ManualClock(1_800_000_000_000));
+
+        tracker.record('default', status('wamid.1', { timestamp: 1_700_000_000 }));
+        tracker.record('default', status('wamid.2', { conversation: { id: 'CONV_2' }, timestamp: 1_700_100_000 }));
+
+        expect(tracker.summarize({ from: 1_700_050_000_000 }).total.conversations).toBe(1);
+        expect(tracker.summarize({ to: 1_700_050_000_000 }).total.conversations).toBe(1);
+        expect(tracker.summarize().total).toEqual({ conversations: 2, billable: 2, cost: 0 });
+    });
+
+    it('should forget charges past the retention period', () => {
+        const clock = new ManualClock(1_700_000_000_000);
+        const tracker = new ConversationCostTracker({}, clock, 30 * DAY);
+
+        tracker.record('default', status('wamid.1'));
+        clock.advance(31 * DAY);
+        tracker.record('default', status('wamid.2', {
+            conversation: { id: 'CONV_2' },
+            timestamp: clock.now() / 1000,
+        }));
+
+        expect(tracker.summarize().total.conversations).toBe(1);
+    });
+});
@@ -0,0 +1,96 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({
+    logger: { error: vi.fn() },
+    stringToUuid: (value: string) => `uuid(${value})`,
+    ContentType: { IMAGE: 'image', AUDIO: 'audio', VIDEO: 'video', DOCUMENT: 'document' },
+}));
+
+import { toMemory, toOutboundMessages } from '../../src/bridge';
+
+describe('bridge', () => {
+    const agentId = 'agent-1';
+
+    it('should map a direct message to the chat room and sender entity', () => {
+        const memory = toMemory(
+            { id: 'wamid.1', from: '1234567890', timestamp: 1700000000, type: 'text', content: 'Hi' },
+            { text: 'Hi' },
+            { agentId, accountId: 'default' }
+        );
+
+        expect(memory).toMatchObject({
+            id: 'uuid(whatsapp-msg-wamid.1)',
+            entityId: 'uuid(whatsapp-entity-1234567890)',
+            roomId: 'uuid(whatsapp-room-1234567890-agent-1)',
+            createdAt: 1700000000000,
+            content: {
+                text: 'Hi',
+                source: 'whatsapp',
+                channelId: '1234567890',
+                whatsappAccount: 'default',
+                whatsappMessageId: 'wamid.1',
+            },
+            metadata: { scope: 'private' },
+        });
+        expect(memory.content.attachments).toBeUndefined();
+    });
+
+    it('should scope rooms by account and attach media', () => {
+        const memory = toMemory(
+            {
+                id: 'wamid.2',
+                from: '120363@g.us',
+                participant: '1234567890@s.whatsapp.net',
+                timestamp: 0,
+                type: 'image',
+                content: 'Receipt',
+                media: { id: 'media-1', mimeType: 'image/jpeg', caption: 'Receipt' },
+            },
+            { text: 'Receipt' },
+            { agentId, accountId: 'sales', group: { id: '120363@g.us' } }
+        );
+
+        expect(memory.roomId).toBe('uuid(whatsapp-room-sales-120363@g.us-agent-1)');
+        expect(memory.entityId).toBe('uuid(whatsapp-entity-1234567890@s.whatsapp.net)');
+        expect(memory.content.attachments).toEqual([{
+            id: 'media-1',
+            url: '',
+            title: 'image',
+            source: 'whatsapp',
+            description: 'image/jpeg',
+            text: 'Receipt',
+            contentType: 'image',
+        }]);
+        expect(memory.metadata.scope).toBe('shared');
+    });
+
+    it('should use an inlined image as the attachment URL', () => {
+        const memory = toMemory(
+            {
+                id: 'wamid.3',
+                from: '1234567890',
+                timestamp: 0,
+                type: 'image',
+                content: '',
+                media: { id: 'media-2', mimeType: 'image/png' },
+            },
+            { text: '' },
+            { agentId, accountId: 'default', inlineMedia: 'data:image/png;base64,iVBORw0KGgo=' }
+        );
+
+        expect(memory.content.attachments).toMatchObject([
+            { id: 'media-2', url: 'data:image/png;base64,iVBORw0KGgo=', contentType: 'image' },
+        ]);
+    });
+
+    it('should split long responses into several sends', () => {
+        const messages = toOutboundMessages('1234567890', { text: 'one two three four five' }, 10);
+
+        expect(messages).toEqual([
+            { type: 'text', to: '1234567890', content: 'one two' },
+            { type: 'text', to: '1234567890', content: 'three four' },
+            { type: 'text', to: '1234567890', content: 'five' },
+        ]);
+        expect(toOutboundMessages('1234567890', {})).toEqual([]);
+    });
+});
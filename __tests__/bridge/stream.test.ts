@@ -0,0 +1,83 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ResponseStreamer, streamText } from '../../src/bridge/stream';
+import { ManualClock } from '../../src/utils/clock';
+
+async function* tokens(text: string) {
+    for (const word of text.split(/(?<= )/)) yield word;
+}
+
+describe('ResponseStreamer', () => {
+    it('should space messages out on the given clock', async () => {
+        const clock = new ManualClock(1_000);
+        const send = vi.fn().mockResolvedValue(undefined);
+        const streamer = new ResponseStreamer(send, { minChars: 5, minIntervalMs: 500 }, clock);
+
+        streamer.push('One. ');
+        streamer.push('Two. ');
+        await vi.waitFor(() => expect(send).toHaveBeenCalledTimes(1));
+        await Promise.resolve();
+        expect(send).toHaveBeenCalledTimes(1);
+
+        clock.advance(500);
+        await vi.waitFor(() => expect(send).toHaveBeenCalledTimes(2));
+    });
+
+    it('should flush on sentence boundaries once enough text has built up', async () => {
+        const send = vi.fn().mockResolvedValue(undefined);
+
+        await streamText(
+            tokens('First sentence here. Second sentence follows! And a trailing bit'),
+            send,
+            { minChars: 15, minIntervalMs: 0 }
+        );
+
+        expect(send.mock.calls.map(([text]) => text)).toEqual([
+            'First sentence here.',
+            'Second sentence follows!',
+            'And a trailing bit',
+        ]);
+    });
+
+    it('should never exceed the message limit', async () => {
+        const send = vi.fn().mockResolvedValue(undefined);
+
+        await streamText(tokens('aaaa bbbb cccc dddd eeee'), send, { limit: 10, minIntervalMs: 0 });
+
+        const sent = send.mock.calls.map(([text]) => text);
+        expect(sent.join(' ')).toBe('aaaa bbbb cccc dddd eeee');
+        expect(sent.every((text) => text.length <= 10)).toBe(true);
+    });
+
+    it('should send the placeholder first and space out sends', async () => {
+        vi.useFakeTimers();
+        const sent: Array<[string, number]> = [];
+        const streamer = new ResponseStreamer(
+            async (text) => { sent.push([text, Date.now()]); },
+            { placeholder: '…', minChars: 1, minIntervalMs: 1000 }
+        );
+
+        streamer.push('Done. ');
+        const done = streamer.end();
+        await vi.runAllTimersAsync();
+        await done;
+
+        expect(sent.map(([text]) => text)).toEqual(['…', 'Done.']);
+        expect(sent[1][1] - sent[0][1]).toBeGreaterThanOrEqual(1000);
+        vi.useRealTimers();
+    });
+
+    it('should end the last message with the closing, or send it alone when nothing is left', async () => {
+        const send = vi.fn().mockResolvedValue(undefined);
+
+        await streamText(tokens('First sentence here. And the rest'), send, { minChars: 15, minIntervalMs: 0, closing: 'Bot' });
+        await streamText(tokens('Only a sentence. '), send, { minChars: 1, minIntervalMs: 0, closing: 'Bot' });
+        await streamText(tokens(''), send, { closing: 'Bot' });
+
+        expect(send.mock.calls.map(([text]) => text)).toEqual([
+            'First sentence here.',
+            'And the rest\n\nBot',
+            'Only a sentence.',
+            'Bot',
+        ]);
+    });
+});
@@ -0,0 +1,36 @@
+import { describe, it, expect } from 'vitest';
+import { readFileSync } from 'fs';
+import { dirname, resolve } from 'path';
+import { chunkText, normalizeWhatsAppTarget, toCloudPayload } from '../../src/browser';
+
+// Runtime imports (type-only ones are erased) of a source file, resolved to paths where relative
+function runtimeImports(file: string): string[] {
+    const source = readFileSync(file, 'utf8');
+    const pattern = /^(?:import|export)\s+(?!type\b)[^;]*?from\s+['"]([^'"]+)['"]/gms;
+    const specifiers = [...source.matchAll(pattern)].map((m) => m[1]);
+    return specifiers.map((spec) => (spec.startsWith('.') ? `${resolve(dirname(file), spec)}.ts` : spec));
+}
+
+describe('browser entry point', () => {
+    it('should not reach Node built-ins or server dependencies', () => {
+        const seen = new Set<string>();
+        const external: string[] = [];
+        const queue = [resolve(__dirname, '../../src/browser/index.ts')];
+        while (queue.length > 0) {
+            const file = queue.pop() as string;
+            if (seen.has(file)) continue;
+            seen.add(file);
+            for (const target of runtimeImports(file)) {
+                if (target.startsWith('/')) queue.push(target);
+                else external.push(`${file}: ${target}`);
+            }
+        }
+        expect(external).toEqual([]);
+    });
+
+    it('should share the plugin rules', () => {
+        expect(normalizeWhatsAppTarget('+1 (555) 123-4567')?.id).toBe('15551234567');
+        expect(chunkText('a'.repeat(5000))).toHaveLength(2);
+        expect(toCloudPayload({ type: 'text', to: '15551234567', content: 'Hi' })).toMatchObject({ type: 'text' });
+    });
+});
@@ -0,0 +1,58 @@
+import { describe, it, expect } from 'vitest';
+import { buildCallActionPayload, parseCallEvents } from '../../src/calls/payloads';
+
+describe('calls', () => {
+    const webhook = (value) => ({
+        object: 'whatsapp_business_account',
+        entry: [{ id: 'WABA_ID', changes: [{ field: 'calls', value }] }],
+    });
+
+    it('should build connect and terminate payloads', () => {
+        expect(buildCallActionPayload('connect', {
+            to: '1234567890',
+            session: { sdpType: 'offer', sdp: 'v=0' },
+        })).toEqual({
+            messaging_product: 'whatsapp',
+            action: 'connect',
+            to: '1234567890',
+            session: { sdp_type: 'offer', sdp: 'v=0' },
+        });
+
+        expect(buildCallActionPayload('terminate', { callId: 'wacid.1' })).toEqual({
+            messaging_product: 'whatsapp',
+            action: 'terminate',
+            call_id: 'wacid.1',
+        });
+    });
+
+    it('should require a call id and SDP where the API does', () => {
+        expect(() => buildCallActionPayload('reject', {})).toThrow('requires a call id');
+        expect(() => buildCallActionPayload('accept', { callId: 'wacid.1' })).toThrow('requires an SDP session');
+    });
+
+    it('should parse connect, terminate, and call status webhooks', () => {
+        const events = parseCallEvents(webhook({
+            calls: [
+                {
+                    id: 'wacid.1', from: '1234567890', to: '5550000000', event: 'connect',
+                    timestamp: '1700000000', direction: 'USER_INITIATED',
+                    session: { sdp_type: 'offer', sdp: 'v=0' },
+                },
+                {
+                    id: 'wacid.1', from: '1234567890', to: '5550000000', event: 'terminate',
+                    timestamp: '1700000060', status: 'COMPLETED', duration: 60,
+                    start_time: '1700000000', end_time: '1700000060',
+                },
+            ],
+            statuses: [
+                { id: 'wacid.2', type: 'call', status: 'RINGING', timestamp: '1700000100', recipient_id: '1234567890' },
+                { id: 'wamid.1', status: 'delivered', timestamp: '1700000100', recipient_id: '1234567890' },
+            ],
+        }));
+
+        expect(events.map((e) => e.kind)).toEqual(['connect', 'terminate', 'status']);
+        expect(events[0]).toMatchObject({ callId: 'wacid.1', session: { sdpType: 'offer', sdp: 'v=0' } });
+        expect(events[1]).toMatchObject({ status: 'COMPLETED', durationSeconds: 60, endTime: 1700000060 });
+        expect(events[2]).toMatchObject({ callId: 'wacid.2', status: 'RINGING' });
+    });
+});
@@ -0,0 +1,42 @@
+import { describe, it, expect } from 'vitest';
+import { CannedResponses } from '../../src/canned/responses';
+
+describe('CannedResponses', () => {
+    it('should build a message for each kind of response', async () => {
+        const canned = new CannedResponses();
+        await canned.set('hours', { kind: 'text', text: 'We are open 9-17.' });
+        await canned.set('prices', { kind: 'media', media: { kind: 'document', url: 'https://example.com/prices.pdf' } });
+        await canned.set('welcome', { kind: 'template', template: { name: 'welcome', language: { code: 'en' } } });
+
+        expect(await canned.toMessage('hours', '111')).toEqual({ type: 'text', to: '111', content: 'We are open 9-17.' });
+        expect(await canned.toMessage('prices', '111')).toEqual({
+            type: 'media',
+            to: '111',
+            content: { kind: 'document', url: 'https://example.com/prices.pdf' },
+        });
+        expect((await canned.toMessage('welcome', '111')).type).toBe('template');
+    });
+
+    it('should match names case-insensitively', async () => {
+        const canned = new CannedResponses();
+        await canned.set('Opening-Hours', { kind: 'text', text: 'We are open 9-17.' });
+
+        expect(await canned.get(' opening-hours ')).toEqual({ kind: 'text', text: 'We are open 9-17.' });
+        expect((await canned.list()).map((entry) => entry.name)).toEqual(['opening-hours']);
+        expect(await canned.remove('OPENING-HOURS')).toBe(true);
+        expect(await canned.remove('opening-hours')).toBe(false);
+    });
+
+    it('should reject incomplete responses', async () => {
+        const canned = new CannedResponses();
+
+        await expect(canned.set('empty', { kind: 'text', text: '' })).rejects.toThrow('needs text');
+        await expect(canned.set('file', { kind: 'media', media: { kind: 'image' } })).rejects.toThrow('url or id');
+        await expect(canned.set('odd', { kind: 'sticker' } as never)).rejects.toThrow('Unknown canned response kind');
+        await expect(canned.set(' ', { kind: 'text', text: 'hi' })).rejects.toThrow('needs a name');
+    });
+
+    it('should fail to build a message for an unknown name', async () => {
+        await expect(new CannedResponses().toMessage('missing', '111')).rejects.toThrow('Unknown canned response');
+    });
+});
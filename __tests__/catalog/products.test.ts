@@ -0,0 +1,75 @@
+import { describe, it, expect, vi } from 'vitest';
+import { buildProductListMessage, buildProductMessage, ProductCatalog } from '../../src/catalog';
+import { ManualClock } from '../../src/utils/clock';
+import { WhatsAppError } from '../../src/errors';
+
+const page = {
+    catalogId: 'CAT_1',
+    products: [
+        { id: '1', retailer_id: 'tea', name: 'Masala tea', price: '₹150.00', availability: 'in stock' },
+        { id: '2', retailer_id: 'mug', name: 'Mug', availability: 'out of stock' },
+    ],
+};
+
+describe('ProductCatalog', () => {
+    it('should summarize products and cache them for the TTL', async () => {
+        const clock = new ManualClock();
+        const fetch = vi.fn().mockResolvedValue(page);
+        const catalog = new ProductCatalog(fetch, 1_000, clock);
+
+        expect(await catalog.list()).toMatchObject({
+            catalogId: 'CAT_1',
+            products: [
+                { retailerId: 'tea', name: 'Masala tea', price: '₹150.00', available: true },
+                { retailerId: 'mug', name: 'Mug', available: false },
+            ],
+        });
+        await catalog.list();
+        expect(fetch).toHaveBeenCalledTimes(1);
+
+        clock.advance(1_000);
+        await catalog.list();
+        expect(fetch).toHaveBeenCalledTimes(2);
+    });
+
+    it('should cache a missing catalog and failed fetches for the TTL', async () => {
+        const clock = new ManualClock();
+        const missing = vi.fn().mockRejectedValue(new WhatsAppError('NOT_FOUND', 'no catalog'));
+        const none = new ProductCatalog(missing, 1_000, clock);
+
+        expect(await none.list()).toMatchObject({ catalogId: '', products: [] });
+        await none.list();
+        expect(missing).toHaveBeenCalledTimes(1);
+
+        const fetch = vi.fn().mockRejectedValueOnce(new Error('Graph is down')).mockResolvedValue(page);
+        const catalog = new ProductCatalog(fetch, 1_000, clock);
+        await expect(catalog.list()).rejects.toThrow('Graph is down');
+        await expect(catalog.list()).rejects.toThrow('Graph is down');
+        expect(fetch).toHaveBeenCalledTimes(1);
+
+        clock.advance(1_000);
+        expect((await catalog.list()).catalogId).toBe('CAT_1');
+    });
+});
+
+describe('product messages', () => {
+    it('should reference products by catalog and retailer id', () => {
+        expect(buildProductMessage('CAT_1', 'tea', 'Our bestseller')).toEqual({
+            type: 'product',
+            body: { text: 'Our bestseller' },
+            action: { catalog_id: 'CAT_1', product_retailer_id: 'tea' },
+        });
+
+        const list = buildProductListMessage('CAT_1', 'Menu', 'Pick your drinks', [{ title: 'Hot', retailerIds: ['tea'] }]);
+        expect(list.action).toEqual({
+            catalog_id: 'CAT_1',
+            sections: [{ title: 'Hot', product_items: [{ product_retailer_id: 'tea' }] }],
+        });
+    });
+
+    it('should enforce product list limits', () => {
+        expect(() => buildProductListMessage('CAT_1', 'Menu', 'Pick', [])).toThrow('at least one product');
+        const many = Array.from({ length: 31 }, (_, i) => `p${i}`);
+        expect(() => buildProductListMessage('CAT_1', 'Menu', 'Pick', [{ title: 'All', retailerIds: many }])).toThrow('30 products');
+    });
+});
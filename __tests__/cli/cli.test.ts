@@ -0,0 +1,71 @@
+import { describe, it, expect, vi } from 'vitest';
+import { runCli, type CliIO } from '../../src/cli';
+
+function io(env: Record<string, string>, client?: Record<string, unknown>) {
+    const out: string[] = [];
+    const err: string[] = [];
+    const cli: CliIO = {
+        out: (line) => out.push(line),
+        err: (line) => err.push(line),
+        env,
+        ...(client ? { client: vi.fn(() => client as any) } : {}),
+    };
+    return { cli, out, err };
+}
+
+const cloudEnv = {
+    WHATSAPP_ACCESS_TOKEN: 'token',
+    WHATSAPP_PHONE_NUMBER_ID: '106540352242922',
+    WHATSAPP_WEBHOOK_VERIFY_TOKEN: 'verify',
+};
+
+describe('whatsapp-cli', () => {
+    it('should report configuration problems and exit non-zero', async () => {
+        const ok = io(cloudEnv);
+        expect(await runCli(['doctor'], ok.cli)).toBe(0);
+        expect(ok.out).toEqual(['Accounts: default', 'OK']);
+
+        const broken = io({ WHATSAPP_ACCOUNTS: JSON.stringify({ sales: { phoneNumberId: '106540352242922' } }) });
+        expect(await runCli(['doctor'], broken.cli)).toBe(1);
+        expect(broken.out).toContain('ERROR [sales] MISSING_ACCESS_TOKEN: phoneNumberId is set but accessToken is missing');
+        expect(broken.out.at(-1)).toBe('Configuration has errors');
+    });
+
+    it('should send messages through the resolved Cloud API account', async () => {
+        const sendMessage = vi.fn(async () => ({ messages: [{ id: 'wamid.1' }] }));
+        const { cli, out } = io(cloudEnv, { sendMessage });
+
+        expect(await runCli(['send', 'text', '15551234567', 'Hello', 'there'], cli)).toBe(0);
+        expect(await runCli(
+            ['send', 'template', '15551234567', 'order_update', '--language', 'pt_BR', '--param', 'A1'],
+            cli
+        )).toBe(0);
+        expect(await runCli(['send', 'media', '15551234567', 'image', 'https://x.test/a.png', '--caption', 'Hi'], cli))
+            .toBe(0);
+
+        expect(sendMessage.mock.calls.map(([message]) => message)).toEqual([
+            { type: 'text', to: '15551234567', content: 'Hello there' },
+            {
+                type: 'template',
+                to: '15551234567',
+                content: {
+                    name: 'order_update',
+                    language: { code: 'pt_BR' },
+                    components: [{ type: 'body', parameters: [{ type: 'text', text: 'A1' }] }],
+                },
+            },
+            { type: 'media', to: '15551234567', content: { kind: 'image', url: 'https://x.test/a.png', caption: 'Hi' } },
+        ]);
+        expect(out[0]).toContain('wamid.1');
+    });
+
+    it('should reject unknown commands and missing arguments', async () => {
+        const unknown = io(cloudEnv);
+        expect(await runCli(['launch'], unknown.cli)).toBe(2);
+        expect(unknown.err[0]).toBe('Unknown command: launch');
+
+        const missing = io(cloudEnv, { sendMessage: vi.fn() });
+        expect(await runCli(['send', 'text'], missing.cli)).toBe(1);
+        expect(missing.err).toEqual(['INVALID_ARGUMENT: Missing <to>']);
+    });
+});
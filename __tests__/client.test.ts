@@ -1,14 +1,18 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
 import axios from 'axios';
 import { WhatsAppClient } from '../src/client';
+import { WhatsAppPlugin } from '../src/index';
 import { WhatsAppConfig, WhatsAppMessage } from '../src/types';
+import { NotOnWhatsAppError, UnsupportedGroupOperationError } from '../src/errors';
 
 vi.mock('axios', () => {
     const mockPost = vi.fn();
+    const mockGet = vi.fn();
     return {
         default: {
             create: () => ({
-                post: mockPost
+                post: mockPost,
+                get: mockGet
             })
         }
     };
@@ -17,6 +21,7 @@ vi.mock('axios', () => {
 describe('WhatsAppClient', () => {
     let client;
     let mockPost;
+    let mockGet;
 
     const mockConfig = {
         accessToken: 'test-token',
@@ -29,6 +34,7 @@ describe('WhatsAppClient', () => {
         vi.clearAllMocks();
         client = new WhatsAppClient(mockConfig);
         mockPost = (axios.create()).post;
+        mockGet = (axios.create()).get;
     });
 
     describe('sendMessage', () => {
@@ -92,6 +98,70 @@ describe('WhatsAppClient', () => {
             expect(response).toEqual(mockResponse);
         });
 
+        it('should send media under its kind', async () => {
+            mockPost.mockResolvedValue({ data: {} });
+
+            await client.sendMessage({
+                type: 'media',
+                to: '1234567890',
+                content: { kind: 'document', url: 'https://example.com/a.pdf', fileName: 'a.pdf', caption: 'Invoice' }
+            });
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/messages`, {
+                messaging_product: 'whatsapp',
+                recipient_type: 'individual',
+                to: '1234567890',
+                type: 'document',
+                document: { link: 'https://example.com/a.pdf', caption: 'Invoice', filename: 'a.pdf' }
+            });
+        });
+
+        it('should address group targets with the group recipient type', async () => {
+            const mockMessage = {
+                type: 'text',
+                to: '120363042@g.us',
+                content: 'Hello, group!'
+            };
+
+            mockPost.mockResolvedValue({ data: {} });
+
+            await client.sendMessage(mockMessage);
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/messages`, {
+                messaging_product: 'whatsapp',
+                recipient_type: 'group',
+                to: '120363042',
+                type: 'text',
+                text: { body: 'Hello, group!' }
+            });
+        });
+
+        it('should reject unsupported message types for groups', async () => {
+            const mockMessage = {
+                type: 'interactive',
+                to: '120363042@g.us',
+                content: { type: 'button', body: { text: 'Pick' }, action: { buttons: [] } }
+            };
+
+            await expect(client.sendMessage(mockMessage)).rejects.toThrow(UnsupportedGroupOperationError);
+            expect(mockPost).not.toHaveBeenCalled();
+        });
+
+        it('should refuse to post to channels', async () => {
+            await expect(client.sendMessage({ type: 'text', to: '120363111@newsletter', content: 'Hi' }))
+                .rejects.toThrow(/Channels/);
+            expect(mockPost).not.toHaveBeenCalled();
+        });
+
+        it('should map error 131026 to NotOnWhatsAppError', async () => {
+            mockPost.mockRejectedValue({
+                response: { data: { error: { code: 131026, message: 'Message undeliverable' } } }
+            });
+
+            await expect(client.sendMessage({ type: 'text', to: '1234567890', content: 'Hi' }))
+                .rejects.toThrow(NotOnWhatsAppError);
+        });
+
         it('should handle API errors correctly', async () => {
             const mockMessage = {
                 type: 'text',
@@ -104,6 +174,242 @@ describe('WhatsAppClient', () => {
 
             await expect(client.sendMessage(mockMessage)).rejects.toThrow('API Error');
         });
+
+        it('should surface Graph API errors from any endpoint as GraphApiError', async () => {
+            mockPost.mockRejectedValue({
+                response: { status: 401, data: { error: { code: 190, message: 'Invalid OAuth access token' } } }
+            });
+
+            await expect(client.checkContacts(['1234567890'])).rejects.toMatchObject({
+                name: 'GraphApiError',
+                code: 'GRAPH_API_ERROR',
+                graphCode: 190,
+                status: 401
+            });
+        });
+    });
+
+    describe('batch', () => {
+        it('should split requests into batches of 50 and decode each item', async () => {
+            const succeed = async (_url, { batch }) => ({
+                data: batch.map(() => ({ code: 200, body: '{"success":true}' }))
+            });
+            mockPost.mockImplementationOnce(succeed).mockImplementationOnce(succeed);
+
+            const results = await client.markReadMany(Array.from({ length: 60 }, (_, i) => `wamid.${i}`));
+
+            expect(mockPost).toHaveBeenCalledTimes(2);
+            expect(mockPost.mock.calls[0][1].batch).toHaveLength(50);
+            expect(mockPost.mock.calls[1][1].batch[0]).toEqual({
+                method: 'POST',
+                relative_url: `${mockConfig.phoneNumberId}/messages`,
+                body: 'messaging_product=whatsapp&status=read&message_id=wamid.50'
+            });
+            expect(results).toHaveLength(60);
+            expect(results.every((r) => r.ok)).toBe(true);
+        });
+
+        it('should report per-message outcomes of a batched send', async () => {
+            mockPost.mockResolvedValue({
+                data: [
+                    { code: 200, body: '{"messaging_product":"whatsapp","messages":[{"id":"wamid.1"}]}' },
+                    { code: 400, body: '{"error":{"code":131026,"message":"Message undeliverable"}}' },
+                ]
+            });
+
+            const results = await client.sendBatch([
+                { type: 'text', to: '1234567890', content: 'Hi' },
+                { type: 'text', to: '1987654321', content: 'Hi' },
+                { type: 'interactive', to: '120363042000000000@g.us', content: { type: 'button', body: { text: 'x' }, action: {} } },
+            ]);
+
+            expect(mockPost.mock.calls[0][1].batch).toHaveLength(2);
+            expect(results[0]).toEqual({ ok: true, status: 200, data: { messaging_product: 'whatsapp', messages: [{ id: 'wamid.1' }] } });
+            expect(results[1].ok).toBe(false);
+            expect(results[1].error).toBeInstanceOf(NotOnWhatsAppError);
+            expect(results[2].error).toBeInstanceOf(UnsupportedGroupOperationError);
+        });
+    });
+
+    describe('sendRaw', () => {
+        it('should post the payload with messaging_product and return the typed response', async () => {
+            const data = { messaging_product: 'whatsapp', messages: [{ id: 'wamid.9' }] };
+            mockPost.mockResolvedValue({ data });
+
+            const response = await client.sendRaw({ to: '1234567890', type: 'sticker', sticker: { id: 'st-1' } });
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/messages`, {
+                messaging_product: 'whatsapp',
+                to: '1234567890',
+                type: 'sticker',
+                sticker: { id: 'st-1' }
+            });
+            expect(response).toEqual(data);
+        });
+
+        it('should parse Graph API errors', async () => {
+            mockPost.mockRejectedValue({
+                response: { status: 400, data: { error: { message: 'Invalid parameter', code: 100, error_subcode: 2494010, fbtrace_id: 'Ab1' } } }
+            });
+
+            await expect(client.sendRaw({ to: '1234567890', type: 'sticker' }, 'other-phone-id')).rejects.toMatchObject({
+                name: 'GraphApiError',
+                message: 'Invalid parameter',
+                status: 400,
+                graphCode: 100,
+                subcode: 2494010,
+                fbtraceId: 'Ab1'
+            });
+            expect(mockPost.mock.calls[0][0]).toBe('/other-phone-id/messages');
+        });
+    });
+
+    describe('media', () => {
+        it('should download media through its short-lived URL', async () => {
+            mockGet
+                .mockResolvedValueOnce({ data: { url: 'https://lookaside.example/media-9', mime_type: 'image/jpeg' } })
+                .mockResolvedValueOnce({ data: new Uint8Array([1, 2, 3]).buffer });
+
+            const media = await client.downloadMedia('media-9');
+
+            expect(mockGet).toHaveBeenNthCalledWith(1, '/media-9');
+            expect(mockGet).toHaveBeenNthCalledWith(2, 'https://lookaside.example/media-9', {
+                baseURL: '',
+                responseType: 'arraybuffer'
+            });
+            expect(media).toEqual({ data: new Uint8Array([1, 2, 3]), mimeType: 'image/jpeg' });
+        });
+
+        it('should refresh an expired media URL and retry the download', async () => {
+            const expired = Object.assign(new Error('Request failed with status code 404'), { response: { status: 404 } });
+            mockGet
+                .mockResolvedValueOnce({ data: { url: 'https://lookaside.example/old', mime_type: 'image/jpeg' } })
+                .mockResolvedValueOnce({ data: new Uint8Array([1]).buffer })
+                .mockRejectedValueOnce(expired)
+                .mockResolvedValueOnce({ data: { url: 'https://lookaside.example/new', mime_type: 'image/jpeg' } })
+                .mockResolvedValueOnce({ data: new Uint8Array([2]).buffer });
+
+            await client.downloadMedia('media-9');
+            const media = await client.downloadMedia('media-9');
+
+            expect(mockGet.mock.calls.map(([url]) => url)).toEqual([
+                '/media-9',
+                'https://lookaside.example/old',
+                'https://lookaside.example/old',
+                '/media-9',
+                'https://lookaside.example/new',
+            ]);
+            expect(media).toEqual({ data: new Uint8Array([2]), mimeType: 'image/jpeg' });
+        });
+
+        it('should not retry downloads that fail for other reasons', async () => {
+            mockGet
+                .mockResolvedValueOnce({ data: { url: 'https://lookaside.example/media-9' } })
+                .mockRejectedValueOnce(Object.assign(new Error('socket hang up'), { code: 'ECONNRESET' }));
+
+            await expect(client.downloadMedia('media-9')).rejects.toThrow('socket hang up');
+            expect(mockGet).toHaveBeenCalledTimes(2);
+        });
+
+        it('should refuse media over maxBytes without downloading it', async () => {
+            mockGet.mockResolvedValueOnce({ data: { url: 'https://lookaside.example/big', file_size: 5_000_000 } });
+
+            await expect(client.downloadMedia('media-big', { maxBytes: 1_000_000 })).rejects.toMatchObject({
+                code: 'MEDIA_TOO_LARGE',
+            });
+            expect(mockGet).toHaveBeenCalledTimes(1);
+        });
+
+        it('should upload raw media before sending it by id', async () => {
+            mockPost.mockResolvedValueOnce({ data: { id: 'uploaded-1' } }).mockResolvedValueOnce({ data: {} });
+
+            await client.sendMessage({
+                type: 'media',
+                to: '1234567890',
+                content: { kind: 'image', data: new Uint8Array([1, 2, 3]), mimeType: 'image/jpeg', caption: 'Receipt' }
+            });
+
+            expect(mockPost.mock.calls[0][0]).toBe(`/${mockConfig.phoneNumberId}/media`);
+            expect(mockPost.mock.calls[0][1]).toBeInstanceOf(FormData);
+            expect(mockPost).toHaveBeenLastCalledWith(`/${mockConfig.phoneNumberId}/messages`, {
+                messaging_product: 'whatsapp',
+                recipient_type: 'individual',
+                to: '1234567890',
+                type: 'image',
+                image: { id: 'uploaded-1', caption: 'Receipt' }
+            });
+        });
+    });
+
+    describe('checkContacts', () => {
+        it('should report which numbers are on WhatsApp', async () => {
+            mockPost.mockResolvedValue({
+                data: {
+                    contacts: [
+                        { input: '+1234567890', status: 'valid', wa_id: '1234567890' },
+                        { input: '+1999', status: 'invalid' }
+                    ]
+                }
+            });
+
+            const results = await client.checkContacts(['+1234567890', '+1999']);
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/contacts`, {
+                blocking: 'wait',
+                contacts: ['+1234567890', '+1999'],
+                force_check: false
+            });
+            expect(results).toEqual([
+                { input: '+1234567890', onWhatsApp: true, waId: '1234567890' },
+                { input: '+1999', onWhatsApp: false }
+            ]);
+        });
+
+        it('should reject contact checks on clients that cannot make them', async () => {
+            const plugin = new WhatsAppPlugin(mockConfig);
+            plugin['client'] = { sendMessage: vi.fn(), getConnectionStatus: vi.fn() } as never;
+
+            await expect(plugin.checkContact('1234567890')).rejects.toMatchObject({ code: 'UNSUPPORTED' });
+        });
+    });
+
+    describe('blockUsers', () => {
+        it('should post normalized wa_ids to the block endpoint', async () => {
+            mockPost.mockResolvedValue({
+                data: { block_users: { added_users: [{ input: '+1 234 567 890', wa_id: '1234567890' }] } }
+            });
+
+            const blocked = await client.blockUsers(['+1 234 567 890']);
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/block_users`, {
+                messaging_product: 'whatsapp',
+                block_users: [{ user: '1234567890' }]
+            });
+            expect(blocked).toEqual(['1234567890']);
+        });
+
+        it('should reject blocklist calls on clients without a blocklist', async () => {
+            const plugin = new WhatsAppPlugin(mockConfig);
+            plugin['client'] = { sendMessage: vi.fn(), getConnectionStatus: vi.fn() } as never;
+
+            await expect(plugin.blockUsers(['1234567890'])).rejects.toMatchObject({ code: 'UNSUPPORTED' });
+            await expect(plugin.listBlockedUsers()).rejects.toMatchObject({ code: 'UNSUPPORTED' });
+        });
+    });
+
+    describe('sendTyping', () => {
+        it('should mark the message read with a typing indicator', async () => {
+            mockPost.mockResolvedValue({ data: { success: true } });
+
+            await client.sendTyping({ id: 'wamid.1', from: '1234567890' });
+
+            expect(mockPost).toHaveBeenCalledWith(`/${mockConfig.phoneNumberId}/messages`, {
+                messaging_product: 'whatsapp',
+                status: 'read',
+                message_id: 'wamid.1',
+                typing_indicator: { type: 'text' }
+            });
+        });
     });
 
     describe('verifyWebhook', () => {
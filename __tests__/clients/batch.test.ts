@@ -0,0 +1,29 @@
+import { describe, it, expect } from 'vitest';
+import { decodeBatchResponse, encodeBatchRequest } from '../../src/clients/batch';
+
+describe('batch requests', () => {
+    it('should form-encode bodies with nested values as JSON', () => {
+        expect(encodeBatchRequest({
+            method: 'POST',
+            relativeUrl: '/123/messages',
+            body: { messaging_product: 'whatsapp', to: '1', type: 'text', text: { body: 'a&b' }, skipped: undefined },
+        })).toEqual({
+            method: 'POST',
+            relative_url: '123/messages',
+            body: 'messaging_product=whatsapp&to=1&type=text&text=%7B%22body%22%3A%22a%26b%22%7D',
+        });
+        expect(encodeBatchRequest({ method: 'GET', relativeUrl: '456' })).toEqual({ method: 'GET', relative_url: '456' });
+    });
+
+    it('should decode successes, Graph errors and timed-out items', () => {
+        expect(decodeBatchResponse({ code: 200, body: '{"url":"https://lookaside.fbsbx.com/x"}' }))
+            .toEqual({ ok: true, status: 200, data: { url: 'https://lookaside.fbsbx.com/x' } });
+
+        const failed = decodeBatchResponse({ code: 400, body: '{"error":{"message":"Invalid parameter","code":100}}' });
+        expect(failed).toMatchObject({ ok: false, status: 400, error: { name: 'GraphApiError', graphCode: 100 } });
+
+        expect(decodeBatchResponse({ code: 500, body: 'oops' }))
+            .toMatchObject({ ok: false, error: { message: 'Batch request failed with status 500' } });
+        expect(decodeBatchResponse(null)).toMatchObject({ ok: false, error: { code: 'BATCH_TIMEOUT' } });
+    });
+});
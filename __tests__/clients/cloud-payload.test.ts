@@ -0,0 +1,148 @@
+import { describe, it, expect } from 'vitest';
+import { fromCloudPayload, toCloudPayload } from '../../src/clients/cloud-payload';
+
+// Bodies as documented for POST /{phone-number-id}/messages
+const payloads = {
+    text: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'text',
+        text: { body: 'Hello there' },
+    },
+    reply: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        context: { message_id: 'wamid.HBgLMTY0NjcwNDM1OTUVAgASGBQzQTRBNjU5OUFFRTAzODEwMTQ0RgA=' },
+        type: 'text',
+        text: { preview_url: true, body: 'Our spring catalog: https://www.luckyshrub.com/spring' },
+    },
+    image: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'image',
+        image: { id: '1479537139650973', caption: 'The best succulent ever?' },
+    },
+    document: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'document',
+        document: { link: 'https://example.com/lucky-shrub-invoice.pdf', caption: 'Invoice', filename: 'invoice.pdf' },
+    },
+    audio: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'audio',
+        audio: { id: '1013859600285441' },
+    },
+    location: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'location',
+        location: { latitude: 37.44216251868683, longitude: -122.16153582049394, name: 'Philz Coffee', address: '101 Forest Ave, Palo Alto, CA 94301' },
+    },
+    template: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'template',
+        template: {
+            name: 'order_confirmation',
+            language: { code: 'en_US' },
+            components: [{ type: 'body', parameters: [{ type: 'text', parameter_name: 'order_id', text: '879' }] }],
+        },
+    },
+    interactive: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'interactive',
+        interactive: {
+            type: 'button',
+            body: { text: 'Change your delivery date?' },
+            action: { buttons: [{ type: 'reply', reply: { id: 'change', title: 'Change' } }, { type: 'reply', reply: { id: 'keep', title: 'Keep' } }] },
+        },
+    },
+    reaction: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'reaction',
+        reaction: { message_id: 'wamid.HBgLMTY0NjcwNDM1OTUVAgASGBQzQTRBNjU5OUFFRTAzODEwMTQ0RgA=', emoji: '\uD83D\uDE00' },
+    },
+    cta_url: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'interactive',
+        interactive: {
+            type: 'cta_url',
+            header: { type: 'text', text: 'Available Dates' },
+            body: { text: 'Tap the button below to see available dates.' },
+            action: { name: 'cta_url', parameters: { display_text: 'See Dates', url: 'https://www.luckyshrub.com?clickID=kqDGWd24Q5TRwoEQTICY7W1JKoXvaZOXWAS7h1P76s0R7Paec4' } },
+        },
+    },
+    flow: {
+        messaging_product: 'whatsapp',
+        recipient_type: 'individual',
+        to: '16505551234',
+        type: 'interactive',
+        interactive: {
+            type: 'flow',
+            body: { text: 'Book your appointment' },
+            action: {
+                name: 'flow',
+                parameters: {
+                    flow_message_version: '3',
+                    flow_token: 'AQAAAAACS5FpgQ_cAAAAAD0QI3s.',
+                    flow_id: '1',
+                    flow_cta: 'Book!',
+                    flow_action: 'navigate',
+                    flow_action_payload: { screen: 'APPOINTMENT', data: { product_name: 'name' } },
+                },
+            },
+        },
+    },
+};
+
+describe('cloud payloads', () => {
+    it.each(Object.entries(payloads))('should round-trip a %s message', (_, payload) => {
+        const { messaging_product, recipient_type, to, ...body } = payload;
+
+        const message = fromCloudPayload(payload);
+
+        expect(message.to).toBe(to);
+        expect(toCloudPayload(message)).toEqual(body);
+    });
+
+    it('should tag media by kind', () => {
+        expect(fromCloudPayload(payloads.image)).toEqual({
+            type: 'media',
+            to: '16505551234',
+            content: { kind: 'image', id: '1479537139650973', caption: 'The best succulent ever?' },
+        });
+    });
+
+    it('should reject media with neither id nor link instead of guessing another type', () => {
+        expect(() => fromCloudPayload({ to: '16505551234', type: 'video', video: { caption: 'x' } })).toThrow(
+            'video message needs an id or link'
+        );
+        expect(() => toCloudPayload({ type: 'media', to: '16505551234', content: { kind: 'video' } })).toThrow(
+            'video media needs an id or url'
+        );
+    });
+
+    it('should explain what is missing', () => {
+        expect(() => fromCloudPayload({ type: 'text', text: { body: 'hi' } })).toThrow('no recipient');
+        expect(() => fromCloudPayload({ to: '1', type: 'text' })).toThrow('"text" message has no "text" object');
+        expect(() => fromCloudPayload({ to: '1', type: 'location', location: { latitude: '1' } })).toThrow('latitude');
+        expect(() => fromCloudPayload({ to: '1', type: 'sticker', sticker: { id: '2' } })).toThrow(
+            'Message type "sticker" is not supported; use sendRaw'
+        );
+    });
+});
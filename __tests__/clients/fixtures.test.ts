@@ -0,0 +1,76 @@
+import { describe, it, expect, beforeEach, afterEach } from 'vitest';
+import axios, { AxiosError, type AxiosAdapter } from 'axios';
+import { mkdtemp, readdir, readFile, rm } from 'fs/promises';
+import { tmpdir } from 'os';
+import { join } from 'path';
+import { fixtureAdapter } from '../../src/clients/fixtures';
+import { GraphApiError } from '../../src/errors';
+
+function graphClient(adapter: AxiosAdapter) {
+    return axios.create({
+        baseURL: 'https://graph.facebook.com/v24.0',
+        headers: { Authorization: 'Bearer SECRET_TOKEN', 'Content-Type': 'application/json' },
+        adapter,
+    });
+}
+
+// Stands in for the network: answers each call with the next status from the list
+function upstream(statuses: number[]): AxiosAdapter & { calls: number } {
+    const adapter = (async (config) => {
+        const status = statuses[Math.min(adapter.calls++, statuses.length - 1)];
+        const body = status === 200
+            ? { messages: [{ id: `wamid.${adapter.calls}` }] }
+            : { error: { message: 'Service temporarily unavailable', code: 2 } };
+        const response = { data: JSON.stringify(body), status, statusText: '', headers: { 'content-type': 'application/json' }, config };
+        if (status >= 400) throw new AxiosError('failed', AxiosError.ERR_BAD_RESPONSE, config, undefined, response);
+        return response;
+    }) as AxiosAdapter & { calls: number };
+    adapter.calls = 0;
+    return adapter;
+}
+
+describe('fixture adapter', () => {
+    let dir: string;
+
+    beforeEach(async () => {
+        dir = await mkdtemp(join(tmpdir(), 'whatsapp-fixtures-'));
+    });
+
+    afterEach(async () => {
+        await rm(dir, { recursive: true, force: true });
+    });
+
+    it('should replay recorded exchanges offline without storing credentials', async () => {
+        const network = upstream([200]);
+        const recorder = graphClient(fixtureAdapter({ mode: 'record', dir }, network));
+        const payload = { messaging_product: 'whatsapp', to: '15551234567', type: 'text', text: { body: 'Hi' } };
+        const live = await recorder.post('/123/messages', payload, { params: { access_token: 'SECRET_TOKEN' } });
+
+        const [file] = await readdir(dir);
+        expect(file).toMatch(/^post-v24_0_123_messages-[0-9a-f]{12}\.json$/);
+        expect(await readFile(join(dir, file), 'utf8')).not.toContain('SECRET_TOKEN');
+
+        const replayer = graphClient(fixtureAdapter({ mode: 'replay', dir }, upstream([500])));
+        const replayed = await replayer.post('/123/messages', payload);
+        expect(replayed.data).toEqual(live.data);
+        expect(replayed.data).toEqual({ messages: [{ id: 'wamid.1' }] });
+        expect(network.calls).toBe(1);
+    });
+
+    it('should replay responses in recorded order, repeating the last', async () => {
+        const recorder = graphClient(fixtureAdapter({ mode: 'record', dir }, upstream([503, 200])));
+        await expect(recorder.get('/123')).rejects.toBeInstanceOf(AxiosError);
+        await recorder.get('/123');
+
+        const replayer = graphClient(fixtureAdapter({ mode: 'replay', dir }));
+        const error = await replayer.get('/123').catch((e) => e);
+        expect(GraphApiError.fromResponse(error)).toMatchObject({ status: 503, graphCode: 2 });
+        expect((await replayer.get('/123')).data).toEqual({ messages: [{ id: 'wamid.2' }] });
+        expect((await replayer.get('/123')).data).toEqual({ messages: [{ id: 'wamid.2' }] });
+    });
+
+    it('should fail requests that were never recorded', async () => {
+        const replayer = graphClient(fixtureAdapter({ mode: 'replay', dir }));
+        await expect(replayer.post('/123/messages', { to: '1' })).rejects.toMatchObject({ code: 'FIXTURE_MISSING' });
+    });
+});
@@ -0,0 +1,69 @@
+import { describe, it, expect, vi } from 'vitest';
+import { CloudAPIClient } from '../../src/clients/cloud-api-client';
+import type { HttpRequest, HttpTransport } from '../../src/clients/transport';
+import { GraphApiError } from '../../src/errors';
+
+function stubTransport(respond: (request: HttpRequest) => { status: number; body: string | Uint8Array }) {
+    const requests: HttpRequest[] = [];
+    const transport: HttpTransport = {
+        request: vi.fn(async (request: HttpRequest) => {
+            requests.push(request);
+            return { headers: { 'content-type': 'application/json' }, ...respond(request) };
+        }),
+    };
+    return { transport, requests };
+}
+
+const config = { accessToken: 'token', phoneNumberId: '123' };
+
+describe('HTTP transport', () => {
+    it('should send Graph API requests through the transport', async () => {
+        const { transport, requests } = stubTransport(() => ({
+            status: 200,
+            body: '{"messaging_product":"whatsapp","messages":[{"id":"wamid.1"}]}',
+        }));
+        const client = new CloudAPIClient(config);
+        client.useTransport(transport);
+
+        const response = await client.sendMessage({ type: 'text', to: '15551234567', content: 'Hi' });
+
+        expect(response.data.messages[0].id).toBe('wamid.1');
+        expect(requests[0]).toMatchObject({
+            method: 'POST',
+            url: 'https://graph.facebook.com/v24.0/123/messages',
+            responseType: 'text',
+        });
+        expect(requests[0].headers.Authorization).toBe('Bearer token');
+        expect(JSON.parse(requests[0].body as string)).toMatchObject({ to: '15551234567', type: 'text' });
+    });
+
+    it('should surface error statuses as Graph API errors', async () => {
+        const { transport } = stubTransport(() => ({
+            status: 400,
+            body: '{"error":{"message":"Invalid parameter","code":100}}',
+        }));
+        const client = new CloudAPIClient(config);
+        client.useTransport(transport);
+
+        const error = await client.getPhoneNumber().catch((e) => e);
+
+        expect(error).toBeInstanceOf(GraphApiError);
+        expect(error).toMatchObject({ status: 400, graphCode: 100 });
+    });
+
+    it('should ask for bytes when downloading media', async () => {
+        const { transport, requests } = stubTransport((request) =>
+            request.url.startsWith('https://lookaside.example/')
+                ? { status: 200, body: new Uint8Array([1, 2, 3]) }
+                : { status: 200, body: '{"url":"https://lookaside.example/m1","mime_type":"image/png"}' }
+        );
+        const client = new CloudAPIClient(config);
+        client.useTransport(transport);
+
+        const media = await client.downloadMedia('m1');
+
+        expect([...media.data]).toEqual([1, 2, 3]);
+        expect(media.mimeType).toBe('image/png');
+        expect(requests.map((r) => r.responseType)).toEqual(['text', 'binary']);
+    });
+});
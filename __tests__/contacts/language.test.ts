@@ -0,0 +1,97 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ContactLanguages, matchTemplateLanguage, RuntimeContactLanguageStore } from '../../src/contacts/language';
+import { ManualClock } from '../../src/utils/clock';
+
+const text = (content: string, overrides = {}) => ({
+    id: 'wamid.in',
+    from: '15551234567',
+    timestamp: 0,
+    type: 'text' as const,
+    content,
+    ...overrides,
+});
+
+describe('matchTemplateLanguage', () => {
+    it('should prefer the exact code, then the bare language, then a regional variant', () => {
+        expect(matchTemplateLanguage('es_MX', ['es', 'es_MX'])).toBe('es_MX');
+        expect(matchTemplateLanguage('es-mx', ['en_US', 'es'])).toBe('es');
+        expect(matchTemplateLanguage('pt', ['en_US', 'pt_BR'])).toBe('pt_BR');
+        expect(matchTemplateLanguage('de', ['en_US'])).toBeUndefined();
+    });
+});
+
+describe('ContactLanguages', () => {
+    it('should learn the language of a template the contact replies to', async () => {
+        const languages = new ContactLanguages(undefined, new ManualClock(1_000));
+        languages.trackTemplate('wamid.out', 'pt_BR');
+
+        expect(await languages.observe(text('Sim', { replyTo: 'wamid.out' }))).toBe('pt_BR');
+        expect(await languages.get('+1 555 123 4567')).toEqual({ language: 'pt_BR', source: 'template', updatedAt: 1_000 });
+    });
+
+    it('should detect the language of longer text messages only', async () => {
+        const languages = new ContactLanguages();
+        const detector = vi.fn().mockReturnValue('es');
+        languages.useDetector(detector);
+
+        await languages.observe(text('Hola'));
+        expect(detector).not.toHaveBeenCalled();
+
+        await languages.observe(text('Hola, quiero cambiar mi pedido'));
+        expect((await languages.get('15551234567'))?.language).toBe('es');
+    });
+
+    it('should not let learned languages replace an explicit one', async () => {
+        const languages = new ContactLanguages();
+        languages.useDetector(() => 'es');
+        languages.trackTemplate('wamid.out', 'en_US');
+        await languages.set('15551234567', 'fr');
+
+        await languages.observe(text('Hola, quiero cambiar mi pedido'));
+        await languages.observe(text('Ok', { replyTo: 'wamid.out' }));
+
+        expect(await languages.get('15551234567')).toMatchObject({ language: 'fr', source: 'explicit' });
+    });
+});
+
+describe('RuntimeContactLanguageStore', () => {
+    function runtime() {
+        const cache = new Map();
+        return {
+            cache,
+            getCache: async (key) => cache.get(key),
+            setCache: vi.fn(async (key, value) => { cache.set(key, value); return true; }),
+        };
+    }
+
+    it('should keep concurrent updates for different contacts', async () => {
+        const store = runtime();
+        const languages = new ContactLanguages(new RuntimeContactLanguageStore(store, 'sales'));
+
+        await Promise.all([languages.set('15551234567', 'es'), languages.set('15557654321', 'pt_BR')]);
+
+        expect(await languages.get('15551234567')).toMatchObject({ language: 'es' });
+        expect(await languages.get('15557654321')).toMatchObject({ language: 'pt_BR' });
+    });
+
+    it('should not rewrite a language that has not changed', async () => {
+        const store = runtime();
+        const languages = new ContactLanguages(new RuntimeContactLanguageStore(store, 'sales'));
+        languages.useDetector(() => 'es');
+
+        await languages.observe(text('Hola, quiero cambiar mi pedido'));
+        await languages.observe(text('Y también la dirección de envío'));
+
+        expect(store.setCache).toHaveBeenCalledTimes(1);
+    });
+
+    it('should read languages stored as one map before they were kept per contact', async () => {
+        const store = runtime();
+        store.cache.set('whatsapp-contact-languages', { '15551234567': { language: 'es', source: 'explicit', updatedAt: 1 } });
+        const languages = new ContactLanguages(new RuntimeContactLanguageStore(store, 'sales'));
+
+        expect(await languages.get('15551234567')).toMatchObject({ language: 'es' });
+        await languages.clear('15551234567');
+        expect(await languages.get('15551234567')).toBeUndefined();
+    });
+});
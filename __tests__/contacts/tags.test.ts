@@ -0,0 +1,69 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { ContactTags, RuntimeContactTagStore } from '../../src/contacts/tags';
+
+describe('ContactTags', () => {
+    let tags;
+
+    beforeEach(async () => {
+        tags = new ContactTags();
+        await tags.add('+1 234 567 890', ['Lead', 'vip']);
+        await tags.add('5550000000@s.whatsapp.net', ['customer']);
+        await tags.add('5551111111', ['customer', 'VIP', 'blocked']);
+    });
+
+    it('should normalize contacts and tags', async () => {
+        expect(await tags.get('1234567890@s.whatsapp.net')).toEqual(['lead', 'vip']);
+        expect(await tags.hasTag('1234567890', 'LEAD')).toBe(true);
+    });
+
+    it('should remove tags', async () => {
+        expect(await tags.remove('1234567890', ['lead'])).toEqual(['vip']);
+    });
+
+    it('should select audiences by any/all tags with exclusions', async () => {
+        expect(await tags.select({ tags: ['vip'] })).toEqual(['1234567890', '5551111111']);
+        expect(await tags.select({ tags: ['customer', 'vip'], match: 'all' })).toEqual(['5551111111']);
+        expect(await tags.select({ tags: ['customer'], exclude: ['blocked'] })).toEqual(['5550000000']);
+    });
+});
+
+describe('RuntimeContactTagStore', () => {
+    function runtime() {
+        const cache = new Map();
+        return {
+            cache,
+            getCache: async (key) => cache.get(key),
+            setCache: async (key, value) => { cache.set(key, value); return true; },
+        };
+    }
+
+    it('should keep each account\'s tags apart', async () => {
+        const shared = runtime();
+        const sales = new ContactTags(new RuntimeContactTagStore(shared, 'sales'));
+        const support = new ContactTags(new RuntimeContactTagStore(shared, 'support'));
+
+        await sales.add('1234567890', ['lead']);
+
+        expect(await sales.get('1234567890')).toEqual(['lead']);
+        expect(await support.get('1234567890')).toEqual([]);
+    });
+
+    it('should keep concurrent updates for different contacts and select them', async () => {
+        const tags = new ContactTags(new RuntimeContactTagStore(runtime(), 'sales'));
+
+        await Promise.all([tags.add('1234567890', ['lead']), tags.add('5550000000', ['lead', 'vip'])]);
+        await tags.remove('1234567890', ['lead']);
+
+        expect(await tags.select({ tags: ['lead'] })).toEqual(['5550000000']);
+    });
+
+    it('should read tags stored as one map before they were kept per contact', async () => {
+        const store = runtime();
+        store.cache.set('whatsapp-contact-tags', { '1234567890': ['lead'] });
+        const tags = new ContactTags(new RuntimeContactTagStore(store, 'sales'));
+
+        await tags.add('5550000000', ['lead']);
+
+        expect(await tags.select({ tags: ['lead'] })).toEqual(['1234567890', '5550000000']);
+    });
+});
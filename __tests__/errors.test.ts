@@ -0,0 +1,27 @@
+import { describe, it, expect } from 'vitest';
+import { GraphApiError, NotOnWhatsAppError, WhatsAppError } from '../src/errors';
+
+describe('WhatsAppError.from', () => {
+    it('should pass WhatsAppErrors through unchanged', () => {
+        const error = new NotOnWhatsAppError('1234567890');
+        expect(WhatsAppError.from(error)).toBe(error);
+    });
+
+    it('should turn Graph API responses into GraphApiError', () => {
+        const axiosError = {
+            response: { status: 400, data: { error: { message: 'Invalid parameter', code: 100, fbtrace_id: 'Ab1' } } }
+        };
+
+        const error = WhatsAppError.from(axiosError);
+
+        expect(error).toBeInstanceOf(GraphApiError);
+        expect(error).toMatchObject({ message: 'Invalid parameter', graphCode: 100, fbtraceId: 'Ab1', cause: axiosError });
+    });
+
+    it('should wrap anything else and keep it as cause', () => {
+        const original = new TypeError('boom');
+
+        expect(WhatsAppError.from(original, 'SEND_FAILED')).toMatchObject({ code: 'SEND_FAILED', message: 'boom', cause: original });
+        expect(WhatsAppError.from('plain string')).toMatchObject({ code: 'UNKNOWN', message: 'plain string' });
+    });
+});
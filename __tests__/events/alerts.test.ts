@@ -0,0 +1,91 @@
+import { describe, it, expect, vi } from 'vitest';
+import { AdminAlertSink, alertFor } from '../../src/events/alerts';
+
+const circuitOpened = {
+    kind: 'circuit_opened',
+    accountId: 'sales',
+    failures: 5,
+    retryInMs: 30_000,
+    error: 'Service unavailable',
+} as const;
+
+describe('alertFor', () => {
+    it('should describe critical events', () => {
+        expect(alertFor(circuitOpened)?.text).toBe(
+            'WhatsApp account sales stopped sending after 5 failures in a row: Service unavailable'
+        );
+        expect(alertFor({ kind: 'webhook_rejected', accountId: 'sales', reason: 'signature' })?.key).toBe('signature');
+        expect(alertFor({ kind: 'auth_failed', accountId: 'sales', reason: 'Logged out' })?.key).toBe('auth');
+    });
+
+    it('should treat a rejected token found by the health check as an auth failure', () => {
+        const alert = alertFor({
+            kind: 'account_degraded',
+            accountId: 'sales',
+            problems: [{ code: 'INVALID_ACCESS_TOKEN', message: 'Session has expired' }],
+        });
+
+        expect(alert).toEqual({ key: 'auth', text: 'WhatsApp account sales failed to authenticate: Session has expired' });
+        expect(alertFor({ kind: 'account_degraded', accountId: 'sales', problems: [] })).toBeUndefined();
+    });
+
+    it('should alert on failed sends only past the threshold', () => {
+        const failed = (failedSends: number) => ({
+            kind: 'send_failed' as const,
+            accountId: 'sales',
+            message: { type: 'text' as const, to: '1234567890', content: 'Hi' },
+            error: { code: 'SEND_FAILED', message: 'boom' },
+            failedSends,
+        });
+
+        expect(alertFor(failed(19), 20)).toBeUndefined();
+        expect(alertFor(failed(20), 20)?.text).toBe('WhatsApp account sales has 20 failed sends waiting for replay');
+    });
+});
+
+describe('AdminAlertSink', () => {
+    it('should message every admin number once per cooldown', async () => {
+        const send = vi.fn().mockResolvedValue({});
+        const sink = new AdminAlertSink({ numbers: ['111', '222'] }, send);
+
+        await sink.send(circuitOpened);
+        await sink.send(circuitOpened);
+
+        expect(send).toHaveBeenCalledTimes(2);
+        expect(send.mock.calls.map(([message]) => message.to)).toEqual(['111', '222']);
+        expect(send.mock.calls[0][0]).toMatchObject({ type: 'text', content: alertFor(circuitOpened)?.text });
+    });
+
+    it('should use the configured template with the alert as a single-line parameter', async () => {
+        const send = vi.fn().mockResolvedValue({});
+        const sink = new AdminAlertSink({ numbers: ['111'], template: { name: 'ops_alert', language: 'en' } }, send);
+
+        await sink.send({ ...circuitOpened, error: 'Bad\n\ngateway' });
+
+        expect(send.mock.calls[0][0]).toEqual({
+            type: 'template',
+            to: '111',
+            content: {
+                name: 'ops_alert',
+                language: { code: 'en' },
+                components: [
+                    {
+                        type: 'body',
+                        parameters: [
+                            { type: 'text', text: 'WhatsApp account sales stopped sending after 5 failures in a row: Bad gateway' },
+                        ],
+                    },
+                ],
+            },
+        });
+    });
+
+    it('should ignore events that are not critical', async () => {
+        const send = vi.fn();
+        const sink = new AdminAlertSink({ numbers: ['111'] }, send);
+
+        await sink.send({ kind: 'circuit_closed', accountId: 'sales' });
+
+        expect(send).not.toHaveBeenCalled();
+    });
+});
@@ -0,0 +1,73 @@
+import { describe, it, expect, vi } from 'vitest';
+import { logger } from '@elizaos/core';
+import { EventBus } from '../../src/events/bus';
+
+describe('EventBus', () => {
+    const received = {
+        kind: 'message_received',
+        accountId: 'default',
+        message: { id: 'm1', from: '1234567890', timestamp: 0, type: 'text', content: 'Hi' },
+    };
+    const status = {
+        kind: 'status_changed',
+        accountId: 'default',
+        status: { messageId: 'wamid.1', status: 'read', recipient: '1234567890', timestamp: 0 },
+    };
+
+    it('should deliver every event to unfiltered subscribers', () => {
+        const bus = new EventBus();
+        const listener = vi.fn();
+        bus.subscribe(listener);
+
+        bus.publish(received);
+        bus.publish(status);
+
+        expect(listener.mock.calls).toEqual([[received], [status]]);
+    });
+
+    it('should filter by event kind and stop after unsubscribe', () => {
+        const bus = new EventBus();
+        const listener = vi.fn();
+        const unsubscribe = bus.subscribe(['status_changed'], listener);
+
+        bus.publish(received);
+        bus.publish(status);
+        unsubscribe();
+        bus.publish(status);
+
+        expect(listener).toHaveBeenCalledTimes(1);
+        expect(listener).toHaveBeenCalledWith(status);
+        expect(bus.size).toBe(0);
+    });
+
+    it('should isolate failing listeners', () => {
+        const bus = new EventBus();
+        const errorSpy = vi.spyOn(logger, 'error').mockImplementation(() => {});
+        const listener = vi.fn();
+        bus.subscribe(() => { throw new Error('boom'); });
+        bus.subscribe(listener);
+
+        bus.publish(received);
+
+        expect(listener).toHaveBeenCalledWith(received);
+        expect(errorSpy).toHaveBeenCalledWith('[WhatsApp] Event listener failed:', 'boom');
+        errorSpy.mockRestore();
+    });
+
+    it('should wait for async subscribers when delivering', async () => {
+        const bus = new EventBus();
+        const errorSpy = vi.spyOn(logger, 'error').mockImplementation(() => {});
+        let finished = false;
+        bus.subscribe(async () => {
+            await new Promise((resolve) => setTimeout(resolve, 5));
+            finished = true;
+        });
+        bus.subscribe(async () => { throw new Error('boom'); });
+
+        await bus.deliver(received);
+
+        expect(finished).toBe(true);
+        expect(errorSpy).toHaveBeenCalledWith('[WhatsApp] Event listener failed:', 'boom');
+        errorSpy.mockRestore();
+    });
+});
@@ -0,0 +1,57 @@
+import { describe, it, expect, vi } from 'vitest';
+import { createHmac } from 'crypto';
+import { WebhookForwarder } from '../../src/events/forwarder';
+
+describe('WebhookForwarder', () => {
+    const event = {
+        kind: 'message_received',
+        accountId: 'default',
+        message: { id: 'm1', from: '1234567890', timestamp: 1700000000, type: 'text', content: 'Hi' },
+    };
+
+    it('should POST the event with an HMAC signature', async () => {
+        const http = { post: vi.fn().mockResolvedValue({ status: 200 }) };
+        const forwarder = new WebhookForwarder({ url: 'https://crm.example.com/hook', secret: 's3cret' }, http);
+
+        await forwarder.send(event);
+
+        const [url, body, { headers }] = http.post.mock.calls[0];
+        const expected = createHmac('sha256', 's3cret')
+            .update(`${headers['X-WhatsApp-Timestamp']}.${body}`)
+            .digest('hex');
+        expect(url).toBe('https://crm.example.com/hook');
+        expect(JSON.parse(body)).toEqual(event);
+        expect(headers['X-WhatsApp-Event']).toBe('message_received');
+        expect(headers['X-WhatsApp-Signature']).toBe(`sha256=${expected}`);
+    });
+
+    it('should retry server errors with backoff', async () => {
+        const http = {
+            post: vi.fn()
+                .mockRejectedValueOnce({ response: { status: 503 } })
+                .mockRejectedValueOnce(new Error('ECONNRESET'))
+                .mockResolvedValue({ status: 200 }),
+        };
+        const forwarder = new WebhookForwarder({ url: 'https://crm.example.com/hook', backoffMs: 0 }, http);
+
+        await forwarder.send(event);
+
+        expect(http.post).toHaveBeenCalledTimes(3);
+    });
+
+    it('should give up on client errors and after the retry budget', async () => {
+        const rejected = { post: vi.fn().mockRejectedValue({ response: { status: 400 } }) };
+        await expect(new WebhookForwarder({ url: 'https://x', backoffMs: 0 }, rejected).send(event))
+            .rejects.toEqual({ response: { status: 400 } });
+        expect(rejected.post).toHaveBeenCalledTimes(1);
+
+        const down = { post: vi.fn().mockRejectedValue({ response: { status: 500 } }) };
+        await expect(new WebhookForwarder({ url: 'https://x', backoffMs: 0, maxRetries: 2 }, down).send(event))
+            .rejects.toBeDefined();
+        expect(down.post).toHaveBeenCalledTimes(3);
+    });
+
+    it('should forward inbound messages and statuses by default', () => {
+        expect(new WebhookForwarder({ url: 'https://x' }).kinds).toEqual(['message_received', 'status_changed']);
+    });
+});
@@ -0,0 +1,26 @@
+import { describe, it, expect } from 'vitest';
+import { WhatsAppEventType, runtimeEventType } from '../../src/events/runtime';
+
+describe('runtimeEventType', () => {
+    it('should map every bus event kind to a declared runtime event name', () => {
+        const kinds = [
+            'message_received',
+            'message_sent',
+            'status_changed',
+            'pairing_requested',
+            'connection_changed',
+            'template_status_changed',
+            'call',
+            'poll_result',
+            'circuit_opened',
+            'circuit_closed',
+            'account_degraded',
+            'account_recovered',
+            'send_failed',
+            'auth_failed',
+            'webhook_rejected',
+        ];
+
+        expect(kinds.map(runtimeEventType)).toEqual(Object.values(WhatsAppEventType));
+    });
+});
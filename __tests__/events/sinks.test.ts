@@ -0,0 +1,46 @@
+import { describe, it, expect, vi } from 'vitest';
+import { EventBus } from '../../src/events/bus';
+import { attachSink, createKafkaSink, createNatsSink } from '../../src/events/sinks';
+
+describe('event sinks', () => {
+    const event = {
+        kind: 'status_changed',
+        accountId: 'sales',
+        status: { messageId: 'wamid.1', status: 'delivered', recipient: '1234567890', timestamp: 0 },
+    };
+
+    it('should publish JSON to a Kafka topic keyed by account', async () => {
+        const producer = { send: vi.fn().mockResolvedValue(undefined) };
+
+        await createKafkaSink(producer).send(event);
+
+        expect(producer.send).toHaveBeenCalledWith({
+            topic: 'whatsapp-events',
+            messages: [{ key: 'sales', value: JSON.stringify(event), headers: { kind: 'status_changed' } }],
+        });
+    });
+
+    it('should publish to per-kind NATS subjects with a custom serializer', async () => {
+        const connection = { publish: vi.fn() };
+        const sink = createNatsSink(connection, { serialize: (e) => e.kind });
+
+        await sink.send(event);
+
+        const [subject, data] = connection.publish.mock.calls[0];
+        expect(subject).toBe('whatsapp.sales.status_changed');
+        expect(new TextDecoder().decode(data)).toBe('status_changed');
+    });
+
+    it('should forward only the requested kinds from the bus', () => {
+        const bus = new EventBus();
+        const sink = { send: vi.fn().mockResolvedValue(undefined) };
+        const detach = attachSink(bus, sink, ['message_sent']);
+
+        bus.publish(event);
+        bus.publish({ kind: 'message_sent', accountId: 'sales', message: { type: 'text', to: '1', content: 'x' }, messageId: 'wamid.2' });
+        detach();
+
+        expect(sink.send).toHaveBeenCalledTimes(1);
+        expect(sink.send.mock.calls[0][0].messageId).toBe('wamid.2');
+    });
+});
@@ -0,0 +1,22 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WhatsAppPlugin } from '../../src/index';
+import { WebhookEventBuilder } from '../../src/testing';
+
+describe('inbound translation', () => {
+    it('should leave translating to the message handler rather than the webhook path', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123', circuitBreaker: false });
+        const translate = vi.fn(async (text: string, to: string) => `[${to}] ${text}`);
+        plugin.useTranslator({ translate }, { agentLanguage: 'en', outbound: false });
+        await plugin.languages.set('34600000000', 'es');
+        const received = vi.fn();
+        plugin.on('message', received);
+        vi.spyOn(console, 'log').mockImplementation(() => {});
+
+        await plugin.handleWebhook(WebhookEventBuilder.textMessage('34600000000', 'Hola', { id: 'wamid.1' }).build());
+
+        expect(translate).not.toHaveBeenCalled();
+        const [msg] = received.mock.calls[0];
+        expect(msg.translation).toBeUndefined();
+        expect((await plugin.translateReceived(msg)).translation).toEqual({ text: '[en] Hola', from: 'es', to: 'en' });
+    });
+});
@@ -0,0 +1,111 @@
+import { describe, it, expect } from 'vitest';
+import { InboundChannel } from '../../src/handlers/inbound';
+
+const tick = () => new Promise((resolve) => setTimeout(resolve, 0));
+
+function deferred() {
+    let resolve!: () => void;
+    const promise = new Promise<void>((r) => (resolve = r));
+    return { promise, resolve };
+}
+
+describe('InboundChannel', () => {
+    it('should run at most `workers` items at once', async () => {
+        const channel = new InboundChannel({ workers: 2 });
+        const gates = [deferred(), deferred(), deferred()];
+        const done = gates.map((gate, i) => channel.push(() => gate.promise, `chat-${i}`));
+
+        await tick();
+        expect(channel.running).toBe(2);
+        expect(channel.depth).toBe(1);
+
+        gates[0].resolve();
+        await tick();
+        expect(channel.running).toBe(2);
+        expect(channel.depth).toBe(0);
+
+        gates[1].resolve();
+        gates[2].resolve();
+        await Promise.all(done);
+        expect(channel.running).toBe(0);
+    });
+
+    it('should keep items with the same key in order', async () => {
+        const channel = new InboundChannel({ workers: 4 });
+        const order: string[] = [];
+        const slow = deferred();
+
+        const first = channel.push(async () => {
+            await slow.promise;
+            order.push('a1');
+        }, 'a');
+        const second = channel.push(async () => void order.push('a2'), 'a');
+        const other = channel.push(async () => void order.push('b1'), 'b');
+
+        await other;
+        expect(order).toEqual(['b1']);
+        slow.resolve();
+        await Promise.all([first, second]);
+        expect(order).toEqual(['b1', 'a1', 'a2']);
+    });
+
+    it('should hold producers back while the queue is full', async () => {
+        const channel = new InboundChannel({ workers: 1, capacity: 1 });
+        const gate = deferred();
+        channel.push(() => gate.promise);
+        channel.push(async () => {});
+
+        let ready = false;
+        const waiting = channel.ready().then(() => (ready = true));
+        await tick();
+        expect(ready).toBe(false);
+
+        gate.resolve();
+        await waiting;
+        expect(ready).toBe(true);
+    });
+
+    it('should reject with the handler error', async () => {
+        const channel = new InboundChannel();
+        await expect(channel.push(async () => { throw new Error('boom'); })).rejects.toThrow('boom');
+        expect(channel.running).toBe(0);
+    });
+
+    it('should drop the oldest waiting item when full under drop_oldest', async () => {
+        const channel = new InboundChannel({ workers: 1, capacity: 1, overflow: 'drop_oldest' });
+        const ran: string[] = [];
+        const dropped: number[] = [];
+        channel.on('dropped', (count) => dropped.push(count));
+
+        await channel.process([
+            { run: async () => void ran.push('first') },
+            { run: async () => void ran.push('second') },
+            { run: async () => void ran.push('third') },
+        ]);
+
+        expect(ran).toEqual(['first', 'third']);
+        expect(dropped).toEqual([1]);
+    });
+
+    it('should refuse a batch that does not fit under reject', async () => {
+        const channel = new InboundChannel({ workers: 1, capacity: 1, overflow: 'reject' });
+        const gate = deferred();
+        const busy = channel.process([{ run: () => gate.promise }, { run: async () => {} }]);
+
+        await expect(channel.process([{ run: async () => {} }])).rejects.toMatchObject({ code: 'INBOUND_FULL' });
+
+        gate.resolve();
+        await busy;
+    });
+
+    it('should report wait and run times', async () => {
+        const channel = new InboundChannel();
+        const processed: Array<{ waitMs: number; runMs: number }> = [];
+        channel.on('processed', (timing) => processed.push(timing));
+
+        await channel.process([{ run: async () => {} }]);
+
+        expect(processed).toHaveLength(1);
+        expect(processed[0].runMs).toBeGreaterThanOrEqual(0);
+    });
+});
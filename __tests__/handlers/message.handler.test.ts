@@ -2,6 +2,7 @@ import { describe, it, expect, vi, beforeEach } from 'vitest';
 import { MessageHandler } from '../../src/handlers/message.handler';
 import { WhatsAppClient } from '../../src/client';
 import { WhatsAppMessage } from '../../src/types';
+import { WhatsAppError } from '../../src/errors';
 
 describe('MessageHandler', () => {
     let messageHandler;
@@ -64,4 +65,16 @@ describe('MessageHandler', () => {
             .rejects
             .toThrow('Failed to send WhatsApp message');
     });
+
+    it('should wrap client errors as WhatsAppError and keep the original as cause', async () => {
+        const original = new Error('socket hang up');
+        (mockClient.sendMessage).mockRejectedValue(original);
+
+        await expect(messageHandler.send({ type: 'text', to: '1234567890', content: 'Hi' }))
+            .rejects
+            .toMatchObject({ code: 'SEND_FAILED', cause: original });
+        await expect(messageHandler.send({ type: 'text', to: '1234567890', content: 'Hi' }))
+            .rejects
+            .toBeInstanceOf(WhatsAppError);
+    });
 });
@@ -0,0 +1,42 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WhatsAppPlugin } from '../../src/index';
+import { WebhookEventBuilder } from '../../src/testing';
+import { ManualClock } from '../../src/utils/clock';
+
+describe('webhook redelivery', () => {
+    it('should hand a redelivered message to subscribers once', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123', circuitBreaker: false });
+        const received = vi.fn();
+        plugin.on('message', received);
+        vi.spyOn(console, 'log').mockImplementation(() => {});
+        const event = WebhookEventBuilder.textMessage('15551234567', 'Hello', { id: 'wamid.1' }).build();
+
+        await plugin.handleWebhook(event);
+        await plugin.handleWebhook(event);
+        await plugin.handleWebhook(WebhookEventBuilder.textMessage('15551234567', 'Again', { id: 'wamid.2' }).build());
+
+        expect(received.mock.calls.map(([m]) => m.id)).toEqual(['wamid.1', 'wamid.2']);
+    });
+});
+
+describe('unsupported message notice', () => {
+    it('should answer a burst of stickers once per chat', async () => {
+        const clock = new ManualClock();
+        const plugin = new WhatsAppPlugin(
+            { accessToken: 'token', phoneNumberId: '123', circuitBreaker: false, unsupportedMessageReply: 'Text only, please' },
+            clock
+        );
+        const send = vi.fn(async () => ({ messages: [{ id: 'wamid.out' }] }));
+        (plugin as unknown as { messageHandler: unknown }).messageHandler = { send };
+        const sticker = (id: string, from = '15551234567') => ({ id, from, timestamp: 1700000000, type: 'sticker' });
+
+        await plugin['receiveUnsupported'](sticker('wamid.1'));
+        await plugin['receiveUnsupported'](sticker('wamid.2'));
+        await plugin['receiveUnsupported'](sticker('wamid.3', '15557654321'));
+        await vi.waitFor(() => expect(send).toHaveBeenCalledTimes(2));
+
+        clock.advance(60 * 60 * 1000);
+        await plugin['receiveUnsupported'](sticker('wamid.4'));
+        await vi.waitFor(() => expect(send).toHaveBeenCalledTimes(3));
+    });
+});
@@ -2,6 +2,8 @@ import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
 import { WebhookHandler } from '../../src/handlers/webhook.handler';
 import { WhatsAppClient } from '../../src/client';
 import { WhatsAppWebhookEvent } from '../../src/types';
+import { redact } from '../../src/utils/redact';
+import { WebhookEventBuilder } from '../../src/testing';
 
 describe('WebhookHandler', () => {
     let webhookHandler;
@@ -47,7 +49,7 @@ describe('WebhookHandler', () => {
 
         await webhookHandler.handle(mockEvent);
 
-        expect(consoleSpy).toHaveBeenCalledWith('Received message:', mockMessage);
+        expect(consoleSpy).toHaveBeenCalledWith('Received message:', redact(mockMessage));
     });
 
     it('should handle status updates correctly', async () => {
@@ -78,7 +80,7 @@ describe('WebhookHandler', () => {
 
         await webhookHandler.handle(mockEvent);
 
-        expect(consoleSpy).toHaveBeenCalledWith('Received status update:', mockStatus);
+        expect(consoleSpy).toHaveBeenCalledWith('Received status update:', redact(mockStatus));
     });
 
     it('should handle events with both messages and statuses', async () => {
@@ -118,8 +120,383 @@ describe('WebhookHandler', () => {
 
         await webhookHandler.handle(mockEvent);
 
-        expect(consoleSpy).toHaveBeenCalledWith('Received message:', mockMessage);
-        expect(consoleSpy).toHaveBeenCalledWith('Received status update:', mockStatus);
+        expect(consoleSpy).toHaveBeenCalledWith('Received message:', redact(mockMessage));
+        expect(consoleSpy).toHaveBeenCalledWith('Received status update:', redact(mockStatus));
+    });
+
+    it('should redact phone numbers and message bodies in logs by default', async () => {
+        const mockEvent = {
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: {
+                            display_phone_number: '1234567890',
+                            phone_number_id: 'PHONE_ID'
+                        },
+                        messages: [{
+                            from: '14155550123',
+                            id: 'msg_id',
+                            timestamp: '1234567890',
+                            text: { body: 'my secret' },
+                            type: 'text'
+                        }]
+                    }
+                }]
+            }]
+        };
+
+        await webhookHandler.handle(mockEvent);
+
+        expect(consoleSpy).toHaveBeenCalledWith('Received message:', {
+            from: '1********23',
+            id: 'msg_id',
+            timestamp: '1234567890',
+            text: { body: '[9 chars]' },
+            type: 'text'
+        });
+
+        consoleSpy.mockClear();
+        await new WebhookHandler(mockClient, { logMessageContent: true }).handle(mockEvent);
+        expect(consoleSpy.mock.calls[0][1].text.body).toBe('my secret');
+    });
+
+    it('should emit media messages with the sender profile name', async () => {
+        const messages = vi.fn();
+        webhookHandler.on('message', messages);
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        contacts: [{ wa_id: '1234567890', profile: { name: 'Ada' } }],
+                        messages: [{
+                            from: '1234567890',
+                            id: 'wamid.3',
+                            timestamp: '1700000000',
+                            type: 'document',
+                            document: { id: 'media-9', mime_type: 'application/pdf', filename: 'invoice.pdf' },
+                        }],
+                    },
+                    field: 'messages'
+                }]
+            }]
+        });
+
+        expect(messages).toHaveBeenCalledWith({
+            id: 'wamid.3',
+            from: '1234567890',
+            timestamp: 1700000000,
+            type: 'document',
+            content: '',
+            senderName: 'Ada',
+            media: { id: 'media-9', mimeType: 'application/pdf', caption: undefined, fileName: 'invoice.pdf' },
+        });
+    });
+
+    it('should emit reactions with the reacted message id', async () => {
+        const messages = vi.fn();
+        webhookHandler.on('message', messages);
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        messages: [{
+                            from: '1234567890',
+                            id: 'wamid.4',
+                            timestamp: '1700000000',
+                            type: 'reaction',
+                            reaction: { message_id: 'wamid.1', emoji: '👍' },
+                        }],
+                    },
+                    field: 'messages'
+                }]
+            }]
+        });
+
+        expect(messages).toHaveBeenCalledWith({
+            id: 'wamid.4',
+            from: '1234567890',
+            timestamp: 1700000000,
+            type: 'reaction',
+            content: '',
+            reaction: { messageId: 'wamid.1', emoji: '👍' },
+        });
+    });
+
+    it('should parse template quick-reply taps apart from interactive replies', async () => {
+        const messages = vi.fn();
+        webhookHandler.on('message', messages);
+
+        await webhookHandler.handle(
+            new WebhookEventBuilder()
+                .templateButtonReply('15551234567', 'wamid.template', 'Stop promotions', 'STOP_PROMOS', { id: 'wamid.5', timestamp: 1700000000 })
+                .templateButtonReply('15551234567', 'wamid.template', 'Talk to us', undefined, { id: 'wamid.6', timestamp: 1700000001 })
+                .build()
+        );
+
+        expect(messages).toHaveBeenNthCalledWith(1, {
+            id: 'wamid.5',
+            from: '15551234567',
+            timestamp: 1700000000,
+            type: 'button',
+            content: 'Stop promotions',
+            replyTo: 'wamid.template',
+            button: { text: 'Stop promotions', payload: 'STOP_PROMOS' },
+        });
+        expect(messages.mock.calls[1][0]).toMatchObject({ type: 'button', button: { text: 'Talk to us', payload: 'Talk to us' } });
+        expect(messages.mock.calls[1][0]).not.toHaveProperty('replyId');
+    });
+
+    it('should report message types it cannot read instead of emitting empty text', async () => {
+        const messages = vi.fn();
+        const unsupported = vi.fn();
+        webhookHandler.on('message', messages);
+        webhookHandler.on('unsupported', unsupported);
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        contacts: [{ wa_id: '15551234567', profile: { name: 'Ada' } }],
+                        messages: [
+                            {
+                                from: '15551234567',
+                                id: 'wamid.7',
+                                timestamp: '1700000000',
+                                type: 'unsupported',
+                                errors: [{
+                                    code: 131051,
+                                    title: 'Message type unknown',
+                                    error_data: { details: 'Message type is currently not supported.' },
+                                }],
+                            },
+                            { from: '15551234567', id: 'wamid.8', timestamp: '1700000001', type: 'sticker' },
+                        ],
+                    },
+                    field: 'messages'
+                }]
+            }]
+        });
+
+        expect(messages).not.toHaveBeenCalled();
+        expect(unsupported).toHaveBeenNthCalledWith(1, {
+            id: 'wamid.7',
+            from: '15551234567',
+            timestamp: 1700000000,
+            type: 'unsupported',
+            senderName: 'Ada',
+            reason: 'Message type is currently not supported.',
+        });
+        expect(unsupported).toHaveBeenNthCalledWith(2, {
+            id: 'wamid.8',
+            from: '15551234567',
+            timestamp: 1700000001,
+            type: 'sticker',
+            senderName: 'Ada',
+        });
+    });
+
+    it('should emit shared locations with a readable description', async () => {
+        const messages = vi.fn();
+        webhookHandler.on('message', messages);
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        messages: [{
+                            from: '15551234567',
+                            id: 'wamid.9',
+                            timestamp: '1700000000',
+                            type: 'location',
+                            location: { latitude: 40.7484, longitude: -73.9857, name: 'Empire State Building' },
+                        }],
+                    },
+                    field: 'messages'
+                }]
+            }]
+        });
+
+        expect(messages).toHaveBeenCalledWith({
+            id: 'wamid.9',
+            from: '15551234567',
+            timestamp: 1700000000,
+            type: 'location',
+            content: 'Shared a location: Empire State Building',
+            location: { latitude: 40.7484, longitude: -73.9857, name: 'Empire State Building' },
+        });
+    });
+
+    it('should emit Business app echoes addressed to the customer', async () => {
+        const echoes = vi.fn();
+        const messages = vi.fn();
+        webhookHandler.on('echo', echoes);
+        webhookHandler.on('message', messages);
+
+        await webhookHandler.handle(
+            new WebhookEventBuilder({ displayPhoneNumber: '15550001111' })
+                .messageEcho('15551234567', 'On our way!', { id: 'wamid.echo', timestamp: 1700000000 })
+                .build()
+        );
+
+        expect(messages).not.toHaveBeenCalled();
+        expect(echoes).toHaveBeenCalledWith({
+            id: 'wamid.echo',
+            from: '15550001111',
+            to: '15551234567',
+            timestamp: 1700000000,
+            type: 'text',
+            content: 'On our way!',
+        });
+    });
+
+    it('should parse submitted addresses', async () => {
+        const messages = vi.fn();
+        webhookHandler.on('message', messages);
+        const response = {
+            saved_address_id: 'home',
+            values: { name: 'Ada', address: '12 Main Rd', city: 'Mumbai', state: 'Maharashtra', in_pin_code: '400063', floor_number: '' },
+        };
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        messages: [{
+                            from: '919000000000',
+                            id: 'wamid.5',
+                            timestamp: '1700000000',
+                            type: 'interactive',
+                            interactive: {
+                                type: 'nfm_reply',
+                                nfm_reply: { name: 'address_message', body: 'Sent', response_json: JSON.stringify(response) },
+                            },
+                        }],
+                    },
+                    field: 'messages'
+                }]
+            }]
+        });
+
+        expect(messages).toHaveBeenCalledWith(expect.objectContaining({
+            type: 'interactive',
+            content: 'Ada, 12 Main Rd, Mumbai, Maharashtra 400063',
+            address: {
+                savedAddressId: 'home',
+                name: 'Ada',
+                address: '12 Main Rd',
+                city: 'Mumbai',
+                state: 'Maharashtra',
+                postalCode: '400063',
+                values: response.values,
+            },
+        }));
+    });
+
+    it('should emit status and template status updates', async () => {
+        const statuses = vi.fn();
+        const templates = vi.fn();
+        webhookHandler.on('status', statuses);
+        webhookHandler.on('templateStatus', templates);
+
+        await webhookHandler.handle({
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [
+                    {
+                        value: {
+                            messaging_product: 'whatsapp',
+                            metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                            statuses: [{ id: 'wamid.1', status: 'read', timestamp: '1700000000', recipient_id: '1234567890' }]
+                        },
+                        field: 'messages'
+                    },
+                    {
+                        value: {
+                            event: 'REJECTED',
+                            message_template_id: 594425479261596,
+                            message_template_name: 'order_update',
+                            message_template_language: 'en_US',
+                            reason: 'INCORRECT_CATEGORY'
+                        },
+                        field: 'message_template_status_update'
+                    }
+                ]
+            }]
+        });
+
+        expect(statuses).toHaveBeenCalledWith({
+            messageId: 'wamid.1',
+            status: 'read',
+            recipient: '1234567890',
+            timestamp: 1700000000,
+        });
+        expect(templates).toHaveBeenCalledWith({
+            templateId: '594425479261596',
+            name: 'order_update',
+            language: 'en_US',
+            status: 'REJECTED',
+            reason: 'INCORRECT_CATEGORY',
+        });
+    });
+
+    it('should not hold up other chats behind a slow listener', async () => {
+        const event = {
+            object: 'whatsapp_business_account',
+            entry: [{
+                id: 'BUSINESS_ID',
+                changes: [{
+                    value: {
+                        messaging_product: 'whatsapp',
+                        metadata: { display_phone_number: '1234567890', phone_number_id: 'PHONE_ID' },
+                        messages: [
+                            { from: '111', id: 'm1', timestamp: '1', text: { body: 'slow' } },
+                            { from: '222', id: 'm2', timestamp: '1', text: { body: 'fast' } },
+                        ],
+                    },
+                }],
+            }],
+        };
+        const handled: string[] = [];
+        let release!: () => void;
+        const slow = new Promise<void>((resolve) => (release = resolve));
+        webhookHandler.on('message', async (message) => {
+            if (message.content === 'slow') await slow;
+            handled.push(message.id);
+        });
+
+        const done = webhookHandler.handle(event);
+        await new Promise((resolve) => setTimeout(resolve, 0));
+        expect(handled).toEqual(['m2']);
+
+        release();
+        await done;
+        expect(handled).toEqual(['m2', 'm1']);
     });
 
     it('should handle errors correctly', async () => {
@@ -0,0 +1,69 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ logger: { error: vi.fn(), warn: vi.fn() } }));
+vi.mock('../../src/service', () => ({ WhatsAppConnectorService: { serviceType: 'whatsapp_connector' } }));
+
+import { createLambdaWebhookHandler } from '../../src/http/lambda';
+
+describe('createLambdaWebhookHandler', () => {
+    const target = {
+        verifyWebhook: vi.fn(async (token) => token === 'secret'),
+        handleWebhook: vi.fn(),
+    };
+    const event = { object: 'whatsapp_business_account', entry: [] };
+
+    it('should answer the verification handshake from a REST API event', async () => {
+        const handler = createLambdaWebhookHandler(target);
+
+        const result = await handler({
+            httpMethod: 'GET',
+            queryStringParameters: {
+                'hub.mode': 'subscribe',
+                'hub.verify_token': 'secret',
+                'hub.challenge': 'abc',
+            },
+        });
+
+        expect(result.statusCode).toBe(200);
+        expect(result.body).toBe('abc');
+    });
+
+    it('should process base64 encoded HTTP API notifications', async () => {
+        const handler = createLambdaWebhookHandler(target);
+
+        const result = await handler({
+            requestContext: { http: { method: 'POST' } },
+            body: Buffer.from(JSON.stringify(event)).toString('base64'),
+            isBase64Encoded: true,
+        });
+
+        expect(result.statusCode).toBe(200);
+        expect(target.handleWebhook).toHaveBeenCalledWith(event);
+    });
+
+    it('should hand the raw body and signature header to signature checks', async () => {
+        const signed = { ...target, verifySignature: vi.fn(() => false) };
+        const handler = createLambdaWebhookHandler(signed);
+        const body = JSON.stringify(event);
+
+        const result = await handler({
+            httpMethod: 'POST',
+            headers: { 'X-Hub-Signature-256': 'sha256=abc' },
+            body,
+        });
+
+        expect(result.statusCode).toBe(401);
+        expect(signed.verifySignature).toHaveBeenCalledWith(event, body, 'sha256=abc');
+    });
+
+    it('should build the target once and reject malformed bodies', async () => {
+        const factory = vi.fn(() => target);
+        const handler = createLambdaWebhookHandler(factory);
+
+        await handler({ httpMethod: 'POST', body: JSON.stringify(event) });
+        const result = await handler({ httpMethod: 'POST', body: '{not json' });
+
+        expect(result.statusCode).toBe(400);
+        expect(factory).toHaveBeenCalledTimes(1);
+    });
+});
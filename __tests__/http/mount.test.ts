@@ -0,0 +1,66 @@
+import { describe, it, expect, vi } from 'vitest';
+import { Readable } from 'stream';
+import { mountRoutes } from '../../src/http/mount';
+
+function request(method, url, body?) {
+    return Object.assign(Readable.from(body ? [Buffer.from(JSON.stringify(body))] : []), {
+        method,
+        url,
+        headers: {},
+    });
+}
+
+function response() {
+    return {
+        statusCode: 200,
+        headers: {},
+        body: undefined,
+        headersSent: false,
+        setHeader(name, value) { this.headers[name] = value; },
+        end(data) { this.body = data; },
+    };
+}
+
+describe('mountRoutes', () => {
+    const runtime = {};
+    const routes = [
+        {
+            type: 'POST',
+            path: '/whatsapp/admin/failed-sends/:id/replay',
+            handler: vi.fn(async (req, res) => res.json({ id: req.params.id, body: req.body })),
+        },
+        {
+            type: 'GET',
+            path: '/whatsapp/webhook',
+            handler: vi.fn(async (req, res) => res.status(200).send(req.query['hub.challenge'])),
+        },
+    ];
+
+    it('should match path params and parse JSON bodies', async () => {
+        const res = response();
+
+        await mountRoutes(routes, runtime)(request('POST', '/whatsapp/admin/failed-sends/abc/replay', { a: 1 }), res);
+
+        expect(JSON.parse(res.body)).toEqual({ id: 'abc', body: { a: 1 } });
+        expect(res.headers['Content-Type']).toBe('application/json');
+        expect(routes[0].handler.mock.calls[0][2]).toBe(runtime);
+    });
+
+    it('should expose query parameters and honour a prefix', async () => {
+        const res = response();
+
+        await mountRoutes(routes, runtime, '/hooks')(request('GET', '/hooks/whatsapp/webhook?hub.challenge=42'), res);
+
+        expect(res.body).toBe('42');
+    });
+
+    it('should defer unmatched requests to next', async () => {
+        const next = vi.fn();
+        const res = response();
+
+        await mountRoutes(routes, runtime)(request('GET', '/elsewhere'), res, next);
+
+        expect(next).toHaveBeenCalledWith();
+        expect(res.body).toBeUndefined();
+    });
+});
@@ -0,0 +1,64 @@
+import { describe, it, expect } from 'vitest';
+import type { IAgentRuntime } from '@elizaos/core';
+import { RuntimeWebhookPayloadStore, WebhookLog, type StoredWebhook, type WebhookPayloadStore } from '../../src/http/webhook-log';
+import { ManualClock } from '../../src/utils/clock';
+
+describe('WebhookLog', () => {
+    it('should keep the most recent notifications within the age limit', () => {
+        const clock = new ManualClock(1_000);
+        const log = new WebhookLog({ maxEntries: 2, maxAgeMs: 60_000 }, clock);
+
+        const first = log.record('{"n":1}', 'sales');
+        clock.advance(10_000);
+        log.record('{"n":2}');
+        log.record('{"n":3}');
+
+        expect(log.get(first.id)).toBeUndefined();
+        expect(log.list().map((entry) => entry.body)).toEqual(['{"n":2}', '{"n":3}']);
+
+        clock.advance(60_000);
+        expect(log.size).toBe(0);
+    });
+
+    it('should restore stored notifications and save each change', async () => {
+        const added: StoredWebhook[] = [];
+        const removed: string[][] = [];
+        const store: WebhookPayloadStore = {
+            load: async () => [{ id: 'webhook-1-1', receivedAt: 1, accountId: 'sales', body: '{}' }],
+            add: async (entry) => void added.push(entry),
+            remove: async (ids) => void removed.push(ids),
+        };
+        const log = new WebhookLog({}, new ManualClock(2));
+
+        await log.useStore(store);
+        const entry = log.record('{"entry":[]}');
+        log.remove('webhook-1-1');
+        await log.flush();
+
+        expect(log.list()).toEqual([entry]);
+        expect(added).toEqual([entry]);
+        expect(removed).toEqual([['webhook-1-1']]);
+    });
+
+    it('should keep one runtime cache key per notification', async () => {
+        const cache = new Map<string, unknown>([
+            ['whatsapp-webhook-log', [{ id: 'webhook-1-1', receivedAt: 1, body: '{}' }]],
+        ]);
+        const runtime = {
+            getCache: async (key: string) => cache.get(key),
+            setCache: async (key: string, value: unknown) => void cache.set(key, value),
+            deleteCache: async (key: string) => void cache.delete(key),
+        } as unknown as IAgentRuntime;
+        const store = new RuntimeWebhookPayloadStore(runtime);
+
+        expect(await store.load()).toEqual([{ id: 'webhook-1-1', receivedAt: 1, body: '{}' }]);
+        expect(cache.has('whatsapp-webhook-log')).toBe(false);
+
+        await store.add({ id: 'webhook-2-2', receivedAt: 2, body: '{"n":2}' });
+        await store.remove(['webhook-1-1']);
+
+        expect(cache.get('whatsapp-webhook-log-index')).toEqual(['webhook-2-2']);
+        expect(cache.has('whatsapp-webhook-log-webhook-1-1')).toBe(false);
+        expect(await store.load()).toEqual([{ id: 'webhook-2-2', receivedAt: 2, body: '{"n":2}' }]);
+    });
+});
@@ -0,0 +1,120 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ logger: { error: vi.fn(), warn: vi.fn() } }));
+vi.mock('../../src/service', () => ({ WhatsAppConnectorService: { serviceType: 'whatsapp_connector' } }));
+
+import { createHmac } from 'crypto';
+import { logger } from '@elizaos/core';
+import { receiveWebhookRequest, verifyWebhookRequest } from '../../src/http/webhook';
+import { isValidSignature } from '../../src/http/signature';
+
+const sign = (body: string, secret: string) => `sha256=${createHmac('sha256', secret).update(body).digest('hex')}`;
+
+describe('webhook requests', () => {
+    const target = {
+        verifyWebhook: vi.fn(async (token) => token === 'secret'),
+        handleWebhook: vi.fn(),
+    };
+
+    it('should echo the challenge for a valid subscription', async () => {
+        const result = await verifyWebhookRequest(target, {
+            'hub.mode': 'subscribe',
+            'hub.verify_token': 'secret',
+            'hub.challenge': '1158201444',
+        });
+
+        expect(result).toEqual({ status: 200, body: '1158201444' });
+    });
+
+    it('should reject a wrong verify token', async () => {
+        const result = await verifyWebhookRequest(target, {
+            'hub.mode': 'subscribe',
+            'hub.verify_token': 'wrong',
+            'hub.challenge': '1158201444',
+        });
+
+        expect(result.status).toBe(403);
+    });
+
+    it('should process notifications and log failures without failing the request', async () => {
+        const event = { object: 'whatsapp_business_account', entry: [] };
+
+        expect(await receiveWebhookRequest(target, event)).toEqual({ status: 200 });
+        await vi.waitFor(() => expect(target.handleWebhook).toHaveBeenCalledWith(event));
+
+        target.handleWebhook.mockRejectedValueOnce(new Error('boom'));
+        expect(await receiveWebhookRequest(target, event)).toEqual({ status: 200 });
+        await vi.waitFor(() =>
+            expect(logger.error).toHaveBeenCalledWith('[WhatsApp] Webhook processing failed:', 'boom')
+        );
+        expect(await receiveWebhookRequest(target, { hello: 'world' })).toEqual({ status: 400 });
+    });
+
+    it('should answer before the notification has been handled', async () => {
+        const event = { object: 'whatsapp_business_account', entry: [] };
+        let finish = () => {};
+        const slow = { ...target, handleWebhook: vi.fn(() => new Promise<void>((resolve) => (finish = resolve))) };
+
+        expect(await receiveWebhookRequest(slow, event)).toEqual({ status: 200 });
+        await vi.waitFor(() => expect(slow.handleWebhook).toHaveBeenCalledTimes(1));
+        finish();
+    });
+
+    it('should pass the account of a per-account callback URL to the verify check', async () => {
+        const query = { 'hub.mode': 'subscribe', 'hub.verify_token': 'secret', 'hub.challenge': '1' };
+
+        await verifyWebhookRequest(target, query, 'sales');
+
+        expect(target.verifyWebhook).toHaveBeenLastCalledWith('secret', 'sales');
+    });
+
+    it('should reject notifications the target cannot verify the signature of', async () => {
+        const event = { object: 'whatsapp_business_account', entry: [] };
+        const rawBody = JSON.stringify(event);
+        const signed = {
+            ...target,
+            handleWebhook: vi.fn(),
+            verifySignature: vi.fn((_e, raw, signature) => isValidSignature(raw ?? '', signature, 'app-secret')),
+        };
+
+        expect(await receiveWebhookRequest(signed, event, { rawBody, signature: sign(rawBody, 'app-secret') }))
+            .toEqual({ status: 200 });
+        expect(await receiveWebhookRequest(signed, event, { rawBody, signature: sign(rawBody, 'other') }))
+            .toEqual({ status: 401 });
+        expect(await receiveWebhookRequest(signed, event)).toEqual({ status: 401 });
+        expect(signed.handleWebhook).toHaveBeenCalledTimes(1);
+    });
+
+    it('should record authentic notifications before handling them', async () => {
+        const event = { object: 'whatsapp_business_account', entry: [] };
+        const rawBody = JSON.stringify(event);
+        const recording = {
+            ...target,
+            handleWebhook: vi.fn().mockRejectedValue(new Error('handler bug')),
+            verifySignature: vi.fn((_e, raw, signature) => isValidSignature(raw ?? '', signature, 'app-secret')),
+            recordWebhook: vi.fn(),
+        };
+
+        expect(await receiveWebhookRequest(recording, event, { rawBody, signature: sign(rawBody, 'app-secret') }))
+            .toEqual({ status: 200 });
+        expect(await receiveWebhookRequest(recording, event, { rawBody, signature: sign(rawBody, 'other') }))
+            .toEqual({ status: 401 });
+        expect(recording.recordWebhook).toHaveBeenCalledTimes(1);
+        expect(recording.recordWebhook).toHaveBeenCalledWith(event, rawBody);
+    });
+});
+
+describe('isValidSignature', () => {
+    const body = '{"object":"whatsapp_business_account","entry":[{"id":"1","changes":[]}]}';
+
+    it('should accept the HMAC of the raw body', () => {
+        expect(isValidSignature(body, sign(body, 'app-secret'), 'app-secret')).toBe(true);
+        expect(isValidSignature(Buffer.from(body), sign(body, 'app-secret'), 'app-secret')).toBe(true);
+    });
+
+    it('should reject missing, malformed and mismatched signatures', () => {
+        expect(isValidSignature(body, undefined, 'app-secret')).toBe(false);
+        expect(isValidSignature(body, 'sha1=abc', 'app-secret')).toBe(false);
+        expect(isValidSignature(`${body} `, sign(body, 'app-secret'), 'app-secret')).toBe(false);
+    });
+});
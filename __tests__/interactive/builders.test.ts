@@ -0,0 +1,163 @@
+import { describe, it, expect } from 'vitest';
+import {
+    AddressMessageBuilder,
+    ButtonMessageBuilder,
+    CtaUrlMessageBuilder,
+    FlowMessageBuilder,
+    ListMessageBuilder,
+} from '../../src/interactive/builders';
+import { InteractiveLimitError } from '../../src/errors';
+
+describe('ListMessageBuilder', () => {
+    it('should build a list message with sections', () => {
+        const list = new ListMessageBuilder('Pick a slot')
+            .header('Appointments')
+            .button('Slots')
+            .section('Today', (s) => s.row('t9', '9:00').row('t10', '10:00', 'With Dr. Lee'))
+            .section('Tomorrow', (s) => s.row('m9', '9:00'))
+            .build();
+
+        expect(list).toEqual({
+            type: 'list',
+            header: { type: 'text', text: 'Appointments' },
+            body: { text: 'Pick a slot' },
+            action: {
+                button: 'Slots',
+                sections: [
+                    { title: 'Today', rows: [{ id: 't9', title: '9:00' }, { id: 't10', title: '10:00', description: 'With Dr. Lee' }] },
+                    { title: 'Tomorrow', rows: [{ id: 'm9', title: '9:00' }] },
+                ],
+            },
+        });
+    });
+
+    it('should reject rows over the length limits with the field name', () => {
+        const list = new ListMessageBuilder('Pick one');
+
+        expect(() => list.section(undefined, (s) => s.row('a', 'x'.repeat(25)))).toThrow(InteractiveLimitError);
+        expect(() => list.section(undefined, (s) => s.row('a', 'x'.repeat(25)))).toThrow(
+            'Row "a" title has 25 characters; WhatsApp allows at most 24'
+        );
+        expect(() => list.section(undefined, (s) => s.row('a', 'A', 'x'.repeat(73)))).toThrow('Row "a" description');
+        expect(() => new ListMessageBuilder('Pick one').button('x'.repeat(21))).toThrow('List button');
+    });
+
+    it('should count characters rather than UTF-16 code units', () => {
+        expect(() => new ListMessageBuilder('Pick').section(undefined, (s) => s.row('a', '🍕'.repeat(24)))).not.toThrow();
+    });
+
+    it('should limit rows across sections and sections per list', () => {
+        const list = new ListMessageBuilder('Pick one')
+            .section('A', (s) => [1, 2, 3, 4, 5, 6].reduce((r, n) => r.row(`a${n}`, `${n}`), s));
+
+        expect(() => list.section('B', (s) => [1, 2, 3, 4, 5].reduce((r, n) => r.row(`b${n}`, `${n}`), s))).toThrow(
+            'List rows has 11 entries'
+        );
+    });
+
+    it('should reject duplicate row ids and untitled sections in multi-section lists', () => {
+        expect(() => new ListMessageBuilder('Pick').section('A', (s) => s.row('x', 'X').row('x', 'Y'))).toThrow(
+            'Row id "x" is used more than once'
+        );
+        const untitled = new ListMessageBuilder('Pick').section(undefined, (s) => s.row('a', 'A')).section('B', (s) => s.row('b', 'B'));
+        expect(() => untitled.build()).toThrow('Every section needs a title');
+        expect(() => new ListMessageBuilder('Pick').build()).toThrow('at least one section');
+    });
+});
+
+describe('ButtonMessageBuilder', () => {
+    it('should build reply buttons', () => {
+        expect(new ButtonMessageBuilder('Confirm?').footer('Reply within 24h').button('yes', 'Yes').button('no', 'No').build()).toEqual({
+            type: 'button',
+            body: { text: 'Confirm?' },
+            footer: { text: 'Reply within 24h' },
+            action: {
+                buttons: [
+                    { type: 'reply', reply: { id: 'yes', title: 'Yes' } },
+                    { type: 'reply', reply: { id: 'no', title: 'No' } },
+                ],
+            },
+        });
+    });
+
+    it('should enforce the button count and title length', () => {
+        const buttons = new ButtonMessageBuilder('Pick').button('a', 'A').button('b', 'B').button('c', 'C');
+
+        expect(() => buttons.button('d', 'D')).toThrow('Reply buttons has 4 entries; WhatsApp allows at most 3');
+        expect(() => new ButtonMessageBuilder('Pick').button('a', 'x'.repeat(21))).toThrow('Button "a" title');
+        expect(() => new ButtonMessageBuilder('')).toThrow('Body cannot be empty');
+    });
+});
+
+describe('CtaUrlMessageBuilder', () => {
+    it('should build a cta_url message', () => {
+        expect(new CtaUrlMessageBuilder('Track your order').link('Track', 'https://example.com/t/42').build()).toEqual({
+            type: 'cta_url',
+            body: { text: 'Track your order' },
+            action: { name: 'cta_url', parameters: { display_text: 'Track', url: 'https://example.com/t/42' } },
+        });
+    });
+
+    it('should require a valid link', () => {
+        expect(() => new CtaUrlMessageBuilder('Track').build()).toThrow('needs a link');
+        expect(() => new CtaUrlMessageBuilder('Track').link('Go', 'ftp://example.com')).toThrow('http or https');
+        expect(() => new CtaUrlMessageBuilder('Track').link('x'.repeat(21), 'https://example.com')).toThrow('Button text');
+    });
+});
+
+describe('FlowMessageBuilder', () => {
+    it('should build a flow message that opens on a screen', () => {
+        const message = new FlowMessageBuilder('Book a table', { flowId: '123', cta: 'Book', token: 'tok-1' })
+            .navigate('DATE', { party: 2 })
+            .build();
+
+        expect(message).toEqual({
+            type: 'flow',
+            body: { text: 'Book a table' },
+            action: {
+                name: 'flow',
+                parameters: {
+                    flow_message_version: '3',
+                    flow_id: '123',
+                    flow_cta: 'Book',
+                    flow_token: 'tok-1',
+                    flow_action: 'navigate',
+                    flow_action_payload: { screen: 'DATE', data: { party: 2 } },
+                },
+            },
+        });
+    });
+
+    it('should need exactly one of flow id or name', () => {
+        expect(() => new FlowMessageBuilder('Book', { cta: 'Book' })).toThrow('exactly one of flowId or flowName');
+        expect(() => new FlowMessageBuilder('Book', { flowId: '1', flowName: 'booking', cta: 'Book' })).toThrow(
+            'exactly one'
+        );
+    });
+});
+
+describe('AddressMessageBuilder', () => {
+    it('should build an address request with prefilled and saved addresses', () => {
+        const message = new AddressMessageBuilder('Where should we deliver?', 'in')
+            .prefill({ name: 'Ada', phone_number: '+919000000000' })
+            .savedAddress('home', { in_pin_code: '400063', city: 'Mumbai' })
+            .build();
+
+        expect(message).toEqual({
+            type: 'address_message',
+            body: { text: 'Where should we deliver?' },
+            action: {
+                name: 'address_message',
+                parameters: {
+                    country: 'IN',
+                    values: { name: 'Ada', phone_number: '+919000000000' },
+                    saved_addresses: [{ id: 'home', value: { in_pin_code: '400063', city: 'Mumbai' } }],
+                },
+            },
+        });
+    });
+
+    it('should reject unsupported countries', () => {
+        expect(() => new AddressMessageBuilder('Address?', 'US')).toThrow('only available in IN, SG');
+    });
+});
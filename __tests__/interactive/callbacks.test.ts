@@ -0,0 +1,35 @@
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { ButtonCallbackRegistry } from '../../src/interactive/callbacks';
+
+describe('ButtonCallbackRegistry', () => {
+    afterEach(() => {
+        vi.useRealTimers();
+    });
+
+    it('should resolve a reply to its callback and context', () => {
+        const registry = new ButtonCallbackRegistry();
+        const confirm = vi.fn();
+        registry.register('+1 234 567 890', { confirm, cancel: { action: 'CANCEL_ORDER' } }, { orderId: '4412' });
+
+        expect(registry.resolve('1234567890', 'confirm')).toEqual({ callback: confirm, context: { orderId: '4412' } });
+    });
+
+    it('should consume every button of a message on the first reply', () => {
+        const registry = new ButtonCallbackRegistry();
+        registry.register('1234567890', { confirm: vi.fn(), cancel: { action: 'CANCEL_ORDER' } });
+
+        expect(registry.resolve('1234567890', 'cancel')?.callback).toEqual({ action: 'CANCEL_ORDER' });
+        expect(registry.resolve('1234567890', 'confirm')).toBeUndefined();
+        expect(registry.size).toBe(0);
+    });
+
+    it('should keep chats apart and ignore expired buttons', () => {
+        vi.useFakeTimers();
+        const registry = new ButtonCallbackRegistry();
+        registry.register('111', { ok: vi.fn() }, {}, 1000);
+
+        expect(registry.resolve('222', 'ok')).toBeUndefined();
+        vi.advanceTimersByTime(1000);
+        expect(registry.resolve('111', 'ok')).toBeUndefined();
+    });
+});
@@ -0,0 +1,76 @@
+import { describe, it, expect } from 'vitest';
+import { ButtonMessageBuilder, ListMessageBuilder } from '../../src/interactive/builders';
+import { InteractivePrompts, matchOfferedOption } from '../../src/interactive/prompts';
+import { ManualClock } from '../../src/utils/clock';
+
+const options = [
+    { id: 'billing', title: 'Billing' },
+    { id: 'support', title: 'Tech support' },
+    { id: 'sales', title: 'Sales' },
+];
+const buttons = (to: string) => ({
+    type: 'interactive' as const,
+    to,
+    content: options.reduce((b, o) => b.button(o.id, o.title), new ButtonMessageBuilder('What can we help with?')).build(),
+});
+
+describe('matchOfferedOption', () => {
+    it('should pick an option by position', () => {
+        expect(matchOfferedOption('2', options)?.id).toBe('support');
+        expect(matchOfferedOption('the first one', options)?.id).toBe('billing');
+        expect(matchOfferedOption('Option 3.', options)?.id).toBe('sales');
+        expect(matchOfferedOption('#2', options)?.id).toBe('support');
+        expect(matchOfferedOption('last', options)?.id).toBe('sales');
+        expect(matchOfferedOption('two please', options)?.id).toBe('support');
+    });
+
+    it('should pick an option by title or id, and nothing for other text', () => {
+        expect(matchOfferedOption('tech support!', options)?.id).toBe('support');
+        expect(matchOfferedOption('SALES', options)?.id).toBe('sales');
+        expect(matchOfferedOption('4', options)).toBeUndefined();
+        expect(matchOfferedOption('nothing yet', options)).toBeUndefined();
+    });
+});
+
+describe('InteractivePrompts', () => {
+    it('should read a typed answer against the last options offered and consume the prompt', async () => {
+        const prompts = new InteractivePrompts();
+        await prompts.offer(buttons('+1 234 567 890'), { menuId: 'intake', stateId: 'topic' });
+
+        expect(await prompts.get('1234567890')).toMatchObject({ kind: 'buttons', menu: { menuId: 'intake', stateId: 'topic' } });
+        expect(await prompts.interpret('1234567890', 'hello')).toBeUndefined();
+        expect(await prompts.interpret('1234567890', 'the second one')).toEqual({ id: 'support', title: 'Tech support' });
+        expect(await prompts.get('1234567890')).toBeUndefined();
+    });
+
+    it('should replace the prompt with a newer one', async () => {
+        const prompts = new InteractivePrompts();
+        await prompts.offer(buttons('1234567890'));
+        const list = new ListMessageBuilder('Pick a plan')
+            .button('Plans')
+            .section(undefined, (s) => s.row('basic', 'Basic').row('pro', 'Pro'))
+            .build();
+        await prompts.offer({ type: 'interactive', to: '1234567890', content: list });
+
+        expect(await prompts.interpret('1234567890', '2')).toEqual({ id: 'pro', title: 'Pro' });
+    });
+
+    it('should clear the prompt when any other message goes to the chat', async () => {
+        const prompts = new InteractivePrompts();
+        await prompts.offer(buttons('1234567890'));
+        await prompts.offer({ type: 'text', to: '1234567890', content: 'How many do you need?' });
+
+        expect(await prompts.get('1234567890')).toBeUndefined();
+        expect(await prompts.interpret('1234567890', '2')).toBeUndefined();
+    });
+
+    it('should forget prompts left unanswered past the time limit', async () => {
+        const clock = new ManualClock();
+        const prompts = new InteractivePrompts(undefined, clock, 1000);
+        await prompts.offer(buttons('1234567890'));
+
+        clock.advance(1000);
+
+        expect(await prompts.interpret('1234567890', '1')).toBeUndefined();
+    });
+});
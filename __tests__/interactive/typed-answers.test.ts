@@ -0,0 +1,40 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WhatsAppPlugin } from '../../src/index';
+import { ButtonMessageBuilder } from '../../src/interactive/builders';
+
+// A Cloud API account whose sends succeed without reaching the network
+function account(): WhatsAppPlugin {
+    const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123', circuitBreaker: false });
+    (plugin as unknown as { messageHandler: unknown }).messageHandler = {
+        send: vi.fn(async () => ({ messages: [{ id: 'wamid.out' }] })),
+    };
+    return plugin;
+}
+
+const buttons = {
+    type: 'interactive' as const,
+    to: '1234567890',
+    content: new ButtonMessageBuilder('What can we help with?').button('billing', 'Billing').button('support', 'Support').build(),
+};
+const typed = (content: string) => ({ id: 'wamid.in', from: '1234567890', timestamp: 0, type: 'text' as const, content });
+
+describe('typed answers', () => {
+    it('should read a typed answer as a tap on the buttons just sent', async () => {
+        const plugin = account();
+        await plugin.sendMessage(buttons);
+
+        expect(await plugin.interpretAnswer(typed('2'))).toMatchObject({ replyId: 'support', replyTitle: 'Support' });
+        expect(await plugin.prompts.get('1234567890')).toBeUndefined();
+    });
+
+    it('should leave an answer to a later question as text', async () => {
+        const plugin = account();
+        await plugin.sendMessage(buttons);
+        await plugin.sendMessage({ type: 'text', to: '1234567890', content: 'How many do you need?' });
+
+        const answer = await plugin.interpretAnswer(typed('2'));
+
+        expect(answer.replyId).toBeUndefined();
+        expect(answer.content).toBe('2');
+    });
+});
@@ -0,0 +1,27 @@
+import { describe, it, expect, vi } from 'vitest';
+import { enrichLocation, formatLocation } from '../../src/location';
+
+describe('formatLocation', () => {
+    it('should prefer the resolved place, then the pin label, then coordinates', () => {
+        const pin = { latitude: -23.550520, longitude: -46.633308 };
+
+        expect(formatLocation({ ...pin, place: 'Praça da Sé, São Paulo', name: 'Office' })).toBe(
+            'Shared a location near Praça da Sé, São Paulo (Office)'
+        );
+        expect(formatLocation({ ...pin, name: 'Cafe Central', address: 'Rua A, 1' })).toBe(
+            'Shared a location: Cafe Central, Rua A, 1'
+        );
+        expect(formatLocation(pin)).toBe('Shared a location: -23.55052, -46.63331');
+    });
+});
+
+describe('enrichLocation', () => {
+    it('should add the place the geocoder finds and keep the location otherwise', async () => {
+        const location = { latitude: 48.8584, longitude: 2.2945 };
+        const reverse = vi.fn().mockResolvedValueOnce('Eiffel Tower, Paris').mockResolvedValueOnce(undefined);
+
+        expect(await enrichLocation(location, { reverse })).toEqual({ ...location, place: 'Eiffel Tower, Paris' });
+        expect(await enrichLocation(location, { reverse })).toBe(location);
+        expect(reverse).toHaveBeenCalledWith(48.8584, 2.2945);
+    });
+});
@@ -0,0 +1,120 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { MenuEngine } from '../../src/menus/state-machine';
+
+describe('MenuEngine', () => {
+    let engine;
+
+    const intake = {
+        id: 'intake',
+        initial: 'topic',
+        states: [
+            {
+                id: 'topic',
+                prompt: 'What can we help with?',
+                options: [
+                    { id: 'billing', title: 'Billing', next: 'urgency' },
+                    { id: 'support', title: 'Support', next: 'urgency' },
+                ],
+            },
+            {
+                id: 'urgency',
+                prompt: 'How urgent is it?',
+                field: 'priority',
+                options: [
+                    { id: 'low', title: 'Low' },
+                    { id: 'high', title: 'High' },
+                ],
+            },
+        ],
+    };
+
+    beforeEach(() => {
+        engine = new MenuEngine();
+        engine.register(intake);
+    });
+
+    it('should render the initial state as reply buttons', async () => {
+        const message = await engine.start('intake', '1234567890');
+
+        expect(message).toEqual({
+            type: 'interactive',
+            to: '1234567890',
+            content: {
+                type: 'button',
+                body: { text: 'What can we help with?' },
+                action: {
+                    buttons: [
+                        { type: 'reply', reply: { id: 'billing', title: 'Billing' } },
+                        { type: 'reply', reply: { id: 'support', title: 'Support' } },
+                    ],
+                },
+            },
+        });
+    });
+
+    it('should walk transitions and emit the final result', async () => {
+        const completed = vi.fn();
+        engine.on('completed', completed);
+
+        await engine.start('intake', '1234567890');
+        const step = await engine.handleReply('1234567890', 'support');
+        expect(step.message.content.body.text).toBe('How urgent is it?');
+
+        const final = await engine.handleReply('1234567890', 'high');
+        expect(final.result.answers).toEqual({
+            topic: { id: 'support', title: 'Support' },
+            priority: { id: 'high', title: 'High' },
+        });
+        expect(completed).toHaveBeenCalledWith(final.result);
+        expect(await engine.getSession('1234567890')).toBeUndefined();
+    });
+
+    it('should ignore replies without an active session or matching option', async () => {
+        expect(await engine.handleReply('1234567890', 'billing')).toBeNull();
+
+        await engine.start('intake', '1234567890');
+        expect(await engine.handleReply('1234567890', 'unknown')).toBeNull();
+        expect((await engine.getSession('1234567890')).stateId).toBe('topic');
+    });
+
+    it('should render lists when options exceed the button limit', async () => {
+        engine.register({
+            id: 'rating',
+            initial: 'score',
+            states: [{
+                id: 'score',
+                prompt: 'Rate us',
+                options: ['1', '2', '3', '4', '5'].map((n) => ({ id: n, title: n })),
+            }],
+        });
+
+        const message = await engine.start('rating', '1234567890');
+
+        expect(message.content.type).toBe('list');
+        expect(message.content.action.sections[0].rows).toHaveLength(5);
+    });
+
+    it('should reject definitions with dangling transitions', () => {
+        expect(() => engine.register({
+            id: 'broken',
+            initial: 'start',
+            states: [{
+                id: 'start',
+                prompt: 'Pick one',
+                options: [{ id: 'a', title: 'A', next: 'missing' }],
+            }],
+        })).toThrow('points to unknown state "missing"');
+    });
+
+    it('should reject option titles WhatsApp would refuse when registering', () => {
+        expect(() => engine.register({
+            id: 'long',
+            initial: 'start',
+            states: [{
+                id: 'start',
+                prompt: 'Pick one',
+                options: [{ id: 'a', title: 'A title that is far too long for a button' }],
+            }],
+        })).toThrow('Button "a" title');
+    });
+});
@@ -0,0 +1,67 @@
+import { describe, it, expect } from 'vitest';
+import { createPrometheusMetrics } from '../../src/metrics/metrics';
+
+// Minimal stand-in for prom-client that records what was registered and observed
+function fakeProm() {
+    const metrics = {};
+    const make = (kind) => class {
+        constructor(config) {
+            this.config = config;
+            this.calls = [];
+            metrics[config.name] = this;
+        }
+        inc(...args) { this.calls.push(['inc', ...args]); }
+        observe(labels, value) { this.calls.push(['observe', labels, value]); }
+        set(...args) { this.calls.push(['set', ...args]); }
+    };
+    return { metrics, prom: { Counter: make('counter'), Histogram: make('histogram'), Gauge: make('gauge') } };
+}
+
+describe('createPrometheusMetrics', () => {
+    it('should register every metric on the supplied registry', () => {
+        const { metrics, prom } = fakeProm();
+        const registry = {};
+
+        createPrometheusMetrics(prom, registry);
+
+        expect(Object.keys(metrics)).toEqual([
+            'whatsapp_messages_sent_total',
+            'whatsapp_messages_received_total',
+            'whatsapp_messages_failed_total',
+            'whatsapp_graph_api_request_duration_seconds',
+            'whatsapp_webhook_processing_duration_seconds',
+            'whatsapp_queue_depth',
+            'whatsapp_active_accounts',
+            'whatsapp_inbound_queue_depth',
+            'whatsapp_inbound_wait_seconds',
+            'whatsapp_inbound_processing_seconds',
+            'whatsapp_inbound_dropped_total',
+            'whatsapp_chat_state_evictions_total',
+        ]);
+        expect(metrics['whatsapp_queue_depth'].config.registers).toEqual([registry]);
+    });
+
+    it('should label counters and histograms', () => {
+        const { metrics, prom } = fakeProm();
+        const recorder = createPrometheusMetrics(prom, {});
+
+        recorder.messageFailed('text', 'sales', 'NOT_ON_WHATSAPP');
+        recorder.observeApiLatency('messages', 0.2);
+
+        expect(metrics['whatsapp_messages_failed_total'].calls)
+            .toEqual([['inc', { type: 'text', account: 'sales', reason: 'NOT_ON_WHATSAPP' }]]);
+        expect(metrics['whatsapp_graph_api_request_duration_seconds'].calls)
+            .toEqual([['observe', { endpoint: 'messages' }, 0.2]]);
+    });
+
+    it('should label inbound pool metrics by account', () => {
+        const { metrics, prom } = fakeProm();
+        const recorder = createPrometheusMetrics(prom, {});
+
+        recorder.setInboundQueueDepth('sales', 12);
+        recorder.inboundDropped('sales', 3);
+
+        expect(metrics['whatsapp_inbound_queue_depth'].calls).toEqual([['set', { account: 'sales' }, 12]]);
+        expect(metrics['whatsapp_inbound_dropped_total'].calls).toEqual([['inc', { account: 'sales' }, 3]]);
+    });
+});
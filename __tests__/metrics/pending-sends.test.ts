@@ -0,0 +1,18 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WhatsAppPlugin } from '../../src/index';
+
+describe('pendingSends', () => {
+    it('should report the count whenever a send starts or ends', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123', circuitBreaker: false });
+        (plugin as unknown as { messageHandler: unknown }).messageHandler = {
+            send: vi.fn(async () => ({ messages: [{ id: 'wamid.out' }] })),
+        };
+        const counts: number[] = [];
+        plugin.on('pendingSends', (count: number) => counts.push(count));
+
+        await plugin.sendMessage({ type: 'text', to: '1234567890', content: 'Hi' });
+
+        expect(counts).toEqual([1, 0]);
+        expect(plugin.pendingSends).toBe(0);
+    });
+});
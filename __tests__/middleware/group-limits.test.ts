@@ -0,0 +1,27 @@
+import { describe, it, expect } from 'vitest';
+import { groupMediaLimit } from '../../src/middleware/group-limits';
+
+const context = { accountId: 'default' };
+const image = (to: string, bytes: number) => ({
+    type: 'media' as const,
+    to,
+    content: { kind: 'image' as const, data: new Uint8Array(bytes) },
+});
+
+describe('groupMediaLimit', () => {
+    const limit = groupMediaLimit({ '120363042000000000@g.us': { mediaMaxMb: 1 } });
+
+    it('should refuse media over the group limit', () => {
+        expect(() => limit(image('120363042000000000@g.us', 2 * 1024 * 1024), context)).toThrow('accepts at most 1 MB');
+    });
+
+    it('should pass smaller media, other chats and media sent by link', () => {
+        const small = image('120363042000000000@g.us', 1024);
+        const direct = image('1234567890', 2 * 1024 * 1024);
+        const link = { type: 'media' as const, to: '120363042000000000@g.us', content: { kind: 'image' as const, url: 'https://example.com/a.png' } };
+
+        expect(limit(small, context)).toBe(small);
+        expect(limit(direct, context)).toBe(direct);
+        expect(limit(link, context)).toBe(link);
+    });
+});
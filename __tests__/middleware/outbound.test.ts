@@ -0,0 +1,38 @@
+import { describe, it, expect } from 'vitest';
+import { OutboundPipeline, expandEmojiShortcodes, mapMessageText } from '../../src/middleware/outbound';
+
+const context = { accountId: 'default' };
+
+describe('OutboundPipeline', () => {
+    it('should run middleware in order until removed', async () => {
+        const pipeline = new OutboundPipeline();
+        pipeline.use(expandEmojiShortcodes);
+        const remove = pipeline.use((message) => mapMessageText(message, (text) => `${text}!`));
+
+        expect(await pipeline.run({ type: 'text', to: '111', content: 'Nice :fire:' }, context)).toEqual({
+            type: 'text',
+            to: '111',
+            content: 'Nice 🔥!',
+        });
+
+        remove();
+        expect(pipeline.size).toBe(1);
+    });
+});
+
+describe('mapMessageText', () => {
+    it('should rewrite captions and interactive text but not templates', () => {
+        const upper = (text: string) => text.toUpperCase();
+
+        expect(mapMessageText({ type: 'media', to: '111', content: { kind: 'image', url: 'https://x', caption: 'hi' } }, upper))
+            .toMatchObject({ content: { caption: 'HI' } });
+        expect(mapMessageText({
+            type: 'interactive',
+            to: '111',
+            content: { type: 'button', body: { text: 'pick' }, footer: { text: 'thanks' }, action: { buttons: [] } },
+        }, upper)).toMatchObject({ content: { body: { text: 'PICK' }, footer: { text: 'THANKS' } } });
+
+        const template = { type: 'template' as const, to: '111', content: { name: 'x', language: { code: 'en' } } };
+        expect(mapMessageText(template, upper)).toBe(template);
+    });
+});
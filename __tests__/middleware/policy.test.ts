@@ -0,0 +1,74 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ContentPolicy, enforcePolicy } from '../../src/middleware/policy';
+import { WhatsAppPlugin } from '../../src/index';
+
+const context = { accountId: 'default' };
+const text = (content: string, to = '1234567890') => ({ type: 'text' as const, to, content });
+
+describe('ContentPolicy', () => {
+    it('should block banned phrases, extra links and phone numbers', () => {
+        const policy = new ContentPolicy({ bannedPhrases: ['Guaranteed Returns'], maxLinks: 1, blockPhoneNumbers: true });
+
+        expect(policy.check(text('See https://a.example and www.b.example, guaranteed returns!'))).toEqual({
+            action: 'block',
+            reasons: ['banned_phrase', 'too_many_links'],
+        });
+        expect(policy.check(text('Call me on +1 (650) 555-1234'))).toEqual({ action: 'block', reasons: ['phone_number'] });
+        expect(policy.check(text('Order 2024-01-15 ships https://a.example'))).toEqual({ action: 'allow' });
+    });
+
+    it('should redact instead of blocking when asked to', () => {
+        const policy = new ContentPolicy({ bannedPatterns: ['\\bcrypto\\w*'], blockPhoneNumbers: true, action: 'redact', redaction: '***' });
+
+        expect(policy.check(text('Cryptocurrency tips: call 650 555 1234'))).toEqual({
+            action: 'rewrite',
+            message: text('*** tips: call ***'),
+            reasons: ['banned_phrase', 'phone_number'],
+        });
+    });
+
+    it('should skip patterns that are not regular expressions instead of failing every send', () => {
+        const policy = new ContentPolicy({ bannedPatterns: ['(unclosed', 'crypto'] });
+
+        expect(policy.check(text('Hello'))).toEqual({ action: 'allow' });
+        expect(policy.check(text('Buy crypto')).action).toBe('block');
+    });
+
+    it('should let a group override the account rules', () => {
+        const policy = new ContentPolicy({ maxLinks: 0 }, { '120363042000000000@g.us': { contentPolicy: { maxLinks: 3 } } });
+
+        expect(policy.check(text('https://a.example', '120363042000000000@g.us'))).toEqual({ action: 'allow' });
+        expect(policy.check(text('https://a.example')).action).toBe('block');
+    });
+});
+
+describe('enforcePolicy', () => {
+    it('should fail blocked messages with POLICY_VIOLATION and send rewrites', async () => {
+        const block = enforcePolicy({ check: () => ({ action: 'block', reasons: ['too_long'] }) });
+        const rewrite = enforcePolicy({ check: (message) => ({ action: 'rewrite', message: { ...message, content: 'ok' }, reasons: [] }) });
+
+        await expect(block(text('hello'), context)).rejects.toMatchObject({ code: 'POLICY_VIOLATION' });
+        expect(await rewrite(text('hello'), context)).toEqual(text('ok'));
+    });
+});
+
+describe('WhatsAppPlugin.sendBatch', () => {
+    it('should check batched messages against the policy and report blocks per message', async () => {
+        const plugin = new WhatsAppPlugin({
+            accessToken: 'token',
+            phoneNumberId: '123',
+            circuitBreaker: false,
+            contentPolicy: { bannedPhrases: ['guaranteed returns'] },
+        });
+        const sendBatch = vi.fn(async (messages: unknown[]) =>
+            messages.map(() => ({ ok: true as const, status: 200, data: { messages: [{ id: 'wamid.1' }] } }))
+        );
+        Object.assign(plugin['client'], { sendBatch });
+
+        const results = await plugin.sendBatch([text('Guaranteed returns!'), text('Hello')]);
+
+        expect(results[0]).toMatchObject({ ok: false, error: { code: 'POLICY_VIOLATION' } });
+        expect(results[1]).toMatchObject({ ok: true });
+        expect(sendBatch).toHaveBeenCalledWith([text('Hello')]);
+    });
+});
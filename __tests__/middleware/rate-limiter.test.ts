@@ -0,0 +1,34 @@
+import { describe, it, expect } from 'vitest';
+import { SenderRateLimiter } from '../../src/middleware/rate-limiter';
+
+describe('SenderRateLimiter', () => {
+    const config = { maxMessages: 2, windowMs: 1000 };
+
+    it('should notify once and then suppress until the window frees up', () => {
+        const limiter = new SenderRateLimiter();
+
+        expect(limiter.check('1234567890', config, 0)).toBe('allow');
+        expect(limiter.check('1234567890', config, 100)).toBe('allow');
+        expect(limiter.check('1234567890', config, 200)).toBe('notify');
+        expect(limiter.check('1234567890', config, 300)).toBe('suppress');
+        expect(limiter.check('1234567890', config, 1050)).toBe('allow');
+    });
+
+    it('should track senders independently', () => {
+        const limiter = new SenderRateLimiter();
+
+        limiter.check('a', { maxMessages: 1 }, 0);
+        expect(limiter.check('a', { maxMessages: 1 }, 1)).toBe('notify');
+        expect(limiter.check('b', { maxMessages: 1 }, 1)).toBe('allow');
+    });
+
+    it('should report the remaining allowance without counting a message', () => {
+        const limiter = new SenderRateLimiter();
+
+        expect(limiter.status('a', config, 0)).toEqual({ limit: 2, remaining: 2, resetInMs: 0, cooldown: false });
+        limiter.check('a', config, 100);
+        limiter.check('a', config, 400);
+        expect(limiter.status('a', config, 500)).toEqual({ limit: 2, remaining: 0, resetInMs: 600, cooldown: true });
+        expect(limiter.status('a', config, 1200)).toEqual({ limit: 2, remaining: 1, resetInMs: 200, cooldown: false });
+    });
+});
@@ -0,0 +1,43 @@
+import { describe, it, expect } from 'vitest';
+import { appendReplyFooter, replyFooterLength } from '../../src/middleware/reply-footer';
+
+const text = (content: string, to = '1234567890') => ({ type: 'text' as const, to, content });
+const groups = {
+    '120363042000000000@g.us': { replyFooter: 'Team bot' },
+    '120363042000000001@g.us': { replyFooter: '' },
+};
+
+describe('appendReplyFooter', () => {
+    const footer = appendReplyFooter('AI assistant — reply STOP to opt out', groups);
+
+    const reply = { accountId: 'default', reply: { index: 0, count: 1 } };
+
+    it('should append the footer to the last part of a reply only', () => {
+        expect(footer(text('Hi'), reply)).toEqual(text('Hi\n\nAI assistant — reply STOP to opt out'));
+        expect(footer(text('Part one'), { accountId: 'default', reply: { index: 0, count: 2 } })).toEqual(text('Part one'));
+        expect(footer(text('Part two'), { accountId: 'default', reply: { index: 1, count: 2 } }).content).toContain('STOP');
+    });
+
+    it('should leave sends that are not replies alone', () => {
+        expect(footer(text('Your code is 123456'), { accountId: 'default' })).toEqual(text('Your code is 123456'));
+    });
+
+    it('should use a group override, where an empty footer turns it off', () => {
+        expect(footer(text('Hi', '120363042000000000@g.us'), reply).content).toBe('Hi\n\nTeam bot');
+        expect(footer(text('Hi', '120363042000000001@g.us'), reply).content).toBe('Hi');
+    });
+
+    it('should leave other message types alone', () => {
+        const reaction = { type: 'reaction' as const, to: '1234567890', content: { messageId: 'wamid.1', emoji: '👍' } };
+
+        expect(footer(reaction, reply)).toBe(reaction);
+    });
+});
+
+describe('replyFooterLength', () => {
+    it('should count the footer and its separator', () => {
+        expect(replyFooterLength('Team bot', undefined, '1234567890')).toBe(10);
+        expect(replyFooterLength('Team bot', groups, '120363042000000001@g.us')).toBe(0);
+        expect(replyFooterLength(undefined, undefined, '1234567890')).toBe(0);
+    });
+});
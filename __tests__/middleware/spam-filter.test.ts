@@ -0,0 +1,56 @@
+import { describe, it, expect } from 'vitest';
+import { SpamFilter } from '../../src/middleware/spam-filter';
+
+const message = (content, from = '1234567890@s.whatsapp.net') => ({
+    id: 'msg_id',
+    from,
+    timestamp: 0,
+    type: 'text',
+    content,
+});
+
+describe('SpamFilter', () => {
+    it('should allow normal traffic', () => {
+        const filter = new SpamFilter();
+
+        expect(filter.inspect(message('Hi, where is my order?'))).toEqual({ action: 'allow', reasons: [] });
+    });
+
+    it('should detect floods within the window and forget them afterwards', () => {
+        const filter = new SpamFilter({ floodMaxMessages: 2, floodWindowMs: 1000 });
+
+        filter.inspect(message('one'), 0);
+        filter.inspect(message('two'), 100);
+        expect(filter.inspect(message('three'), 200).reasons).toContain('flood');
+        expect(filter.inspect(message('four'), 5000).action).toBe('allow');
+    });
+
+    it('should detect duplicates, banned keywords, and link spam', () => {
+        const filter = new SpamFilter({
+            action: 'flag',
+            duplicateThreshold: 2,
+            bannedKeywords: ['Free Crypto'],
+            maxLinks: 1,
+        });
+
+        filter.inspect(message('same text'));
+        expect(filter.inspect(message('same text')).reasons).toEqual(['duplicate']);
+        expect(filter.inspect(message('get FREE crypto now')).reasons).toEqual(['banned_keyword']);
+        expect(filter.inspect(message('http://a.example www.b.example')))
+            .toEqual({ action: 'flag', reasons: ['link_spam'] });
+    });
+
+    it('should report filtered volume', () => {
+        const filter = new SpamFilter({ action: 'block', bannedKeywords: ['spam'] });
+
+        filter.inspect(message('spam'));
+        filter.inspect(message('hello'));
+
+        expect(filter.getMetrics()).toMatchObject({
+            inspected: 2,
+            allowed: 1,
+            blocked: 1,
+            byReason: { banned_keyword: 1 },
+        });
+    });
+});
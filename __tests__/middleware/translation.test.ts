@@ -0,0 +1,68 @@
+import { describe, it, expect, vi } from 'vitest';
+
+vi.mock('@elizaos/core', () => ({ ModelType: { TEXT_SMALL: 'TEXT_SMALL' } }));
+
+import { RuntimeModelTranslator, translateInbound, translateOutbound } from '../../src/middleware/translation';
+import { ContactLanguages } from '../../src/contacts/language';
+
+const context = { accountId: 'default' };
+const translator = { translate: vi.fn(async (text: string, to: string) => `[${to}] ${text}`) };
+
+async function languagesWith(contacts: Record<string, string>): Promise<ContactLanguages> {
+    const languages = new ContactLanguages();
+    for (const [waId, language] of Object.entries(contacts)) await languages.set(waId, language);
+    return languages;
+}
+
+describe('translateOutbound', () => {
+    it('should translate text into the recipient language when it differs from the agent', async () => {
+        const translate = translateOutbound(translator, await languagesWith({ '5511999999999': 'pt_BR', '15551234567': 'en_US' }));
+
+        expect(await translate({ type: 'text', to: '5511999999999', content: 'Hello' }, context)).toEqual({
+            type: 'text',
+            to: '5511999999999',
+            content: '[pt_BR] Hello',
+        });
+        expect(await translate({ type: 'text', to: '15551234567', content: 'Hello' }, context)).toMatchObject({ content: 'Hello' });
+        expect(await translate({ type: 'text', to: '4915112345678', content: 'Hello' }, context)).toMatchObject({ content: 'Hello' });
+    });
+
+    it('should translate interactive body and footer but leave templates alone', async () => {
+        const translate = translateOutbound(translator, await languagesWith({ '34600000000': 'es' }));
+        const interactive = await translate({
+            type: 'interactive',
+            to: '34600000000',
+            content: {
+                type: 'button',
+                body: { text: 'Confirm?' },
+                footer: { text: 'Reply below' },
+                action: { buttons: [{ type: 'reply', reply: { id: 'yes', title: 'Yes' } }] },
+            },
+        }, context);
+        const template = { type: 'template' as const, to: '34600000000', content: { name: 'order_update', language: { code: 'en' } } };
+
+        expect(interactive).toMatchObject({ content: { body: { text: '[es] Confirm?' }, footer: { text: '[es] Reply below' } } });
+        expect(await translate(template, context)).toBe(template);
+    });
+});
+
+describe('translateInbound', () => {
+    it('should translate what contacts write in another language, keeping both languages', async () => {
+        const languages = await languagesWith({ '34600000000': 'es' });
+        const message = { id: 'wamid.1', from: '34600000000', timestamp: 0, type: 'text' as const, content: 'Hola' };
+
+        expect(await translateInbound(message, translator, languages, 'en')).toEqual({ text: '[en] Hola', from: 'es', to: 'en' });
+        expect(await translateInbound({ ...message, from: '15551234567' }, translator, languages, 'en')).toBeUndefined();
+    });
+});
+
+describe('RuntimeModelTranslator', () => {
+    it('should ask the small text model and fall back to the original on an empty answer', async () => {
+        const useModel = vi.fn().mockResolvedValueOnce('  Hola  ').mockResolvedValueOnce('');
+        const model = new RuntimeModelTranslator({ useModel } as any);
+
+        expect(await model.translate('Hello', 'es', 'en')).toBe('Hola');
+        expect(await model.translate('Hello', 'es')).toBe('Hello');
+        expect(useModel).toHaveBeenCalledWith('TEXT_SMALL', { prompt: expect.stringContaining('from en into es') });
+    });
+});
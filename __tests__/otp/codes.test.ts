@@ -0,0 +1,69 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { IAgentRuntime } from '@elizaos/core';
+import { OtpManager } from '../../src/otp/codes';
+import { WhatsAppConnectorService } from '../../src/service';
+import { WhatsAppPlugin } from '../../src/index';
+
+describe('OtpManager', () => {
+    it('should issue numeric codes that verify once', async () => {
+        const otp = new OtpManager({ digits: 8 });
+        const code = await otp.issue('+1 234 567 890');
+
+        expect(code).toMatch(/^\d{8}$/);
+        expect(await otp.verify('1234567890', code)).toBe('valid');
+        expect(await otp.verify('1234567890', code)).toBe('missing');
+    });
+
+    it('should expire codes after the ttl', async () => {
+        const otp = new OtpManager({ ttlMs: 1000 });
+        const code = await otp.issue('1234567890', 0);
+
+        expect(await otp.verify('1234567890', code, 1000)).toBe('expired');
+        expect(await otp.verify('1234567890', code, 1001)).toBe('missing');
+    });
+
+    it('should discard the code after too many wrong attempts', async () => {
+        const otp = new OtpManager({ maxAttempts: 2 });
+        const code = await otp.issue('1234567890');
+        const wrong = code === '000000' ? '111111' : '000000';
+
+        expect(await otp.verify('1234567890', wrong)).toBe('invalid');
+        expect(await otp.verify('1234567890', wrong)).toBe('locked');
+        expect(await otp.verify('1234567890', code)).toBe('missing');
+    });
+
+    it('should replace a pending code when a new one is issued', async () => {
+        const otp = new OtpManager();
+        const first = await otp.issue('1234567890');
+        const second = await otp.issue('1234567890');
+
+        if (first !== second) expect(await otp.verify('1234567890', first)).toBe('invalid');
+        expect(await otp.verify('1234567890', second)).toBe('valid');
+    });
+});
+
+describe('WhatsAppConnectorService OTP', () => {
+    it('should only verify a code on the account that sent it', async () => {
+        const connector = new WhatsAppConnectorService({} as IAgentRuntime);
+        const sent: string[] = [];
+        for (const [accountId, phoneNumberId] of [['default', '123'], ['sales', '456']]) {
+            const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId });
+            vi.spyOn(plugin, 'sendMessage').mockImplementation(async (message) => {
+                sent.push((message.content as { components: Array<{ parameters: Array<{ text: string }> }> }).components[0].parameters[0].text);
+                return {};
+            });
+            connector['accounts'].set(accountId, {
+                plugin,
+                config: { accountId, enabled: true, accessToken: 'token', phoneNumberId },
+                enabled: true,
+            });
+        }
+        connector['otpSettings'] = { template: 'verify_code' };
+
+        await connector.sendOtp('1234567890', 'sales');
+        const [code] = sent;
+
+        expect(await connector.verifyOtp('1234567890', code, 'default')).toBe('missing');
+        expect(await connector.verifyOtp('1234567890', code, 'sales')).toBe('valid');
+    });
+});
@@ -0,0 +1,137 @@
+import { describe, it, expect, vi } from 'vitest';
+import { allocateRecipients, distributeBroadcast, isDeferred } from '../../src/outbox/broadcast';
+import { ManualClock } from '../../src/utils/clock';
+
+const recipients = (n: number) => Array.from({ length: n }, (_, i) => `1555000${String(i).padStart(4, '0')}`);
+
+describe('allocateRecipients', () => {
+    it('should split recipients in proportion to remaining capacity', () => {
+        const { assigned, unassigned } = allocateRecipients(recipients(10), [750, 250]);
+
+        expect(assigned.map((a) => a.length)).toEqual([8, 2]);
+        expect(unassigned).toEqual([]);
+    });
+
+    it('should hand out rounding leftovers by largest remainder', () => {
+        const { assigned } = allocateRecipients(recipients(10), [100, 100, 100]);
+
+        expect(assigned.map((a) => a.length).sort()).toEqual([3, 3, 4]);
+    });
+
+    it('should never exceed a number\'s capacity', () => {
+        const audience = recipients(10);
+        const { assigned, unassigned } = allocateRecipients(audience, [3, 0, 4]);
+
+        expect(assigned.map((a) => a.length)).toEqual([3, 0, 4]);
+        expect(unassigned).toEqual(audience.slice(7));
+    });
+
+    it('should give everything to unlimited numbers', () => {
+        const { assigned } = allocateRecipients(recipients(5), [1000, Number.POSITIVE_INFINITY]);
+
+        expect(assigned.map((a) => a.length)).toEqual([0, 5]);
+    });
+});
+
+describe('distributeBroadcast', () => {
+    it('should report the sending account for each recipient', async () => {
+        const sales = vi.fn().mockResolvedValue({});
+        const support = vi.fn().mockRejectedValueOnce(new Error('Rate limit hit')).mockResolvedValue({});
+
+        const results = await distributeBroadcast(
+            ['111', '222', '333', '444', '111'],
+            [
+                { accountId: 'sales', capacity: 2, send: sales },
+                { accountId: 'support', capacity: 1, send: support },
+            ],
+            { paceMs: 0 }
+        );
+
+        expect(results).toEqual([
+            { to: '111', accountId: 'sales', ok: true },
+            { to: '222', accountId: 'sales', ok: true },
+            { to: '333', accountId: 'support', ok: false, error: 'Rate limit hit' },
+            { to: '444', ok: false, error: 'No number has messaging capacity left' },
+        ]);
+        expect(sales).toHaveBeenCalledTimes(2);
+    });
+
+    it('should report the account that actually sent, e.g. after failover', async () => {
+        const send = vi.fn().mockResolvedValue({ id: 'wamid.1', accountId: 'backup' });
+
+        const results = await distributeBroadcast(['111'], [{ accountId: 'sales', capacity: 1, send }], { paceMs: 0 });
+
+        expect(results).toEqual([{ to: '111', accountId: 'backup', ok: true }]);
+    });
+
+    it('should pace sends from the same number', async () => {
+        vi.useFakeTimers();
+        const send = vi.fn().mockResolvedValue({});
+
+        const done = distributeBroadcast(['111', '222'], [{ accountId: 'sales', capacity: 10, send }], {
+            paceMs: 1000,
+        });
+        await vi.advanceTimersByTimeAsync(0);
+        expect(send).toHaveBeenCalledTimes(1);
+
+        await vi.advanceTimersByTimeAsync(1000);
+        await done;
+        expect(send).toHaveBeenCalledTimes(2);
+        vi.useRealTimers();
+    });
+
+    it('should slow down while the sender is paced', async () => {
+        vi.useFakeTimers();
+        const send = vi.fn().mockResolvedValue({});
+
+        const done = distributeBroadcast(
+            ['111', '222'],
+            [{ accountId: 'sales', capacity: 10, send, pace: () => 'slow' as const }],
+            { paceMs: 100, slowPaceMs: 2000 }
+        );
+        await vi.advanceTimersByTimeAsync(1000);
+        expect(send).toHaveBeenCalledTimes(1);
+
+        await vi.advanceTimersByTimeAsync(1000);
+        await done;
+        expect(send).toHaveBeenCalledTimes(2);
+        vi.useRealTimers();
+    });
+
+    it('should stop a sender once its template is paused', async () => {
+        const send = vi.fn().mockResolvedValue({});
+        const pace = vi.fn().mockReturnValueOnce('normal').mockReturnValue('paused');
+
+        const results = await distributeBroadcast(
+            ['111', '222', '333'],
+            [{ accountId: 'sales', capacity: 10, send, pace }],
+            { paceMs: 0 }
+        );
+
+        expect(send).toHaveBeenCalledTimes(1);
+        expect(results).toEqual([
+            { to: '111', accountId: 'sales', ok: true },
+            { to: '222', accountId: 'sales', ok: false, error: 'Template is paused' },
+            { to: '333', accountId: 'sales', ok: false, error: 'Template is paused' },
+        ]);
+    });
+
+    it('should return recipients in quiet hours deferred instead of waiting for them', async () => {
+        const send = vi.fn().mockResolvedValue({});
+        const sendAt = (to: string, now: number) => (to === '111' ? now + 60_000 : now);
+
+        const results = await distributeBroadcast(
+            ['111', '222'],
+            [{ accountId: 'sales', capacity: 10, send, sendAt }],
+            { paceMs: 0 },
+            new ManualClock(1_000)
+        );
+
+        expect(send.mock.calls).toEqual([['222']]);
+        expect(results).toEqual([
+            { to: '111', accountId: 'sales', ok: false, deferredUntil: 61_000 },
+            { to: '222', accountId: 'sales', ok: true },
+        ]);
+        expect(results.map(isDeferred)).toEqual([true, false]);
+    });
+});
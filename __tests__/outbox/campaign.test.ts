@@ -0,0 +1,108 @@
+import { describe, it, expect, vi } from 'vitest';
+import { planCampaign, runCampaign } from '../../src/outbox/campaign';
+import { ManualClock } from '../../src/utils/clock';
+
+const DAY = 86_400_000;
+const recipients = (n: number) => Array.from({ length: n }, (_, i) => `1555000${String(i).padStart(4, '0')}`);
+
+describe('planCampaign', () => {
+    it('should use remaining capacity today and full limits on later days', () => {
+        const audience = recipients(12);
+        const plan = planCampaign(
+            audience,
+            [
+                { accountId: 'sales', capacity: 2, limit: 4 },
+                { accountId: 'support', capacity: 0, limit: 2 },
+            ],
+            { now: 1_000 }
+        );
+
+        expect(plan.days.map((d) => [d.day, d.startsAt, Object.values(d.recipients).flat().length])).toEqual([
+            [0, 1_000, 2],
+            [1, 1_000 + DAY, 6],
+            [2, 1_000 + 2 * DAY, 4],
+        ]);
+        expect(plan.days[1].recipients.support).toHaveLength(2);
+        expect(plan.unscheduled).toEqual([]);
+    });
+
+    it('should skip a full today and report recipients beyond maxDays', () => {
+        const plan = planCampaign(recipients(5), [{ accountId: 'sales', capacity: 0, limit: 2 }], {
+            now: 0,
+            maxDays: 2,
+        });
+
+        expect(plan.days.map((d) => d.day)).toEqual([1]);
+        expect(plan.unscheduled).toHaveLength(3);
+    });
+});
+
+describe('runCampaign', () => {
+    it('should send each day when it starts and roll over what no longer fits', async () => {
+        const clock = new ManualClock();
+        const audience = recipients(3);
+        const plan = planCampaign(audience, [{ accountId: 'sales', capacity: 2, limit: 2 }], { now: 0 });
+        const send = vi.fn().mockResolvedValue({});
+        const capacity = vi.fn().mockReturnValueOnce(2).mockReturnValue(0);
+
+        const campaign = runCampaign(
+            plan,
+            async () => [{ accountId: 'sales', capacity: capacity(), send }],
+            { paceMs: 0 },
+            clock
+        );
+        await vi.waitFor(() => expect(send).toHaveBeenCalledTimes(2));
+
+        clock.advance(DAY);
+        expect(await campaign.done).toEqual([
+            { to: audience[0], accountId: 'sales', ok: true },
+            { to: audience[1], accountId: 'sales', ok: true },
+            { to: audience[2], ok: false, error: 'No number has messaging capacity left' },
+        ]);
+    });
+
+    it('should fail the remaining days once cancelled', async () => {
+        const clock = new ManualClock();
+        const audience = recipients(2);
+        const plan = planCampaign(audience, [{ accountId: 'sales', capacity: 0, limit: 2 }], { now: 0 });
+        const send = vi.fn().mockResolvedValue({});
+
+        const campaign = runCampaign(plan, async () => [{ accountId: 'sales', capacity: 2, send }], {}, clock);
+        campaign.cancel();
+
+        expect(await campaign.done).toEqual(audience.map((to) => ({ to, ok: false, error: 'Campaign cancelled' })));
+        expect(send).not.toHaveBeenCalled();
+    });
+
+    it('should wait for days further off than one timer reaches', async () => {
+        const clock = new ManualClock();
+        const setTimeout = vi.spyOn(clock, 'setTimeout');
+        const plan = { days: [{ day: 30, startsAt: 30 * DAY, recipients: { sales: ['15550000000'] } }], unscheduled: [] };
+        const send = vi.fn().mockResolvedValue({});
+
+        const campaign = runCampaign(plan, async () => [{ accountId: 'sales', capacity: 1, send }], { paceMs: 0 }, clock);
+        clock.advance(29 * DAY);
+        await vi.waitFor(() => expect(setTimeout).toHaveBeenCalledTimes(2));
+        expect(send).not.toHaveBeenCalled();
+
+        clock.advance(DAY);
+        expect(await campaign.done).toEqual([{ to: '15550000000', accountId: 'sales', ok: true }]);
+        expect(Math.max(...setTimeout.mock.calls.map(([, ms]) => ms))).toBeLessThanOrEqual(2 ** 31 - 1);
+    });
+
+    it('should send to recipients in quiet hours once those hours end', async () => {
+        const clock = new ManualClock(1_000);
+        const plan = { days: [{ day: 0, startsAt: 1_000, recipients: { sales: ['111', '222'] } }], unscheduled: [] };
+        const send = vi.fn().mockResolvedValue({});
+        const sendAt = (to: string, now: number) => (to === '111' && now < 61_000 ? 61_000 : now);
+
+        const campaign = runCampaign(plan, async () => [{ accountId: 'sales', capacity: 10, send, sendAt }], { paceMs: 0 }, clock);
+        await vi.waitFor(() => expect(send.mock.calls).toEqual([['222']]));
+
+        clock.advance(60_000);
+        expect(await campaign.done).toEqual([
+            { to: '111', accountId: 'sales', ok: true, deferredUntil: 61_000 },
+            { to: '222', accountId: 'sales', ok: true },
+        ]);
+    });
+});
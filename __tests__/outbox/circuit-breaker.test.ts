@@ -0,0 +1,164 @@
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { CircuitBreaker } from '../../src/outbox/circuit-breaker';
+import { CircuitOpenError, GraphApiError, NotOnWhatsAppError, UnsupportedGroupOperationError, WhatsAppError } from '../../src/errors';
+
+const fail = () => Promise.reject(new GraphApiError('Service unavailable', { status: 503 }));
+const ok = () => Promise.resolve('sent');
+
+async function trip(breaker: CircuitBreaker, times: number) {
+    for (let i = 0; i < times; i++) {
+        await breaker.run(fail).catch(() => {});
+    }
+}
+
+describe('CircuitBreaker', () => {
+    afterEach(() => {
+        vi.useRealTimers();
+    });
+
+    it('should open after the failure threshold and fail fast', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 3 });
+        const opened = vi.fn();
+        breaker.on('open', opened);
+
+        await trip(breaker, 3);
+        const call = vi.fn(ok);
+
+        await expect(breaker.run(call)).rejects.toBeInstanceOf(CircuitOpenError);
+        expect(call).not.toHaveBeenCalled();
+        expect(breaker.state).toBe('open');
+        expect(opened).toHaveBeenCalledTimes(1);
+        expect(opened.mock.calls[0][0]).toMatchObject({ failures: 3, retryInMs: 30_000 });
+    });
+
+    it('should reset the count on success', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 3 });
+
+        await trip(breaker, 2);
+        await breaker.run(ok);
+        await trip(breaker, 2);
+
+        expect(breaker.state).toBe('closed');
+        expect(breaker.consecutiveFailures).toBe(2);
+    });
+
+    it('should not count errors caused by the message', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1 });
+
+        await breaker.run(() => Promise.reject(new NotOnWhatsAppError('123'))).catch(() => {});
+        await breaker.run(() => Promise.reject(new WhatsAppError('INVALID_RECIPIENT', 'bad'))).catch(() => {});
+        await breaker.run(() => Promise.reject(new WhatsAppError('UNSUPPORTED', 'no channels'))).catch(() => {});
+        await breaker.run(() => Promise.reject(new UnsupportedGroupOperationError('send poll', '1@g.us'))).catch(() => {});
+        for (const code of [100, 131008, 131009]) {
+            await breaker.run(() => Promise.reject(new GraphApiError('Invalid parameter', { status: 400, code }))).catch(() => {});
+        }
+
+        expect(breaker.state).toBe('closed');
+    });
+
+    it('should count auth, rate limit, server and connection failures', async () => {
+        const failures = [
+            new GraphApiError('Invalid token', { status: 401, code: 190 }),
+            new GraphApiError('Rate limit hit', { status: 400, code: 130429 }),
+            new WhatsAppError('SEND_FAILED', 'Failed to send', { cause: new GraphApiError('Bad gateway', { status: 502 }) }),
+            new WhatsAppError('NOT_CONNECTED', 'Not connected to WhatsApp'),
+        ];
+        for (const error of failures) {
+            const breaker = new CircuitBreaker('sales', { failureThreshold: 1 });
+            await breaker.run(() => Promise.reject(error)).catch(() => {});
+            expect(breaker.state).toBe('open');
+            breaker.clear();
+        }
+    });
+
+    it('should close after a successful half-open probe', async () => {
+        vi.useFakeTimers();
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, resetTimeoutMs: 1000 });
+        const closed = vi.fn();
+        breaker.on('close', closed);
+
+        await trip(breaker, 1);
+        vi.advanceTimersByTime(1000);
+
+        await expect(breaker.run(ok)).resolves.toBe('sent');
+        expect(breaker.state).toBe('closed');
+        expect(closed).toHaveBeenCalledTimes(1);
+    });
+
+    it('should reopen when the probe fails', async () => {
+        vi.useFakeTimers();
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 2, resetTimeoutMs: 1000 });
+        const opened = vi.fn();
+        breaker.on('open', opened);
+
+        await trip(breaker, 2);
+        vi.advanceTimersByTime(1000);
+        await trip(breaker, 1);
+
+        expect(breaker.state).toBe('open');
+        expect(breaker.retryInMs()).toBe(1000);
+        expect(opened).toHaveBeenCalledTimes(2);
+    });
+
+    it('should let only one probe through while half-open', async () => {
+        vi.useFakeTimers();
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, resetTimeoutMs: 1000 });
+
+        await trip(breaker, 1);
+        vi.advanceTimersByTime(1000);
+        let finish: (value: string) => void = () => {};
+        const probe = breaker.run(() => new Promise<string>((resolve) => (finish = resolve)));
+
+        await expect(breaker.run(ok)).rejects.toBeInstanceOf(CircuitOpenError);
+        finish('sent');
+        await expect(probe).resolves.toBe('sent');
+        expect(breaker.state).toBe('closed');
+    });
+
+    it('should queue sends while open and release them on recovery', async () => {
+        vi.useFakeTimers();
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, resetTimeoutMs: 1000, queue: true });
+
+        await trip(breaker, 1);
+        const first = breaker.run(() => Promise.resolve('first'));
+        const second = breaker.run(() => Promise.resolve('second'));
+        expect(breaker.queued).toBe(2);
+
+        await vi.advanceTimersByTimeAsync(1000);
+
+        await expect(first).resolves.toBe('first');
+        await expect(second).resolves.toBe('second');
+        expect(breaker.state).toBe('closed');
+    });
+
+    it('should fail fast once the queue is full', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, queue: true, maxQueued: 1 });
+
+        await trip(breaker, 1);
+        const queued = breaker.run(ok);
+
+        await expect(breaker.run(ok)).rejects.toBeInstanceOf(CircuitOpenError);
+        breaker.clear();
+        await expect(queued).rejects.toBeInstanceOf(CircuitOpenError);
+    });
+
+    it('should fail fast for sends that opt out of the queue', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, queue: true });
+
+        await trip(breaker, 1);
+
+        await expect(breaker.run(ok, { wait: false })).rejects.toBeInstanceOf(CircuitOpenError);
+        expect(breaker.queued).toBe(0);
+    });
+
+    it('should close and release queued sends on reset', async () => {
+        const breaker = new CircuitBreaker('sales', { failureThreshold: 1, queue: true });
+
+        await trip(breaker, 1);
+        const queued = breaker.run(ok);
+        breaker.reset();
+
+        await expect(queued).resolves.toBe('sent');
+        expect(breaker.state).toBe('closed');
+    });
+});
@@ -0,0 +1,72 @@
+import { describe, it, expect } from 'vitest';
+import { DeliveryLedger, messageHandle } from '../../src/outbox/delivery';
+import { ManualClock } from '../../src/utils/clock';
+
+const status = (messageId: string, value: string) => ({ messageId, status: value, recipient: '16505551234', timestamp: 0 });
+
+describe('DeliveryLedger', () => {
+    it('should only move a status forward', () => {
+        const ledger = new DeliveryLedger();
+
+        ledger.record(status('wamid.1', 'read'));
+        ledger.record(status('wamid.1', 'delivered'));
+
+        expect(ledger.status('wamid.1')).toBe('read');
+    });
+
+    it('should resolve waits when the status arrives, including one that arrived first', async () => {
+        const ledger = new DeliveryLedger();
+        ledger.record(status('wamid.1', 'delivered'));
+
+        const read = ledger.waitFor('wamid.1', 'read');
+        await expect(ledger.waitFor('wamid.1', 'delivered')).resolves.toBeUndefined();
+
+        ledger.record(status('wamid.1', 'read'));
+        await expect(read).resolves.toBeUndefined();
+    });
+
+    it('should reject waits when the message fails or the timeout passes', async () => {
+        const clock = new ManualClock();
+        const ledger = new DeliveryLedger(clock);
+
+        const failed = ledger.waitFor('wamid.1', 'delivered');
+        ledger.record(status('wamid.1', 'failed'));
+        await expect(failed).rejects.toMatchObject({ code: 'DELIVERY_FAILED' });
+
+        const late = ledger.waitFor('wamid.2', 'read', 1000);
+        clock.advance(1000);
+        await expect(late).rejects.toMatchObject({ code: 'DELIVERY_TIMEOUT' });
+    });
+
+    it('should give up on a wait after a day when given no timeout', async () => {
+        const clock = new ManualClock();
+        const ledger = new DeliveryLedger(clock);
+
+        const unanswered = ledger.waitFor('wamid.1', 'read');
+        clock.advance(24 * 60 * 60 * 1000);
+
+        await expect(unanswered).rejects.toMatchObject({ code: 'DELIVERY_TIMEOUT' });
+    });
+});
+
+describe('messageHandle', () => {
+    it('should wait on every chunk of a split message', async () => {
+        const ledger = new DeliveryLedger();
+        const handle = messageHandle('sales', [
+            { id: 'wamid.1', accountId: 'sales', ledger, response: {} },
+            { id: 'wamid.2', accountId: 'sales', ledger, response: {} },
+        ]);
+        let delivered = false;
+        const waiting = handle.awaitDelivered().then(() => {
+            delivered = true;
+        });
+
+        ledger.record(status('wamid.1', 'delivered'));
+        await Promise.resolve();
+        expect(delivered).toBe(false);
+
+        ledger.record(status('wamid.2', 'read'));
+        await waiting;
+        expect(handle).toMatchObject({ wamid: 'wamid.1', accountId: 'sales', chunkIds: ['wamid.1', 'wamid.2'] });
+    });
+});
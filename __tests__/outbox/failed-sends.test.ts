@@ -0,0 +1,86 @@
+import { describe, it, expect } from 'vitest';
+import { FailedSendLog, type DeadLetterStore, type FailedSend } from '../../src/outbox/failed-sends';
+
+describe('FailedSendLog', () => {
+    const message = { type: 'text', to: '1234567890', content: 'Hello' };
+
+    it('should record the error code and message', () => {
+        const log = new FailedSendLog();
+        const error = Object.assign(new Error('Rate limit hit'), { code: 'WHATSAPP_131056' });
+
+        const entry = log.record(message, error);
+
+        expect(entry.message).toBe(message);
+        expect(entry.error).toEqual({ code: 'WHATSAPP_131056', message: 'Rate limit hit' });
+        expect(entry.attempts).toEqual([{ code: 'WHATSAPP_131056', message: 'Rate limit hit', failedAt: entry.failedAt }]);
+        expect(log.get(entry.id)).toBe(entry);
+    });
+
+    it('should keep earlier attempts ahead of the final one', () => {
+        const log = new FailedSendLog();
+        const earlier = [{ code: 'SEND_FAILED', message: 'socket hang up', failedAt: 1 }];
+
+        const entry = log.record(message, new Error('timeout'), earlier);
+
+        expect(entry.attempts.map((a) => a.message)).toEqual(['socket hang up', 'timeout']);
+    });
+
+    it('should add a failed replay to the existing entry', () => {
+        const log = new FailedSendLog();
+        const entry = log.record(message, new Error('one'));
+
+        log.recordAttempt(entry.id, new Error('two'));
+
+        expect(log.size).toBe(1);
+        expect(entry.error.message).toBe('two');
+        expect(entry.attempts.map((a) => a.message)).toEqual(['one', 'two']);
+        expect(log.recordAttempt('missing', new Error('three'))).toBeUndefined();
+    });
+
+    it('should drop the oldest entries beyond the limit', () => {
+        const log = new FailedSendLog(2);
+
+        const first = log.record(message, new Error('one'));
+        log.record(message, new Error('two'));
+        log.record(message, new Error('three'));
+
+        expect(log.size).toBe(2);
+        expect(log.get(first.id)).toBeUndefined();
+        expect(log.list().map((e) => e.error.message)).toEqual(['two', 'three']);
+    });
+
+    it('should remove entries by id', () => {
+        const log = new FailedSendLog();
+        const entry = log.record(message, new Error('boom'));
+
+        expect(log.remove(entry.id)).toBe(true);
+        expect(log.list()).toEqual([]);
+    });
+
+    it('should restore stored entries and save every change', async () => {
+        let saved: FailedSend[] = [];
+        const stored: FailedSend = {
+            id: 'failed-1-1',
+            message,
+            error: { code: 'SEND_FAILED', message: 'old' },
+            failedAt: 1,
+            attempts: [{ code: 'SEND_FAILED', message: 'old', failedAt: 1 }],
+        };
+        const store: DeadLetterStore = {
+            load: async () => [stored],
+            save: async (entries) => {
+                saved = entries;
+            },
+        };
+        const log = new FailedSendLog();
+
+        await log.useStore(store);
+        const entry = log.record(message, new Error('new'));
+        await log.flush();
+        expect(saved.map((e) => e.id)).toEqual(['failed-1-1', entry.id]);
+
+        log.remove('failed-1-1');
+        await log.flush();
+        expect(saved.map((e) => e.id)).toEqual([entry.id]);
+    });
+});
@@ -0,0 +1,35 @@
+import { describe, it, expect } from 'vitest';
+import { failoverReason, isServiceWindowClosed } from '../../src/outbox/failover';
+import { CircuitOpenError, GraphApiError, NotOnWhatsAppError, WhatsAppError } from '../../src/errors';
+
+describe('failoverReason', () => {
+    it('should reroute when the circuit is open', () => {
+        expect(failoverReason(new CircuitOpenError('sales', 1000))).toBe('circuit_open');
+    });
+
+    it('should reroute on throughput and pair rate limits', () => {
+        expect(failoverReason(new GraphApiError('Rate limit hit', { status: 400, code: 130429 }))).toBe('rate_limited');
+        expect(failoverReason(new GraphApiError('Pair rate limit', { status: 400, code: 131056 }))).toBe('rate_limited');
+        expect(failoverReason(new GraphApiError('Too many requests', { status: 429 }))).toBe('rate_limited');
+    });
+
+    it('should reroute when the number is restricted', () => {
+        expect(failoverReason(new GraphApiError('Account locked', { status: 400, code: 131031 }))).toBe('flagged');
+        expect(failoverReason(new GraphApiError('Spam rate limit hit', { status: 400, code: 131048 }))).toBe('flagged');
+    });
+
+    it('should not reroute errors another number would hit too', () => {
+        expect(failoverReason(new GraphApiError('Invalid parameter', { status: 400, code: 100 }))).toBeUndefined();
+        expect(failoverReason(new NotOnWhatsAppError('1234567890'))).toBeUndefined();
+        expect(failoverReason(new WhatsAppError('NOT_CONNECTED', 'Not connected'))).toBeUndefined();
+        expect(failoverReason(new Error('socket hang up'))).toBeUndefined();
+    });
+});
+
+describe('isServiceWindowClosed', () => {
+    it('should recognise free-form messages sent after the 24-hour window', () => {
+        expect(isServiceWindowClosed(new GraphApiError('Re-engagement message', { status: 400, code: 131047 }))).toBe(true);
+        expect(isServiceWindowClosed(new GraphApiError('Invalid parameter', { status: 400, code: 100 }))).toBe(false);
+        expect(isServiceWindowClosed(new Error('socket hang up'))).toBe(false);
+    });
+});
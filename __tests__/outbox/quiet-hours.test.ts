@@ -0,0 +1,42 @@
+import { describe, it, expect } from 'vitest';
+import { QuietHours } from '../../src/outbox/quiet-hours';
+
+const utc = (hours: number, minutes = 0) => Date.UTC(2026, 0, 15, hours, minutes);
+
+describe('QuietHours', () => {
+    it('should defer recipients in quiet hours until their local morning', () => {
+        const quiet = new QuietHours({ start: '21:00', end: '08:00' });
+
+        // 22:30 in London: wait until 08:00
+        expect(quiet.sendAt('447700900123', utc(22, 30))).toBe(utc(22, 30) + 9.5 * 3_600_000);
+        // 04:00 the next day in Kolkata: wait until 08:00
+        expect(quiet.sendAt('919876543210', utc(22, 30))).toBe(utc(22, 30) + 4 * 3_600_000);
+        // 17:30 in New York: fine
+        expect(quiet.sendAt('15551234567', utc(22, 30))).toBe(utc(22, 30));
+    });
+
+    it('should support windows that do not wrap midnight', () => {
+        const quiet = new QuietHours({ start: '12:00', end: '14:00', timeZone: 'UTC' });
+
+        expect(quiet.sendAt('999123456', utc(13, 15))).toBe(utc(14));
+        expect(quiet.sendAt('999123456', utc(14))).toBe(utc(14));
+    });
+
+    it('should prefer known time zones over inference', () => {
+        const quiet = new QuietHours({ timeZones: { '+1 503 555 0100': 'America/Los_Angeles' } });
+        quiet.setTimeZone('15551234567', 'Asia/Tokyo');
+
+        expect(quiet.timeZoneOf('15035550100')).toBe('America/Los_Angeles');
+        expect(quiet.timeZoneOf('15551234567@s.whatsapp.net')).toBe('Asia/Tokyo');
+    });
+
+    it('should reject malformed times', () => {
+        expect(() => new QuietHours({ start: '25:00' })).toThrow(/quietHours.start/);
+    });
+
+    it('should reject unknown time zones up front', () => {
+        expect(() => new QuietHours({ timeZone: 'Mars/Olympus' })).toThrow(/quietHours.timeZone/);
+        expect(() => new QuietHours({ timeZones: { '15551234567': 'Nowhere' } })).toThrow(/quietHours.timeZones/);
+        expect(() => new QuietHours().setTimeZone('15551234567', 'Nowhere')).toThrow(/not a known time zone/);
+    });
+});
@@ -0,0 +1,123 @@
+import { describe, it, expect, vi } from 'vitest';
+import { GraphApiError, WhatsAppError } from '../../src/errors';
+import { isTimeoutError, isTransientError, retryDelay } from '../../src/outbox/retry';
+import type { IAgentRuntime } from '@elizaos/core';
+import { WhatsAppPlugin } from '../../src/index';
+import { WhatsAppConnectorService } from '../../src/service';
+
+describe('isTransientError', () => {
+    const axiosError = (status?: number) =>
+        Object.assign(new Error('request failed'), {
+            isAxiosError: true,
+            response: status === undefined ? undefined : { status },
+        });
+
+    it('should retry network failures and server errors', () => {
+        expect(isTransientError(axiosError())).toBe(true);
+        expect(isTransientError(axiosError(503))).toBe(true);
+        expect(isTransientError(new WhatsAppError('SEND_FAILED', 'boom', { cause: axiosError() }))).toBe(true);
+        expect(isTransientError(new GraphApiError('Service unavailable', { status: 503 }))).toBe(true);
+        expect(isTransientError(new GraphApiError('Temporary error', { status: 400, code: 131016 }))).toBe(true);
+    });
+
+    it('should not retry client errors', () => {
+        expect(isTransientError(axiosError(400))).toBe(false);
+        expect(isTransientError(new GraphApiError('Rate limit hit', { status: 400, code: 131056 }))).toBe(false);
+        expect(isTransientError(new WhatsAppError('INVALID_RECIPIENT', 'bad number'))).toBe(false);
+        expect(isTransientError(new Error('boom'))).toBe(false);
+    });
+});
+
+describe('isTimeoutError', () => {
+    const axiosError = (code?: string, status?: number) =>
+        Object.assign(new Error('request failed'), {
+            isAxiosError: true,
+            code,
+            response: status === undefined ? undefined : { status },
+        });
+
+    it('should recognize requests that got no answer in time', () => {
+        expect(isTimeoutError(axiosError('ECONNABORTED'))).toBe(true);
+        expect(isTimeoutError(axiosError(undefined, 504))).toBe(true);
+        expect(isTimeoutError(new WhatsAppError('SEND_FAILED', 'boom', { cause: axiosError('ETIMEDOUT') }))).toBe(true);
+        expect(isTimeoutError(new GraphApiError('Gateway timeout', { status: 504 }))).toBe(true);
+    });
+
+    it('should not treat other failures as timeouts', () => {
+        expect(isTimeoutError(axiosError('ECONNREFUSED'))).toBe(false);
+        expect(isTimeoutError(axiosError(undefined, 503))).toBe(false);
+        expect(isTimeoutError(new GraphApiError('Service unavailable', { status: 503 }))).toBe(false);
+    });
+});
+
+describe('retryDelay', () => {
+    it('should double the delay on each retry', () => {
+        expect(retryDelay({}, 0)).toBe(500);
+        expect(retryDelay({ backoffMs: 100 }, 2)).toBe(400);
+    });
+});
+
+describe('send retries', () => {
+    const timeout = () => Object.assign(new Error('timeout of 30000ms exceeded'), { isAxiosError: true, code: 'ECONNABORTED' });
+
+    function plugin(send: () => Promise<unknown>, config = {}) {
+        const plugin = new WhatsAppPlugin({
+            accessToken: 'token',
+            phoneNumberId: '123',
+            sendRetry: { backoffMs: 0 },
+            ...config,
+        });
+        (plugin as unknown as { messageHandler: unknown }).messageHandler = { send };
+        vi.spyOn(console, 'error').mockImplementation(() => {});
+        return plugin;
+    }
+
+    it('should only retry a timeout when the send has an idempotency key', async () => {
+        const send = vi.fn().mockRejectedValueOnce(timeout()).mockResolvedValue({ messages: [{ id: 'wamid.1' }] });
+        const unkeyed = plugin(send);
+        await expect(unkeyed.sendMessage({ type: 'text', to: '1234567890', content: 'Hi' })).rejects.toThrow('timeout');
+        expect(send).toHaveBeenCalledTimes(1);
+
+        send.mockRejectedValueOnce(timeout());
+        const keyed = plugin(send);
+        await keyed.sendMessage({ type: 'text', to: '1234567890', content: 'Hi' }, { idempotencyKey: 'k1' });
+        expect(send).toHaveBeenCalledTimes(3);
+    });
+
+    it('should count a send once toward the circuit breaker however many tries it took', async () => {
+        const send = vi.fn().mockRejectedValue(new GraphApiError('Service unavailable', { status: 503 }));
+        const sender = plugin(send, { circuitBreaker: { failureThreshold: 2 } });
+
+        await expect(sender.sendMessage({ type: 'text', to: '1234567890', content: 'Hi' })).rejects.toThrow();
+
+        expect(send).toHaveBeenCalledTimes(3);
+        expect(sender.circuit?.consecutiveFailures).toBe(1);
+        expect(sender.circuit?.state).toBe('closed');
+    });
+});
+
+describe('idempotent sends', () => {
+    it('should send a key once even when a repeat goes out from another account', async () => {
+        const connector = new WhatsAppConnectorService({} as IAgentRuntime);
+        const sends: Record<string, ReturnType<typeof vi.fn>> = {};
+        for (const [accountId, phoneNumberId] of [['default', '123'], ['sales', '456']]) {
+            const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId });
+            sends[accountId] = vi.fn(async () => ({ messages: [{ id: `wamid.${accountId}` }] }));
+            (plugin as unknown as { messageHandler: unknown }).messageHandler = { send: sends[accountId] };
+            connector['accounts'].set(accountId, {
+                plugin,
+                config: { accountId, enabled: true, accessToken: 'token', phoneNumberId },
+                enabled: true,
+            });
+        }
+        const message = { type: 'text' as const, to: '1234567890', content: 'Hi' };
+
+        const first = await connector.sendMessage(message, { accountId: 'default', idempotencyKey: 'k1' });
+        const repeat = await connector.sendMessage(message, { accountId: 'sales', idempotencyKey: 'k1' });
+
+        expect(sends.default).toHaveBeenCalledTimes(1);
+        expect(sends.sales).not.toHaveBeenCalled();
+        expect(repeat).toMatchObject({ wamid: 'wamid.default', accountId: 'default' });
+        expect(first.wamid).toBe('wamid.default');
+    });
+});
@@ -0,0 +1,42 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WhatsAppPlugin } from '../../src/index';
+import { ConversationWindow, messagingTierLimit } from '../../src/outbox/tier';
+
+describe('messagingTierLimit', () => {
+    it('should map Meta tiers to daily conversation limits', () => {
+        expect(messagingTierLimit('TIER_1K')).toBe(1000);
+        expect(messagingTierLimit('TIER_UNLIMITED')).toBe(Number.POSITIVE_INFINITY);
+        expect(messagingTierLimit('TIER_UNKNOWN')).toBeUndefined();
+        expect(messagingTierLimit(undefined)).toBeUndefined();
+    });
+});
+
+describe('ConversationWindow', () => {
+    it('should count distinct recipients over the last 24 hours', () => {
+        const window = new ConversationWindow();
+        const day = 24 * 60 * 60 * 1000;
+
+        window.record('111', 0);
+        window.record('222', 1000);
+        window.record('111', 2000);
+
+        expect(window.count(2000)).toBe(2);
+        expect(window.count(1000 + day)).toBe(1);
+        expect(window.count(2000 + day)).toBe(0);
+    });
+});
+
+describe('WhatsAppPlugin.remainingCapacity', () => {
+    it('should only count chats a template was sent to', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123', circuitBreaker: false, messagingLimit: 10 });
+        (plugin as unknown as { messageHandler: unknown }).messageHandler = {
+            send: vi.fn(async () => ({ messages: [{ id: 'wamid.out' }] })),
+        };
+
+        await plugin.sendMessage({ type: 'text', to: '1234567890', content: 'Thanks for writing' });
+        expect(await plugin.remainingCapacity()).toBe(10);
+
+        await plugin.sendMessage({ type: 'template', to: '0987654321', content: { name: 'welcome', language: { code: 'en' } } });
+        expect(await plugin.remainingCapacity()).toBe(9);
+    });
+});
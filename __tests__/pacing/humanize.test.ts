@@ -0,0 +1,15 @@
+import { describe, it, expect } from 'vitest';
+import { typingDelay } from '../../src/pacing/humanize';
+
+describe('typingDelay', () => {
+    it('should scale with message length', () => {
+        expect(typingDelay('x'.repeat(50))).toBe(2000);
+        expect(typingDelay('x'.repeat(50), { msPerChar: 20 })).toBe(1000);
+    });
+
+    it('should stay within the configured bounds', () => {
+        expect(typingDelay('ok')).toBe(800);
+        expect(typingDelay('x'.repeat(1000))).toBe(6000);
+        expect(typingDelay('x'.repeat(1000), { minDelayMs: 500, maxDelayMs: 300 })).toBe(500);
+    });
+});
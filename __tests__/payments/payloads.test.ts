@@ -0,0 +1,76 @@
+import { describe, it, expect } from 'vitest';
+import { buildOrderDetails, buildOrderStatus, parsePaymentStatus } from '../../src/payments';
+
+describe('payments', () => {
+    it('should build order details with computed totals', () => {
+        const message = buildOrderDetails({
+            referenceId: 'order-42',
+            body: 'Your order',
+            items: [
+                { retailerId: 'tea', name: 'Masala tea', amount: 15000, quantity: 2 },
+                { retailerId: 'mug', name: 'Mug', amount: 50000, saleAmount: 40000, quantity: 1 },
+            ],
+            tax: 1000,
+            shipping: 5000,
+            paymentConfiguration: 'default',
+        });
+
+        expect(message.type).toBe('order_details');
+        expect(message.action.name).toBe('review_and_pay');
+        expect(message.action.parameters).toMatchObject({
+            reference_id: 'order-42',
+            type: 'physical-goods',
+            payment_configuration: 'default',
+            currency: 'INR',
+            total_amount: { value: 76000, offset: 100 },
+            order: {
+                status: 'pending',
+                subtotal: { value: 70000, offset: 100 },
+                tax: { value: 1000, offset: 100 },
+                shipping: { value: 5000, offset: 100 },
+            },
+        });
+    });
+
+    it('should reject empty orders and fractional amounts', () => {
+        expect(() => buildOrderDetails({ referenceId: 'o1', body: 'Pay', items: [] })).toThrow('has no items');
+        expect(() =>
+            buildOrderDetails({ referenceId: 'o1', body: 'Pay', items: [{ retailerId: 'a', name: 'A', amount: 1.5, quantity: 1 }] })
+        ).toThrow('minor units');
+    });
+
+    it('should build order status updates', () => {
+        expect(buildOrderStatus('order-42', 'shipped', 'On its way', 'Courier: Blue Dart').action).toEqual({
+            name: 'review_order',
+            parameters: { reference_id: 'order-42', order: { status: 'shipped', description: 'Courier: Blue Dart' } },
+        });
+    });
+
+    it('should parse payment status webhooks and skip delivery statuses', () => {
+        const payment = parsePaymentStatus({
+            id: 'wamid.1',
+            status: 'captured',
+            timestamp: '1700000000',
+            recipient_id: '919000000000',
+            type: 'payment',
+            payment: {
+                reference_id: 'order-42',
+                amount: { value: 76000, offset: 100 },
+                currency: 'INR',
+                transaction: { id: 'txn-1', type: 'upi', status: 'success' },
+            },
+        });
+
+        expect(payment).toEqual({
+            referenceId: 'order-42',
+            status: 'captured',
+            recipient: '919000000000',
+            timestamp: 1700000000,
+            amount: { value: 76000, offset: 100 },
+            currency: 'INR',
+            transactionId: 'txn-1',
+            transactionStatus: 'success',
+        });
+        expect(parsePaymentStatus({ id: 'wamid.2', status: 'read', timestamp: '1', recipient_id: '1' })).toBeNull();
+    });
+});
@@ -0,0 +1,137 @@
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { PollManager } from '../../src/polls/poll';
+import { ManualClock } from '../../src/utils/clock';
+
+const definition = (overrides = {}) => ({
+    id: 'lunch',
+    question: 'Team lunch on Friday?',
+    options: [
+        { id: 'yes', title: 'Yes' },
+        { id: 'no', title: 'No' },
+    ],
+    recipients: ['111', '222', '333'],
+    windowMs: 60_000,
+    ...overrides,
+});
+
+describe('PollManager', () => {
+    afterEach(() => {
+        vi.useRealTimers();
+    });
+
+    it('should send namespaced reply buttons to every recipient', () => {
+        const polls = new PollManager();
+        const { pollId, messages } = polls.open(definition());
+
+        expect(pollId).toBe('lunch');
+        expect(messages.map((m) => m.to)).toEqual(['111', '222', '333']);
+        expect(messages[0].content).toMatchObject({
+            type: 'button',
+            body: { text: 'Team lunch on Friday?' },
+            action: { buttons: [{ reply: { id: 'poll:lunch:yes' } }, { reply: { id: 'poll:lunch:no' } }] },
+        });
+    });
+
+    it('should use a list for more than three options', () => {
+        const polls = new PollManager();
+        const options = ['a', 'b', 'c', 'd'].map((id) => ({ id, title: id.toUpperCase() }));
+        const { messages } = polls.open(definition({ options }));
+
+        expect(messages[0].content).toMatchObject({ type: 'list', action: { button: 'Vote' } });
+    });
+
+    it('should tally votes when the window closes', () => {
+        vi.useFakeTimers();
+        const polls = new PollManager();
+        const results = vi.fn();
+        polls.on('result', results);
+        polls.open(definition());
+
+        expect(polls.vote('111', 'poll:lunch:yes')).toBe(true);
+        expect(polls.vote('222', 'poll:lunch:no')).toBe(true);
+        expect(polls.vote('222', 'poll:lunch:yes')).toBe(true);
+        expect(results).not.toHaveBeenCalled();
+
+        vi.advanceTimersByTime(60_000);
+
+        expect(results).toHaveBeenCalledWith(
+            expect.objectContaining({
+                pollId: 'lunch',
+                tallies: [
+                    { id: 'yes', title: 'Yes', votes: 2 },
+                    { id: 'no', title: 'No', votes: 0 },
+                ],
+                votes: { 111: 'yes', 222: 'yes' },
+            })
+        );
+        expect(polls.isOpen('lunch')).toBe(false);
+    });
+
+    it('should close on the injected clock', () => {
+        const clock = new ManualClock(1_000);
+        const polls = new PollManager(clock);
+        const results = vi.fn();
+        polls.on('result', results);
+        polls.open(definition());
+
+        clock.advance(59_999);
+        expect(polls.isOpen('lunch')).toBe(true);
+        clock.advance(1);
+
+        expect(results).toHaveBeenCalledWith(expect.objectContaining({ startedAt: 1_000, closedAt: 61_000 }));
+        expect(polls.isOpen('lunch')).toBe(false);
+    });
+
+    it('should close early once every recipient voted', () => {
+        const polls = new PollManager();
+        const results = vi.fn();
+        polls.on('result', results);
+        polls.open(definition({ recipients: ['111'] }));
+
+        polls.vote('111', 'poll:lunch:no');
+
+        expect(results).toHaveBeenCalledTimes(1);
+        expect(polls.vote('111', 'poll:lunch:yes')).toBe(false);
+    });
+
+    it('should keep a group poll open for its whole window', () => {
+        const polls = new PollManager();
+        const results = vi.fn();
+        polls.on('result', results);
+        polls.open(definition({ recipients: ['120363042000000000@g.us'] }));
+
+        expect(polls.vote('4915112345678', 'poll:lunch:yes', '120363042000000000@g.us')).toBe(true);
+
+        expect(results).not.toHaveBeenCalled();
+        expect(polls.isOpen('lunch')).toBe(true);
+    });
+
+    it('should only count votes from the chats the poll went to', () => {
+        const polls = new PollManager();
+        polls.open(definition({ recipients: ['111', '120363042000000000@g.us'] }));
+
+        expect(polls.vote('999', 'poll:lunch:yes')).toBe(false);
+        expect(polls.vote('4915112345678', 'poll:lunch:yes', '120363042000000001@g.us')).toBe(false);
+        expect(polls.vote('4915112345678', 'poll:lunch:yes', '120363042000000000@g.us')).toBe(true);
+        expect(polls.vote('111', 'poll:lunch:no')).toBe(true);
+        expect(polls.isOpen('lunch')).toBe(true);
+    });
+
+    it('should ignore replies that are not votes for an open poll', () => {
+        const polls = new PollManager();
+        polls.open(definition());
+
+        expect(polls.vote('111', 'billing')).toBe(false);
+        expect(polls.vote('111', 'poll:other:yes')).toBe(false);
+        expect(polls.vote('111', 'poll:lunch:maybe')).toBe(false);
+    });
+
+    it('should reject invalid polls', () => {
+        const polls = new PollManager();
+
+        expect(() => polls.open(definition({ options: [{ id: 'yes', title: 'Yes' }] }))).toThrow('at least two');
+        expect(() => polls.open(definition({ recipients: [] }))).toThrow('recipient');
+        polls.open(definition());
+        expect(() => polls.open(definition())).toThrow('already open');
+    });
+});
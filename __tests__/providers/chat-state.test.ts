@@ -0,0 +1,145 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { IAgentRuntime } from '@elizaos/core';
+import { WhatsAppConnectorService, type ChatState } from '../../src/service';
+import { WhatsAppPlugin } from '../../src/index';
+import { InMemoryMessageArchive, type ArchivedMessage } from '../../src/archive';
+import { chatStateProvider } from '../../src/providers/chat-state';
+import type { WhatsAppProviderContext } from '../../src/providers/provider';
+import { ManualClock } from '../../src/utils/clock';
+
+const HOUR = 60 * 60 * 1000;
+
+const archived = (text: string, timestamp: number, direction: 'inbound' | 'outbound' = 'inbound'): ArchivedMessage => ({
+    id: `wamid.${timestamp}`,
+    chatId: '16505551234',
+    direction,
+    sender: direction === 'inbound' ? '16505551234' : 'agent',
+    type: 'text',
+    text,
+    timestamp,
+});
+
+// A service with one Cloud API account whose archive already holds `history`
+async function service(clock: ManualClock, history: ArchivedMessage[] = []): Promise<WhatsAppConnectorService> {
+    const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123' }, clock);
+    const archive = new InMemoryMessageArchive({}, clock);
+    for (const message of history) await archive.append(message);
+    plugin.useArchive(archive);
+    const connector = new WhatsAppConnectorService({} as IAgentRuntime);
+    connector['accounts'].set('default', {
+        plugin,
+        config: { accountId: 'default', enabled: true, accessToken: 'token', phoneNumberId: '123' },
+        enabled: true,
+    });
+    return connector;
+}
+
+describe('getChatState', () => {
+    it('should judge the service window on the account clock', async () => {
+        const clock = new ManualClock(10 * HOUR);
+        const connector = await service(clock, [archived('Hi', 9 * HOUR), archived('Hello!', 9 * HOUR + 1, 'outbound')]);
+
+        expect(await connector.getChatState('whatsapp:+1 650-555-1234')).toEqual({
+            chatId: '16505551234',
+            kind: 'user',
+            accountId: 'default',
+            language: undefined,
+            windowOpen: true,
+            windowClosesAt: 33 * HOUR,
+            recent: [],
+        });
+
+        clock.advance(24 * HOUR);
+        expect(await connector.getChatState('16505551234')).toMatchObject({ windowOpen: false, windowClosesAt: undefined });
+    });
+
+    it('should only return recent messages when asked', async () => {
+        const connector = await service(new ManualClock(10 * HOUR), [archived('One', 1), archived('Two', 2)]);
+
+        expect((await connector.getChatState('16505551234', 1)).recent.map((m) => m.text)).toEqual(['Two']);
+    });
+
+    it('should leave the window out for groups and Baileys accounts', async () => {
+        const clock = new ManualClock(10 * HOUR);
+        const connector = await service(clock, [archived('Hi', 9 * HOUR)]);
+
+        expect(await connector.getChatState('120363042000000000@g.us')).not.toHaveProperty('windowOpen');
+
+        const plugin = connector['accounts'].get('default')?.plugin as WhatsAppPlugin;
+        vi.spyOn(plugin, 'usesCloudApi', 'get').mockReturnValue(false);
+        expect((await connector.getChatState('16505551234')).windowOpen).toBeUndefined();
+    });
+
+    it('should describe the chat as the given account sees it', async () => {
+        const clock = new ManualClock(10 * HOUR);
+        const connector = await service(clock, [archived('Hi', 9 * HOUR)]);
+        const sales = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '456' }, clock);
+        connector['accounts'].set('sales', {
+            plugin: sales,
+            config: { accountId: 'sales', enabled: true, accessToken: 'token', phoneNumberId: '456' },
+            enabled: true,
+        });
+
+        expect(await connector.getChatState('16505551234', 0, 'sales')).toMatchObject({ accountId: 'sales', windowOpen: false });
+    });
+
+    it('should reject anything that is not a chat address', async () => {
+        const connector = await service(new ManualClock());
+
+        await expect(connector.getChatState('not a number')).rejects.toThrow();
+    });
+});
+
+describe('chatStateProvider', () => {
+    const state: ChatState = {
+        chatId: '16505551234',
+        kind: 'user',
+        accountId: 'default',
+        language: { language: 'es', source: 'explicit', updatedAt: 0 },
+        windowOpen: false,
+        recent: [],
+    };
+    const context = (getChatState: (...args: unknown[]) => Promise<ChatState>, chatId: string | undefined = '16505551234') =>
+        ({
+            runtime: { character: { name: 'Ada' } },
+            message: {},
+            state: {},
+            service: { getChatState },
+            chatId,
+            accountId: 'default',
+        }) as unknown as WhatsAppProviderContext;
+
+    it('should describe the chat for the agent', async () => {
+        const result = await chatStateProvider.get(context(async () => state));
+
+        expect(result.text).toContain('Chat: 16505551234 (user)');
+        expect(result.text).toContain('Contact language: es (explicit)');
+        expect(result.text).toContain('Service window: closed');
+        expect(result.text).not.toContain('Last messages');
+        expect(result.values).toEqual({
+            whatsappChatId: '16505551234',
+            whatsappChatKind: 'user',
+            whatsappLanguage: 'es',
+            whatsappWindowOpen: false,
+        });
+    });
+
+    it('should ask for the chat on the account the message arrived on', async () => {
+        const getChatState = vi.fn(async () => state);
+        await chatStateProvider.get(context(getChatState));
+
+        expect(getChatState).toHaveBeenCalledWith('16505551234', undefined, 'default');
+    });
+
+    it('should quote recent messages when the state has them', async () => {
+        const recent = [archived('Hi', 1), archived('Hello!', 2, 'outbound')];
+        const result = await chatStateProvider.get(context(async () => ({ ...state, recent })));
+
+        expect(result.text).toContain('Last messages:\n- 16505551234: Hi\n- Ada: Hello!');
+    });
+
+    it('should add nothing without a chat or when the lookup fails', async () => {
+        expect(await chatStateProvider.get(context(async () => state, undefined))).toEqual({ text: '' });
+        expect(await chatStateProvider.get(context(async () => { throw new Error('boom'); }))).toEqual({ text: '' });
+    });
+});
@@ -0,0 +1,53 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { IAgentRuntime, Memory, State } from '@elizaos/core';
+import { providerContext, toRuntimeProvider, type WhatsAppProvider } from '../../src/providers/provider';
+
+const service = { running: true };
+const runtime = (running = true) =>
+    ({ getService: vi.fn((type: string) => (running && type === 'whatsapp_connector' ? service : null)) }) as unknown as IAgentRuntime;
+const message = (source = 'whatsapp') =>
+    ({ content: { source, channelId: '16505551234', whatsappAccount: 'sales' } }) as unknown as Memory;
+const state = {} as State;
+
+describe('providerContext', () => {
+    it('should carry the service, chat and account of a WhatsApp message', () => {
+        const rt = runtime();
+        const msg = message();
+
+        expect(providerContext(rt, msg, state)).toEqual({
+            runtime: rt,
+            message: msg,
+            state,
+            service,
+            chatId: '16505551234',
+            accountId: 'sales',
+        });
+    });
+
+    it('should be undefined for other sources or when the connector is not running', () => {
+        expect(providerContext(runtime(), message('discord'), state)).toBeUndefined();
+        expect(providerContext(runtime(false), message(), state)).toBeUndefined();
+    });
+});
+
+describe('toRuntimeProvider', () => {
+    const provider: WhatsAppProvider = {
+        name: 'WHATSAPP_TEST',
+        description: 'Test provider',
+        get: vi.fn(async ({ chatId }) => ({ text: `chat ${chatId}` })),
+    };
+    const adapted = toRuntimeProvider(provider);
+
+    it('should keep the name and description and pass the context', async () => {
+        expect(adapted).toMatchObject({ name: 'WHATSAPP_TEST', description: 'Test provider' });
+        expect(await adapted.get(runtime(), message(), state)).toEqual({ text: 'chat 16505551234' });
+    });
+
+    it('should return an empty text without calling the provider when it does not apply', async () => {
+        vi.mocked(provider.get).mockClear();
+
+        expect(await adapted.get(runtime(), message('telegram'), state)).toEqual({ text: '' });
+        expect(await adapted.get(runtime(false), message(), state)).toEqual({ text: '' });
+        expect(provider.get).not.toHaveBeenCalled();
+    });
+});
@@ -0,0 +1,67 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ReactionTally, formatReactions } from '../../src/reactions/tally';
+import { WhatsAppPlugin } from '../../src/index';
+import { InMemoryMessageArchive } from '../../src/archive';
+
+describe('ReactionTally', () => {
+    it('should count one reaction per reactor, replacing earlier ones', () => {
+        const tally = new ReactionTally();
+        tally.record('wamid.1', '111', '👍');
+        tally.record('wamid.1', '222', '👍');
+        tally.record('wamid.1', '333', '😂');
+        const reactions = tally.record('wamid.1', '333', '❤️');
+
+        expect(reactions).toEqual({
+            messageId: 'wamid.1',
+            total: 3,
+            counts: { '👍': 2, '❤️': 1 },
+            reactors: { '111': '👍', '222': '👍', '333': '❤️' },
+        });
+        expect(formatReactions(reactions)).toBe('👍 2, ❤️ 1');
+    });
+
+    it('should drop reactions that are removed', () => {
+        const tally = new ReactionTally();
+        tally.record('wamid.1', '111', '👍');
+        tally.record('wamid.1', '111', '');
+
+        expect(tally.get('wamid.1')).toEqual({ messageId: 'wamid.1', total: 0, counts: {}, reactors: {} });
+    });
+
+    it('should keep only the most recently reacted-to messages', () => {
+        const tally = new ReactionTally({ maxChats: 1 });
+        tally.record('wamid.1', '111', '👍');
+        tally.record('wamid.2', '111', '👍');
+
+        expect(tally.get('wamid.1').total).toBe(0);
+        expect(tally.get('wamid.2').total).toBe(1);
+    });
+});
+
+describe('WhatsAppPlugin reactions', () => {
+    const reaction = (messageId: string, from = '16505551234') => ({
+        id: 'wamid.reaction',
+        from,
+        timestamp: 1700000000,
+        type: 'reaction' as const,
+        content: '',
+        reaction: { messageId, emoji: '👍' },
+    });
+
+    it('should find the reacted message by id without reading the chat archive', async () => {
+        const plugin = new WhatsAppPlugin({ accessToken: 'token', phoneNumberId: '123' });
+        const archive = new InMemoryMessageArchive();
+        plugin.useArchive(archive);
+        (plugin as unknown as { messageHandler: unknown }).messageHandler = {
+            send: vi.fn(async () => ({ messages: [{ id: 'wamid.out' }] })),
+        };
+        await plugin.sendMessage({ type: 'text', to: '16505551234', content: 'Hi' });
+        const recent = vi.spyOn(archive, 'recent');
+
+        await plugin['handleReaction'](reaction('wamid.out'));
+        await plugin['handleReaction'](reaction('wamid.out', '16505550000'));
+
+        expect(plugin.getReactions('wamid.out').total).toBe(1);
+        expect(recent).not.toHaveBeenCalled();
+    });
+});
@@ -0,0 +1,63 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ReactionWorkflows } from '../../src/reactions/workflows';
+
+const context = (emoji) => ({
+    emoji,
+    reactor: '1234567890',
+    chatId: '1234567890',
+    accountId: 'default',
+    message: {
+        id: 'wamid.1',
+        chatId: '1234567890',
+        direction: 'outbound',
+        sender: 'agent',
+        type: 'text',
+        text: 'Your ticket is open',
+        timestamp: Date.now(),
+    },
+});
+
+describe('ReactionWorkflows', () => {
+    it('should run handlers registered for the emoji', async () => {
+        const workflows = new ReactionWorkflows();
+        const resolve = vi.fn();
+        const escalate = vi.fn();
+        workflows.on('👍', resolve);
+        workflows.on(['❌', '👎'], escalate);
+
+        expect(await workflows.dispatch(context('👎'))).toBe(1);
+        expect(escalate).toHaveBeenCalledWith(expect.objectContaining({ emoji: '👎' }));
+        expect(resolve).not.toHaveBeenCalled();
+        expect(await workflows.dispatch(context('🎉'))).toBe(0);
+    });
+
+    it('should ignore the emoji presentation selector', async () => {
+        const workflows = new ReactionWorkflows();
+        const handler = vi.fn();
+        workflows.on('❤', handler);
+
+        expect(workflows.has('❤️')).toBe(true);
+        await workflows.dispatch(context('❤️'));
+        expect(handler).toHaveBeenCalledTimes(1);
+    });
+
+    it('should stop running a handler once unsubscribed', async () => {
+        const workflows = new ReactionWorkflows();
+        const handler = vi.fn();
+        const off = workflows.on(['👍', '✅'], handler);
+
+        off();
+        expect(workflows.has('👍')).toBe(false);
+        expect(await workflows.dispatch(context('✅'))).toBe(0);
+        expect(handler).not.toHaveBeenCalled();
+    });
+
+    it('should accept handlers registered by shortcode', async () => {
+        const workflows = new ReactionWorkflows();
+        const resolve = vi.fn();
+        workflows.on([':thumbsup:', 'heart'], resolve);
+
+        expect(workflows.has('👍')).toBe(true);
+        expect(await workflows.dispatch(context('❤'))).toBe(1);
+    });
+});
@@ -0,0 +1,74 @@
+import { describe, it, expect } from 'vitest';
+import { readStateSnapshot, STATE_SNAPSHOT_VERSION } from '../../src/state/snapshot';
+import { InMemoryMessageArchive } from '../../src/archive/message-archive';
+import { InMemoryMenuSessionStore } from '../../src/menus/store';
+import { ConversationWindow } from '../../src/outbox/tier';
+import { StickyRoutes } from '../../src/accounts/sticky';
+import { SenderRateLimiter } from '../../src/middleware/rate-limiter';
+import { ManualClock } from '../../src/utils/clock';
+
+describe('readStateSnapshot', () => {
+    it('should accept a snapshot or its JSON', () => {
+        const snapshot = { version: STATE_SNAPSHOT_VERSION, exportedAt: 1, accounts: {}, routes: [['111', 'sales']], rateLimits: [] };
+
+        expect(readStateSnapshot(snapshot)).toEqual(snapshot);
+        expect(readStateSnapshot(JSON.stringify(snapshot))).toEqual(snapshot);
+    });
+
+    it('should refuse unknown versions', () => {
+        expect(() => readStateSnapshot({ version: 99 })).toThrow(expect.objectContaining({ code: 'UNSUPPORTED_SNAPSHOT' }));
+        expect(() => readStateSnapshot(null)).toThrow(expect.objectContaining({ code: 'INVALID_SNAPSHOT' }));
+    });
+});
+
+describe('state snapshots', () => {
+    const message = (id: string, timestamp: number) => ({
+        id,
+        chatId: '111',
+        direction: 'inbound' as const,
+        sender: '111',
+        type: 'text',
+        text: 'Hi',
+        timestamp,
+    });
+
+    it('should carry in-memory history to a new archive', async () => {
+        const clock = new ManualClock(10_000);
+        const before = new InMemoryMessageArchive({}, clock);
+        await before.append(message('m1', 9_000));
+        await before.append(message('m2', 9_500));
+
+        const after = new InMemoryMessageArchive({}, clock);
+        after.restore(JSON.parse(JSON.stringify(before.snapshot())));
+
+        expect((await after.recent('111', 10)).map((m) => m.id)).toEqual(['m1', 'm2']);
+    });
+
+    it('should carry menu sessions, conversation windows, routes and rate windows', async () => {
+        const sessions = new InMemoryMenuSessionStore();
+        const session = { menuId: 'support', userId: '111', stateId: 'root', answers: {}, startedAt: 1, updatedAt: 2 };
+        await sessions.set(session);
+        const restoredSessions = new InMemoryMenuSessionStore();
+        restoredSessions.restore(sessions.snapshot());
+        expect(await restoredSessions.get('111')).toEqual(session);
+
+        const clock = new ManualClock(1_000);
+        const window = new ConversationWindow(clock);
+        window.record('111');
+        const restoredWindow = new ConversationWindow(clock);
+        restoredWindow.restore(window.snapshot());
+        expect(restoredWindow.count()).toBe(1);
+
+        const routes = new StickyRoutes();
+        routes.remember('111', 'sales');
+        const restoredRoutes = new StickyRoutes();
+        restoredRoutes.restore(routes.snapshot());
+        expect(restoredRoutes.get('111')).toBe('sales');
+
+        const limiter = new SenderRateLimiter(clock);
+        limiter.check('111', { maxMessages: 1 });
+        const restoredLimiter = new SenderRateLimiter(clock);
+        restoredLimiter.restore(limiter.snapshot());
+        expect(restoredLimiter.check('111', { maxMessages: 1 })).toBe('notify');
+    });
+});
@@ -0,0 +1,110 @@
+import { describe, it, expect } from 'vitest';
+import {
+    buildAuthenticationTemplate,
+    buildCarouselTemplate,
+    buildTemplate,
+    templateParameters,
+} from '../../src/templates/builder';
+
+describe('buildTemplate', () => {
+    it('should build named parameters from a map', () => {
+        expect(buildTemplate('order_update', 'en_US', {
+            header: { order_id: '4412' },
+            body: { first_name: 'Ada', ship_date: 'May 3' },
+        })).toEqual({
+            name: 'order_update',
+            language: { code: 'en_US' },
+            components: [
+                { type: 'header', parameters: [{ type: 'text', parameter_name: 'order_id', text: '4412' }] },
+                {
+                    type: 'body',
+                    parameters: [
+                        { type: 'text', parameter_name: 'first_name', text: 'Ada' },
+                        { type: 'text', parameter_name: 'ship_date', text: 'May 3' },
+                    ],
+                },
+            ],
+        });
+    });
+
+    it('should build positional parameters from an array', () => {
+        expect(buildTemplate('order_update', 'en', { body: ['Ada', '4412'] }).components).toEqual([
+            { type: 'body', parameters: [{ type: 'text', text: 'Ada' }, { type: 'text', text: '4412' }] },
+        ]);
+    });
+
+    it('should omit components for templates without parameters', () => {
+        expect(buildTemplate('hello_world', 'en_US')).toEqual({ name: 'hello_world', language: { code: 'en_US' } });
+    });
+});
+
+describe('templateParameters', () => {
+    it('should reject names Meta does not accept and empty values', () => {
+        expect(() => templateParameters({ 'First Name': 'Ada' })).toThrow('Invalid template parameter name');
+        expect(() => templateParameters({ first_name: '' })).toThrow('{{first_name}} is empty');
+        expect(() => templateParameters(['Ada', ''])).toThrow('{{2}} is empty');
+    });
+});
+
+describe('buildCarouselTemplate', () => {
+    const card = (n, overrides = {}) => ({
+        header: { kind: 'image', url: `https://example.com/${n}.jpg` },
+        body: [`Item ${n}`],
+        buttons: [{ kind: 'quick_reply', payload: `buy-${n}` }, { kind: 'url', text: `items/${n}` }],
+        ...overrides,
+    });
+
+    it('should build a body bubble followed by indexed cards', () => {
+        const template = buildCarouselTemplate('spring_sale', 'en_US', {
+            body: { discount: '20%' },
+            cards: [card(1), card(2)],
+        });
+
+        expect(template.components[0]).toEqual({
+            type: 'body',
+            parameters: [{ type: 'text', parameter_name: 'discount', text: '20%' }],
+        });
+        expect(template.components[1].cards[1]).toEqual({
+            card_index: 1,
+            components: [
+                { type: 'header', parameters: [{ type: 'image', image: { link: 'https://example.com/2.jpg' } }] },
+                { type: 'body', parameters: [{ type: 'text', text: 'Item 2' }] },
+                { type: 'button', sub_type: 'quick_reply', index: 0, parameters: [{ type: 'payload', payload: 'buy-2' }] },
+                { type: 'button', sub_type: 'url', index: 1, parameters: [{ type: 'text', text: 'items/2' }] },
+            ],
+        });
+    });
+
+    it('should reject carousels with too few or too many cards', () => {
+        expect(() => buildCarouselTemplate('t', 'en', { cards: [card(1)] })).toThrow('2-10 cards (got 1)');
+        const cards = Array.from({ length: 11 }, (_, i) => card(i));
+        expect(() => buildCarouselTemplate('t', 'en', { cards })).toThrow('(got 11)');
+    });
+
+    it('should require every card to share the first card\'s layout', () => {
+        const video = card(2, { header: { kind: 'video', url: 'https://example.com/2.mp4' } });
+        expect(() => buildCarouselTemplate('t', 'en', { cards: [card(1), video] })).toThrow('Carousel card 1 has header/buttons');
+        const oneButton = card(2, { buttons: [{ kind: 'quick_reply', payload: 'buy-2' }] });
+        expect(() => buildCarouselTemplate('t', 'en', { cards: [card(1), oneButton] })).toThrow('Carousel card 1');
+    });
+
+    it('should reject cards without header media or button values', () => {
+        const noMedia = card(2, { header: { kind: 'image' } });
+        expect(() => buildCarouselTemplate('t', 'en', { cards: [card(1), noMedia] })).toThrow('needs a header image url or id');
+        const noPayload = card(2, { buttons: [{ kind: 'quick_reply', payload: '' }, { kind: 'url', text: 'x' }] });
+        expect(() => buildCarouselTemplate('t', 'en', { cards: [card(1), noPayload] })).toThrow('needs a payload');
+    });
+});
+
+describe('buildAuthenticationTemplate', () => {
+    it('should pass the code to the body and the copy-code button', () => {
+        expect(buildAuthenticationTemplate('verify_code', 'en_US', '482913').components).toEqual([
+            { type: 'body', parameters: [{ type: 'text', text: '482913' }] },
+            { type: 'button', sub_type: 'url', index: 0, parameters: [{ type: 'text', text: '482913' }] },
+        ]);
+    });
+
+    it('should reject codes longer than 15 characters', () => {
+        expect(() => buildAuthenticationTemplate('verify_code', 'en_US', '1'.repeat(16))).toThrow('1-15 characters');
+    });
+});
@@ -0,0 +1,63 @@
+import { describe, it, expect, vi } from 'vitest';
+import { TemplateCatalog, templateVariables, toTemplateSummary } from '../../src/templates/catalog';
+
+const record = (overrides = {}) => ({
+    id: '1',
+    name: 'order_update',
+    language: 'en_US',
+    status: 'APPROVED',
+    category: 'UTILITY',
+    quality_score: { score: 'GREEN' },
+    components: [
+        { type: 'HEADER', format: 'TEXT', text: 'Order {{1}}' },
+        { type: 'BODY', text: 'Hi {{2}}, order {{1}} ships on {{ ship_date }}.' },
+    ],
+    ...overrides,
+});
+
+describe('templateVariables', () => {
+    it('should list distinct positional and named placeholders in order', () => {
+        expect(templateVariables(record().components)).toEqual(['1', '2', 'ship_date']);
+        expect(templateVariables(undefined)).toEqual([]);
+    });
+});
+
+describe('toTemplateSummary', () => {
+    it('should keep the fields an agent needs to pick a template', () => {
+        expect(toTemplateSummary(record())).toEqual({
+            name: 'order_update',
+            language: 'en_US',
+            category: 'UTILITY',
+            status: 'APPROVED',
+            quality: 'GREEN',
+            variables: ['1', '2', 'ship_date'],
+            body: 'Hi {{2}}, order {{1}} ships on {{ ship_date }}.',
+        });
+    });
+});
+
+describe('TemplateCatalog', () => {
+    it('should cache templates until invalidated', async () => {
+        const fetch = vi.fn(async () => [record(), record({ id: '2', name: 'promo', status: 'REJECTED' })]);
+        const catalog = new TemplateCatalog(fetch);
+
+        expect((await catalog.approved()).map((t) => t.name)).toEqual(['order_update']);
+        await catalog.list();
+        expect(fetch).toHaveBeenCalledTimes(1);
+
+        catalog.invalidate();
+        await catalog.list();
+        expect(fetch).toHaveBeenCalledTimes(2);
+    });
+
+    it('should share one request between concurrent callers and retry after a failure', async () => {
+        const fetch = vi.fn()
+            .mockRejectedValueOnce(new Error('rate limited'))
+            .mockResolvedValue([record()]);
+        const catalog = new TemplateCatalog(fetch);
+
+        await expect(Promise.all([catalog.list(), catalog.list()])).rejects.toThrow('rate limited');
+        expect(fetch).toHaveBeenCalledTimes(1);
+        expect(await catalog.list()).toHaveLength(1);
+    });
+});
@@ -0,0 +1,63 @@
+import { describe, it, expect } from 'vitest';
+import { HELD_STATUS, TemplatePacing } from '../../src/templates/pacing';
+import { ManualClock } from '../../src/utils/clock';
+
+const held = (messageId: string) => ({ messageId, status: HELD_STATUS, recipient: '111', timestamp: 0 });
+
+describe('TemplatePacing', () => {
+    it('should slow a template whose sends are held, until the hold lapses', () => {
+        const clock = new ManualClock();
+        const pacing = new TemplatePacing(clock, 1_000);
+        pacing.track('wamid.1', 'spring_sale');
+        pacing.track('wamid.2', 'spring_sale');
+
+        expect(pacing.observeStatus(held('wamid.1'))).toEqual({
+            name: 'spring_sale',
+            pace: 'slow',
+            reason: HELD_STATUS,
+        });
+        expect(pacing.observeStatus(held('wamid.2'))).toBeUndefined();
+        expect(pacing.pace('spring_sale')).toBe('slow');
+
+        clock.advance(1_000);
+        expect(pacing.pace('spring_sale')).toBe('normal');
+        expect(pacing.list()).toEqual([]);
+    });
+
+    it('should ignore statuses of untracked messages and other statuses', () => {
+        const pacing = new TemplatePacing(new ManualClock());
+        pacing.track('wamid.1', 'spring_sale');
+
+        expect(pacing.observeStatus(held('wamid.9'))).toBeUndefined();
+        expect(pacing.observeStatus({ ...held('wamid.1'), status: 'delivered' })).toBeUndefined();
+        expect(pacing.pace('spring_sale')).toBe('normal');
+    });
+
+    it('should pause a template until it is approved again', () => {
+        const pacing = new TemplatePacing(new ManualClock());
+
+        expect(pacing.observeTemplate({ name: 'spring_sale', status: 'PAUSED' })).toEqual({
+            name: 'spring_sale',
+            pace: 'paused',
+            reason: 'PAUSED',
+        });
+        expect(pacing.pace('spring_sale')).toBe('paused');
+        expect(pacing.observeTemplate({ name: 'spring_sale', status: 'APPROVED' })).toMatchObject({ pace: 'normal' });
+        expect(pacing.pace('spring_sale')).toBe('normal');
+    });
+
+    it('should keep a held template slow when the catalog still lists it as approved', () => {
+        const pacing = new TemplatePacing(new ManualClock());
+        pacing.track('wamid.1', 'spring_sale');
+        pacing.observeStatus(held('wamid.1'));
+
+        expect(pacing.observeCatalog([
+            { name: 'spring_sale', status: 'APPROVED' },
+            { name: 'order_update', status: 'DISABLED' },
+        ])).toEqual([{ name: 'order_update', pace: 'paused', reason: 'DISABLED' }]);
+        expect(pacing.list()).toEqual([
+            { name: 'spring_sale', pace: 'slow', reason: HELD_STATUS },
+            { name: 'order_update', pace: 'paused', reason: 'DISABLED' },
+        ]);
+    });
+});
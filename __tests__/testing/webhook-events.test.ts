@@ -0,0 +1,63 @@
+import { describe, it, expect, vi } from 'vitest';
+import { WebhookEventBuilder } from '../../src/testing';
+import { WebhookHandler } from '../../src/handlers/webhook.handler';
+import { isValidSignature } from '../../src/http/signature';
+
+describe('WebhookEventBuilder', () => {
+    it('should build payloads the webhook handler understands', async () => {
+        const event = WebhookEventBuilder.textMessage('15551234567', 'Hello', { id: 'wamid.1', timestamp: 1700000000, name: 'Ada' })
+            .interactiveButtonReply('15551234567', 'yes', 'Yes', { id: 'wamid.2' })
+            .reaction('15551234567', 'wamid.1', '👍', { id: 'wamid.3' })
+            .status('wamid.out', 'delivered')
+            .build();
+        const handler = new WebhookHandler({} as never);
+        const messages = vi.fn();
+        const statuses = vi.fn();
+        handler.on('message', messages);
+        handler.on('status', statuses);
+        vi.spyOn(console, 'log').mockImplementation(() => {});
+
+        await handler.handle(event);
+
+        expect(messages.mock.calls.map(([m]) => [m.id, m.type, m.content])).toEqual([
+            ['wamid.1', 'text', 'Hello'],
+            ['wamid.2', 'interactive', 'Yes'],
+            ['wamid.3', 'reaction', ''],
+        ]);
+        expect(messages.mock.calls[0][0]).toMatchObject({ senderName: 'Ada', timestamp: 1700000000 });
+        expect(messages.mock.calls[1][0].replyId).toBe('yes');
+        expect(statuses).toHaveBeenCalledWith(expect.objectContaining({ messageId: 'wamid.out', status: 'delivered' }));
+    });
+
+    it('should put template status updates in their own change', () => {
+        const event = new WebhookEventBuilder({ businessAccountId: '123' })
+            .templateStatus('987', 'order_update', 'REJECTED', 'INCORRECT_CATEGORY')
+            .build();
+
+        expect(event.entry[0].id).toBe('123');
+        expect(event.entry[0].changes).toEqual([
+            expect.objectContaining({
+                field: 'message_template_status_update',
+                value: expect.objectContaining({ event: 'REJECTED', message_template_id: '987', reason: 'INCORRECT_CATEGORY' }),
+            }),
+        ]);
+    });
+
+    it('should include the document file name for media', () => {
+        const event = new WebhookEventBuilder()
+            .media('15551234567', 'document', { id: 'media-1', mime_type: 'application/pdf', filename: 'invoice.pdf' })
+            .build();
+
+        expect(event.entry[0].changes[0].value.messages?.[0]).toMatchObject({
+            type: 'document',
+            document: { id: 'media-1', mime_type: 'application/pdf', filename: 'invoice.pdf' },
+        });
+    });
+
+    it('should sign the body with the app secret', () => {
+        const { body, signature } = WebhookEventBuilder.textMessage('15551234567', 'Hi').buildSigned('secret');
+
+        expect(isValidSignature(body, signature, 'secret')).toBe(true);
+        expect(isValidSignature(body, signature, 'other')).toBe(false);
+    });
+});
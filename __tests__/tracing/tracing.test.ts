@@ -0,0 +1,57 @@
+import { describe, it, expect, vi } from 'vitest';
+import { withSpan } from '../../src/tracing/tracing';
+
+// Records spans the way @opentelemetry/api's Tracer would hand them out
+function fakeTracer() {
+    const spans = [];
+    const tracer = {
+        startActiveSpan: (name, options, fn) => {
+            const span = {
+                name,
+                attributes: { ...options.attributes },
+                status: undefined,
+                exceptions: [],
+                ended: false,
+                setAttribute(key, value) { this.attributes[key] = value; },
+                setStatus(status) { this.status = status; },
+                recordException(error) { this.exceptions.push(error); },
+                end() { this.ended = true; },
+            };
+            spans.push(span);
+            return fn(span);
+        },
+    };
+    return { spans, tracer };
+}
+
+describe('withSpan', () => {
+    it('should run the callback directly without a tracer', async () => {
+        const fn = vi.fn().mockResolvedValue('ok');
+
+        expect(await withSpan(undefined, 'whatsapp.send', {}, fn)).toBe('ok');
+        expect(fn).toHaveBeenCalledWith();
+    });
+
+    it('should record attributes and end the span', async () => {
+        const { spans, tracer } = fakeTracer();
+
+        await withSpan(tracer, 'whatsapp.send', { 'whatsapp.account': 'main', skipped: undefined },
+            async (span) => span.setAttribute('whatsapp.wamid', 'wamid.123'));
+
+        expect(spans[0].name).toBe('whatsapp.send');
+        expect(spans[0].attributes).toEqual({ 'whatsapp.account': 'main', 'whatsapp.wamid': 'wamid.123' });
+        expect(spans[0].ended).toBe(true);
+    });
+
+    it('should mark the span as failed and rethrow', async () => {
+        const { spans, tracer } = fakeTracer();
+        const error = new Error('boom');
+
+        await expect(withSpan(tracer, 'whatsapp.webhook', {}, async () => { throw error; }))
+            .rejects.toThrow('boom');
+
+        expect(spans[0].exceptions).toEqual([error]);
+        expect(spans[0].status).toEqual({ code: 2, message: 'boom' });
+        expect(spans[0].ended).toBe(true);
+    });
+});
@@ -0,0 +1,55 @@
+import { describe, it, expect } from 'vitest';
+import { chunkText, chunkTextWithMarkers } from '../../src/utils/chunk';
+
+describe('chunkText', () => {
+    it('should keep short text in one chunk', () => {
+        expect(chunkText('  Hello there  ')).toEqual(['Hello there']);
+        expect(chunkText('   ')).toEqual([]);
+    });
+
+    it('should prefer paragraph, then word boundaries', () => {
+        expect(chunkText('First paragraph.\n\nSecond one here.', 20)).toEqual([
+            'First paragraph.',
+            'Second one here.',
+        ]);
+        expect(chunkText('one two three four five', 10)).toEqual(['one two', 'three four', 'five']);
+    });
+
+    it('should hard-split long words without breaking surrogate pairs', () => {
+        const chunks = chunkText('abcd😀efgh', 5);
+
+        expect(chunks).toEqual(['abcd', '😀efg', 'h']);
+        expect(chunks.every((c) => c.length <= 5)).toBe(true);
+    });
+
+    it('should split very long replies into full chunks without losing text', () => {
+        const text = `${'Lorem ipsum dolor sit amet. '.repeat(10_000)}\n\n${'z'.repeat(20_000)}`;
+
+        const chunks = chunkText(text);
+
+        expect(chunks.every((c) => c.length <= 4096)).toBe(true);
+        expect(chunks.slice(0, -1).every((c) => c.length > 2048)).toBe(true);
+        expect(chunks.join('').replace(/\s/g, '')).toBe(text.replace(/\s/g, ''));
+    });
+});
+
+describe('chunkTextWithMarkers', () => {
+    it('should number the parts, keeping each within the limit', () => {
+        const chunks = chunkTextWithMarkers('one two three four five', 16, { numbering: true });
+
+        expect(chunks).toEqual(['one two (1/3)', 'three four (2/3)', 'five (3/3)']);
+        expect(chunks.every((c) => c.length <= 16)).toBe(true);
+    });
+
+    it('should mark every part but the last as continued', () => {
+        expect(chunkTextWithMarkers('one two three four five', 12, { continuation: ' …' })).toEqual([
+            'one two …',
+            'three four …',
+            'five',
+        ]);
+    });
+
+    it('should leave replies that fit in one message alone', () => {
+        expect(chunkTextWithMarkers('Short answer', 4096, { numbering: true, continuation: ' …' })).toEqual(['Short answer']);
+    });
+});
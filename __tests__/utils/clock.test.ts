@@ -0,0 +1,51 @@
+import { describe, it, expect } from 'vitest';
+import { ManualClock } from '../../src/utils/clock';
+
+describe('ManualClock', () => {
+    it('should only move when advanced', () => {
+        const clock = new ManualClock(1_000);
+
+        expect(clock.now()).toBe(1_000);
+        clock.advance(500);
+        expect(clock.now()).toBe(1_500);
+    });
+
+    it('should fire due timers in order, at their due time', () => {
+        const clock = new ManualClock();
+        const fired: Array<[string, number]> = [];
+        clock.setTimeout(() => fired.push(['late', clock.now()]), 200);
+        clock.setTimeout(() => fired.push(['early', clock.now()]), 100);
+
+        clock.advance(150);
+        expect(fired).toEqual([['early', 100]]);
+        expect(clock.now()).toBe(150);
+
+        clock.advance(100);
+        expect(fired).toEqual([['early', 100], ['late', 200]]);
+        expect(clock.pending).toBe(0);
+    });
+
+    it('should not fire cleared timers', () => {
+        const clock = new ManualClock();
+        let fired = false;
+        const timer = clock.setTimeout(() => (fired = true), 10);
+
+        clock.clearTimeout(timer);
+        clock.advance(20);
+
+        expect(fired).toBe(false);
+    });
+
+    it('should fire timers scheduled by other timers when they fall due', () => {
+        const clock = new ManualClock();
+        const fired: number[] = [];
+        clock.setTimeout(() => {
+            fired.push(clock.now());
+            clock.setTimeout(() => fired.push(clock.now()), 10);
+        }, 10);
+
+        clock.advance(30);
+
+        expect(fired).toEqual([10, 20]);
+    });
+});
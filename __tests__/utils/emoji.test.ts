@@ -0,0 +1,42 @@
+import { describe, it, expect } from 'vitest';
+import { expandShortcodes, isSingleEmoji, normalizeReaction } from '../../src/utils/emoji';
+
+describe('expandShortcodes', () => {
+    it('should expand known shortcodes', () => {
+        expect(expandShortcodes('Done :white_check_mark: thanks :+1: :TADA:')).toBe('Done ✅ thanks 👍 🎉');
+    });
+
+    it('should leave unknown shortcodes, times and code alone', () => {
+        expect(expandShortcodes('See you at 10:30:00 :not_an_emoji:')).toBe('See you at 10:30:00 :not_an_emoji:');
+        expect(expandShortcodes('Use `:smile:` or\n```\n:fire:\n``` :fire:')).toBe('Use `:smile:` or\n```\n:fire:\n``` 🔥');
+    });
+});
+
+describe('normalizeReaction', () => {
+    it('should accept emoji and shortcodes', () => {
+        expect(normalizeReaction(' 👍 ')).toBe('👍');
+        expect(normalizeReaction(':thumbsup:')).toBe('👍');
+        expect(normalizeReaction('heart')).toBe('❤️');
+        expect(normalizeReaction('')).toBe('');
+    });
+
+    it('should reject anything but one emoji', () => {
+        expect(() => normalizeReaction('ok thanks')).toThrow(/not a single emoji/);
+        expect(() => normalizeReaction('👍👍')).toThrow(/not a single emoji/);
+        expect(() => normalizeReaction('é')).toThrow(/not a single emoji/);
+    });
+});
+
+describe('isSingleEmoji', () => {
+    it('should accept sequences that render as one emoji', () => {
+        for (const emoji of ['❤', '❤️', '👍🏽', '👩\u200D💻', '👨\u200D👩\u200D👧', '🇧🇷', '1️⃣', '#\u20E3']) {
+            expect(isSingleEmoji(emoji)).toBe(true);
+        }
+    });
+
+    it('should reject letters, digits and several emoji', () => {
+        for (const text of ['a', '7', 'ab', '🇧', '🇧🇷🇵🇹', '👍 ', '😀x']) {
+            expect(isSingleEmoji(text)).toBe(false);
+        }
+    });
+});
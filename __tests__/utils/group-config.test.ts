@@ -0,0 +1,47 @@
+import { describe, it, expect } from 'vitest';
+import { checkGroupMessage, groupTextLimit, resolveWhatsAppGroupConfig } from '../../src/utils/group-config';
+
+describe('group config', () => {
+    const groups = {
+        '120363042@g.us': { requireMention: true, allowFrom: ['+1 234 567 890'] },
+        '*': { enabled: false },
+    };
+
+    it('should resolve exact group entries before the wildcard', () => {
+        expect(resolveWhatsAppGroupConfig(groups, '120363042@g.us')).toBe(groups['120363042@g.us']);
+        expect(resolveWhatsAppGroupConfig(groups, '999999@g.us')).toBe(groups['*']);
+        expect(resolveWhatsAppGroupConfig(undefined, '120363042@g.us')).toEqual({});
+    });
+
+    it('should enforce enabled, allowFrom, and requireMention', () => {
+        const config = groups['120363042@g.us'];
+
+        expect(checkGroupMessage(groups['*'], '1234567890@s.whatsapp.net', true))
+            .toEqual({ allowed: false, reason: 'disabled' });
+        expect(checkGroupMessage(config, '5550000000@s.whatsapp.net', true))
+            .toEqual({ allowed: false, reason: 'sender_not_allowed' });
+        expect(checkGroupMessage(config, '1234567890@s.whatsapp.net', false))
+            .toEqual({ allowed: false, reason: 'not_mentioned' });
+        expect(checkGroupMessage(config, '1234567890@s.whatsapp.net', true))
+            .toEqual({ allowed: true });
+    });
+
+    it('should match @lid senders verbatim and never let unparseable entries match', () => {
+        const lid = { allowFrom: ['whatsapp:123456789012345@lid', 'not a number'] };
+
+        expect(checkGroupMessage(lid, '123456789012345@lid', true)).toEqual({ allowed: true });
+        expect(checkGroupMessage(lid, '999999999999999@lid', true))
+            .toEqual({ allowed: false, reason: 'sender_not_allowed' });
+        expect(checkGroupMessage({ allowFrom: ['not a number'] }, 'someone@broadcast', true))
+            .toEqual({ allowed: false, reason: 'sender_not_allowed' });
+    });
+
+    it('should cap text per message at the group limit but never above WhatsApp\'s', () => {
+        const limits = { '120363042@g.us': { textChunkLimit: 500 }, '*': { textChunkLimit: 10_000 } };
+
+        expect(groupTextLimit(limits, '120363042@g.us')).toBe(500);
+        expect(groupTextLimit(limits, '120363099@g.us')).toBe(4096);
+        expect(groupTextLimit(limits, '1234567890')).toBe(4096);
+        expect(groupTextLimit(undefined, '120363042@g.us')).toBe(4096);
+    });
+});
@@ -0,0 +1,42 @@
+import { describe, it, expect } from 'vitest';
+import { groupInviteLink, parseGroupInviteLink, resolveGroupInviteKeys } from '../../src/utils/invite-link';
+
+const CODE = 'AbCdEfGhIjKlMnOpQrStUv';
+
+describe('group invite links', () => {
+    it('should extract the invite code from common link shapes', () => {
+        expect(parseGroupInviteLink(`https://chat.whatsapp.com/${CODE}`)).toBe(CODE);
+        expect(parseGroupInviteLink(`chat.whatsapp.com/invite/${CODE}?ref=qr`)).toBe(CODE);
+        expect(parseGroupInviteLink(` HTTPS://Chat.WhatsApp.com/${CODE}/ `)).toBe(CODE);
+        expect(groupInviteLink(CODE)).toBe(`https://chat.whatsapp.com/${CODE}`);
+    });
+
+    it('should reject other hosts and malformed codes', () => {
+        expect(parseGroupInviteLink(`https://example.com/${CODE}`)).toBeNull();
+        expect(parseGroupInviteLink('https://chat.whatsapp.com/short')).toBeNull();
+        expect(parseGroupInviteLink('120363042@g.us')).toBeNull();
+    });
+
+    it('should rewrite invite keys to group JIDs and keep unresolved ones', async () => {
+        const link = `https://chat.whatsapp.com/${CODE}`;
+        const broken = 'https://chat.whatsapp.com/ZZZZZZZZZZZZZZZZZZZZZZ';
+        const groups = {
+            [link]: { requireMention: true },
+            [broken]: { enabled: false },
+            '*': { enabled: false },
+        };
+
+        const result = await resolveGroupInviteKeys(groups, async (code) => {
+            if (code !== CODE) throw new Error('revoked');
+            return '120363042@g.us';
+        });
+
+        expect(groups).toEqual({
+            '120363042@g.us': { requireMention: true },
+            [broken]: { enabled: false },
+            '*': { enabled: false },
+        });
+        expect(result.resolved).toEqual({ [link]: '120363042@g.us' });
+        expect(result.failed.map((f) => f.link)).toEqual([broken]);
+    });
+});
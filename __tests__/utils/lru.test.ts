@@ -0,0 +1,86 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ManualClock } from '../../src/utils/clock';
+import { LruCache } from '../../src/utils/lru';
+import { RuntimeMenuSessionStore } from '../../src/menus/store';
+
+describe('LruCache', () => {
+    it('should evict the least recently used key beyond maxChats', () => {
+        const evicted: Array<[string, string]> = [];
+        const cache = new LruCache<number>({ maxChats: 2, onEvict: (key, reason) => evicted.push([key, reason]) });
+
+        cache.set('a', 1);
+        cache.set('b', 2);
+        cache.get('a');
+        cache.set('c', 3);
+
+        expect(cache.get('b')).toBeUndefined();
+        expect(cache.get('a')).toBe(1);
+        expect(evicted).toEqual([['b', 'size']]);
+        expect(cache.evictions).toEqual({ size: 1, idle: 0 });
+    });
+
+    it('should evict keys idle for maxIdleMs', () => {
+        const clock = new ManualClock();
+        const cache = new LruCache<number>({ maxIdleMs: 1_000, clock });
+
+        cache.set('a', 1);
+        clock.advance(600);
+        cache.set('b', 2);
+        clock.advance(600);
+
+        expect(cache.get('a')).toBeUndefined();
+        expect(cache.get('b')).toBe(2);
+        expect(cache.size).toBe(1);
+        expect(cache.evictions.idle).toBe(1);
+    });
+
+    it('should count reads as use', () => {
+        const clock = new ManualClock();
+        const cache = new LruCache<number>({ maxIdleMs: 1_000, clock });
+
+        cache.set('a', 1);
+        clock.advance(900);
+        cache.get('a');
+        clock.advance(900);
+
+        expect(cache.get('a')).toBe(1);
+    });
+});
+
+describe('chat-state limits on runtime stores', () => {
+    function runtime() {
+        const cache = new Map();
+        return {
+            cache,
+            getCache: async (key) => cache.get(key),
+            setCache: async (key, value) => { cache.set(key, value); return true; },
+            deleteCache: async (key) => cache.delete(key),
+        };
+    }
+
+    const session = (userId: string, updatedAt: number) =>
+        ({ menuId: 'main', userId, stateId: 'start', answers: {}, startedAt: updatedAt, updatedAt });
+
+    it('should delete the least recently used chat beyond maxChats', async () => {
+        const store = runtime();
+        const onEvict = vi.fn();
+        const sessions = new RuntimeMenuSessionStore(store, 'sales', { maxChats: 1, onEvict });
+
+        await sessions.set(session('111', 0));
+        await sessions.set(session('222', 0));
+
+        await vi.waitFor(() => expect(store.cache.has('whatsapp-menu-sales-111')).toBe(false));
+        expect(await sessions.get('222')).toBeDefined();
+        expect(onEvict).toHaveBeenCalledWith('111', 'size');
+    });
+
+    it('should drop stored state idle past maxIdleMs, even from before a restart', async () => {
+        const store = runtime();
+        const clock = new ManualClock(10_000);
+        store.cache.set('whatsapp-menu-sales-111', session('111', 0));
+        const sessions = new RuntimeMenuSessionStore(store, 'sales', { maxIdleMs: 5_000, clock });
+
+        expect(await sessions.get('111')).toBeUndefined();
+        expect(store.cache.has('whatsapp-menu-sales-111')).toBe(false);
+    });
+});
@@ -0,0 +1,57 @@
+import { describe, it, expect } from 'vitest';
+import {
+    canonicalChatId,
+    isGroupJid,
+    isNewsletterJid,
+    normalizeE164,
+    normalizeWhatsAppTarget,
+    toJid,
+} from '../../src/utils/normalize';
+
+describe('normalize', () => {
+    it('should classify group and user targets', () => {
+        expect(normalizeWhatsAppTarget('120363042@g.us')).toEqual({ kind: 'group', id: '120363042' });
+        expect(normalizeWhatsAppTarget('1234567890@s.whatsapp.net')).toEqual({ kind: 'user', id: '1234567890' });
+        expect(normalizeWhatsAppTarget('1234567890:12@s.whatsapp.net')).toEqual({ kind: 'user', id: '1234567890' });
+        expect(normalizeWhatsAppTarget('whatsapp:+1 (234) 567-890')).toEqual({ kind: 'user', id: '1234567890' });
+    });
+
+    it('should recognize channel JIDs', () => {
+        expect(normalizeWhatsAppTarget('120363111@newsletter')).toEqual({ kind: 'newsletter', id: '120363111' });
+        expect(toJid('whatsapp:120363111@newsletter')).toBe('120363111@newsletter');
+        expect(canonicalChatId('120363111@newsletter')).toBe('120363111@newsletter');
+        expect(isNewsletterJid('120363111@newsletter')).toBe(true);
+        expect(isNewsletterJid('120363042@g.us')).toBe(false);
+    });
+
+    it('should reject malformed targets', () => {
+        expect(normalizeWhatsAppTarget('123')).toBeNull();
+        expect(normalizeWhatsAppTarget('someone@example.com')).toBeNull();
+        expect(normalizeE164('not a number')).toBeNull();
+    });
+
+    it('should format E.164 numbers and JIDs', () => {
+        expect(normalizeE164('1 234 567 890')).toBe('+1234567890');
+        expect(toJid('+1234567890')).toBe('1234567890@s.whatsapp.net');
+        expect(toJid('120363042@g.us')).toBe('120363042@g.us');
+        expect(isGroupJid('whatsapp:120363042@g.us')).toBe(true);
+    });
+
+    it('should accept any Unicode space between digits', () => {
+        expect(normalizeE164('+33\u202f6\u202f12\u202f34\u202f56\u202f78')).toBe('+33612345678');
+        expect(normalizeE164('+44\u20097700\u2009900123')).toBe('+447700900123');
+        expect(normalizeE164('\u3000+1 234 567 890\ufeff')).toBe('+1234567890');
+    });
+
+    it('should reject stray characters and numbers outside the E.164 range', () => {
+        expect(normalizeE164('+1 555 123 4567 ext 2')).toBeNull();
+        expect(normalizeE164('1234567890123456')).toBeNull();
+        expect(normalizeE164('WhatsApp:+1234567')).toBe('+1234567');
+    });
+
+    it('should not let callers mutate cached targets', () => {
+        const first = normalizeWhatsAppTarget('+1234567890');
+        first!.id = 'changed';
+        expect(normalizeWhatsAppTarget('+1234567890')).toEqual({ kind: 'user', id: '1234567890' });
+    });
+});
@@ -0,0 +1,31 @@
+import { describe, it, expect } from 'vitest';
+import { maskPhone, redact, redactText } from '../../src/utils/redact';
+
+describe('redact', () => {
+    it('should keep the country code and last two digits of phone numbers', () => {
+        expect(maskPhone('+14155550123')).toBe('+1********23');
+        expect(maskPhone('447700900123')).toBe('44********23');
+        expect(maskPhone('971501234567@s.whatsapp.net')).toBe('971*******67@s.whatsapp.net');
+        expect(maskPhone('12345')).toBe('12345');
+    });
+
+    it('should mask tokens and numbers in free text', () => {
+        expect(redactText('Bearer abcdefghijklmnop failed for +14155550123'))
+            .toBe('[redacted token] failed for +1********23');
+    });
+
+    it('should redact structured payloads', () => {
+        expect(redact({
+            to: '14155550123',
+            accessToken: 'secret',
+            content: 'hello',
+            id: 'wamid.123456789012',
+        })).toEqual({
+            to: '1********23',
+            accessToken: '[redacted]',
+            content: '[5 chars]',
+            id: 'wamid.123456789012',
+        });
+        expect(redact({ content: 'hello' }, { logMessageContent: true })).toEqual({ content: 'hello' });
+    });
+});
@@ -0,0 +1,55 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { Action, IAgentRuntime, Memory } from '@elizaos/core';
+import { filterActions, isSkillEnabled } from '../../src/utils/skills';
+import { WhatsAppConnectorService } from '../../src/service';
+
+describe('skills', () => {
+    it('should allow everything when no lists are configured', () => {
+        expect(isSkillEnabled(undefined, undefined, 'SEND_MESSAGE')).toBe(true);
+        expect(isSkillEnabled({}, {}, 'SEND_MESSAGE')).toBe(true);
+    });
+
+    it('should require both the account and group lists to permit a skill', () => {
+        const account = { skills: ['SEND_MESSAGE', 'send_reaction'] };
+
+        expect(isSkillEnabled(account, undefined, 'SEND_REACTION')).toBe(true);
+        expect(isSkillEnabled(account, { skills: ['SEND_MESSAGE'] }, 'SEND_REACTION')).toBe(false);
+        expect(isSkillEnabled(account, { skills: ['*'] }, 'SEND_REACTION')).toBe(true);
+        expect(isSkillEnabled(account, undefined, 'BLOCK_USER')).toBe(false);
+    });
+
+    it('should match actions by name or simile', () => {
+        const actions = [
+            { name: 'SEND_MESSAGE', similes: ['REPLY'] },
+            { name: 'BLOCK_USER', similes: [] },
+        ];
+
+        expect(filterActions(actions, { skills: ['REPLY'] }, undefined)).toEqual([actions[0]]);
+    });
+});
+
+describe('action gating', () => {
+    it('should gate copies and leave the shared action objects alone', async () => {
+        const validate = vi.fn(async () => true);
+        const block: Action = { name: 'BLOCK_USER', similes: [], description: '', examples: [], validate, handler: vi.fn() };
+        const runtime = { actions: [block] } as unknown as IAgentRuntime;
+        const connector = new WhatsAppConnectorService(runtime);
+        connector['accounts'].set('default', {
+            plugin: {} as never,
+            config: { accountId: 'default', enabled: true, skills: ['SEND_MESSAGE'] } as never,
+            enabled: true,
+        });
+        (runtime as unknown as { getService: () => unknown }).getService = () => connector;
+
+        connector['gateRuntimeActions']();
+        connector['gateRuntimeActions']();
+        const gated = runtime.actions[0];
+        const message = { content: { source: 'whatsapp', whatsappAccount: 'default' } } as unknown as Memory;
+
+        expect(gated).not.toBe(block);
+        expect(block.validate).toBe(validate);
+        expect(await gated.validate(runtime, message)).toBe(false);
+        expect(await gated.validate(runtime, { content: { source: 'discord' } } as unknown as Memory)).toBe(true);
+        expect(validate).toHaveBeenCalledTimes(1);
+    });
+});
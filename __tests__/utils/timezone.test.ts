@@ -0,0 +1,25 @@
+import { describe, it, expect } from 'vitest';
+import { inferTimeZone, localMinutes } from '../../src/utils/timezone';
+
+describe('inferTimeZone', () => {
+    it('should match the longest calling code prefix', () => {
+        expect(inferTimeZone('+91 98765 43210')).toBe('Asia/Kolkata');
+        expect(inferTimeZone('2348012345678@s.whatsapp.net')).toBe('Africa/Lagos');
+        expect(inferTimeZone('15551234567')).toBe('America/New_York');
+    });
+
+    it('should return undefined for unknown codes', () => {
+        expect(inferTimeZone('999123456')).toBeUndefined();
+        expect(inferTimeZone('')).toBeUndefined();
+    });
+});
+
+describe('localMinutes', () => {
+    it('should convert to local wall-clock minutes', () => {
+        const noonUtc = Date.UTC(2026, 0, 15, 12, 0);
+
+        expect(localMinutes(noonUtc, 'UTC')).toBe(12 * 60);
+        expect(localMinutes(noonUtc, 'Asia/Kolkata')).toBe(17 * 60 + 30);
+        expect(localMinutes(noonUtc, 'America/New_York')).toBe(7 * 60);
+    });
+});
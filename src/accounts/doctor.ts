@@ -0,0 +1,145 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { CloudAPIConfig } from '../types';
+import { CloudAPIClient } from '../clients/cloud-api-client';
+import { GraphApiError } from '../errors';
+import { isGroupJid } from '../utils/normalize';
+import { isGroupInviteLink } from '../utils/invite-link';
+import { invalidPolicyPatterns } from '../middleware/policy';
+import { listWhatsAppAccountIds, mergeWhatsAppAccountSettings, type WhatsAppAccountSettings } from './resolve';
+
+export interface DeploymentIssue {
+  severity: 'error' | 'warning';
+  code: string;
+  message: string;
+  accountId?: string;     // Absent for deployment-wide issues
+}
+
+export interface DeploymentReport {
+  ok: boolean;            // No errors (warnings allowed)
+  accounts: string[];
+  issues: DeploymentIssue[];
+}
+
+export interface ValidateDeploymentOptions {
+  live?: boolean;         // Also call the Graph API to check each Cloud API token and phone number id
+}
+
+// Graph API error codes for expired or revoked access tokens
+const INVALID_TOKEN_CODES = [190, 102];
+
+export function isTokenRejected(err: unknown): boolean {
+  return err instanceof GraphApiError && (err.status === 401 || INVALID_TOKEN_CODES.includes(err.graphCode ?? 0));
+}
+
+function checkAccount(accountId: string, account: WhatsAppAccountSettings): DeploymentIssue[] {
+  const issues: DeploymentIssue[] = [];
+  const error = (code: string, message: string) => issues.push({ severity: 'error', code, message, accountId });
+  const warn = (code: string, message: string) => issues.push({ severity: 'warning', code, message, accountId });
+
+  if (account.authDir) {
+    if (account.accessToken || account.phoneNumberId) {
+      warn('MIXED_CREDENTIALS', 'Both authDir and Cloud API credentials are set; Baileys is used');
+    }
+  } else if (!account.accessToken && !account.phoneNumberId) {
+    error('MISSING_CREDENTIALS', 'Set authDir for Baileys or accessToken and phoneNumberId for the Cloud API');
+  } else {
+    if (!account.accessToken) error('MISSING_ACCESS_TOKEN', 'phoneNumberId is set but accessToken is missing');
+    if (!account.phoneNumberId) {
+      error('MISSING_PHONE_NUMBER_ID', 'accessToken is set but phoneNumberId is missing');
+    } else if (!/^\d+$/.test(account.phoneNumberId)) {
+      error('INVALID_PHONE_NUMBER_ID', `phoneNumberId "${account.phoneNumberId}" should be the numeric id from Meta, not a phone number`);
+    }
+    if (!account.webhookVerifyToken) {
+      warn('MISSING_WEBHOOK_TOKEN', 'No webhookVerifyToken; Meta cannot verify the webhook, so no messages are received');
+    }
+    if (account.apiVersion && !/^v\d+\.\d+$/.test(account.apiVersion)) {
+      warn('INVALID_API_VERSION', `apiVersion "${account.apiVersion}" should look like v24.0`);
+    }
+  }
+
+  const policies = [
+    ['the account', account.contentPolicy] as const,
+    ...Object.entries(account.groups ?? {}).map(([groupId, group]) => [`group ${groupId}`, group.contentPolicy] as const),
+  ];
+  for (const [scope, policy] of policies) {
+    for (const pattern of invalidPolicyPatterns(policy)) {
+      error('INVALID_POLICY_PATTERN', `bannedPatterns entry ${JSON.stringify(pattern)} in ${scope} content policy is not a valid regular expression and is ignored`);
+    }
+  }
+
+  for (const groupId of Object.keys(account.groups ?? {})) {
+    if (isGroupInviteLink(groupId)) {
+      if (!account.authDir) {
+        warn('UNRESOLVABLE_GROUP_INVITE', `Group key "${groupId}" is an invite link, which only Baileys accounts can resolve; use the group JID`);
+      }
+    } else if (groupId !== '*' && !isGroupJid(groupId)) {
+      warn('UNKNOWN_GROUP', `Group key "${groupId}" is not a group JID (…@g.us) and never matches a chat`);
+    }
+  }
+  return issues;
+}
+
+async function checkLive(accountId: string, account: WhatsAppAccountSettings): Promise<DeploymentIssue | null> {
+  try {
+    await new CloudAPIClient({ ...(account as CloudAPIConfig), accountId }).getPhoneNumber();
+    return null;
+  } catch (err) {
+    return {
+      severity: 'error',
+      code: isTokenRejected(err) ? 'INVALID_ACCESS_TOKEN' : 'PHONE_NUMBER_CHECK_FAILED',
+      message: (err as Error).message,
+      accountId,
+    };
+  }
+}
+
+// Check every configured account, including disabled ones, and report problems instead of failing at runtime
+export async function validateDeployment(
+  runtime: IAgentRuntime,
+  options: ValidateDeploymentOptions = {}
+): Promise<DeploymentReport> {
+  const accountIds = listWhatsAppAccountIds(runtime);
+  const accounts = accountIds.map((id) => [id, mergeWhatsAppAccountSettings(runtime, id)] as const);
+  const issues: DeploymentIssue[] = [];
+
+  if (accounts.length === 0) {
+    issues.push({ severity: 'error', code: 'NO_ACCOUNTS', message: 'No WhatsApp account is configured' });
+  }
+
+  const owners = new Map<string, string>();
+  for (const [accountId, account] of accounts) {
+    issues.push(...checkAccount(accountId, account));
+
+    const fallback = account.fallbackAccount;
+    if (fallback && (fallback === accountId || !accountIds.includes(fallback))) {
+      issues.push({
+        severity: 'error',
+        code: 'UNKNOWN_FALLBACK_ACCOUNT',
+        message: `fallbackAccount "${fallback}" must name another configured account`,
+        accountId,
+      });
+    }
+
+    const phoneNumberId = account.authDir ? undefined : account.phoneNumberId;
+    if (!phoneNumberId) continue;
+    const owner = owners.get(phoneNumberId);
+    if (owner) {
+      issues.push({
+        severity: 'error',
+        code: 'DUPLICATE_PHONE_NUMBER_ID',
+        message: `phoneNumberId ${phoneNumberId} is also used by account ${owner}; webhooks cannot be routed`,
+        accountId,
+      });
+    } else {
+      owners.set(phoneNumberId, accountId);
+    }
+  }
+
+  if (options.live) {
+    const cloud = accounts.filter(([, a]) => !a.authDir && a.accessToken && a.phoneNumberId);
+    const results = await Promise.all(cloud.map(([id, account]) => checkLive(id, account)));
+    issues.push(...results.filter((issue): issue is DeploymentIssue => !!issue));
+  }
+
+  return { ok: !issues.some((i) => i.severity === 'error'), accounts: accountIds, issues };
+}
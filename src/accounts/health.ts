@@ -0,0 +1,115 @@
+import { EventEmitter } from 'events';
+import type { PhoneNumberDetails } from '../clients/interface';
+import type { ConnectionStatus } from '../types';
+import { systemClock, type Clock, type ClockTimer } from '../utils/clock';
+import { isTokenRejected } from './doctor';
+
+export interface HealthCheckConfig {
+  intervalMs?: number;                  // Default 5 minutes
+  webhookSilenceMs?: number;            // Cloud API accounts without a webhook for this long are degraded (default: not checked)
+  notify?: string;                      // Number told when an account degrades or recovers
+}
+
+export interface HealthProblem {
+  code: string;                         // DISCONNECTED, INVALID_ACCESS_TOKEN, PHONE_NUMBER_CHECK_FAILED, NUMBER_FLAGGED, LOW_QUALITY, WEBHOOK_SILENT
+  message: string;
+}
+
+// What a health check can see of one account
+export interface HealthProbe {
+  connection: ConnectionStatus;
+  phoneNumber?: () => Promise<PhoneNumberDetails>;  // Cloud API only
+  lastWebhookAt?: number;
+}
+
+const DEFAULT_INTERVAL_MS = 5 * 60 * 1000;
+
+// Number states Meta uses for numbers restricted over quality or policy
+const FLAGGED_STATUSES = ['FLAGGED', 'RESTRICTED'];
+
+export async function checkHealth(
+  probe: HealthProbe,
+  config: HealthCheckConfig = {},
+  now = systemClock.now()
+): Promise<HealthProblem[]> {
+  const problems: HealthProblem[] = [];
+  if (probe.connection === 'close') {
+    problems.push({ code: 'DISCONNECTED', message: 'The WhatsApp connection is closed' });
+  }
+
+  if (probe.phoneNumber) {
+    try {
+      const number = await probe.phoneNumber();
+      if (number.status && FLAGGED_STATUSES.includes(number.status.toUpperCase())) {
+        problems.push({ code: 'NUMBER_FLAGGED', message: `The phone number is ${number.status.toLowerCase()}` });
+      }
+      if (number.quality_rating?.toUpperCase() === 'RED') {
+        problems.push({ code: 'LOW_QUALITY', message: 'The phone number quality rating is RED' });
+      }
+    } catch (err) {
+      problems.push({
+        code: isTokenRejected(err) ? 'INVALID_ACCESS_TOKEN' : 'PHONE_NUMBER_CHECK_FAILED',
+        message: (err as Error).message,
+      });
+    }
+
+    const silence = config.webhookSilenceMs;
+    if (silence && probe.lastWebhookAt !== undefined && now - probe.lastWebhookAt > silence) {
+      const minutes = Math.round((now - probe.lastWebhookAt) / 60_000);
+      problems.push({ code: 'WEBHOOK_SILENT', message: `No webhook received for ${minutes} minute(s)` });
+    }
+  }
+  return problems;
+}
+
+/**
+ * Runs a health check on an interval and reports changes: "degraded" with the problems when an
+ * account becomes unhealthy or its problems change, "recovered" once a check comes back clean.
+ */
+export class HealthMonitor extends EventEmitter {
+  private timer?: ClockTimer;
+  private current: HealthProblem[] = [];
+
+  constructor(
+    private readonly check: () => Promise<HealthProblem[]>,
+    private readonly intervalMs = DEFAULT_INTERVAL_MS,
+    private readonly clock: Clock = systemClock
+  ) {
+    super();
+  }
+
+  get problems(): HealthProblem[] {
+    return this.current;
+  }
+
+  start(): void {
+    if (this.timer) return;
+    this.schedule();
+  }
+
+  stop(): void {
+    this.clock.clearTimeout(this.timer);
+    this.timer = undefined;
+  }
+
+  // The clock has no intervals, so each tick books the next one before checking
+  private schedule(): void {
+    this.timer = this.clock.setTimeout(() => {
+      this.schedule();
+      void this.run().catch((err) => this.emit('error', err));
+    }, this.intervalMs);
+    this.timer.unref?.();
+  }
+
+  async run(): Promise<HealthProblem[]> {
+    const problems = await this.check();
+    const codes = (list: HealthProblem[]) => list.map((p) => p.code).sort().join(',');
+    if (problems.length > 0 && codes(problems) !== codes(this.current)) {
+      this.emit('degraded', problems);
+    } else if (problems.length === 0 && this.current.length > 0) {
+      this.emit('recovered');
+    }
+    this.current = problems;
+    return problems;
+  }
+}
@@ -0,0 +1,14 @@
+export {
+  DEFAULT_ACCOUNT_ID,
+  accountSettingPrefix,
+  listWhatsAppAccountIds,
+  resolveWhatsAppAccount,
+  listEnabledWhatsAppAccounts,
+  mergeWhatsAppAccountSettings,
+} from './resolve';
+export { validateDeployment } from './doctor';
+export { StickyRoutes } from './sticky';
+export { HealthMonitor, checkHealth } from './health';
+export type { HealthCheckConfig, HealthProbe, HealthProblem } from './health';
+export type { DeploymentIssue, DeploymentReport, ValidateDeploymentOptions } from './doctor';
+export type { WhatsAppAccountSettings, ResolvedWhatsAppAccount } from './resolve';
@@ -0,0 +1,203 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type {
+  BaileysConfig,
+  CloudAPIConfig,
+  WhatsAppAccountOptions,
+  WhatsAppConfig,
+  WhatsAppGroupConfig,
+} from '../types';
+import type { ContentPolicyConfig, SenderRateLimitConfig } from '../middleware';
+import type { HumanizeConfig } from '../pacing';
+import type { InboundConfig } from '../handlers';
+import type { ChatStateLimits } from '../utils/lru';
+import type { ChunkMarkers } from '../utils/chunk';
+import type { CircuitBreakerConfig, QuietHoursConfig, SendRetryConfig } from '../outbox';
+import type { FixtureConfig } from '../clients/fixtures';
+import { getAliasedSetting, getJsonSetting, getSetting } from '../settings';
+
+export const DEFAULT_ACCOUNT_ID = 'default';
+
+// Per-account entry under character.settings.whatsapp.accounts; unset fields fall back to the shared settings
+export type WhatsAppAccountSettings = Partial<Omit<BaileysConfig, 'accountId'>> &
+  Partial<Omit<CloudAPIConfig, 'accountId'>> & {
+    enabled?: boolean;
+  };
+
+export type ResolvedWhatsAppAccount = WhatsAppConfig & {
+  accountId: string;
+  enabled: boolean;
+};
+
+function characterAccounts(runtime: IAgentRuntime): Record<string, WhatsAppAccountSettings> {
+  const settings = runtime.character?.settings as
+    | { whatsapp?: { accounts?: Record<string, WhatsAppAccountSettings> } }
+    | undefined;
+  return settings?.whatsapp?.accounts ?? {};
+}
+
+// WHATSAPP_ACCOUNTS: the same per-account map as JSON, for deployments configured only through env vars
+function settingAccounts(runtime: IAgentRuntime): Record<string, WhatsAppAccountSettings> {
+  const accounts = getJsonSetting<Record<string, WhatsAppAccountSettings>>(runtime, 'WHATSAPP_ACCOUNTS');
+  return accounts && typeof accounts === 'object' && !Array.isArray(accounts) ? accounts : {};
+}
+
+// Older name of the verify token setting, still accepted with a deprecation warning
+const WEBHOOK_TOKEN_ALIASES = ['WEBHOOK_TOKEN'];
+
+// Per-account secrets such as WHATSAPP_SALES_ACCESS_TOKEN, so tokens need not sit in WHATSAPP_ACCOUNTS
+const ACCOUNT_OVERRIDES: Array<[string, keyof WhatsAppAccountSettings, string[]?]> = [
+  ['ACCESS_TOKEN', 'accessToken'],
+  ['PHONE_NUMBER_ID', 'phoneNumberId'],
+  ['WEBHOOK_VERIFY_TOKEN', 'webhookVerifyToken', WEBHOOK_TOKEN_ALIASES],
+  ['BUSINESS_ID', 'businessAccountId'],
+  ['CATALOG_ID', 'catalogId'],
+  ['API_VERSION', 'apiVersion'],
+  ['AUTH_DIR', 'authDir'],
+  ['APP_SECRET', 'appSecret'],
+  ['FALLBACK_ACCOUNT', 'fallbackAccount'],
+];
+
+// Env var prefix of an account: "sales-eu" reads WHATSAPP_SALES_EU_*
+export function accountSettingPrefix(accountId: string): string {
+  return `WHATSAPP_${accountId.toUpperCase().replace(/[^A-Z0-9]+/g, '_')}_`;
+}
+
+function accountOverrides(runtime: IAgentRuntime, accountId: string): WhatsAppAccountSettings {
+  const prefix = accountSettingPrefix(accountId);
+  const overrides: WhatsAppAccountSettings = {};
+  for (const [suffix, field, aliases = []] of ACCOUNT_OVERRIDES) {
+    const value = getAliasedSetting(runtime, `${prefix}${suffix}`, aliases.map((alias) => `${prefix}${alias}`));
+    if (value) (overrides as Record<string, string>)[field] = value;
+  }
+  return overrides;
+}
+
+// WHATSAPP_HUMANIZE is "true" for the defaults or a JSON HumanizeConfig
+function humanizeSetting(runtime: IAgentRuntime): HumanizeConfig | undefined {
+  const raw = getSetting(runtime, 'WHATSAPP_HUMANIZE');
+  if (!raw || raw === 'false') return undefined;
+  if (raw === 'true') return {};
+  return getJsonSetting<HumanizeConfig>(runtime, 'WHATSAPP_HUMANIZE') ?? undefined;
+}
+
+// WHATSAPP_CIRCUIT_BREAKER is "false" to turn the breaker off or a JSON CircuitBreakerConfig
+function circuitBreakerSetting(runtime: IAgentRuntime): CircuitBreakerConfig | false | undefined {
+  const raw = getSetting(runtime, 'WHATSAPP_CIRCUIT_BREAKER');
+  if (!raw || raw === 'true') return undefined;
+  if (raw === 'false') return false;
+  return getJsonSetting<CircuitBreakerConfig>(runtime, 'WHATSAPP_CIRCUIT_BREAKER') ?? undefined;
+}
+
+// Policies shared by every account unless an account overrides them
+function sharedOptions(runtime: IAgentRuntime): WhatsAppAccountOptions {
+  return {
+    groups: getJsonSetting<Record<string, WhatsAppGroupConfig>>(runtime, 'WHATSAPP_GROUPS') ?? undefined,
+    skills: getSetting(runtime, 'WHATSAPP_SKILLS')
+      ?.split(',')
+      .map((s) => s.trim())
+      .filter(Boolean),
+    rateLimit: getJsonSetting<SenderRateLimitConfig>(runtime, 'WHATSAPP_RATE_LIMIT') ?? undefined,
+    verifyRecipients: getSetting(runtime, 'WHATSAPP_VERIFY_RECIPIENTS') === 'true',
+    logMessageContent: getSetting(runtime, 'WHATSAPP_LOG_MESSAGE_CONTENT') === 'true',
+    humanize: humanizeSetting(runtime),
+    circuitBreaker: circuitBreakerSetting(runtime),
+    sendRetry: getJsonSetting<SendRetryConfig>(runtime, 'WHATSAPP_SEND_RETRY') ?? undefined,
+    inbound: getJsonSetting<InboundConfig>(runtime, 'WHATSAPP_INBOUND') ?? undefined,
+    chatState: getJsonSetting<ChatStateLimits>(runtime, 'WHATSAPP_CHAT_STATE') ?? undefined,
+    chunkMarkers: getJsonSetting<ChunkMarkers>(runtime, 'WHATSAPP_CHUNK_MARKERS') ?? undefined,
+    contentPolicy: getJsonSetting<ContentPolicyConfig>(runtime, 'WHATSAPP_CONTENT_POLICY') ?? undefined,
+    replyFooter: getSetting(runtime, 'WHATSAPP_REPLY_FOOTER') || undefined,
+    quietHours: getJsonSetting<QuietHoursConfig>(runtime, 'WHATSAPP_QUIET_HOURS') ?? undefined,
+    unsupportedMessageReply: getSetting(runtime, 'WHATSAPP_UNSUPPORTED_MESSAGE_REPLY') || undefined,
+    inlineImageMaxBytes: Number(getSetting(runtime, 'WHATSAPP_INLINE_IMAGE_MAX_BYTES')) || undefined,
+  };
+}
+
+// Webhook secrets shared by every account that doesn't set its own
+function sharedWebhookSecrets(runtime: IAgentRuntime): WhatsAppAccountSettings {
+  const webhookVerifyToken = getAliasedSetting(
+    runtime,
+    'WHATSAPP_WEBHOOK_VERIFY_TOKEN',
+    WEBHOOK_TOKEN_ALIASES.map((alias) => `WHATSAPP_${alias}`)
+  );
+  const appSecret = getSetting(runtime, 'WHATSAPP_APP_SECRET');
+  return {
+    ...(webhookVerifyToken ? { webhookVerifyToken } : {}),
+    ...(appSecret ? { appSecret } : {}),
+  };
+}
+
+// WHATSAPP_FIXTURES: JSON {"mode": "record" | "replay", "dir": "..."} for Cloud API accounts
+function fixtureSettings(runtime: IAgentRuntime): WhatsAppAccountSettings {
+  const fixtures = getJsonSetting<FixtureConfig>(runtime, 'WHATSAPP_FIXTURES');
+  return fixtures ? { fixtures } : {};
+}
+
+// Credentials from the WHATSAPP_* settings, used by the default account
+function settingsCredentials(runtime: IAgentRuntime): WhatsAppAccountSettings {
+  // Baileys (QR code) preferred when authDir is set
+  const authDir = getSetting(runtime, 'WHATSAPP_AUTH_DIR');
+  if (authDir) {
+    return { authDir, printQRInTerminal: true };
+  }
+
+  const accessToken = getSetting(runtime, 'WHATSAPP_ACCESS_TOKEN');
+  const phoneNumberId = getSetting(runtime, 'WHATSAPP_PHONE_NUMBER_ID');
+  if (accessToken && phoneNumberId) {
+    return {
+      accessToken,
+      phoneNumberId,
+      businessAccountId: getSetting(runtime, 'WHATSAPP_BUSINESS_ID') ?? undefined,
+      catalogId: getSetting(runtime, 'WHATSAPP_CATALOG_ID') ?? undefined,
+      apiVersion: getSetting(runtime, 'WHATSAPP_API_VERSION') ?? undefined,
+    };
+  }
+
+  return {};
+}
+
+function hasCredentials(account: WhatsAppAccountSettings): boolean {
+  return !!account.authDir || !!(account.accessToken && account.phoneNumberId);
+}
+
+// Ids of every configured account: the settings-based default account plus WHATSAPP_ACCOUNTS and character accounts
+export function listWhatsAppAccountIds(runtime: IAgentRuntime): string[] {
+  const ids = new Set([...Object.keys(settingAccounts(runtime)), ...Object.keys(characterAccounts(runtime))]);
+  if (hasCredentials(settingsCredentials(runtime))) ids.add(DEFAULT_ACCOUNT_ID);
+  return [...ids].sort((a, b) => (a === DEFAULT_ACCOUNT_ID ? -1 : b === DEFAULT_ACCOUNT_ID ? 1 : a.localeCompare(b)));
+}
+
+// Merge shared settings and account overrides, later sources winning: shared policies and webhook secrets,
+// WHATSAPP_* credentials (default account), WHATSAPP_ACCOUNTS, character settings, then WHATSAPP_<ACCOUNT>_* variables
+export function mergeWhatsAppAccountSettings(runtime: IAgentRuntime, accountId: string): WhatsAppAccountSettings {
+  return {
+    ...sharedOptions(runtime),
+    ...sharedWebhookSecrets(runtime),
+    ...fixtureSettings(runtime),
+    ...(accountId === DEFAULT_ACCOUNT_ID ? settingsCredentials(runtime) : {}),
+    ...settingAccounts(runtime)[accountId],
+    ...characterAccounts(runtime)[accountId],
+    ...accountOverrides(runtime, accountId),
+  };
+}
+
+// Merged account settings; null when the account has no usable credentials
+export function resolveWhatsAppAccount(
+  runtime: IAgentRuntime,
+  accountId: string
+): ResolvedWhatsAppAccount | null {
+  const account = mergeWhatsAppAccountSettings(runtime, accountId);
+  if (!hasCredentials(account)) return null;
+
+  return {
+    ...account,
+    accountId,
+    enabled: account.enabled !== false,
+  } as ResolvedWhatsAppAccount;
+}
+
+export function listEnabledWhatsAppAccounts(runtime: IAgentRuntime): ResolvedWhatsAppAccount[] {
+  return listWhatsAppAccountIds(runtime)
+    .map((id) => resolveWhatsAppAccount(runtime, id))
+    .filter((account): account is ResolvedWhatsAppAccount => !!account?.enabled);
+}
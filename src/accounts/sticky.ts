@@ -0,0 +1,37 @@
+import { canonicalChatId } from '../utils/normalize';
+
+const DEFAULT_MAX_ENTRIES = 10_000;
+
+// Account each chat last exchanged messages with, so a conversation stays on one phone number
+export class StickyRoutes {
+  private routes = new Map<string, string>();
+
+  constructor(private readonly maxEntries = DEFAULT_MAX_ENTRIES) {}
+
+  remember(chat: string, accountId: string): void {
+    const key = canonicalChatId(chat);
+    // Re-insert so the Map's first key is always the least recently used chat
+    this.routes.delete(key);
+    this.routes.set(key, accountId);
+    while (this.routes.size > this.maxEntries) {
+      this.routes.delete(this.routes.keys().next().value as string);
+    }
+  }
+
+  get(chat: string): string | undefined {
+    return this.routes.get(canonicalChatId(chat));
+  }
+
+  get size(): number {
+    return this.routes.size;
+  }
+
+  // Chats and their accounts, least recently used first
+  snapshot(): Array<[string, string]> {
+    return [...this.routes];
+  }
+
+  restore(routes: Array<[string, string]>): void {
+    this.routes = new Map(routes.slice(-this.maxEntries));
+  }
+}
@@ -0,0 +1,17 @@
+import type { IAgentRuntime, Memory } from '@elizaos/core';
+import { resolveWhatsAppAccount } from '../accounts/resolve';
+
+// Account an action sends from: an `accountId` option, else the one the message arrived on.
+// Returns an error message when the named account is not configured or is disabled.
+export function actionAccount(
+  runtime: IAgentRuntime,
+  message: Memory,
+  options?: { [key: string]: unknown }
+): { accountId?: string } | string {
+  const requested = typeof options?.accountId === 'string' && options.accountId ? options.accountId : undefined;
+  if (!requested) return { accountId: message.content.whatsappAccount as string | undefined };
+  const account = resolveWhatsAppAccount(runtime, requested);
+  if (!account) return `No WhatsApp account named ${requested}`;
+  if (!account.enabled) return `WhatsApp account ${requested} is disabled`;
+  return { accountId: requested };
+}
@@ -0,0 +1,61 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { actionAccount } from './account';
+
+// Block the sender of the current message, or an explicit `target` passed in options, on `accountId` or the current account
+export const blockUserAction: Action = {
+  name: 'BLOCK_WHATSAPP_USER',
+  similes: ['BLOCK_USER', 'WHATSAPP_BLOCK'],
+  description:
+    'Block a WhatsApp user so they can no longer message this account. Use only for abusive or spam senders.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const target = (options?.target as string | undefined) ?? (message.content.whatsappSender as string | undefined);
+    if (!service || !target) {
+      return { success: false, text: 'No WhatsApp user to block' };
+    }
+
+    const account = actionAccount(runtime, message, options);
+    if (typeof account === 'string') {
+      return { success: false, text: account };
+    }
+
+    const blocked = await service.blockUsers([target], account.accountId);
+    const text = blocked.length > 0 ? `Blocked ${target} on WhatsApp` : `Could not block ${target}`;
+    await callback?.({ text, actions: ['BLOCK_WHATSAPP_USER'], source: 'whatsapp' });
+    return { success: blocked.length > 0, text, data: { blocked } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'Buy cheap followers now!!! http://spam.example' } },
+      {
+        name: '{{agentName}}',
+        content: { text: 'Blocking this sender.', actions: ['BLOCK_WHATSAPP_USER'] },
+      },
+    ],
+  ],
+};
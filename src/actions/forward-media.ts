@@ -0,0 +1,63 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { actionAccount } from './account';
+
+// Forward media from the current message (or `mediaId`) to `target`, optionally from another `accountId`
+export const forwardMediaAction: Action = {
+  name: 'FORWARD_WHATSAPP_MEDIA',
+  similes: ['FORWARD_MEDIA', 'WHATSAPP_FORWARD'],
+  description:
+    'Forward a photo, video, voice note or document received on WhatsApp to another WhatsApp contact or group.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const mediaId = (options?.mediaId as string | undefined) ?? message.content.attachments?.[0]?.id;
+    const target = options?.target as string | undefined;
+    if (!service || !mediaId || !target) {
+      return { success: false, text: 'Media to forward and a recipient are required' };
+    }
+
+    const via = actionAccount(runtime, message, options);
+    if (typeof via === 'string') {
+      return { success: false, text: via };
+    }
+
+    const fromAccount = message.content.whatsappAccount as string | undefined;
+    await service.forwardMedia(mediaId, target, fromAccount, via.accountId);
+    const text = `Forwarded media to ${target}`;
+    await callback?.({ text, actions: ['FORWARD_WHATSAPP_MEDIA'], source: 'whatsapp' });
+    return { success: true, text, data: { mediaId, target } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'Please pass this receipt on to accounting.' } },
+      {
+        name: '{{agentName}}',
+        content: { text: "I've forwarded it to accounting.", actions: ['FORWARD_WHATSAPP_MEDIA'] },
+      },
+    ],
+  ],
+};
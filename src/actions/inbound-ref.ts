@@ -0,0 +1,12 @@
+import type { Memory } from '@elizaos/core';
+import type { InboundMessageRef } from '../clients/interface';
+
+// The WhatsApp message a runtime memory was created from
+export function inboundRef(message: Memory): InboundMessageRef | undefined {
+  const id = message.content.whatsappMessageId as string | undefined;
+  const from = message.content.channelId as string | undefined;
+  if (!id || !from) return undefined;
+
+  const sender = message.content.whatsappSender as string | undefined;
+  return { id, from, ...(sender && sender !== from ? { participant: sender } : {}) };
+}
@@ -0,0 +1,33 @@
+import { blockUserAction } from './block-user';
+import { forwardMediaAction } from './forward-media';
+import { markReadAction } from './mark-read';
+import { sendCannedResponseAction } from './send-canned-response';
+import { sendLocationAction } from './send-location';
+import { sendOtpAction } from './send-otp';
+import { tagContactAction } from './tag-contact';
+import { typingIndicatorAction } from './typing-indicator';
+
+export {
+  blockUserAction,
+  forwardMediaAction,
+  markReadAction,
+  sendCannedResponseAction,
+  sendLocationAction,
+  sendOtpAction,
+  tagContactAction,
+  typingIndicatorAction,
+};
+export { actionAccount } from './account';
+export { parseLocation } from './send-location';
+
+// Everything the plugin registers with the runtime
+export const whatsappActions = [
+  blockUserAction,
+  tagContactAction,
+  sendCannedResponseAction,
+  sendLocationAction,
+  forwardMediaAction,
+  markReadAction,
+  typingIndicatorAction,
+  sendOtpAction,
+];
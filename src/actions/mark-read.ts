@@ -0,0 +1,55 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { inboundRef } from './inbound-ref';
+
+// Send a read receipt (blue ticks) for the current message
+export const markReadAction: Action = {
+  name: 'MARK_WHATSAPP_READ',
+  similes: ['WHATSAPP_MARK_READ', 'MARK_AS_READ', 'READ_RECEIPT'],
+  description:
+    'Mark the current WhatsApp message as read so the sender sees blue ticks, e.g. before a reply that takes a while.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!inboundRef(message) &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    _options?: { [key: string]: unknown },
+    _callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const ref = inboundRef(message);
+    if (!service || !ref) {
+      return { success: false, text: 'No WhatsApp message to mark as read' };
+    }
+
+    await service.markRead(ref, message.content.whatsappAccount as string | undefined);
+    return { success: true, text: 'Marked message as read', data: { messageId: ref.id } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'Can you check my order status? It is #4412.' } },
+      {
+        name: '{{agentName}}',
+        content: { text: 'Let me look that up.', actions: ['MARK_WHATSAPP_READ'] },
+      },
+    ],
+  ],
+};
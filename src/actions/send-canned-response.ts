@@ -0,0 +1,68 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { actionAccount } from './account';
+
+// Send the canned response `name` to the current chat, or to an explicit `target`, optionally from `accountId`
+export const sendCannedResponseAction: Action = {
+  name: 'SEND_CANNED_RESPONSE',
+  similes: ['SEND_QUICK_REPLY', 'WHATSAPP_CANNED_REPLY'],
+  description:
+    'Send a pre-approved WhatsApp reply (text, media or template) by name, such as opening-hours or price-list.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    const service = runtime.getService<WhatsAppConnectorService>(WhatsAppConnectorService.serviceType);
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!service &&
+      (await service.cannedResponses.list()).length > 0
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const name = options?.name as string | undefined;
+    // Answer in the chat the message came from, which for a group is the group, not the member
+    const target = (options?.target as string | undefined) ?? (message.content.channelId as string | undefined);
+    if (!service || !name || !target) {
+      return { success: false, text: 'A canned response name and recipient are required' };
+    }
+    if (!(await service.cannedResponses.get(name))) {
+      return { success: false, text: `No canned response named ${name}` };
+    }
+
+    const account = actionAccount(runtime, message, options);
+    if (typeof account === 'string') {
+      return { success: false, text: account };
+    }
+
+    await service.sendCannedResponse(name, target, account);
+    const text = `Sent canned response ${name} to ${target}`;
+    await callback?.({ text, actions: ['SEND_CANNED_RESPONSE'], source: 'whatsapp' });
+    return { success: true, text, data: { name, target } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'When are you open?' } },
+      {
+        name: '{{agentName}}',
+        content: { text: 'Here are our opening hours.', actions: ['SEND_CANNED_RESPONSE'] },
+      },
+    ],
+  ],
+};
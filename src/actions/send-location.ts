@@ -0,0 +1,92 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { actionAccount } from './account';
+import type { WhatsAppLocation } from '../types';
+
+function coordinate(value: unknown): number | undefined {
+  const parsed = typeof value === 'string' && value.trim() ? Number(value) : value;
+  return typeof parsed === 'number' && Number.isFinite(parsed) ? parsed : undefined;
+}
+
+// Read `latitude`/`longitude` (or `lat`/`lon`) plus optional `name` and `address` from action options
+export function parseLocation(options?: { [key: string]: unknown }): WhatsAppLocation | string {
+  const latitude = coordinate(options?.latitude ?? options?.lat);
+  const longitude = coordinate(options?.longitude ?? options?.lon);
+  if (latitude === undefined || longitude === undefined) {
+    return 'Latitude and longitude are required';
+  }
+  if (latitude < -90 || latitude > 90) {
+    return `Latitude ${latitude} is outside -90..90`;
+  }
+  if (longitude < -180 || longitude > 180) {
+    return `Longitude ${longitude} is outside -180..180`;
+  }
+
+  const name = typeof options?.name === 'string' && options.name ? options.name : undefined;
+  const address = typeof options?.address === 'string' && options.address ? options.address : undefined;
+  return { latitude, longitude, ...(name ? { name } : {}), ...(address ? { address } : {}) };
+}
+
+// Send a location pin to the chat of the current message, or to `target`, optionally from `accountId`
+export const sendLocationAction: Action = {
+  name: 'SEND_WHATSAPP_LOCATION',
+  similes: ['SHARE_LOCATION', 'WHATSAPP_LOCATION'],
+  description:
+    'Send a map pin (latitude, longitude, optional name and address) on WhatsApp, e.g. a store or meeting point.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    // A pin asked for in a group goes to the group, not to the member who asked
+    const target = (options?.target as string | undefined) ?? (message.content.channelId as string | undefined);
+    if (!service || !target) {
+      return { success: false, text: 'No WhatsApp recipient for the location' };
+    }
+
+    const location = parseLocation(options);
+    if (typeof location === 'string') {
+      return { success: false, text: location };
+    }
+
+    const account = actionAccount(runtime, message, options);
+    if (typeof account === 'string') {
+      return { success: false, text: account };
+    }
+
+    await service.sendLocation(target, location, account);
+    const text = `Sent ${location.name ?? 'a location'} to ${target}`;
+    await callback?.({ text, actions: ['SEND_WHATSAPP_LOCATION'], source: 'whatsapp' });
+    return { success: true, text, data: { location } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'Where is your shop?' } },
+      {
+        name: '{{agentName}}',
+        content: { text: "Here's our location.", actions: ['SEND_WHATSAPP_LOCATION'] },
+      },
+    ],
+  ],
+};
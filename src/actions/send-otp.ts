@@ -0,0 +1,79 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { getSetting } from '../settings';
+import { actionAccount } from './account';
+
+const VERIFICATION_TEXT = {
+  valid: 'Thanks, your number is verified.',
+  invalid: "That code doesn't match. Please check it and try again.",
+  expired: 'That code has expired. I can send you a new one.',
+  missing: "There's no code waiting for verification. I can send you one.",
+  locked: 'Too many wrong attempts. I can send you a new code.',
+} as const;
+
+// Send a one-time code to the sender (or `target`) from `accountId` or the current account; with `code`, verify it on that account instead
+export const sendOtpAction: Action = {
+  name: 'SEND_WHATSAPP_OTP',
+  similes: ['SEND_OTP', 'VERIFY_OTP', 'WHATSAPP_VERIFICATION_CODE'],
+  description:
+    'Send a one-time verification code over WhatsApp, or check a code the user sent back (pass it as `code`).',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!getSetting(runtime, 'WHATSAPP_OTP') &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const target = (options?.target as string | undefined) ?? (message.content.whatsappSender as string | undefined);
+    if (!service || !target) {
+      return { success: false, text: 'No WhatsApp recipient for the code' };
+    }
+
+    const account = actionAccount(runtime, message, options);
+    if (typeof account === 'string') {
+      return { success: false, text: account };
+    }
+
+    const code = options?.code as string | undefined;
+    if (code) {
+      const result = await service.verifyOtp(target, String(code), account.accountId);
+      const text = VERIFICATION_TEXT[result];
+      await callback?.({ text, actions: ['SEND_WHATSAPP_OTP'], source: 'whatsapp' });
+      return { success: result === 'valid', text, data: { verification: result } };
+    }
+
+    await service.sendOtp(target, account.accountId);
+    const text = 'I sent you a verification code. Please reply with it.';
+    await callback?.({ text, actions: ['SEND_WHATSAPP_OTP'], source: 'whatsapp' });
+    return { success: true, text, data: { sent: true } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'I want to change the email on my account.' } },
+      {
+        name: '{{agentName}}',
+        content: { text: 'First I need to verify your number.', actions: ['SEND_WHATSAPP_OTP'] },
+      },
+    ],
+  ],
+};
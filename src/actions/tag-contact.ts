@@ -0,0 +1,70 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { actionAccount } from './account';
+
+function parseTags(value: unknown): string[] {
+  if (Array.isArray(value)) return value.map(String);
+  if (typeof value === 'string') return value.split(',').map((t) => t.trim()).filter(Boolean);
+  return [];
+}
+
+// Label the sender (or `target`) with `tags` on `accountId` or the current account; `remove: true` takes the labels off instead
+export const tagContactAction: Action = {
+  name: 'TAG_WHATSAPP_CONTACT',
+  similes: ['LABEL_CONTACT', 'WHATSAPP_TAG'],
+  description:
+    'Attach labels such as lead, customer, or vip to a WhatsApp contact so they can be found and targeted later.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    options?: { [key: string]: unknown },
+    callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const target = (options?.target as string | undefined) ?? (message.content.whatsappSender as string | undefined);
+    const tags = parseTags(options?.tags);
+    if (!service || !target || tags.length === 0) {
+      return { success: false, text: 'A contact and at least one tag are required' };
+    }
+
+    const account = actionAccount(runtime, message, options);
+    if (typeof account === 'string') {
+      return { success: false, text: account };
+    }
+
+    const current = options?.remove
+      ? await service.untagContact(target, tags, account.accountId)
+      : await service.tagContact(target, tags, account.accountId);
+    const text = `Tags for ${target}: ${current.join(', ') || '(none)'}`;
+    await callback?.({ text, actions: ['TAG_WHATSAPP_CONTACT'], source: 'whatsapp' });
+    return { success: true, text, data: { tags: current } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: "I'd like a quote for 200 units." } },
+      {
+        name: '{{agentName}}',
+        content: { text: "I'll get you a quote right away.", actions: ['TAG_WHATSAPP_CONTACT'] },
+      },
+    ],
+  ],
+};
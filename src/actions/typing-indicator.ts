@@ -0,0 +1,55 @@
+import type {
+  Action,
+  ActionResult,
+  HandlerCallback,
+  IAgentRuntime,
+  Memory,
+  State,
+} from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import { inboundRef } from './inbound-ref';
+
+// Show "typing…" in the chat of the current message; WhatsApp clears it on the next reply or after ~25s
+export const typingIndicatorAction: Action = {
+  name: 'SEND_WHATSAPP_TYPING',
+  similes: ['WHATSAPP_TYPING', 'SHOW_TYPING', 'TYPING_INDICATOR'],
+  description:
+    'Show a typing indicator in the current WhatsApp chat while preparing a longer reply.',
+
+  validate: async (runtime: IAgentRuntime, message: Memory): Promise<boolean> => {
+    return (
+      message.content?.source === 'whatsapp' &&
+      !!inboundRef(message) &&
+      !!runtime.getService(WhatsAppConnectorService.serviceType)
+    );
+  },
+
+  handler: async (
+    runtime: IAgentRuntime,
+    message: Memory,
+    _state?: State,
+    _options?: { [key: string]: unknown },
+    _callback?: HandlerCallback
+  ): Promise<ActionResult> => {
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    const ref = inboundRef(message);
+    if (!service || !ref) {
+      return { success: false, text: 'No WhatsApp chat to show typing in' };
+    }
+
+    await service.sendTyping(ref, message.content.whatsappAccount as string | undefined);
+    return { success: true, text: 'Showing typing indicator', data: { messageId: ref.id } };
+  },
+
+  examples: [
+    [
+      { name: '{{user1}}', content: { text: 'Can you summarize the contract I sent?' } },
+      {
+        name: '{{agentName}}',
+        content: { text: 'Reading it now.', actions: ['SEND_WHATSAPP_TYPING'] },
+      },
+    ],
+  ],
+};
@@ -0,0 +1 @@
+export { adminRoutes, ADMIN_PATH } from './routes';
@@ -0,0 +1,272 @@
+import { createHash, timingSafeEqual } from 'crypto';
+import type { IAgentRuntime, Route } from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+import type { CannedResponse } from '../canned';
+import type { StateSnapshot } from '../state';
+
+type RouteHandler = NonNullable<Route['handler']>;
+type Request = Parameters<RouteHandler>[0];
+type Response = Parameters<RouteHandler>[1];
+type AdminHandler = (
+  req: Request,
+  res: Response,
+  service: WhatsAppConnectorService
+) => Promise<void> | void;
+
+export const ADMIN_PATH = '/whatsapp/admin';
+
+function header(req: Request, name: string): string | undefined {
+  const value = (req.headers as Record<string, string | string[] | undefined> | undefined)?.[name];
+  return Array.isArray(value) ? value[0] : value;
+}
+
+// Compare digests so the check takes the same time whatever the key length
+function keysMatch(provided: string, expected: string): boolean {
+  const digest = (value: string) => createHash('sha256').update(value).digest();
+  return timingSafeEqual(digest(provided), digest(expected));
+}
+
+function presentedKey(req: Request): string | undefined {
+  const authorization = header(req, 'authorization');
+  if (authorization?.startsWith('Bearer ')) return authorization.slice('Bearer '.length);
+  return header(req, 'x-api-key');
+}
+
+// Optional ?accountId= selecting a non-default account
+function accountQuery(req: Request): string | undefined {
+  const value = (req.query as Record<string, unknown> | undefined)?.accountId;
+  return typeof value === 'string' && value ? value : undefined;
+}
+
+// ?from= / ?to= as milliseconds since epoch or an ISO date
+function timeQuery(req: Request, name: string): number | undefined {
+  const value = (req.query as Record<string, unknown> | undefined)?.[name];
+  if (typeof value !== 'string' || !value) return undefined;
+  const time = /^\d+$/.test(value) ? Number(value) : Date.parse(value);
+  return Number.isNaN(time) ? undefined : time;
+}
+
+function param(req: Request, name: string): string {
+  return decodeURIComponent((req.params as Record<string, string> | undefined)?.[name] ?? '');
+}
+
+// The admin API only answers when WHATSAPP_ADMIN_API_KEY is set, and only to callers presenting it
+function admin(handler: AdminHandler): RouteHandler {
+  return async (req: Request, res: Response, runtime: IAgentRuntime) => {
+    const apiKey = runtime.getSetting('WHATSAPP_ADMIN_API_KEY') ?? process.env.WHATSAPP_ADMIN_API_KEY;
+    if (!apiKey) {
+      res.status(404).json({ error: 'WhatsApp admin API is disabled' });
+      return;
+    }
+
+    const provided = presentedKey(req);
+    if (!provided || !keysMatch(provided, String(apiKey))) {
+      res.status(401).json({ error: 'Invalid or missing API key' });
+      return;
+    }
+
+    const service = runtime.getService<WhatsAppConnectorService>(
+      WhatsAppConnectorService.serviceType
+    );
+    if (!service) {
+      res.status(503).json({ error: 'WhatsApp connector is not running' });
+      return;
+    }
+
+    try {
+      await handler(req, res, service);
+    } catch (error) {
+      res.status(500).json({ error: (error as Error).message });
+    }
+  };
+}
+
+function toggle(enabled: boolean): AdminHandler {
+  return (req, res, service) => {
+    const accountId = param(req, 'accountId');
+    if (!service.setAccountEnabled(accountId, enabled)) {
+      res.status(404).json({ error: `Unknown account ${accountId}` });
+      return;
+    }
+    res.json({ accountId, enabled });
+  };
+}
+
+export const adminRoutes: Route[] = [
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/accounts`,
+    handler: admin((_req, res, service) => {
+      res.json({ accounts: service.listAccounts() });
+    }),
+  },
+  {
+    type: 'POST',
+    path: `${ADMIN_PATH}/accounts/:accountId/enable`,
+    handler: admin(toggle(true)),
+  },
+  {
+    type: 'POST',
+    path: `${ADMIN_PATH}/accounts/:accountId/disable`,
+    handler: admin(toggle(false)),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/groups`,
+    handler: admin((req, res, service) => {
+      res.json({ groups: service.getGroups(accountQuery(req)) });
+    }),
+  },
+  {
+    type: 'PUT',
+    path: `${ADMIN_PATH}/groups/:groupId/allow-from`,
+    handler: admin((req, res, service) => {
+      const allowFrom = (req.body as { allowFrom?: unknown } | undefined)?.allowFrom;
+      if (!Array.isArray(allowFrom) || !allowFrom.every((entry) => typeof entry === 'string')) {
+        res.status(400).json({ error: 'allowFrom must be an array of strings' });
+        return;
+      }
+      const groupId = param(req, 'groupId');
+      res.json({ groupId, group: service.setGroupAllowFrom(groupId, allowFrom, accountQuery(req)) });
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/canned-responses`,
+    handler: admin(async (_req, res, service) => {
+      res.json({ cannedResponses: await service.cannedResponses.list() });
+    }),
+  },
+  {
+    type: 'PUT',
+    path: `${ADMIN_PATH}/canned-responses/:name`,
+    handler: admin(async (req, res, service) => {
+      const name = param(req, 'name');
+      try {
+        await service.cannedResponses.set(name, req.body as CannedResponse);
+      } catch (error) {
+        res.status(400).json({ error: (error as Error).message });
+        return;
+      }
+      res.json({ name, response: await service.cannedResponses.get(name) });
+    }),
+  },
+  {
+    type: 'DELETE',
+    path: `${ADMIN_PATH}/canned-responses/:name`,
+    handler: admin(async (req, res, service) => {
+      const name = param(req, 'name');
+      if (!(await service.cannedResponses.remove(name))) {
+        res.status(404).json({ error: `No canned response named ${name}` });
+        return;
+      }
+      res.json({ name, removed: true });
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/doctor`,
+    handler: admin(async (req, res, service) => {
+      const live = (req.query as Record<string, unknown> | undefined)?.live === 'true';
+      res.json(await service.validateDeployment(live));
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/state`,
+    handler: admin((_req, res, service) => {
+      res.json(service.exportState());
+    }),
+  },
+  {
+    type: 'PUT',
+    path: `${ADMIN_PATH}/state`,
+    handler: admin((req, res, service) => {
+      try {
+        service.importState(req.body as StateSnapshot);
+      } catch (error) {
+        res.status(400).json({ error: (error as Error).message });
+        return;
+      }
+      res.json({ imported: true });
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/costs`,
+    handler: admin((req, res, service) => {
+      res.json(service.getConversationCosts({ from: timeQuery(req, 'from'), to: timeQuery(req, 'to') }));
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/dead-letters`,
+    handler: admin((_req, res, service) => {
+      res.json({ deadLetters: service.listDeadLetters() });
+    }),
+  },
+  {
+    type: 'POST',
+    path: `${ADMIN_PATH}/dead-letters/:id/replay`,
+    handler: admin(async (req, res, service) => {
+      const id = param(req, 'id');
+      if (!service.listDeadLetters().some((entry) => entry.id === id)) {
+        res.status(404).json({ error: `No dead letter with id ${id}` });
+        return;
+      }
+      res.json({ id, response: await service.replayDeadLetter(id) });
+    }),
+  },
+  {
+    type: 'DELETE',
+    path: `${ADMIN_PATH}/dead-letters/:id`,
+    handler: admin((req, res, service) => {
+      const id = param(req, 'id');
+      if (!service.listDeadLetters().some((entry) => entry.id === id)) {
+        res.status(404).json({ error: `No dead letter with id ${id}` });
+        return;
+      }
+      service.discardDeadLetter(id);
+      res.json({ id, discarded: true });
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/webhooks`,
+    handler: admin((_req, res, service) => {
+      res.json({ webhooks: service.listStoredWebhooks() });
+    }),
+  },
+  {
+    type: 'POST',
+    path: `${ADMIN_PATH}/webhooks/:id/replay`,
+    handler: admin(async (req, res, service) => {
+      const id = param(req, 'id');
+      if (!service.listStoredWebhooks().some((entry) => entry.id === id)) {
+        res.status(404).json({ error: `No stored webhook with id ${id}` });
+        return;
+      }
+      await service.replayWebhook(id);
+      res.json({ id, replayed: true });
+    }),
+  },
+  {
+    type: 'GET',
+    path: `${ADMIN_PATH}/failed-sends`,
+    handler: admin((_req, res, service) => {
+      res.json({ failedSends: service.listFailedSends() });
+    }),
+  },
+  {
+    type: 'POST',
+    path: `${ADMIN_PATH}/failed-sends/:id/replay`,
+    handler: admin(async (req, res, service) => {
+      const id = param(req, 'id');
+      if (!service.listFailedSends().some((entry) => entry.id === id)) {
+        res.status(404).json({ error: `No failed send with id ${id}` });
+        return;
+      }
+      res.json({ id, response: await service.replayFailedSend(id) });
+    }),
+  },
+];
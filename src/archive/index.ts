@@ -0,0 +1,11 @@
+export { InMemoryMessageArchive, RuntimeMessageArchive } from './message-archive';
+export type { ArchivedMessage, ArchiveRetention, MessageArchive } from './message-archive';
+export { ConversationSummaries, InMemorySummaryStore, RuntimeSummaryStore } from './summary';
+export type {
+  ConversationContext,
+  ConversationSummary,
+  Summarizer,
+  SummarizerInput,
+  SummaryOptions,
+  SummaryStore,
+} from './summary';
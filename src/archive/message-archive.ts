@@ -0,0 +1,146 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import { systemClock, type Clock } from '../utils/clock';
+import { LruCache, StoredChatBounds, type LruOptions } from '../utils/lru';
+
+export interface ArchivedMessage {
+  id: string;
+  chatId: string;                       // canonicalChatId() of the conversation
+  direction: 'inbound' | 'outbound';
+  sender: string;                       // Sender JID/number for inbound; "agent", or "business" for Business app echoes
+  type: string;
+  text: string;
+  timestamp: number;                    // Milliseconds since epoch
+}
+
+export interface ArchiveRetention {
+  maxAgeMs?: number;                    // Default 30 days
+  maxPerChat?: number;                  // Default 200
+}
+
+// Conversation history per chat, oldest first
+export interface MessageArchive {
+  append(message: ArchivedMessage): Promise<void>;
+  recent(chatId: string, limit: number): Promise<ArchivedMessage[]>;
+  // Implemented by archives held in process memory, so their contents can move to a new process
+  snapshot?(): Array<[string, ArchivedMessage[], number]>;
+  restore?(chats: Array<[string, ArchivedMessage[], number]>): void;
+}
+
+const DEFAULT_MAX_AGE_MS = 30 * 24 * 60 * 60 * 1000;
+const DEFAULT_MAX_PER_CHAT = 200;
+
+function applyRetention(
+  messages: ArchivedMessage[],
+  retention: Required<ArchiveRetention>,
+  now: number
+): ArchivedMessage[] {
+  return messages
+    .filter((m) => m.timestamp > now - retention.maxAgeMs)
+    .slice(-retention.maxPerChat);
+}
+
+function withDefaults(retention: ArchiveRetention): Required<ArchiveRetention> {
+  return {
+    maxAgeMs: retention.maxAgeMs ?? DEFAULT_MAX_AGE_MS,
+    maxPerChat: retention.maxPerChat ?? DEFAULT_MAX_PER_CHAT,
+  };
+}
+
+// History in process memory, bounded by `limits` in the number of chats kept
+export class InMemoryMessageArchive implements MessageArchive {
+  private chats: LruCache<ArchivedMessage[]>;
+  private retention: Required<ArchiveRetention>;
+
+  constructor(
+    retention: ArchiveRetention = {},
+    private readonly clock: Clock = systemClock,
+    limits: LruOptions = {}
+  ) {
+    this.retention = withDefaults(retention);
+    this.chats = new LruCache({ clock, ...limits });
+  }
+
+  async append(message: ArchivedMessage): Promise<void> {
+    const history = this.chats.get(message.chatId) ?? [];
+    history.push(message);
+    this.chats.set(message.chatId, applyRetention(history, this.retention, this.clock.now()));
+  }
+
+  async recent(chatId: string, limit: number): Promise<ArchivedMessage[]> {
+    const history = applyRetention(this.chats.get(chatId) ?? [], this.retention, this.clock.now());
+    return history.slice(-limit);
+  }
+
+  snapshot(): Array<[string, ArchivedMessage[], number]> {
+    return this.chats.snapshot();
+  }
+
+  restore(chats: Array<[string, ArchivedMessage[], number]>): void {
+    this.chats.restore(chats);
+  }
+}
+
+// One runtime cache entry per chat, trimmed to the retention policy on every write and bounded by
+// the chat-state limits like the in-memory archive
+export class RuntimeMessageArchive implements MessageArchive {
+  private retention: Required<ArchiveRetention>;
+  // Last pending write per chat; appends are read-modify-write, so each waits for the one before
+  private writes = new Map<string, Promise<void>>();
+  private bounds: StoredChatBounds;
+
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string,
+    retention: ArchiveRetention = {},
+    private readonly clock: Clock = systemClock,
+    limits: LruOptions = {}
+  ) {
+    this.retention = withDefaults(retention);
+    this.bounds = new StoredChatBounds({ clock, ...limits }, (chatId) => this.runtime.deleteCache(this.key(chatId)));
+  }
+
+  append(message: ArchivedMessage): Promise<void> {
+    const { chatId } = message;
+    const write = (this.writes.get(chatId) ?? Promise.resolve())
+      .catch(() => {})
+      .then(async () => {
+        const history = await this.load(chatId);
+        history.push(message);
+        await this.runtime.setCache(this.key(chatId), applyRetention(history, this.retention, this.clock.now()));
+        this.bounds.touch(chatId);
+      });
+    this.writes.set(chatId, write);
+    const settled = () => {
+      if (this.writes.get(chatId) === write) this.writes.delete(chatId);
+    };
+    write.then(settled, settled);
+    return write;
+  }
+
+  async recent(chatId: string, limit: number): Promise<ArchivedMessage[]> {
+    const history = applyRetention(await this.load(chatId), this.retention, this.clock.now());
+    if (history.length === 0) return [];
+    this.bounds.touch(chatId);
+    return history.slice(-limit);
+  }
+
+  // History written before archives were kept per account is read until the chat's next write moves it over
+  private async load(chatId: string): Promise<ArchivedMessage[]> {
+    const history =
+      (await this.runtime.getCache<ArchivedMessage[]>(this.key(chatId))) ??
+      (await this.runtime.getCache<ArchivedMessage[]>(`whatsapp-archive-${chatId}`)) ??
+      [];
+    // A chat idle past the limits is dropped, as the in-memory archive would have evicted it
+    const last = history[history.length - 1];
+    if (last && this.bounds.isIdle(last.timestamp)) {
+      this.bounds.forget(chatId);
+      await this.runtime.deleteCache(this.key(chatId));
+      return [];
+    }
+    return history;
+  }
+
+  private key(chatId: string): string {
+    return `whatsapp-archive-${this.accountId}-${chatId}`;
+  }
+}
@@ -0,0 +1,160 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import { systemClock, type Clock } from '../utils/clock';
+import type { ArchivedMessage } from './message-archive';
+
+export interface ConversationSummary {
+  text: string;
+  throughId: string;                    // Last message the summary covers
+  throughTimestamp: number;
+  turns: number;                        // Messages folded into the summary so far
+  updatedAt: number;
+}
+
+export interface SummarizerInput {
+  chatId: string;
+  previous?: string;                    // Summary so far, to extend with `messages`
+  messages: ArchivedMessage[];          // Oldest first
+}
+
+// Condenses turns into a summary, typically with a language model
+export type Summarizer = (input: SummarizerInput) => Promise<string>;
+
+export interface SummaryOptions {
+  threshold?: number;                   // Unsummarized turns that trigger folding older ones in (default 30)
+  keep?: number;                        // Most recent turns always left verbatim (default 10)
+}
+
+// What the agent sees of a chat: a summary of older turns, then the rest as written
+export interface ConversationContext {
+  summary?: ConversationSummary;
+  recent: ArchivedMessage[];
+}
+
+export interface SummaryStore {
+  get(chatId: string): Promise<ConversationSummary | undefined>;
+  set(chatId: string, summary: ConversationSummary): Promise<void>;
+}
+
+export class InMemorySummaryStore implements SummaryStore {
+  private summaries = new Map<string, ConversationSummary>();
+
+  async get(chatId: string): Promise<ConversationSummary | undefined> {
+    return this.summaries.get(chatId);
+  }
+
+  async set(chatId: string, summary: ConversationSummary): Promise<void> {
+    this.summaries.set(chatId, summary);
+  }
+}
+
+// One runtime cache entry per chat and account, next to its archived history
+export class RuntimeSummaryStore implements SummaryStore {
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string
+  ) {}
+
+  // Summaries written before they were kept per account are read until the chat's next fold
+  async get(chatId: string): Promise<ConversationSummary | undefined> {
+    return (
+      (await this.runtime.getCache<ConversationSummary>(this.key(chatId))) ??
+      (await this.runtime.getCache<ConversationSummary>(`whatsapp-summary-${chatId}`)) ??
+      undefined
+    );
+  }
+
+  async set(chatId: string, summary: ConversationSummary): Promise<void> {
+    await this.runtime.setCache(this.key(chatId), summary);
+  }
+
+  private key(chatId: string): string {
+    return `whatsapp-summary-${this.accountId}-${chatId}`;
+  }
+}
+
+const DEFAULT_THRESHOLD = 30;
+const DEFAULT_KEEP = 10;
+
+// Turns of `history` after those `summary` covers
+function after(history: ArchivedMessage[], summary: ConversationSummary | undefined): ArchivedMessage[] {
+  if (!summary) return history;
+  const index = history.findIndex((m) => m.id === summary.throughId);
+  return index >= 0 ? history.slice(index + 1) : history.filter((m) => m.timestamp > summary.throughTimestamp);
+}
+
+/**
+ * Rolling summaries that keep long chats within a token budget. Once more than `threshold` turns
+ * follow the current summary, all but the last `keep` are folded into it with the summarizer, so
+ * the summarizer runs once every `threshold - keep` turns rather than on every message.
+ */
+export class ConversationSummaries {
+  private store: SummaryStore;
+  private summarizer?: Summarizer;
+  private readonly threshold: number;
+  private readonly keep: number;
+  private folding = new Map<string, Promise<ConversationSummary>>();
+
+  constructor(options: SummaryOptions = {}, store?: SummaryStore, private readonly clock: Clock = systemClock) {
+    this.keep = Math.max(0, options.keep ?? DEFAULT_KEEP);
+    this.threshold = Math.max(this.keep + 1, options.threshold ?? DEFAULT_THRESHOLD);
+    this.store = store ?? new InMemorySummaryStore();
+  }
+
+  useStore(store: SummaryStore): void {
+    this.store = store;
+  }
+
+  useSummarizer(summarizer: Summarizer | undefined): void {
+    this.summarizer = summarizer;
+  }
+
+  get enabled(): boolean {
+    return this.summarizer !== undefined;
+  }
+
+  // Context for a chat from its `history` (oldest first); without a summarizer, the last `keep` turns
+  async context(chatId: string, history: ArchivedMessage[]): Promise<ConversationContext> {
+    const summarizer = this.summarizer;
+    if (!summarizer) return { recent: history.slice(-this.keep) };
+    const current = await this.store.get(chatId);
+    const pending = after(history, current);
+    if (pending.length <= this.threshold) {
+      return { ...(current ? { summary: current } : {}), recent: pending };
+    }
+    const summary = await this.fold(summarizer, chatId, current, pending.slice(0, pending.length - this.keep));
+    return { summary, recent: after(pending, summary) };
+  }
+
+  // Concurrent requests for one chat share a single summarizer call
+  private fold(
+    summarizer: Summarizer,
+    chatId: string,
+    current: ConversationSummary | undefined,
+    messages: ArchivedMessage[]
+  ): Promise<ConversationSummary> {
+    const running = this.folding.get(chatId);
+    if (running) return running;
+    const folding = this.summarize(summarizer, chatId, current, messages).finally(() => this.folding.delete(chatId));
+    this.folding.set(chatId, folding);
+    return folding;
+  }
+
+  private async summarize(
+    summarizer: Summarizer,
+    chatId: string,
+    current: ConversationSummary | undefined,
+    messages: ArchivedMessage[]
+  ): Promise<ConversationSummary> {
+    const text = await summarizer({ chatId, ...(current ? { previous: current.text } : {}), messages });
+    const last = messages[messages.length - 1];
+    const summary = {
+      text,
+      throughId: last.id,
+      throughTimestamp: last.timestamp,
+      turns: (current?.turns ?? 0) + messages.length,
+      updatedAt: this.clock.now(),
+    };
+    await this.store.set(chatId, summary);
+    return summary;
+  }
+}
@@ -4,6 +4,8 @@ import pino from 'pino';
 import { EventEmitter } from 'events';
 import type { BaileysAuthManager } from './auth';
 import type { ConnectionStatus } from '../types';
+import { redactText } from '../utils/redact';
+import { WhatsAppError } from '../errors';
 
 export class BaileysConnection extends EventEmitter {
   private socket?: WASocket;
@@ -26,6 +28,7 @@ export class BaileysConnection extends EventEmitter {
       this.socket.ev.removeAllListeners('connection.update');
       this.socket.ev.removeAllListeners('creds.update');
       this.socket.ev.removeAllListeners('messages.upsert');
+      this.socket.ev.removeAllListeners('messages.update');
     }
 
     this.socket = makeWASocket({
@@ -64,7 +67,7 @@ export class BaileysConnection extends EventEmitter {
           console.error('  - Baileys version is outdated');
           console.error('  - WhatsApp protocol has changed');
           console.error('  - Browser info is rejected by WhatsApp');
-          this.emit('error', new Error('WhatsApp connection rejected (405). Try updating @whiskeysockets/baileys'));
+          this.emit('error', new WhatsAppError('CONNECTION_REJECTED', 'WhatsApp connection rejected (405). Try updating @whiskeysockets/baileys'));
           return; // Don't reconnect on 405
         }
 
@@ -76,9 +79,14 @@ export class BaileysConnection extends EventEmitter {
 
         const shouldReconnect = statusCode !== DisconnectReason.loggedOut;
 
-        // Only emit error for unexpected errors (not QR timeout)
-        if (lastDisconnect?.error && !isQRTimeout) {
-          console.error('Connection error:', lastDisconnect.error.message || lastDisconnect.error);
+        // Only emit error for unexpected errors (not QR timeout); a logout is reported once, as LOGGED_OUT
+        if (!shouldReconnect) {
+          this.emit('error', new WhatsAppError('LOGGED_OUT', 'WhatsApp session was logged out; pair the account again', { cause: lastDisconnect?.error }));
+        } else if (lastDisconnect?.error && !isQRTimeout) {
+          console.error(
+            'Connection error:',
+            redactText(lastDisconnect.error.message || String(lastDisconnect.error))
+          );
         }
 
         if (shouldReconnect && statusCode !== 405) {
@@ -90,7 +98,7 @@ export class BaileysConnection extends EventEmitter {
           // Check if max attempts reached
           if (this.reconnectAttempts >= this.MAX_RECONNECT_ATTEMPTS) {
             console.error(`Max reconnection attempts (${this.MAX_RECONNECT_ATTEMPTS}) reached. Giving up.`);
-            this.emit('error', new Error('Max reconnection attempts reached'));
+            this.emit('error', new WhatsAppError('RECONNECT_FAILED', 'Max reconnection attempts reached'));
             return;
           }
 
@@ -130,6 +138,11 @@ export class BaileysConnection extends EventEmitter {
     this.socket.ev.on('messages.upsert', ({ messages }) => {
       this.emit('messages', messages);
     });
+
+    // Receipts for sent messages
+    this.socket.ev.on('messages.update', (updates) => {
+      this.emit('updates', updates);
+    });
   }
 
   getSocket() {
@@ -146,6 +159,7 @@ export class BaileysConnection extends EventEmitter {
       this.socket.ev.removeAllListeners('connection.update');
       this.socket.ev.removeAllListeners('creds.update');
       this.socket.ev.removeAllListeners('messages.upsert');
+      this.socket.ev.removeAllListeners('messages.update');
 
       // Close the WebSocket connection (preserves session for next connection)
       if (this.socket.ws) {
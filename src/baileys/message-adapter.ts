@@ -1,40 +1,265 @@
 import type { proto } from '@whiskeysockets/baileys';
-import type { UnifiedMessage, WhatsAppMessage } from '../types';
+import type {
+  InboundLocation,
+  UnifiedMedia,
+  UnifiedMessage,
+  UnsupportedMessage,
+  WhatsAppCtaUrlParameters,
+  WhatsAppInteractive,
+  WhatsAppMedia,
+  WhatsAppMessage,
+} from '../types';
+import { toJid } from '../utils/normalize';
+import { normalizeReaction } from '../utils/emoji';
+import { formatLocation } from '../location';
+import { WhatsAppError } from '../errors';
+import type { MessageStatusUpdate } from '../events/types';
+import { systemClock, type Clock } from '../utils/clock';
+import { LruCache } from '../utils/lru';
+
+// Delivery status names for Baileys receipt levels (ERROR, PENDING, SERVER_ACK, DELIVERY_ACK, READ, PLAYED)
+const RECEIPT_STATUSES = ['failed', undefined, 'sent', 'delivered', 'read', 'read'];
+
+// Content people send that the unified format can't carry, by Baileys message field
+const UNSUPPORTED_CONTENT: Record<string, string> = {
+  stickerMessage: 'sticker',
+  liveLocationMessage: 'live_location',
+  contactMessage: 'contacts',
+  contactsArrayMessage: 'contacts',
+  ptvMessage: 'video_note',
+};
+
+// Ids of messages this account sent, so quotes and reactions can point at them
+const OWN_MESSAGE_LIMIT = 10_000;
 
 export class MessageAdapter {
-  // Convert Baileys message to unified format
-  toUnified(msg: proto.IWebMessageInfo): UnifiedMessage {
+  private ownMessages: LruCache<true>;
+
+  constructor(private readonly clock: Clock = systemClock) {
+    this.ownMessages = new LruCache({ maxChats: OWN_MESSAGE_LIMIT, clock });
+  }
+
+  // Note a message sent from this account, by the agent or from the linked phone
+  rememberOwn(id: string | null | undefined): void {
+    if (id) this.ownMessages.set(id, true);
+  }
+
+  // Baileys message key for `id` in the chat with `to`; WhatsApp only matches it with the right sender
+  private messageKey(to: string, id: string): proto.IMessageKey {
+    return { remoteJid: toJid(to), id, fromMe: this.ownMessages.get(id) === true };
+  }
+
+  // Type of a message toUnified can't represent, e.g. "sticker"; undefined for everything else
+  unsupportedType(msg: proto.IWebMessageInfo): string | undefined {
+    const content = (msg.message ?? {}) as Record<string, unknown>;
+    const field = Object.keys(UNSUPPORTED_CONTENT).find((key) => content[key]);
+    return field ? UNSUPPORTED_CONTENT[field] : undefined;
+  }
+
+  toUnsupported(msg: proto.IWebMessageInfo, type: string): UnsupportedMessage {
+    const participant = msg.key?.participant;
+    return {
+      id: msg.key?.id ?? '',
+      from: msg.key?.remoteJid ?? '',
+      timestamp: Number(msg.messageTimestamp ?? 0),
+      type,
+      ...(participant ? { participant } : {}),
+      ...(msg.pushName ? { senderName: msg.pushName } : {}),
+    };
+  }
+
+  // Convert Baileys message to unified format (ownJid is used to detect @mentions in groups)
+  toUnified(msg: proto.IWebMessageInfo, ownJid?: string): UnifiedMessage {
+    const replyId = this.extractReplyId(msg);
+    const participant = msg.key?.participant;
+    const media = this.extractMedia(msg);
+    const reaction = msg.message?.reactionMessage;
+    const replyTo = this.quotedId(msg);
+    const location = this.extractLocation(msg);
     return {
       id: msg.key?.id ?? '',
       from: msg.key?.remoteJid ?? '',
       timestamp: Number(msg.messageTimestamp ?? 0),
-      type: this.detectType(msg),
-      content: this.extractContent(msg)
+      type: replyId ? 'interactive' : reaction ? 'reaction' : this.detectType(msg),
+      content: this.extractContent(msg) || media?.caption || (location ? formatLocation(location) : ''),
+      ...(replyId ? { replyId } : {}),
+      ...(replyTo ? { replyTo } : {}),
+      ...(participant ? { participant, mentioned: this.mentions(msg, ownJid) } : {}),
+      ...(msg.pushName ? { senderName: msg.pushName } : {}),
+      ...(media ? { media } : {}),
+      ...(location ? { location } : {}),
+      ...(reaction?.key?.id ? { reaction: { messageId: reaction.key.id, emoji: reaction.text ?? '' } } : {}),
     };
   }
 
   // Convert unified message to Baileys format
   toBaileys(msg: WhatsAppMessage) {
     if (msg.type === 'text') {
-      return { text: msg.content as string };
+      return { text: msg.content };
+    }
+    if (msg.type === 'interactive') {
+      // Native buttons/lists are not delivered to multi-device clients, so render as text
+      return { text: this.renderInteractive(msg.content) };
+    }
+    if (msg.type === 'location') {
+      const location = msg.content;
+      return {
+        location: {
+          degreesLatitude: location.latitude,
+          degreesLongitude: location.longitude,
+          name: location.name,
+          address: location.address,
+        },
+      };
+    }
+    if (msg.type === 'media') {
+      return this.mediaToBaileys(msg.content);
+    }
+    if (msg.type === 'reaction') {
+      return { react: { text: normalizeReaction(msg.content.emoji), key: this.messageKey(msg.to, msg.content.messageId) } };
     }
     // Add more types as needed
-    throw new Error(`Message type ${msg.type} not yet supported for Baileys`);
+    throw new WhatsAppError('UNSUPPORTED', `Message type ${msg.type} not yet supported for Baileys`);
+  }
+
+  // Status update for a receipt on one of our messages; undefined for anything else
+  toStatus(update: { key: proto.IMessageKey; update: Partial<proto.IWebMessageInfo> }): MessageStatusUpdate | undefined {
+    const { key } = update;
+    const status = RECEIPT_STATUSES[update.update.status ?? -1];
+    if (!key.fromMe || !key.id || !status) return undefined;
+    return {
+      messageId: key.id,
+      status,
+      recipient: key.remoteJid ?? '',
+      timestamp: Math.floor(this.clock.now() / 1000),
+    };
+  }
+
+  // Send options quoting `msg.replyTo`; Baileys only needs the key to link the reply
+  sendOptions(msg: WhatsAppMessage): { quoted: proto.IWebMessageInfo } | undefined {
+    if (!msg.replyTo) return undefined;
+    return { quoted: { key: this.messageKey(msg.to, msg.replyTo), message: { conversation: '' } } };
+  }
+
+  // Baileys sends raw bytes or downloads from a link; Cloud API media ids are not usable here
+  private mediaToBaileys(media: WhatsAppMedia) {
+    if (!media.data && !media.url) {
+      throw new WhatsAppError('INVALID_MESSAGE', `Baileys needs data or a url to send ${media.kind} media`);
+    }
+    const source = media.data ? Buffer.from(media.data) : { url: media.url as string };
+    const mimetype = media.mimeType ? { mimetype: media.mimeType } : {};
+    switch (media.kind) {
+      case 'image':
+        return { image: source, caption: media.caption };
+      case 'video':
+        return { video: source, caption: media.caption, ...mimetype };
+      case 'audio':
+        return { audio: source, mimetype: media.mimeType ?? 'audio/mpeg' };
+      case 'document':
+        return {
+          document: source,
+          mimetype: media.mimeType ?? 'application/octet-stream',
+          fileName: media.fileName,
+          caption: media.caption,
+        };
+    }
+  }
+
+  private renderInteractive(interactive: WhatsAppInteractive): string {
+    if (interactive.type === 'flow') {
+      throw new WhatsAppError('UNSUPPORTED', 'Flows are only supported by the Cloud API');
+    }
+    if (interactive.type === 'address_message') {
+      throw new WhatsAppError('UNSUPPORTED', 'Address messages are only supported by the Cloud API');
+    }
+    if (interactive.type === 'order_details' || interactive.type === 'order_status') {
+      throw new WhatsAppError('UNSUPPORTED', 'Payments are only supported by the Cloud API');
+    }
+    if (interactive.type === 'product' || interactive.type === 'product_list') {
+      throw new WhatsAppError('UNSUPPORTED', 'Product messages are only supported by the Cloud API');
+    }
+    const link = interactive.type === 'cta_url' ? (interactive.action.parameters as WhatsAppCtaUrlParameters) : undefined;
+    const options = interactive.type === 'button'
+      ? (interactive.action.buttons ?? []).map((b) => b.reply.title)
+      : (interactive.action.sections ?? []).flatMap((s) => (s.rows ?? []).map((r) => r.title));
+
+    return [
+      interactive.header?.text,
+      interactive.body.text,
+      options.map((title, i) => `${i + 1}. ${title}`).join('\n'),
+      link ? `${link.display_text}: ${link.url}` : undefined,
+      interactive.footer?.text,
+    ].filter(Boolean).join('\n\n');
   }
 
-  private detectType(msg: proto.IWebMessageInfo): 'text' | 'image' | 'audio' | 'video' | 'document' {
+  private detectType(msg: proto.IWebMessageInfo): 'text' | 'image' | 'audio' | 'video' | 'document' | 'location' {
     if (msg.message?.conversation) return 'text';
     if (msg.message?.extendedTextMessage) return 'text';
     if (msg.message?.imageMessage) return 'image';
     if (msg.message?.audioMessage) return 'audio';
     if (msg.message?.videoMessage) return 'video';
     if (msg.message?.documentMessage) return 'document';
+    if (msg.message?.locationMessage) return 'location';
     return 'text';
   }
 
+  private extractLocation(msg: proto.IWebMessageInfo): InboundLocation | undefined {
+    const location = msg.message?.locationMessage;
+    if (location?.degreesLatitude == null || location.degreesLongitude == null) return undefined;
+    return {
+      latitude: location.degreesLatitude,
+      longitude: location.degreesLongitude,
+      ...(location.name ? { name: location.name } : {}),
+      ...(location.address ? { address: location.address } : {}),
+      ...(location.url ? { url: location.url } : {}),
+    };
+  }
+
+  private extractMedia(msg: proto.IWebMessageInfo): UnifiedMedia | undefined {
+    const m = msg.message;
+    const media = m?.imageMessage ?? m?.videoMessage ?? m?.audioMessage ?? m?.documentMessage;
+    if (!media) return undefined;
+
+    return {
+      id: msg.key?.id ?? '',
+      ...(media.mimetype ? { mimeType: media.mimetype } : {}),
+      ...('caption' in media && media.caption ? { caption: media.caption } : {}),
+      ...(m?.documentMessage?.fileName ? { fileName: m.documentMessage.fileName } : {}),
+      ...(media.url ? { url: media.url } : {}),
+    };
+  }
+
+  private mentions(msg: proto.IWebMessageInfo, ownJid?: string): boolean {
+    const own = ownJid ? toJid(ownJid) : undefined;
+    if (!own) return false;
+    const mentioned = msg.message?.extendedTextMessage?.contextInfo?.mentionedJid ?? [];
+    return mentioned.some((jid) => toJid(jid) === own);
+  }
+
+  // Quoted replies and template button taps carry the id of the message they answer
+  private quotedId(msg: proto.IWebMessageInfo): string | undefined {
+    const m = msg.message;
+    return (
+      m?.extendedTextMessage?.contextInfo?.stanzaId ??
+      m?.templateButtonReplyMessage?.contextInfo?.stanzaId ??
+      m?.buttonsResponseMessage?.contextInfo?.stanzaId ??
+      undefined
+    );
+  }
+
+  private extractReplyId(msg: proto.IWebMessageInfo): string | undefined {
+    return msg.message?.buttonsResponseMessage?.selectedButtonId ||
+           msg.message?.listResponseMessage?.singleSelectReply?.selectedRowId ||
+           msg.message?.templateButtonReplyMessage?.selectedId ||
+           undefined;
+  }
+
   private extractContent(msg: proto.IWebMessageInfo): string {
     return msg.message?.conversation ||
            msg.message?.extendedTextMessage?.text ||
+           msg.message?.buttonsResponseMessage?.selectedDisplayText ||
+           msg.message?.listResponseMessage?.title ||
+           msg.message?.templateButtonReplyMessage?.selectedDisplayText ||
            '';
   }
 }
@@ -0,0 +1,119 @@
+import type { MessageStatusUpdate } from '../events/types';
+import { systemClock, type Clock } from '../utils/clock';
+
+export type ConversationCategory = 'marketing' | 'utility' | 'authentication' | 'service' | 'referral_conversion';
+
+// WHATSAPP_PRICING: what one billable conversation (or message, under per-message pricing) costs
+export interface ConversationPricing {
+  currency?: string;                    // Default USD
+  rates?: Record<string, number>;      // Keyed by ConversationCategory, e.g. { marketing: 0.025 }
+}
+
+export interface CostRange {
+  from?: number;                        // Milliseconds since epoch, inclusive
+  to?: number;                          // Exclusive; default now
+}
+
+export interface CostBucket {
+  conversations: number;                // Charges opened: conversations, or messages under per-message pricing
+  billable: number;
+  cost: number;                         // billable x rate for the category
+}
+
+export interface ConversationCostReport {
+  currency: string;
+  from?: number;
+  to: number;
+  total: CostBucket;
+  byCategory: Record<string, CostBucket>;
+  byAccount: Record<string, CostBucket & { byCategory: Record<string, CostBucket> }>;
+}
+
+interface Charge {
+  accountId: string;
+  category: string;
+  billable: boolean;
+  timestamp: number;
+}
+
+// Charges older than this are dropped (WhatsApp invoices monthly); checked at most hourly
+const DEFAULT_RETENTION_MS = 35 * 24 * 60 * 60 * 1000;
+const PRUNE_INTERVAL_MS = 60 * 60 * 1000;
+
+const emptyBucket = (): CostBucket => ({ conversations: 0, billable: 0, cost: 0 });
+
+/**
+ * Tallies what delivery statuses say each conversation costs. Meta repeats the same `pricing` on
+ * every status of a conversation, so a charge is counted once per conversation id (or per message
+ * under per-message pricing). Rates are the operator's, so costs are estimates, not invoices.
+ */
+export class ConversationCostTracker {
+  private charges = new Map<string, Charge>();
+  private prunedAt = 0;
+
+  constructor(
+    private pricing: ConversationPricing = {},
+    private readonly clock: Clock = systemClock,
+    private readonly retentionMs = DEFAULT_RETENTION_MS
+  ) {}
+
+  usePricing(pricing: ConversationPricing): void {
+    this.pricing = pricing;
+  }
+
+  record(accountId: string, status: MessageStatusUpdate): void {
+    const { pricing } = status;
+    if (!pricing) return;
+    // Conversation-based pricing charges per conversation; per-message pricing per message
+    const key = pricing.model !== 'PMP' && status.conversation?.id
+      ? `${accountId}:conversation:${status.conversation.id}`
+      : `${accountId}:message:${status.messageId}`;
+    if (this.charges.has(key)) return;
+
+    this.prune();
+    this.charges.set(key, {
+      accountId,
+      category: pricing.category,
+      billable: pricing.billable,
+      timestamp: status.timestamp ? status.timestamp * 1000 : this.clock.now(),
+    });
+  }
+
+  summarize(range: CostRange = {}): ConversationCostReport {
+    const to = range.to ?? this.clock.now();
+    const report: ConversationCostReport = {
+      currency: this.pricing.currency ?? 'USD',
+      ...(range.from !== undefined ? { from: range.from } : {}),
+      to,
+      total: emptyBucket(),
+      byCategory: {},
+      byAccount: {},
+    };
+
+    for (const charge of this.charges.values()) {
+      if (charge.timestamp >= to || (range.from !== undefined && charge.timestamp < range.from)) continue;
+      const account = (report.byAccount[charge.accountId] ??= { ...emptyBucket(), byCategory: {} });
+      const buckets = [
+        report.total,
+        (report.byCategory[charge.category] ??= emptyBucket()),
+        account,
+        (account.byCategory[charge.category] ??= emptyBucket()),
+      ];
+      const cost = charge.billable ? this.pricing.rates?.[charge.category] ?? 0 : 0;
+      for (const bucket of buckets) {
+        bucket.conversations++;
+        if (charge.billable) bucket.billable++;
+        bucket.cost += cost;
+      }
+    }
+    return report;
+  }
+
+  private prune(now = this.clock.now()): void {
+    if (now - this.prunedAt < PRUNE_INTERVAL_MS) return;
+    this.prunedAt = now;
+    for (const [key, charge] of this.charges) {
+      if (charge.timestamp < now - this.retentionMs) this.charges.delete(key);
+    }
+  }
+}
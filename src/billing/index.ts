@@ -0,0 +1,8 @@
+export { ConversationCostTracker } from './costs';
+export type {
+  ConversationCategory,
+  ConversationCostReport,
+  ConversationPricing,
+  CostBucket,
+  CostRange,
+} from './costs';
@@ -0,0 +1,12 @@
+export {
+  senderOf,
+  toAttachments,
+  toMemory,
+  whatsappEntityId,
+  whatsappRoomId,
+  whatsappWorldId,
+} from './memory';
+export type { BridgeContext } from './memory';
+export { toOutboundMessages } from './response';
+export { ResponseStreamer, streamText } from './stream';
+export type { StreamOptions } from './stream';
@@ -0,0 +1,88 @@
+import {
+  type Content,
+  type Media,
+  type Memory,
+  type UUID,
+  ContentType,
+  stringToUuid,
+} from '@elizaos/core';
+import type { UnifiedMessage } from '../types';
+import { DEFAULT_ACCOUNT_ID } from '../accounts';
+
+const CONTENT_TYPES: Partial<Record<UnifiedMessage['type'], ContentType>> = {
+  image: ContentType.IMAGE,
+  audio: ContentType.AUDIO,
+  video: ContentType.VIDEO,
+  document: ContentType.DOCUMENT,
+};
+
+export interface BridgeContext {
+  agentId: UUID;
+  accountId: string;
+  group?: { id: string; systemPrompt?: string; skills?: string[] };
+  inlineMedia?: string;                 // data: URI of the attachment, when it was downloaded up front
+}
+
+// Sender of the message: the participant in groups, the chat itself in direct messages
+export function senderOf(msg: UnifiedMessage): string {
+  return msg.participant ?? msg.from;
+}
+
+export function whatsappEntityId(sender: string): UUID {
+  return stringToUuid(`whatsapp-entity-${sender}`);
+}
+
+// Rooms of additional accounts are scoped by account so the same chat on two numbers stays apart
+export function whatsappRoomId(agentId: UUID, accountId: string, chat: string): UUID {
+  const chatKey = accountId === DEFAULT_ACCOUNT_ID ? chat : `${accountId}-${chat}`;
+  return stringToUuid(`whatsapp-room-${chatKey}-${agentId}`);
+}
+
+export function whatsappWorldId(agentId: UUID): UUID {
+  return stringToUuid(`whatsapp-world-${agentId}`);
+}
+
+// Media on an inbound message as runtime attachments; `inlineUrl` replaces the transport's URL
+export function toAttachments(msg: UnifiedMessage, inlineUrl?: string): Media[] {
+  if (!msg.media) return [];
+  const contentType = CONTENT_TYPES[msg.type];
+  return [
+    {
+      id: msg.media.id,
+      url: inlineUrl ?? msg.media.url ?? '',
+      title: msg.media.fileName ?? msg.type,
+      source: 'whatsapp',
+      description: msg.media.mimeType ?? '',
+      text: msg.media.caption ?? '',
+      ...(contentType ? { contentType } : {}),
+    },
+  ];
+}
+
+// Build the runtime memory for an inbound message; `content` carries the text the agent should see
+export function toMemory(msg: UnifiedMessage, content: Content, context: BridgeContext): Memory {
+  const sender = senderOf(msg);
+  const attachments = toAttachments(msg, context.inlineMedia);
+  const { group } = context;
+
+  return {
+    id: stringToUuid(`whatsapp-msg-${msg.id}`),
+    agentId: context.agentId,
+    entityId: whatsappEntityId(sender),
+    roomId: whatsappRoomId(context.agentId, context.accountId, msg.from),
+    content: {
+      ...content,
+      ...(attachments.length > 0 ? { attachments } : {}),
+      source: 'whatsapp',
+      channelId: msg.from,
+      whatsappSender: sender,
+      whatsappAccount: context.accountId,
+      whatsappMessageId: msg.id,
+      ...(group
+        ? { whatsappGroup: { id: group.id, systemPrompt: group.systemPrompt, skills: group.skills } }
+        : {}),
+    },
+    createdAt: msg.timestamp ? msg.timestamp * 1000 : Date.now(),
+    metadata: { type: 'message', timestamp: Date.now(), scope: group ? 'shared' : 'private' },
+  };
+}
@@ -0,0 +1,14 @@
+import type { Content } from '@elizaos/core';
+import type { WhatsAppMessage } from '../types';
+import { chunkTextWithMarkers, MAX_TEXT_LENGTH, type ChunkMarkers } from '../utils/chunk';
+
+// Agent response as WhatsApp text messages, split to fit the per-message limit and marked when split
+export function toOutboundMessages(
+  to: string,
+  content: Content,
+  limit = MAX_TEXT_LENGTH,
+  markers?: ChunkMarkers
+): WhatsAppMessage[] {
+  if (!content.text) return [];
+  return chunkTextWithMarkers(content.text, limit, markers).map((chunk) => ({ type: 'text', to, content: chunk }));
+}
@@ -0,0 +1,108 @@
+import { chunkText, MAX_TEXT_LENGTH } from '../utils/chunk';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface StreamOptions {
+  limit?: number;                       // Max characters per message (default 4096)
+  minChars?: number;                    // Don't flush before this much text has built up (default 200)
+  minIntervalMs?: number;               // Gap kept between sends to the same recipient (default 1000)
+  placeholder?: string;                 // Sent once when the stream starts, e.g. "…"
+  closing?: string;                     // Ends the last message after a blank line, e.g. a reply footer
+}
+
+// Last position in the buffer where a message could end cleanly: a paragraph or sentence end
+function boundary(text: string): number {
+  const paragraph = text.lastIndexOf('\n\n');
+  let sentence = -1;
+  for (const match of text.matchAll(/[.!?…](?=\s)/g)) {
+    sentence = (match.index ?? 0) + 1;
+  }
+  return Math.max(paragraph, sentence);
+}
+
+/**
+ * Turns streamed LLM tokens into a series of WhatsApp messages, flushing on sentence or
+ * paragraph boundaries so users see progress on long answers.
+ */
+export class ResponseStreamer {
+  private buffer = '';
+  private queue: Promise<void> = Promise.resolve();
+  private lastSentAt = 0;
+  private started = false;
+
+  constructor(
+    private readonly send: (text: string) => Promise<unknown>,
+    private readonly options: StreamOptions = {},
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  push(token: string): void {
+    if (!this.started) {
+      this.started = true;
+      if (this.options.placeholder) this.enqueue(this.options.placeholder);
+    }
+
+    this.buffer += token;
+    const limit = this.options.limit ?? MAX_TEXT_LENGTH;
+
+    // Over the limit: send full chunks and keep the untouched tail for more tokens
+    if (this.buffer.length > limit) {
+      while (this.buffer.length > limit) {
+        const [chunk] = chunkText(this.buffer, limit);
+        if (!chunk) {
+          this.buffer = '';
+          break;
+        }
+        this.buffer = this.buffer.slice(this.buffer.indexOf(chunk) + chunk.length);
+        this.enqueue(chunk);
+      }
+      return;
+    }
+
+    if (this.buffer.length < (this.options.minChars ?? 200)) return;
+    const end = boundary(this.buffer);
+    if (end > 0) {
+      this.enqueue(this.buffer.slice(0, end));
+      this.buffer = this.buffer.slice(end);
+    }
+  }
+
+  // Send whatever is left and wait for every queued message to go out
+  async end(): Promise<void> {
+    const chunks = chunkText(this.buffer, this.options.limit ?? MAX_TEXT_LENGTH);
+    const closing = this.started ? this.options.closing : undefined;
+    // The closing goes on the last chunk, or on its own when everything already went out
+    const last = closing ? (chunks.pop()?.trim() ?? '') : undefined;
+    for (const chunk of chunks) {
+      this.enqueue(chunk);
+    }
+    if (closing) this.enqueue(last ? `${last}\n\n${closing}` : closing);
+    this.buffer = '';
+    await this.queue;
+  }
+
+  private enqueue(text: string): void {
+    const trimmed = text.trim();
+    if (!trimmed) return;
+
+    this.queue = this.queue.then(async () => {
+      const wait = this.lastSentAt + (this.options.minIntervalMs ?? 1000) - this.clock.now();
+      if (this.lastSentAt > 0 && wait > 0) await new Promise((resolve) => this.clock.setTimeout(resolve, wait));
+      await this.send(trimmed);
+      this.lastSentAt = this.clock.now();
+    });
+  }
+}
+
+// Consume a token stream and deliver it as messages
+export async function streamText(
+  tokens: AsyncIterable<string>,
+  send: (text: string) => Promise<unknown>,
+  options?: StreamOptions,
+  clock?: Clock
+): Promise<void> {
+  const streamer = new ResponseStreamer(send, options, clock);
+  for await (const token of tokens) {
+    streamer.push(token);
+  }
+  await streamer.end();
+}
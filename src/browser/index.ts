@@ -0,0 +1,41 @@
+// Dependency-free helpers published as "@elizaos/plugin-whatsapp/browser", so config editors and
+// other browser code share the plugin's exact normalization, chunking and payload rules. Nothing
+// reachable from here may import Node built-ins, axios, Baileys or @elizaos/core.
+export {
+  canonicalChatId,
+  isGroupJid,
+  isNewsletterJid,
+  isUserJid,
+  normalizeE164,
+  normalizePhoneDigits,
+  normalizeWhatsAppTarget,
+  toJid,
+} from '../utils/normalize';
+export type { WhatsAppTarget, WhatsAppTargetKind } from '../utils/normalize';
+export { groupInviteLink, isGroupInviteLink, parseGroupInviteLink } from '../utils/invite-link';
+export { chunkText, chunkTextWithMarkers, MAX_TEXT_LENGTH } from '../utils/chunk';
+export type { ChunkMarkers } from '../utils/chunk';
+export { CALLING_CODE_TIME_ZONES, inferTimeZone } from '../utils/timezone';
+export { EMOJI_SHORTCODES, emojiForShortcode, expandShortcodes, normalizeReaction } from '../utils/emoji';
+export { validateMessage, validatePhoneNumber, validateTemplate } from '../utils/validators';
+export { fromCloudPayload, toCloudPayload } from '../clients/cloud-payload';
+export {
+  AddressMessageBuilder,
+  ButtonMessageBuilder,
+  CtaUrlMessageBuilder,
+  FlowMessageBuilder,
+  INTERACTIVE_LIMITS,
+  ListMessageBuilder,
+  ListSectionBuilder,
+} from '../interactive/builders';
+export { formatAddress, parseAddressSubmission } from '../interactive/address';
+export {
+  buildAuthenticationTemplate,
+  buildCarouselTemplate,
+  buildTemplate,
+  templateParameters,
+} from '../templates/builder';
+export type { CarouselCardInput, CarouselInput, TemplateInput, TemplateValues } from '../templates/builder';
+export { buildOrderDetails, buildOrderStatus } from '../payments/payloads';
+export { WhatsAppError } from '../errors';
+export type { AddressSubmission, WhatsAppInteractive, WhatsAppMedia, WhatsAppMessage, WhatsAppTemplate } from '../types';
@@ -0,0 +1,2 @@
+export * from './types';
+export { buildCallPermissionRequest, buildCallActionPayload, parseCallEvents } from './payloads';
@@ -0,0 +1,98 @@
+import type { WhatsAppMessage, WhatsAppWebhookEvent } from '../types';
+import type { CallAction, CallActionOptions, CallEvent } from './types';
+import { WhatsAppError } from '../errors';
+
+// Ask a user for permission to call them (required before business-initiated calls)
+export function buildCallPermissionRequest(to: string, body: string): WhatsAppMessage {
+  return {
+    type: 'interactive',
+    to,
+    content: {
+      type: 'call_permission_request',
+      body: { text: body },
+      action: { name: 'call_permission_request' },
+    },
+  };
+}
+
+export function buildCallActionPayload(action: CallAction, options: CallActionOptions) {
+  if (action === 'connect' && !options.callId && !options.to) {
+    throw new WhatsAppError('INVALID_CALL', 'Calling a user requires a recipient');
+  }
+  if (action !== 'connect' && !options.callId) {
+    throw new WhatsAppError('INVALID_CALL', `Call action "${action}" requires a call id`);
+  }
+  if ((action === 'connect' || action === 'pre_accept' || action === 'accept') && !options.session) {
+    throw new WhatsAppError('INVALID_CALL', `Call action "${action}" requires an SDP session`);
+  }
+
+  return {
+    messaging_product: 'whatsapp',
+    action,
+    ...(options.to ? { to: options.to } : {}),
+    ...(options.callId ? { call_id: options.callId } : {}),
+    ...(options.session
+      ? { session: { sdp_type: options.session.sdpType, sdp: options.session.sdp } }
+      : {}),
+  };
+}
+
+function toNumber(value: string | undefined): number | undefined {
+  return value === undefined ? undefined : Number(value);
+}
+
+// Extract call lifecycle events from a webhook payload
+export function parseCallEvents(event: WhatsAppWebhookEvent): CallEvent[] {
+  const events: CallEvent[] = [];
+
+  for (const entry of event.entry ?? []) {
+    for (const change of entry.changes ?? []) {
+      for (const call of change.value?.calls ?? []) {
+        const timestamp = Number(call.timestamp);
+        if (call.event === 'connect') {
+          events.push({
+            kind: 'connect',
+            callId: call.id,
+            from: call.from,
+            to: call.to,
+            direction: call.direction as 'USER_INITIATED' | 'BUSINESS_INITIATED' | undefined,
+            timestamp,
+            ...(call.session
+              ? {
+                  session: {
+                    sdpType: call.session.sdp_type as 'offer' | 'answer',
+                    sdp: call.session.sdp,
+                  },
+                }
+              : {}),
+          });
+        } else if (call.event === 'terminate') {
+          events.push({
+            kind: 'terminate',
+            callId: call.id,
+            from: call.from,
+            to: call.to,
+            timestamp,
+            status: call.status,
+            durationSeconds: call.duration,
+            startTime: toNumber(call.start_time),
+            endTime: toNumber(call.end_time),
+          });
+        }
+      }
+
+      for (const status of change.value?.statuses ?? []) {
+        if (status.type !== 'call') continue;
+        events.push({
+          kind: 'status',
+          callId: status.id,
+          recipientId: status.recipient_id,
+          status: status.status,
+          timestamp: Number(status.timestamp),
+        });
+      }
+    }
+  }
+
+  return events;
+}
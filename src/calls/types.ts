@@ -0,0 +1,44 @@
+export type CallAction = 'connect' | 'pre_accept' | 'accept' | 'reject' | 'terminate';
+
+export interface CallSession {
+  sdpType: 'offer' | 'answer';
+  sdp: string;
+}
+
+export interface CallConnectEvent {
+  kind: 'connect';
+  callId: string;
+  from: string;
+  to: string;
+  direction?: 'USER_INITIATED' | 'BUSINESS_INITIATED';
+  timestamp: number;
+  session?: CallSession;
+}
+
+export interface CallTerminateEvent {
+  kind: 'terminate';
+  callId: string;
+  from: string;
+  to: string;
+  timestamp: number;
+  status?: string;            // e.g. COMPLETED, FAILED
+  durationSeconds?: number;
+  startTime?: number;
+  endTime?: number;
+}
+
+export interface CallStatusEvent {
+  kind: 'status';
+  callId: string;
+  recipientId: string;
+  status: string;             // RINGING, ACCEPTED, REJECTED
+  timestamp: number;
+}
+
+export type CallEvent = CallConnectEvent | CallTerminateEvent | CallStatusEvent;
+
+export interface CallActionOptions {
+  to?: string;                // Required for business-initiated connect
+  callId?: string;            // Required for every action on an existing call
+  session?: CallSession;
+}
@@ -0,0 +1,2 @@
+export { CannedResponses, InMemoryCannedResponseStore, RuntimeCannedResponseStore } from './responses';
+export type { CannedResponse, CannedResponseStore } from './responses';
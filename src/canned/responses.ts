@@ -0,0 +1,140 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { WhatsAppMedia, WhatsAppMessage, WhatsAppTemplate } from '../types';
+import { WhatsAppError } from '../errors';
+
+// Content an operator can send by name
+export type CannedResponse =
+  | { kind: 'text'; text: string }
+  | { kind: 'media'; media: WhatsAppMedia }
+  | { kind: 'template'; template: WhatsAppTemplate };
+
+export interface CannedResponseStore {
+  get(name: string): Promise<CannedResponse | undefined>;
+  set(name: string, response: CannedResponse): Promise<void>;
+  delete(name: string): Promise<boolean>;
+  entries(): Promise<Array<[string, CannedResponse]>>;
+}
+
+export class InMemoryCannedResponseStore implements CannedResponseStore {
+  private responses = new Map<string, CannedResponse>();
+
+  async get(name: string): Promise<CannedResponse | undefined> {
+    return this.responses.get(name);
+  }
+
+  async set(name: string, response: CannedResponse): Promise<void> {
+    this.responses.set(name, response);
+  }
+
+  async delete(name: string): Promise<boolean> {
+    return this.responses.delete(name);
+  }
+
+  async entries(): Promise<Array<[string, CannedResponse]>> {
+    return [...this.responses.entries()];
+  }
+}
+
+// All responses under one runtime cache key, shared by every account
+export class RuntimeCannedResponseStore implements CannedResponseStore {
+  private static readonly KEY = 'whatsapp-canned-responses';
+
+  constructor(private runtime: IAgentRuntime) {}
+
+  async get(name: string): Promise<CannedResponse | undefined> {
+    return (await this.load())[name];
+  }
+
+  async set(name: string, response: CannedResponse): Promise<void> {
+    const all = await this.load();
+    all[name] = response;
+    await this.runtime.setCache(RuntimeCannedResponseStore.KEY, all);
+  }
+
+  async delete(name: string): Promise<boolean> {
+    const all = await this.load();
+    if (!(name in all)) return false;
+    delete all[name];
+    await this.runtime.setCache(RuntimeCannedResponseStore.KEY, all);
+    return true;
+  }
+
+  async entries(): Promise<Array<[string, CannedResponse]>> {
+    return Object.entries(await this.load());
+  }
+
+  private async load(): Promise<Record<string, CannedResponse>> {
+    return (await this.runtime.getCache<Record<string, CannedResponse>>(RuntimeCannedResponseStore.KEY)) ?? {};
+  }
+}
+
+// Names are matched case-insensitively, so "Opening-Hours" and "opening-hours" are the same entry
+function normalizeName(name: string): string {
+  return name.trim().toLowerCase();
+}
+
+function validate(response: CannedResponse): void {
+  switch (response?.kind) {
+    case 'text':
+      if (!response.text) throw new WhatsAppError('INVALID_CANNED_RESPONSE', 'A text response needs text');
+      return;
+    case 'media':
+      if (!response.media?.url && !response.media?.id) throw new WhatsAppError('INVALID_CANNED_RESPONSE', 'A media response needs a url or id');
+      return;
+    case 'template':
+      if (!response.template?.name) throw new WhatsAppError('INVALID_CANNED_RESPONSE', 'A template response needs a template name');
+      return;
+    default:
+      throw new WhatsAppError('INVALID_CANNED_RESPONSE', `Unknown canned response kind "${(response as { kind?: string })?.kind}"`);
+  }
+}
+
+export class CannedResponses {
+  private store: CannedResponseStore;
+
+  constructor(store?: CannedResponseStore) {
+    this.store = store ?? new InMemoryCannedResponseStore();
+  }
+
+  useStore(store: CannedResponseStore): void {
+    this.store = store;
+  }
+
+  async get(name: string): Promise<CannedResponse | undefined> {
+    return this.store.get(normalizeName(name));
+  }
+
+  async set(name: string, response: CannedResponse): Promise<void> {
+    const key = normalizeName(name);
+    if (!key) throw new WhatsAppError('INVALID_CANNED_RESPONSE', 'A canned response needs a name');
+    validate(response);
+    await this.store.set(key, response);
+  }
+
+  async remove(name: string): Promise<boolean> {
+    return this.store.delete(normalizeName(name));
+  }
+
+  async list(): Promise<Array<{ name: string; response: CannedResponse }>> {
+    const entries = await this.store.entries();
+    return entries
+      .map(([name, response]) => ({ name, response }))
+      .sort((a, b) => a.name.localeCompare(b.name));
+  }
+
+  // Build the message for a named response; throws when it is not registered
+  async toMessage(name: string, to: string): Promise<WhatsAppMessage> {
+    const response = await this.get(name);
+    if (!response) {
+      throw new WhatsAppError('NOT_FOUND', `Unknown canned response "${name}"`);
+    }
+    switch (response.kind) {
+      case 'text':
+        return { type: 'text', to, content: response.text };
+      case 'media':
+        return { type: 'media', to, content: response.media };
+      case 'template':
+        return { type: 'template', to, content: response.template };
+    }
+  }
+}
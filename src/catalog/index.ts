@@ -0,0 +1,8 @@
+export {
+  buildProductListMessage,
+  buildProductMessage,
+  PRODUCT_LIST_LIMITS,
+  ProductCatalog,
+  toProductSummary,
+} from './products';
+export type { ProductCatalogPage, ProductRecord, ProductSummary } from './products';
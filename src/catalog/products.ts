@@ -0,0 +1,128 @@
+import { WhatsAppError } from '../errors';
+import type { WhatsAppInteractive } from '../types';
+import { systemClock, type Clock } from '../utils/clock';
+
+// Product as returned by GET /{catalog-id}/products
+export interface ProductRecord {
+  id: string;
+  retailer_id: string;                  // The id product messages refer to
+  name: string;
+  description?: string;
+  price?: string;                       // Formatted, e.g. "₹150.00"
+  currency?: string;
+  availability?: string;                // in stock, out of stock, ...
+  url?: string;
+  image_url?: string;
+}
+
+export interface ProductSummary {
+  retailerId: string;
+  name: string;
+  price?: string;
+  available: boolean;
+}
+
+export interface ProductCatalogPage {
+  catalogId: string;
+  products: ProductRecord[];
+}
+
+// Cloud API limits for product list messages
+export const PRODUCT_LIST_LIMITS = { sections: 10, products: 30 } as const;
+
+const DEFAULT_TTL_MS = 10 * 60 * 1000;
+
+export function toProductSummary(record: ProductRecord): ProductSummary {
+  return {
+    retailerId: record.retailer_id,
+    name: record.name,
+    ...(record.price ? { price: record.price } : {}),
+    available: (record.availability ?? 'in stock').toLowerCase() === 'in stock',
+  };
+}
+
+// Single product message, e.g. in reply to "do you have masala tea?"
+export function buildProductMessage(catalogId: string, retailerId: string, body: string, footer?: string): WhatsAppInteractive {
+  return {
+    type: 'product',
+    body: { text: body },
+    ...(footer ? { footer: { text: footer } } : {}),
+    action: { catalog_id: catalogId, product_retailer_id: retailerId },
+  };
+}
+
+// Multi-product message; sections group retailer ids under a title. Needs a header and body.
+export function buildProductListMessage(
+  catalogId: string,
+  header: string,
+  body: string,
+  sections: Array<{ title: string; retailerIds: string[] }>
+): WhatsAppInteractive {
+  const count = sections.reduce((n, s) => n + s.retailerIds.length, 0);
+  if (count === 0) {
+    throw new WhatsAppError('INTERACTIVE_LIMIT', 'A product list needs at least one product');
+  }
+  if (sections.length > PRODUCT_LIST_LIMITS.sections || count > PRODUCT_LIST_LIMITS.products) {
+    throw new WhatsAppError(
+      'INTERACTIVE_LIMIT',
+      `A product list allows ${PRODUCT_LIST_LIMITS.sections} sections and ${PRODUCT_LIST_LIMITS.products} products`
+    );
+  }
+  return {
+    type: 'product_list',
+    header: { type: 'text', text: header },
+    body: { text: body },
+    action: {
+      catalog_id: catalogId,
+      sections: sections.map((s) => ({
+        title: s.title,
+        product_items: s.retailerIds.map((id) => ({ product_retailer_id: id })),
+      })),
+    },
+  };
+}
+
+// Caches the business catalog; products change rarely but are read for every agent turn. An
+// account without a catalog caches as empty and a failed fetch is remembered for the same TTL, so
+// neither costs a Graph call per turn.
+export class ProductCatalog {
+  private cached?: { catalogId: string; products: ProductSummary[]; expiresAt: number };
+  private failed?: { error: unknown; expiresAt: number };
+  private pending?: Promise<{ catalogId: string; products: ProductSummary[] }>;
+
+  constructor(
+    private fetch: () => Promise<ProductCatalogPage>,
+    private ttlMs = DEFAULT_TTL_MS,
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  async list(): Promise<{ catalogId: string; products: ProductSummary[] }> {
+    const now = this.clock.now();
+    if (this.cached && this.cached.expiresAt > now) {
+      return this.cached;
+    }
+    if (this.failed && this.failed.expiresAt > now) {
+      throw this.failed.error;
+    }
+    this.pending ??= this.fetch()
+      .catch((error): ProductCatalogPage => {
+        if (error instanceof WhatsAppError && error.code === 'NOT_FOUND') return { catalogId: '', products: [] };
+        this.failed = { error, expiresAt: this.clock.now() + this.ttlMs };
+        throw error;
+      })
+      .then(({ catalogId, products }) => {
+        this.cached = { catalogId, products: products.map(toProductSummary), expiresAt: this.clock.now() + this.ttlMs };
+        this.failed = undefined;
+        return this.cached;
+      })
+      .finally(() => {
+        this.pending = undefined;
+      });
+    return this.pending;
+  }
+
+  invalidate(): void {
+    this.cached = undefined;
+    this.failed = undefined;
+  }
+}
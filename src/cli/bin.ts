@@ -0,0 +1,8 @@
+#!/usr/bin/env node
+import { runCli } from './index';
+
+runCli(process.argv.slice(2), { out: (line) => console.log(line), err: (line) => console.error(line) }).then(
+  (code) => {
+    process.exitCode = code;
+  }
+);
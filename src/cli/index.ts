@@ -0,0 +1,262 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import axios from 'axios';
+import { randomBytes } from 'crypto';
+import { writeFile } from 'fs/promises';
+import { parseArgs } from 'util';
+import { CloudAPIClient } from '../clients/cloud-api-client';
+import { DEFAULT_ACCOUNT_ID, resolveWhatsAppAccount, validateDeployment } from '../accounts';
+import { ADMIN_PATH } from '../admin/routes';
+import { WEBHOOK_PATH } from '../http/webhook';
+import { WhatsAppError } from '../errors';
+import type { CloudAPIConfig, WhatsAppMedia, WhatsAppMessage, WhatsAppTemplateParameter } from '../types';
+import { detectAuthMethod } from '../utils/config-detector';
+
+export interface CliIO {
+  out(line: string): void;
+  err(line: string): void;
+  env?: Record<string, string | undefined>;                 // Default process.env
+  client?: (config: CloudAPIConfig) => CloudAPIClient;     // Swapped out in tests
+}
+
+const USAGE = `Usage: whatsapp-cli <command> [options]
+
+Commands:
+  doctor [--live]                                 Check configuration; --live also calls the Graph API
+  send text <to> <text>                           Send a text message
+  send media <to> <image|video|audio|document> <url|media-id> [--caption c] [--filename f]
+  send template <to> <name> [--language en_US] [--param value]...
+  templates list                                  Message templates of the business account
+  media download <media-id> [--out file]          Save received media (to stdout without --out)
+  webhook verify-challenge [--url u] [--token t]  Run Meta's subscription handshake against a URL
+  webhook list --server <url>                     Webhooks stored by a running agent
+  webhook replay <id> --server <url>              Handle a stored webhook again
+
+Options:
+  --account <id>   Account to use (default "default")
+
+Configuration is read from the same WHATSAPP_* environment variables as the plugin.`;
+
+const OPTIONS = {
+  account: { type: 'string' },
+  live: { type: 'boolean' },
+  caption: { type: 'string' },
+  filename: { type: 'string' },
+  language: { type: 'string' },
+  param: { type: 'string', multiple: true },
+  out: { type: 'string' },
+  url: { type: 'string' },
+  token: { type: 'string' },
+  server: { type: 'string' },
+  help: { type: 'boolean', short: 'h' },
+} as const;
+
+interface Options {
+  account?: string;
+  live?: boolean;
+  caption?: string;
+  filename?: string;
+  language?: string;
+  param?: string[];
+  out?: string;
+  url?: string;
+  token?: string;
+  server?: string;
+  help?: boolean;
+}
+
+// Settings come from the environment only, read through the same resolution code as the plugin
+function envRuntime(env: Record<string, string | undefined>): IAgentRuntime {
+  return { getSetting: (key: string) => env[key] ?? null, character: { settings: {} } } as unknown as IAgentRuntime;
+}
+
+/**
+ * Command-line companion for operating an account without a running agent: validate configuration,
+ * send messages, inspect templates and media, and check or replay webhooks. Resolves to the exit code.
+ */
+export async function runCli(argv: string[], io: CliIO): Promise<number> {
+  let parsed: { values: Options; positionals: string[] };
+  try {
+    parsed = parseArgs({ args: argv, options: OPTIONS, allowPositionals: true }) as typeof parsed;
+  } catch (error) {
+    io.err((error as Error).message);
+    io.err(USAGE);
+    return 2;
+  }
+  const { values, positionals } = parsed;
+  const [command, sub, ...rest] = positionals;
+  if (values.help || !command) {
+    io.out(USAGE);
+    return command || values.help ? 0 : 2;
+  }
+
+  const env = io.env ?? process.env;
+  const runtime = envRuntime(env);
+  try {
+    switch (`${command} ${sub ?? ''}`.trim()) {
+      case 'doctor':
+        return await doctor(runtime, values, io);
+      case 'send text':
+      case 'send media':
+      case 'send template': {
+        const client = cloudClient(runtime, values, io);
+        const response = await client.sendMessage(outboundMessage(sub, rest, values));
+        io.out(JSON.stringify(response, null, 2));
+        return 0;
+      }
+      case 'templates list': {
+        for (const t of await cloudClient(runtime, values, io).listTemplates()) {
+          io.out(`${t.name}\t${t.language}\t${t.status}\t${t.category}`);
+        }
+        return 0;
+      }
+      case 'media download':
+        return await downloadMedia(cloudClient(runtime, values, io), rest[0], values, io);
+      case 'webhook verify-challenge':
+        return await verifyChallenge(runtime, values, io);
+      case 'webhook list':
+      case 'webhook replay':
+        return await storedWebhooks(sub, rest[0], env, values, io);
+      default:
+        io.err(`Unknown command: ${positionals.join(' ')}`);
+        io.err(USAGE);
+        return 2;
+    }
+  } catch (error) {
+    const code = (error as WhatsAppError).code;
+    io.err(`${code ? `${code}: ` : ''}${(error as Error).message}`);
+    return 1;
+  }
+}
+
+async function doctor(runtime: IAgentRuntime, values: Options, io: CliIO): Promise<number> {
+  const report = await validateDeployment(runtime, { live: values.live });
+  io.out(`Accounts: ${report.accounts.join(', ') || 'none'}`);
+  for (const issue of report.issues) {
+    const where = issue.accountId ? ` [${issue.accountId}]` : '';
+    io.out(`${issue.severity.toUpperCase()}${where} ${issue.code}: ${issue.message}`);
+  }
+  io.out(report.ok ? 'OK' : 'Configuration has errors');
+  return report.ok ? 0 : 1;
+}
+
+// Cloud API client for --account; Baileys sessions need the running agent, which owns the socket
+function cloudClient(runtime: IAgentRuntime, values: Options, io: CliIO): CloudAPIClient {
+  const accountId = values.account ?? DEFAULT_ACCOUNT_ID;
+  const config = resolveWhatsAppAccount(runtime, accountId);
+  if (!config) {
+    throw new WhatsAppError('INVALID_CONFIG', `Account ${accountId} has no credentials`);
+  }
+  if (detectAuthMethod(config) !== 'cloudapi') {
+    throw new WhatsAppError(
+      'INVALID_CONFIG',
+      `Account ${accountId} uses Baileys; the CLI only drives Cloud API accounts`
+    );
+  }
+  return (io.client ?? ((c) => new CloudAPIClient(c)))(config as CloudAPIConfig);
+}
+
+function required(value: string | undefined, name: string): string {
+  if (!value) throw new WhatsAppError('INVALID_ARGUMENT', `Missing <${name}>`);
+  return value;
+}
+
+function outboundMessage(kind: string, args: string[], values: Options): WhatsAppMessage {
+  const to = required(args[0], 'to');
+  if (kind === 'text') {
+    return { type: 'text', to, content: required(args.slice(1).join(' '), 'text') };
+  }
+  if (kind === 'media') {
+    const mediaKind = required(args[1], 'kind') as WhatsAppMedia['kind'];
+    if (!['image', 'video', 'audio', 'document'].includes(mediaKind)) {
+      throw new WhatsAppError('INVALID_ARGUMENT', `Unknown media kind "${mediaKind}"`);
+    }
+    const source = required(args[2], 'url|media-id');
+    return {
+      type: 'media',
+      to,
+      content: {
+        kind: mediaKind,
+        ...(/^https?:\/\//.test(source) ? { url: source } : { id: source }),
+        ...(values.caption ? { caption: values.caption } : {}),
+        ...(values.filename ? { fileName: values.filename } : {}),
+      },
+    };
+  }
+  const parameters: WhatsAppTemplateParameter[] = (values.param ?? []).map((text) => ({ type: 'text', text }));
+  return {
+    type: 'template',
+    to,
+    content: {
+      name: required(args[1], 'name'),
+      language: { code: values.language ?? 'en_US' },
+      ...(parameters.length > 0 ? { components: [{ type: 'body', parameters }] } : {}),
+    },
+  };
+}
+
+async function downloadMedia(
+  client: CloudAPIClient,
+  mediaId: string | undefined,
+  values: Options,
+  io: CliIO
+): Promise<number> {
+  const media = await client.downloadMedia(required(mediaId, 'media-id'));
+  if (values.out) {
+    await writeFile(values.out, media.data);
+    io.out(`Saved ${media.data.byteLength} bytes${media.mimeType ? ` (${media.mimeType})` : ''} to ${values.out}`);
+  } else {
+    process.stdout.write(media.data);
+  }
+  return 0;
+}
+
+// Send the GET Meta sends when subscribing a callback URL and check the challenge comes back
+async function verifyChallenge(runtime: IAgentRuntime, values: Options, io: CliIO): Promise<number> {
+  const account = resolveWhatsAppAccount(runtime, values.account ?? DEFAULT_ACCOUNT_ID);
+  const token = values.token ?? (account && 'webhookVerifyToken' in account ? account.webhookVerifyToken : undefined);
+  if (!token) {
+    throw new WhatsAppError('INVALID_ARGUMENT', 'No verify token; pass --token or set WHATSAPP_WEBHOOK_VERIFY_TOKEN');
+  }
+  const url = values.url ?? `http://localhost:3000${WEBHOOK_PATH}`;
+  const challenge = randomBytes(8).toString('hex');
+  const response = await axios.get(url, {
+    params: { 'hub.mode': 'subscribe', 'hub.verify_token': token, 'hub.challenge': challenge },
+    responseType: 'text',
+    validateStatus: () => true,
+  });
+  if (response.status === 200 && String(response.data).trim() === challenge) {
+    io.out(`OK: ${url} echoed the challenge`);
+    return 0;
+  }
+  io.err(`FAILED: ${url} answered ${response.status}; Meta would not accept this callback URL`);
+  return 1;
+}
+
+// Stored webhooks live in the agent's runtime cache, so these go through its admin API
+async function storedWebhooks(
+  action: string,
+  id: string | undefined,
+  env: Record<string, string | undefined>,
+  values: Options,
+  io: CliIO
+): Promise<number> {
+  const server = required(values.server ?? env.WHATSAPP_ADMIN_URL, 'server');
+  const apiKey = env.WHATSAPP_ADMIN_API_KEY;
+  if (!apiKey) throw new WhatsAppError('INVALID_CONFIG', 'Set WHATSAPP_ADMIN_API_KEY to reach the admin API');
+  const admin = axios.create({
+    baseURL: `${server.replace(/\/+$/, '')}${ADMIN_PATH}`,
+    headers: { Authorization: `Bearer ${apiKey}` },
+  });
+  if (action === 'list') {
+    const { data } = await admin.get<{ webhooks: Array<{ id: string; receivedAt: number; accountId?: string }> }>(
+      '/webhooks'
+    );
+    for (const hook of data.webhooks) {
+      io.out(`${hook.id}\t${new Date(hook.receivedAt).toISOString()}\t${hook.accountId ?? '-'}`);
+    }
+    return 0;
+  }
+  await admin.post(`/webhooks/${encodeURIComponent(required(id, 'id'))}/replay`);
+  io.out(`Replayed ${id}`);
+  return 0;
+}
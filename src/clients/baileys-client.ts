@@ -1,10 +1,26 @@
 import { EventEmitter } from 'events';
-import type { IWhatsAppClient } from './interface';
+import { downloadMediaMessage } from '@whiskeysockets/baileys';
+import pino from 'pino';
+import type {
+  DownloadedMedia,
+  DownloadMediaOptions,
+  GroupInviteInfo,
+  InboundMessageRef,
+  IWhatsAppClient,
+  NewsletterInfo,
+} from './interface';
 import type { BaileysConfig, WhatsAppMessage, ConnectionStatus } from '../types';
+import type { ContactCheckResult } from '../contacts';
 import { BaileysAuthManager } from '../baileys/auth';
 import { BaileysConnection } from '../baileys/connection';
 import { QRCodeGenerator } from '../baileys/qr-code';
 import { MessageAdapter } from '../baileys/message-adapter';
+import { toJid } from '../utils/normalize';
+import { WhatsAppError } from '../errors';
+import { systemClock, type Clock } from '../utils/clock';
+
+// Inbound media messages kept for downloading and forwarding
+const MEDIA_CACHE_SIZE = 200;
 
 export class BaileysClient extends EventEmitter implements IWhatsAppClient {
   private config: BaileysConfig;
@@ -12,14 +28,15 @@ export class BaileysClient extends EventEmitter implements IWhatsAppClient {
   private connection: BaileysConnection;
   private qrGenerator: QRCodeGenerator;
   private adapter: MessageAdapter;
+  private mediaMessages = new Map<string, any>();
 
-  constructor(config: BaileysConfig) {
+  constructor(config: BaileysConfig, clock: Clock = systemClock) {
     super();
     this.config = config;
     this.authManager = new BaileysAuthManager(config.authDir);
     this.connection = new BaileysConnection(this.authManager);
     this.qrGenerator = new QRCodeGenerator();
-    this.adapter = new MessageAdapter();
+    this.adapter = new MessageAdapter(clock);
 
     this.setupEventForwarding();
   }
@@ -53,13 +70,28 @@ export class BaileysClient extends EventEmitter implements IWhatsAppClient {
     // Messages
     this.connection.on('messages', (messages: any[]) => {
       for (const msg of messages) {
+        if (msg.key.fromMe) this.adapter.rememberOwn(msg.key.id);
         if (!msg.key.fromMe && msg.message) {
-          const unified = this.adapter.toUnified(msg);
+          const unsupported = this.adapter.unsupportedType(msg);
+          if (unsupported) {
+            this.emit('unsupported', this.adapter.toUnsupported(msg, unsupported));
+            continue;
+          }
+          const unified = this.adapter.toUnified(msg, this.connection.getSocket()?.user?.id);
+          if (unified.media) this.rememberMedia(unified.media.id, msg);
           this.emit('message', unified);
         }
       }
     });
 
+    // Delivery receipts
+    this.connection.on('updates', (updates: any[]) => {
+      for (const update of updates) {
+        const status = this.adapter.toStatus(update);
+        if (status) this.emit('status', status);
+      }
+    });
+
     // Error forwarding
     this.connection.on('error', (err: Error) => {
       this.emit('error', err);
@@ -75,16 +107,126 @@ export class BaileysClient extends EventEmitter implements IWhatsAppClient {
   }
 
   async sendMessage(message: WhatsAppMessage): Promise<any> {
+    const socket = this.requireSocket();
+    const content = this.adapter.toBaileys(message);
+    const sent = await socket.sendMessage(toJid(message.to), content, this.adapter.sendOptions(message));
+    this.adapter.rememberOwn(sent?.key?.id);
+    return sent;
+  }
+
+  // Media is downloaded from the original message, so keep the most recent ones around
+  private rememberMedia(id: string, msg: any): void {
+    this.mediaMessages.set(id, msg);
+    if (this.mediaMessages.size > MEDIA_CACHE_SIZE) {
+      this.mediaMessages.delete(this.mediaMessages.keys().next().value as string);
+    }
+  }
+
+  async downloadMedia(mediaId: string, { maxBytes }: DownloadMediaOptions = {}): Promise<DownloadedMedia> {
+    const msg = this.mediaMessages.get(mediaId);
+    if (!msg) {
+      throw new WhatsAppError('NOT_FOUND', `Media ${mediaId} is no longer available`);
+    }
+    const m = msg.message;
+    const media = m?.imageMessage ?? m?.videoMessage ?? m?.audioMessage ?? m?.documentMessage;
+    const tooLarge = (size: number) =>
+      new WhatsAppError('MEDIA_TOO_LARGE', `Media ${mediaId} is ${size} bytes, over the ${maxBytes} byte limit`);
+    const size = Number(media?.fileLength ?? 0);
+    if (maxBytes !== undefined && size > maxBytes) throw tooLarge(size);
+    // Expired media links are re-requested from the sender's phone before downloading again
     const socket = this.connection.getSocket();
-    if (!socket) {
-      throw new Error('Not connected to WhatsApp');
+    const data = await downloadMediaMessage(msg, 'buffer', {}, {
+      logger: pino({ level: 'silent' }),
+      reuploadRequest: socket ? socket.updateMediaMessage : async (original) => original,
+    });
+    if (maxBytes !== undefined && data.length > maxBytes) throw tooLarge(data.length);
+    return { data: new Uint8Array(data), mimeType: media?.mimetype ?? undefined };
+  }
+
+  async markRead(message: InboundMessageRef): Promise<void> {
+    await this.requireSocket().readMessages([
+      { remoteJid: toJid(message.from), id: message.id, participant: message.participant },
+    ]);
+  }
+
+  async sendTyping(message: InboundMessageRef): Promise<void> {
+    await this.requireSocket().sendPresenceUpdate('composing', toJid(message.from));
+  }
+
+  async checkContacts(users: string[]): Promise<ContactCheckResult[]> {
+    const socket = this.requireSocket();
+    const results = (await socket.onWhatsApp(...users.map(toJid))) ?? [];
+    return users.map((input) => {
+      const match = results.find((r) => r.jid === toJid(input));
+      return match?.exists
+        ? { input, onWhatsApp: true, waId: match.jid }
+        : { input, onWhatsApp: false };
+    });
+  }
+
+  async blockUsers(users: string[]): Promise<string[]> {
+    const socket = this.requireSocket();
+    const jids = users.map(toJid);
+    for (const jid of jids) {
+      await socket.updateBlockStatus(jid, 'block');
     }
+    return jids;
+  }
 
-    const content = this.adapter.toBaileys(message);
-    return socket.sendMessage(message.to, content);
+  async unblockUsers(users: string[]): Promise<string[]> {
+    const socket = this.requireSocket();
+    const jids = users.map(toJid);
+    for (const jid of jids) {
+      await socket.updateBlockStatus(jid, 'unblock');
+    }
+    return jids;
+  }
+
+  async listBlockedUsers(): Promise<string[]> {
+    return (await this.requireSocket().fetchBlocklist()).filter(Boolean) as string[];
+  }
+
+  async getGroupInviteInfo(code: string): Promise<GroupInviteInfo> {
+    const metadata = await this.requireSocket().groupGetInviteInfo(code);
+    return { id: metadata.id, subject: metadata.subject, size: metadata.size };
+  }
+
+  async getNewsletter(id: string): Promise<NewsletterInfo> {
+    const metadata = await this.requireSocket().newsletterMetadata('jid', toJid(id));
+    if (!metadata) {
+      throw new WhatsAppError('NOT_FOUND', `Channel ${id} was not found`);
+    }
+    return toNewsletterInfo(metadata);
+  }
+
+  // The linked account becomes the channel owner, so it can post to it with sendMessage
+  async createNewsletter(name: string, description?: string): Promise<NewsletterInfo> {
+    return toNewsletterInfo(await this.requireSocket().newsletterCreate(name, description));
+  }
+
+  private requireSocket() {
+    const socket = this.connection.getSocket();
+    if (!socket) {
+      throw new WhatsAppError('NOT_CONNECTED', 'Not connected to WhatsApp');
+    }
+    return socket;
   }
 
   getConnectionStatus(): ConnectionStatus {
     return this.connection.getStatus();
   }
 }
+
+// Baileys has reported channel metadata both flat and under thread_metadata across versions
+function toNewsletterInfo(metadata: any): NewsletterInfo {
+  const thread = metadata.thread_metadata ?? {};
+  const subscribers = Number(metadata.subscribers ?? thread.subscribers_count);
+  return {
+    id: metadata.id,
+    name: metadata.name ?? thread.name?.text ?? '',
+    description: metadata.description ?? thread.description?.text ?? undefined,
+    inviteCode: metadata.invite ?? thread.invite ?? undefined,
+    subscribers: Number.isFinite(subscribers) ? subscribers : undefined,
+    role: metadata.viewer_metadata?.role ?? undefined,
+  };
+}
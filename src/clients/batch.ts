@@ -0,0 +1,60 @@
+import { GraphApiError, WhatsAppError } from '../errors';
+
+// Graph API limit on requests per batch call
+export const MAX_BATCH_SIZE = 50;
+
+export interface BatchRequest {
+  method: 'GET' | 'POST' | 'DELETE';
+  relativeUrl: string;                 // Relative to the API version, e.g. "123/messages"
+  body?: Record<string, unknown>;
+}
+
+// Outcome of one request in a batch; items fail independently of each other
+export type BatchResult<T = unknown> =
+  | { ok: true; status: number; data: T }
+  | { ok: false; status?: number; error: WhatsAppError };
+
+interface BatchResponseItem {
+  code: number;
+  body?: string;
+}
+
+// Batch items carry form-encoded bodies; nested values are sent as JSON strings
+function formEncode(body: Record<string, unknown>): string {
+  return Object.entries(body)
+    .filter(([, value]) => value !== undefined)
+    .map(([key, value]) => {
+      const text = typeof value === 'object' && value !== null ? JSON.stringify(value) : String(value);
+      return `${encodeURIComponent(key)}=${encodeURIComponent(text)}`;
+    })
+    .join('&');
+}
+
+export function encodeBatchRequest(request: BatchRequest) {
+  return {
+    method: request.method,
+    relative_url: request.relativeUrl.replace(/^\//, ''),
+    ...(request.body ? { body: formEncode(request.body) } : {}),
+  };
+}
+
+export function decodeBatchResponse<T>(item: BatchResponseItem | null | undefined): BatchResult<T> {
+  // Graph answers null for items it did not get to before the batch timed out
+  if (!item) {
+    return { ok: false, error: new WhatsAppError('BATCH_TIMEOUT', 'The batch ended before this request ran') };
+  }
+
+  let data: unknown;
+  try {
+    data = item.body ? JSON.parse(item.body) : undefined;
+  } catch {
+    data = item.body;
+  }
+  if (item.code >= 200 && item.code < 300) {
+    return { ok: true, status: item.code, data: data as T };
+  }
+  const error =
+    GraphApiError.fromResponse({ response: { status: item.code, data } }) ??
+    new GraphApiError(`Batch request failed with status ${item.code}`, { status: item.code });
+  return { ok: false, status: item.code, error };
+}
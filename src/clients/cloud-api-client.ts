@@ -1,23 +1,117 @@
-import axios, { type AxiosInstance } from "axios";
+import axios, { type AxiosAdapter, type AxiosInstance } from "axios";
 import { EventEmitter } from "events";
-import type { IWhatsAppClient } from "./interface";
-import type { CloudAPIConfig, WhatsAppMessage, ConnectionStatus } from "../types";
+import type { DownloadedMedia, DownloadMediaOptions, InboundMessageRef, IWhatsAppClient, PhoneNumberDetails } from "./interface";
+import type {
+    CloudAPIConfig,
+    ConnectionStatus,
+    SendMessageResponse,
+    WhatsAppMedia,
+    WhatsAppMessage,
+    WhatsAppMessageType,
+} from "../types";
+import { toCloudPayload } from "./cloud-payload";
+import { decodeBatchResponse, encodeBatchRequest, MAX_BATCH_SIZE, type BatchRequest, type BatchResult } from "./batch";
+import { normalizeWhatsAppTarget, normalizePhoneDigits } from "../utils/normalize";
+import { GraphApiError, NotOnWhatsAppError, UnsupportedGroupOperationError, WhatsAppError } from "../errors";
+import type { ContactCheckResult } from "../contacts";
+import { buildCallActionPayload, type CallAction, type CallActionOptions } from "../calls";
+import { noopMetrics, type WhatsAppMetrics } from "../metrics";
+import { withSpan, type TracerLike } from "../tracing";
+import type { MessageTemplateRecord } from "../templates";
+import type { ProductCatalogPage, ProductRecord } from "../catalog";
+import { fixtureAdapter } from "./fixtures";
+import { transportAdapter, type HttpTransport } from "./transport";
+import { systemClock, type Clock } from "../utils/clock";
+
+// Graph API error code for sends to numbers that aren't on WhatsApp
+const NOT_ON_WHATSAPP_CODE = 131026;
+
+// Media download URLs are valid for five minutes; reuse a resolved one for a little less
+const MEDIA_URL_TTL_MS = 4 * 60 * 1000;
+
+// Lookaside answers one of these once a media URL has expired
+const EXPIRED_MEDIA_STATUSES = new Set([401, 403, 404, 410]);
+
+function mediaUrlExpired(error: any): boolean {
+    const status = error instanceof GraphApiError ? error.status : error?.response?.status;
+    return status !== undefined && EXPIRED_MEDIA_STATUSES.has(status);
+}
+
+interface MediaUrl {
+    url: string;
+    mimeType?: string;
+    size?: number;
+    expiresAt: number;
+}
+
+function tooLarge(mediaId: string, size: number, maxBytes: number): WhatsAppError {
+    return new WhatsAppError("MEDIA_TOO_LARGE", `Media ${mediaId} is ${size} bytes, over the ${maxBytes} byte limit`);
+}
+
+// GET /{media-id}
+type MediaUrlInfo = { id: string; url: string; mime_type?: string; file_size?: number };
+
+// Message types the Cloud API Groups endpoint does not accept
+const GROUP_UNSUPPORTED_TYPES: ReadonlyArray<WhatsAppMessageType> = ["interactive"];
 
 export class CloudAPIClient extends EventEmitter implements IWhatsAppClient {
     private client: AxiosInstance;
     private config: CloudAPIConfig;
+    private metrics: WhatsAppMetrics = noopMetrics;
+    private tracer?: TracerLike;
+    private mediaUrls = new Map<string, MediaUrl>();
+    private readonly clock: Clock;
 
-    constructor(config: CloudAPIConfig) {
+    constructor(config: CloudAPIConfig, clock: Clock = systemClock) {
         super();
         this.config = config;
+        this.clock = clock;
         // Default to v24.0 (current version). Supported range: v19.0 - v24.0
         const apiVersion = config.apiVersion || 'v24.0';
+        const adapter = this.adapter();
         this.client = axios.create({
             baseURL: `https://graph.facebook.com/${apiVersion}`,
             headers: {
                 Authorization: `Bearer ${config.accessToken}`,
                 "Content-Type": "application/json",
             },
+            ...(adapter ? { adapter } : {}),
+        });
+    }
+
+    useMetrics(metrics: WhatsAppMetrics): void {
+        this.metrics = metrics;
+    }
+
+    useTracer(tracer: TracerLike): void {
+        this.tracer = tracer;
+    }
+
+    // Send requests through `transport` instead of axios's Node HTTP adapter; fixtures still apply
+    useTransport(transport: HttpTransport): void {
+        this.client.defaults.adapter = this.adapter(transportAdapter(transport));
+    }
+
+    // Fixture recording and replay wrap whatever sends the requests (axios's default when undefined)
+    private adapter(upstream?: AxiosAdapter): AxiosAdapter | undefined {
+        return this.config.fixtures ? fixtureAdapter(this.config.fixtures, upstream) : upstream;
+    }
+
+    // Trace and record Graph API latency per endpoint, whether or not the request succeeds
+    private async timed<T>(endpoint: string, request: () => Promise<T>): Promise<T> {
+        const attributes = {
+            "http.route": endpoint,
+            "whatsapp.phone_number_id": this.config.phoneNumberId,
+        };
+        return withSpan(this.tracer, `whatsapp.graph_api.${endpoint}`, attributes, async () => {
+            const started = performance.now();
+            try {
+                return await request();
+            } catch (error) {
+                throw GraphApiError.fromResponse(error) ?? error;
+            } finally {
+                this.metrics.observeApiLatency(endpoint, (performance.now() - started) / 1000);
+            }
         });
     }
 
@@ -33,18 +127,338 @@ export class CloudAPIClient extends EventEmitter implements IWhatsAppClient {
 
     async sendMessage(message: WhatsAppMessage): Promise<any> {
         const endpoint = `/${this.config.phoneNumberId}/messages`;
+        const payload = await this.buildPayload(message);
+
+        try {
+            return await this.timed("messages", () => this.client.post(endpoint, payload));
+        } catch (error: any) {
+            if (error instanceof GraphApiError && error.graphCode === NOT_ON_WHATSAPP_CODE) {
+                throw new NotOnWhatsAppError(message.to);
+            }
+            throw error;
+        }
+    }
 
-        const payload = {
+    // Full request body for a message, uploading raw media bytes first
+    private async buildPayload(message: WhatsAppMessage): Promise<Record<string, unknown>> {
+        if (message.type === "media" && message.content.data) {
+            message = { ...message, content: await this.uploadMedia(message.content) };
+        }
+        const target = normalizeWhatsAppTarget(message.to);
+        const isGroup = target?.kind === "group";
+
+        if (target?.kind === "newsletter") {
+            throw new WhatsAppError("UNSUPPORTED", `The Cloud API cannot post to WhatsApp Channels (${message.to}); use a Baileys account`);
+        }
+
+        if (isGroup && GROUP_UNSUPPORTED_TYPES.includes(message.type)) {
+            throw new UnsupportedGroupOperationError(`send ${message.type}`, target.id);
+        }
+
+        return {
             messaging_product: "whatsapp",
-            recipient_type: "individual",
-            to: message.to,
-            type: message.type,
-            ...(message.type === "text"
-                ? { text: { body: message.content } }
-                : { template: message.content }),
+            recipient_type: isGroup ? "group" : "individual",
+            to: target?.id ?? message.to,
+            ...toCloudPayload(message),
         };
+    }
+
+    // Run requests through the Graph batch endpoint, 50 per round trip; results keep the request order
+    async batch<T = unknown>(requests: BatchRequest[]): Promise<BatchResult<T>[]> {
+        const results: BatchResult<T>[] = [];
+        for (let start = 0; start < requests.length; start += MAX_BATCH_SIZE) {
+            const chunk = requests.slice(start, start + MAX_BATCH_SIZE);
+            const { data } = await this.timed("batch", () =>
+                this.client.post<Array<{ code: number; body?: string } | null>>("/", {
+                    batch: chunk.map(encodeBatchRequest),
+                })
+            );
+            results.push(...chunk.map((_, i) => decodeBatchResponse<T>(data?.[i])));
+        }
+        return results;
+    }
+
+    // Send several messages in as few round trips as possible; each message succeeds or fails on its own
+    async sendBatch(messages: WhatsAppMessage[]): Promise<BatchResult<SendMessageResponse>[]> {
+        const payloads = await Promise.all(
+            messages.map((message) => this.buildPayload(message).catch((error: unknown) => error as Error))
+        );
+        const requests = payloads.flatMap((payload) =>
+            payload instanceof Error
+                ? []
+                : [{ method: "POST" as const, relativeUrl: `${this.config.phoneNumberId}/messages`, body: payload }]
+        );
+        const sent = await this.batch<SendMessageResponse>(requests);
+
+        return payloads.map((payload, i): BatchResult<SendMessageResponse> => {
+            if (payload instanceof Error) return { ok: false, error: WhatsAppError.from(payload) };
+            const result = sent.shift() as BatchResult<SendMessageResponse>;
+            if (!result.ok && result.error instanceof GraphApiError && result.error.graphCode === NOT_ON_WHATSAPP_CODE) {
+                return { ok: false, status: result.status, error: new NotOnWhatsAppError(messages[i].to) };
+            }
+            return result;
+        });
+    }
+
+    // Mark many inbound messages read with one batch call
+    async markReadMany(messageIds: string[]): Promise<BatchResult[]> {
+        return this.batch(
+            messageIds.map((id) => ({
+                method: "POST" as const,
+                relativeUrl: `${this.config.phoneNumberId}/messages`,
+                body: { messaging_product: "whatsapp", status: "read", message_id: id },
+            }))
+        );
+    }
+
+    // Resolve the short-lived download URLs of many media ids with one batch call
+    async getMediaUrls(mediaIds: string[]): Promise<BatchResult<MediaUrlInfo>[]> {
+        const results = await this.batch<MediaUrlInfo>(
+            mediaIds.map((id) => ({ method: "GET" as const, relativeUrl: id }))
+        );
+        results.forEach((result, i) => {
+            if (result.ok) this.rememberMediaUrl(mediaIds[i], result.data);
+        });
+        return results;
+    }
+
+    async markRead(message: InboundMessageRef): Promise<void> {
+        await this.timed("messages", () =>
+            this.client.post(`/${this.config.phoneNumberId}/messages`, {
+                messaging_product: "whatsapp",
+                status: "read",
+                message_id: message.id,
+            })
+        );
+    }
+
+    // Escape hatch for message types not modelled yet; messaging_product is filled in
+    async sendRaw(payload: Record<string, unknown>, phoneNumberId = this.config.phoneNumberId): Promise<SendMessageResponse> {
+        try {
+            const { data } = await this.timed("messages", () =>
+                this.client.post<SendMessageResponse>(`/${phoneNumberId}/messages`, {
+                    messaging_product: "whatsapp",
+                    ...payload,
+                })
+            );
+            return data;
+        } catch (error: any) {
+            if (error instanceof GraphApiError && error.graphCode === NOT_ON_WHATSAPP_CODE) {
+                throw new NotOnWhatsAppError(String(payload.to ?? ""));
+            }
+            throw error;
+        }
+    }
+
+    // Media ids are scoped to the receiving phone number: resolve the short-lived URL, then fetch it.
+    // A URL that has expired since it was resolved is looked up again and the download retried once.
+    async downloadMedia(mediaId: string, options: DownloadMediaOptions = {}): Promise<DownloadedMedia> {
+        const cached = this.mediaUrls.get(mediaId);
+        const media = cached && cached.expiresAt > this.clock.now() ? cached : await this.resolveMediaUrl(mediaId);
+        try {
+            return await this.fetchMedia(mediaId, media, options);
+        } catch (error) {
+            if (!mediaUrlExpired(error)) throw error;
+            this.mediaUrls.delete(mediaId);
+            return this.fetchMedia(mediaId, await this.resolveMediaUrl(mediaId), options);
+        }
+    }
+
+    private async resolveMediaUrl(mediaId: string): Promise<MediaUrl> {
+        const { data } = await this.timed("media", () => this.client.get<MediaUrlInfo>(`/${mediaId}`));
+        return this.rememberMediaUrl(mediaId, data);
+    }
+
+    private rememberMediaUrl(mediaId: string, info: MediaUrlInfo): MediaUrl {
+        const now = this.clock.now();
+        for (const [id, media] of this.mediaUrls) {
+            if (media.expiresAt <= now) this.mediaUrls.delete(id);
+        }
+        const media = { url: info.url, mimeType: info.mime_type, size: info.file_size, expiresAt: now + MEDIA_URL_TTL_MS };
+        this.mediaUrls.set(mediaId, media);
+        return media;
+    }
+
+    private async fetchMedia(mediaId: string, media: MediaUrl, { maxBytes }: DownloadMediaOptions): Promise<DownloadedMedia> {
+        if (maxBytes !== undefined && media.size !== undefined && media.size > maxBytes) {
+            throw tooLarge(mediaId, media.size, maxBytes);
+        }
+        const { data } = await this.timed("media_download", () =>
+            this.client.get<ArrayBuffer>(media.url, { baseURL: "", responseType: "arraybuffer" })
+        );
+        if (maxBytes !== undefined && data.byteLength > maxBytes) {
+            throw tooLarge(mediaId, data.byteLength, maxBytes);
+        }
+        return { data: new Uint8Array(data), mimeType: media.mimeType };
+    }
+
+    // Upload raw bytes to this phone number and return the media to send by id
+    private async uploadMedia(media: WhatsAppMedia): Promise<WhatsAppMedia> {
+        const mimeType = media.mimeType ?? "application/octet-stream";
+        const form = new FormData();
+        form.append("messaging_product", "whatsapp");
+        form.append("type", mimeType);
+        form.append("file", new Blob([media.data as Uint8Array], { type: mimeType }), media.fileName ?? media.kind);
+
+        const { data } = await this.timed("media_upload", () =>
+            this.client.post<{ id: string }>(`/${this.config.phoneNumberId}/media`, form, {
+                headers: { "Content-Type": "multipart/form-data" },
+            })
+        );
+        const { data: _bytes, url: _url, ...rest } = media;
+        return { ...rest, id: data.id };
+    }
+
+    // Details of the sending number; fails with GraphApiError when the token or id is wrong
+    async getPhoneNumber(): Promise<PhoneNumberDetails> {
+        const { data } = await this.timed("phone_number", () =>
+            this.client.get(`/${this.config.phoneNumberId}`, {
+                params: { fields: "display_phone_number,verified_name,quality_rating,status,messaging_limit_tier" },
+            })
+        );
+        return data;
+    }
+
+    // All templates of the business account, following the paging cursors
+    async listTemplates(): Promise<MessageTemplateRecord[]> {
+        if (!this.config.businessAccountId) {
+            throw new WhatsAppError("INVALID_CONFIG", "businessAccountId is required to list message templates");
+        }
+
+        const templates: MessageTemplateRecord[] = [];
+        let after: string | undefined;
+        do {
+            const { data } = await this.timed("message_templates", () =>
+                this.client.get<{ data: MessageTemplateRecord[]; paging?: { cursors?: { after?: string }; next?: string } }>(
+                    `/${this.config.businessAccountId}/message_templates`,
+                    {
+                        params: {
+                            fields: "id,name,language,status,category,quality_score,components",
+                            limit: 100,
+                            ...(after ? { after } : {}),
+                        },
+                    }
+                )
+            );
+            templates.push(...data.data);
+            after = data.paging?.next ? data.paging.cursors?.after : undefined;
+        } while (after);
+        return templates;
+    }
+
+    // Catalog configured for the account, else the first one connected to the business account
+    private async catalogId(): Promise<string> {
+        if (this.config.catalogId) return this.config.catalogId;
+        if (!this.config.businessAccountId) {
+            throw new WhatsAppError("INVALID_CONFIG", "catalogId or businessAccountId is required to list products");
+        }
+        const { data } = await this.timed("product_catalogs", () =>
+            this.client.get<{ data: Array<{ id: string }> }>(`/${this.config.businessAccountId}/product_catalogs`)
+        );
+        const catalogId = data.data?.[0]?.id;
+        if (!catalogId) {
+            throw new WhatsAppError("NOT_FOUND", `Business account ${this.config.businessAccountId} has no product catalog`);
+        }
+        return catalogId;
+    }
+
+    // All products of the catalog, following the paging cursors
+    async listProducts(): Promise<ProductCatalogPage> {
+        const catalogId = await this.catalogId();
+        const products: ProductRecord[] = [];
+        let after: string | undefined;
+        do {
+            const { data } = await this.timed("products", () =>
+                this.client.get<{ data: ProductRecord[]; paging?: { cursors?: { after?: string }; next?: string } }>(
+                    `/${catalogId}/products`,
+                    {
+                        params: {
+                            fields: "id,retailer_id,name,description,price,currency,availability,url,image_url",
+                            limit: 100,
+                            ...(after ? { after } : {}),
+                        },
+                    }
+                )
+            );
+            products.push(...data.data);
+            after = data.paging?.next ? data.paging.cursors?.after : undefined;
+        } while (after);
+        return { catalogId, products };
+    }
+
+    // Marks the message read and shows "typing…" until the reply is sent (or 25 seconds pass)
+    async sendTyping(message: InboundMessageRef): Promise<void> {
+        await this.timed("messages", () =>
+            this.client.post(`/${this.config.phoneNumberId}/messages`, {
+                messaging_product: "whatsapp",
+                status: "read",
+                message_id: message.id,
+                typing_indicator: { type: "text" },
+            })
+        );
+    }
+
+    async checkContacts(users: string[]): Promise<ContactCheckResult[]> {
+        const response = await this.timed("contacts", () =>
+            this.client.post(`/${this.config.phoneNumberId}/contacts`, {
+                blocking: "wait",
+                contacts: users,
+                force_check: false,
+            })
+        );
+        const contacts: Array<{ input: string; status: string; wa_id?: string }> =
+            response.data?.contacts ?? [];
+        return users.map((input) => {
+            const contact = contacts.find((c) => c.input === input);
+            return contact?.status === "valid"
+                ? { input, onWhatsApp: true, waId: contact.wa_id }
+                : { input, onWhatsApp: false };
+        });
+    }
+
+    async blockUsers(users: string[]): Promise<string[]> {
+        const response = await this.timed("block_users", () =>
+            this.client.post(`/${this.config.phoneNumberId}/block_users`, this.blockPayload(users))
+        );
+        return (response.data?.block_users?.added_users ?? []).map((u: { wa_id: string }) => u.wa_id);
+    }
+
+    async unblockUsers(users: string[]): Promise<string[]> {
+        const response = await this.timed("block_users", () =>
+            this.client.delete(`/${this.config.phoneNumberId}/block_users`, {
+                data: this.blockPayload(users),
+            })
+        );
+        return (response.data?.block_users?.removed_users ?? []).map((u: { wa_id: string }) => u.wa_id);
+    }
+
+    async listBlockedUsers(): Promise<string[]> {
+        const blocked: string[] = [];
+        let after: string | undefined;
+        do {
+            const response = await this.timed("block_users", () =>
+                this.client.get(`/${this.config.phoneNumberId}/block_users`, {
+                    params: after ? { after } : {},
+                })
+            );
+            blocked.push(...(response.data?.data ?? []).map((u: { wa_id: string }) => u.wa_id));
+            after = response.data?.paging?.next ? response.data.paging.cursors?.after : undefined;
+        } while (after);
+        return blocked;
+    }
+
+    private blockPayload(users: string[]) {
+        return {
+            messaging_product: "whatsapp",
+            block_users: users.map((user) => ({ user: normalizePhoneDigits(user) ?? user })),
+        };
+    }
 
-        return this.client.post(endpoint, payload);
+    async manageCall(action: CallAction, options: CallActionOptions): Promise<any> {
+        const endpoint = `/${this.config.phoneNumberId}/calls`;
+        return this.timed("calls", () =>
+            this.client.post(endpoint, buildCallActionPayload(action, options))
+        );
     }
 
     async verifyWebhook(token: string): Promise<boolean> {
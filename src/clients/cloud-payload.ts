@@ -0,0 +1,112 @@
+import { WhatsAppError } from '../errors';
+import { normalizeReaction } from '../utils/emoji';
+import type {
+  WhatsAppInteractive,
+  WhatsAppLocation,
+  WhatsAppMedia,
+  WhatsAppMessage,
+  WhatsAppTemplate,
+} from '../types';
+
+const MEDIA_KINDS: ReadonlyArray<WhatsAppMedia['kind']> = ['image', 'video', 'audio', 'document'];
+
+// Cloud API body for a message, minus the envelope (messaging_product, recipient_type, to)
+export function toCloudPayload(message: WhatsAppMessage): Record<string, unknown> {
+  return {
+    ...(message.replyTo ? { context: { message_id: message.replyTo } } : {}),
+    ...messageBody(message),
+  };
+}
+
+function messageBody(message: WhatsAppMessage): Record<string, unknown> {
+  switch (message.type) {
+    case 'text':
+      return { type: 'text', text: { body: message.content, ...(message.previewUrl ? { preview_url: true } : {}) } };
+    case 'interactive':
+      return { type: 'interactive', interactive: message.content };
+    case 'location':
+      return { type: 'location', location: message.content };
+    case 'template':
+      return { type: 'template', template: message.content };
+    case 'reaction':
+      return { type: 'reaction', reaction: { message_id: message.content.messageId, emoji: normalizeReaction(message.content.emoji) } };
+    case 'media': {
+      const media = message.content;
+      if (!media.id && !media.url) {
+        throw new WhatsAppError('INVALID_MESSAGE', `${media.kind} media needs an id or url`);
+      }
+      return {
+        type: media.kind,
+        [media.kind]: {
+          ...(media.id ? { id: media.id } : { link: media.url }),
+          ...(media.caption && media.kind !== 'audio' ? { caption: media.caption } : {}),
+          ...(media.fileName && media.kind === 'document' ? { filename: media.fileName } : {}),
+        },
+      };
+    }
+  }
+}
+
+// Parse a Cloud API message body (as sent to POST /{phone-number-id}/messages) back into a message
+export function fromCloudPayload(payload: Record<string, unknown>): WhatsAppMessage {
+  const message = parseBody(payload);
+  const replyTo = (payload.context as { message_id?: unknown } | undefined)?.message_id;
+  return typeof replyTo === 'string' && replyTo ? { ...message, replyTo } : message;
+}
+
+function parseBody(payload: Record<string, unknown>): WhatsAppMessage {
+  const { to, type } = payload;
+  if (typeof to !== 'string' || !to) {
+    throw new WhatsAppError('INVALID_MESSAGE', 'Message payload has no recipient');
+  }
+  if (typeof type !== 'string') {
+    throw new WhatsAppError('INVALID_MESSAGE', 'Message payload has no type');
+  }
+
+  const body = payload[type] as Record<string, any> | undefined;
+  if (!body || typeof body !== 'object') {
+    throw new WhatsAppError('INVALID_MESSAGE', `"${type}" message has no "${type}" object`);
+  }
+
+  switch (type) {
+    case 'text':
+      if (typeof body.body !== 'string') {
+        throw new WhatsAppError('INVALID_MESSAGE', 'Text message has no body');
+      }
+      return { type: 'text', to, content: body.body, ...(body.preview_url === true ? { previewUrl: true } : {}) };
+    case 'interactive':
+      return { type: 'interactive', to, content: body as WhatsAppInteractive };
+    case 'location':
+      if (typeof body.latitude !== 'number' || typeof body.longitude !== 'number') {
+        throw new WhatsAppError('INVALID_MESSAGE', 'Location message needs numeric latitude and longitude');
+      }
+      return { type: 'location', to, content: body as WhatsAppLocation };
+    case 'template':
+      if (typeof body.name !== 'string' || typeof body.language?.code !== 'string') {
+        throw new WhatsAppError('INVALID_MESSAGE', 'Template message needs a name and language code');
+      }
+      return { type: 'template', to, content: body as WhatsAppTemplate };
+    case 'reaction':
+      if (typeof body.message_id !== 'string' || typeof body.emoji !== 'string') {
+        throw new WhatsAppError('INVALID_MESSAGE', 'Reaction message needs a message_id and emoji');
+      }
+      return { type: 'reaction', to, content: { messageId: body.message_id, emoji: body.emoji } };
+  }
+
+  if (!MEDIA_KINDS.includes(type as WhatsAppMedia['kind'])) {
+    throw new WhatsAppError('UNSUPPORTED_MESSAGE_TYPE', `Message type "${type}" is not supported; use sendRaw`);
+  }
+  if (!body.id && !body.link) {
+    throw new WhatsAppError('INVALID_MESSAGE', `${type} message needs an id or link`);
+  }
+  return {
+    type: 'media',
+    to,
+    content: {
+      kind: type as WhatsAppMedia['kind'],
+      ...(body.id ? { id: body.id } : { url: body.link }),
+      ...(body.caption ? { caption: body.caption } : {}),
+      ...(body.filename ? { fileName: body.filename } : {}),
+    },
+  };
+}
@@ -3,15 +3,16 @@ import type { IWhatsAppClient } from './interface';
 import { BaileysClient } from './baileys-client';
 import { CloudAPIClient } from './cloud-api-client';
 import { detectAuthMethod } from '../utils/config-detector';
+import { systemClock, type Clock } from '../utils/clock';
 
 export class ClientFactory {
-  static create(config: WhatsAppConfig): IWhatsAppClient {
+  static create(config: WhatsAppConfig, clock: Clock = systemClock): IWhatsAppClient {
     const authMethod = detectAuthMethod(config);
 
     if (authMethod === 'baileys') {
-      return new BaileysClient(config as BaileysConfig);
+      return new BaileysClient(config as BaileysConfig, clock);
     } else {
-      return new CloudAPIClient(config as CloudAPIConfig);
+      return new CloudAPIClient(config as CloudAPIConfig, clock);
     }
   }
 }
@@ -0,0 +1,167 @@
+import axios, { AxiosError, type AxiosAdapter, type AxiosResponse, type InternalAxiosRequestConfig } from 'axios';
+import { createHash } from 'crypto';
+import { mkdir, readFile, writeFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import { WhatsAppError } from '../errors';
+import { settleResponse } from './transport';
+
+// "record" passes requests through to the Graph API and saves each exchange; "replay" answers
+// from the saved files only and fails requests that were never recorded
+export type FixtureMode = 'record' | 'replay';
+
+export interface FixtureConfig {
+  mode: FixtureMode;
+  dir: string;                          // Directory holding one JSON file per distinct request
+}
+
+export type FixtureBody = { json: unknown } | { text: string } | { base64: string };
+
+export interface FixtureResponse {
+  status: number;
+  contentType?: string;
+  body: FixtureBody;
+}
+
+// One distinct request and its responses in the order they were recorded
+export interface FixtureFile {
+  request: { method: string; url: string; body?: string };
+  responses: FixtureResponse[];
+}
+
+// Query parameters that carry credentials and never go into fixture files or keys
+const REDACTED_PARAMS = ['access_token', 'appsecret_proof'];
+
+/**
+ * Axios adapter for the Cloud API client that records request/response pairs to fixture files, or
+ * replays them offline so Graph API edge cases can be reproduced in CI without credentials.
+ * Requests are matched on method, URL and body; the Authorization header is never stored.
+ * A request made repeatedly gets its recorded responses in order, the last one repeating.
+ */
+export function fixtureAdapter(config: FixtureConfig, upstream?: AxiosAdapter): AxiosAdapter {
+  const files = new Map<string, FixtureFile>();
+  const served = new Map<string, number>();
+
+  return async (request) => {
+    const described = describeRequest(request);
+    const name = fixtureName(described);
+    const path = join(config.dir, name);
+
+    if (config.mode === 'replay') {
+      const file = files.get(name) ?? (await loadFixture(path));
+      if (!file || file.responses.length === 0) {
+        throw new WhatsAppError(
+          'FIXTURE_MISSING',
+          `No recorded response for ${described.method} ${described.url} (expected ${path})`
+        );
+      }
+      files.set(name, file);
+      const index = served.get(name) ?? 0;
+      served.set(name, index + 1);
+      return settle(request, file.responses[Math.min(index, file.responses.length - 1)]);
+    }
+
+    const send = upstream ?? axios.getAdapter(axios.defaults.adapter);
+    let response: AxiosResponse;
+    try {
+      response = await send(request);
+    } catch (error) {
+      // Graph API errors are worth recording too; network failures have nothing to replay
+      if (!(error instanceof AxiosError) || !error.response) throw error;
+      response = error.response;
+      await record(files, path, name, described, response);
+      throw error;
+    }
+    await record(files, path, name, described, response);
+    return response;
+  };
+}
+
+// File name for a request: readable method and path, plus a hash that tells bodies and queries apart
+export function fixtureName(request: FixtureFile['request']): string {
+  const hash = createHash('sha256')
+    .update(`${request.method} ${request.url}\n${request.body ?? ''}`)
+    .digest('hex')
+    .slice(0, 12);
+  const path = new URL(request.url, 'https://graph.facebook.com').pathname;
+  const slug = path.replace(/[^A-Za-z0-9]+/g, '_').replace(/^_+|_+$/g, '').slice(0, 60);
+  return `${request.method.toLowerCase()}-${slug || 'root'}-${hash}.json`;
+}
+
+function describeRequest(request: InternalAxiosRequestConfig): FixtureFile['request'] {
+  const url = new URL(axios.getUri(request), 'https://graph.facebook.com');
+  for (const param of REDACTED_PARAMS) url.searchParams.delete(param);
+  url.searchParams.sort();
+  const method = (request.method ?? 'get').toUpperCase();
+  const body = serializeBody(request.data);
+  return { method, url: url.toString(), ...(body !== undefined ? { body } : {}) };
+}
+
+// Request bodies reach the adapter already serialized, except multipart uploads
+function serializeBody(data: unknown): string | undefined {
+  if (data === undefined || data === null) return undefined;
+  if (typeof data === 'string') return data;
+  if (Buffer.isBuffer(data)) return `base64:${data.toString('base64')}`;
+  if (typeof FormData !== 'undefined' && data instanceof FormData) {
+    return `form-data:${[...data.keys()].sort().join(',')}`;
+  }
+  return JSON.stringify(data);
+}
+
+async function loadFixture(path: string): Promise<FixtureFile | undefined> {
+  try {
+    return JSON.parse(await readFile(path, 'utf8')) as FixtureFile;
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return undefined;
+    throw new WhatsAppError('INVALID_FIXTURE', `Could not read fixture ${path}`, { cause: error });
+  }
+}
+
+// The first response recorded in a session replaces what the file held before
+async function record(
+  files: Map<string, FixtureFile>,
+  path: string,
+  name: string,
+  request: FixtureFile['request'],
+  response: AxiosResponse
+): Promise<void> {
+  const file = files.get(name) ?? { request, responses: [] };
+  const contentType = response.headers?.['content-type'];
+  file.responses.push({
+    status: response.status,
+    ...(typeof contentType === 'string' ? { contentType } : {}),
+    body: encodeBody(response.data),
+  });
+  files.set(name, file);
+  await mkdir(dirname(path), { recursive: true });
+  await writeFile(path, `${JSON.stringify(file, null, 2)}\n`);
+}
+
+function encodeBody(data: unknown): FixtureBody {
+  if (Buffer.isBuffer(data)) return { base64: data.toString('base64') };
+  if (data instanceof ArrayBuffer) return { base64: Buffer.from(data).toString('base64') };
+  if (typeof data !== 'string') return { json: data };
+  try {
+    return { json: JSON.parse(data) };
+  } catch {
+    return { text: data };
+  }
+}
+
+function decodeBody(body: FixtureBody, request: InternalAxiosRequestConfig): unknown {
+  if ('base64' in body) {
+    const buffer = Buffer.from(body.base64, 'base64');
+    return request.responseType === 'arraybuffer' ? buffer : buffer.toString('utf8');
+  }
+  // Left as text, so the client's response transforms parse it as they would a live body
+  return 'text' in body ? body.text : JSON.stringify(body.json);
+}
+
+function settle(request: InternalAxiosRequestConfig, recorded: FixtureResponse): AxiosResponse {
+  return settleResponse(request, {
+    data: decodeBody(recorded.body, request),
+    status: recorded.status,
+    statusText: '',
+    headers: recorded.contentType ? { 'content-type': recorded.contentType } : {},
+    config: request,
+  });
+}
@@ -2,3 +2,6 @@ export { IWhatsAppClient } from './interface';
 export { ClientFactory } from './factory';
 export { BaileysClient } from './baileys-client';
 export { CloudAPIClient } from './cloud-api-client';
+export { fromCloudPayload, toCloudPayload } from './cloud-payload';
+export { fixtureAdapter, fixtureName, type FixtureConfig, type FixtureMode } from './fixtures';
+export { fetchTransport, transportAdapter, type HttpRequest, type HttpResponse, type HttpTransport } from './transport';
@@ -1,5 +1,57 @@
 import { EventEmitter } from 'events';
-import type { WhatsAppMessage, ConnectionStatus } from '../types';
+import type { WhatsAppMessage, ConnectionStatus, SendMessageResponse } from '../types';
+import type { BatchResult } from './batch';
+import type { CallAction, CallActionOptions } from '../calls';
+import type { ContactCheckResult } from '../contacts';
+import type { WhatsAppMetrics } from '../metrics';
+import type { TracerLike } from '../tracing';
+import type { HttpTransport } from './transport';
+import type { MessageTemplateRecord } from '../templates';
+import type { ProductCatalogPage } from '../catalog';
+
+// Identifies an inbound message for read receipts and typing indicators
+export interface InboundMessageRef {
+  id: string;
+  from: string;
+  participant?: string;
+}
+
+// Bytes of a media file received on this account
+export interface DownloadedMedia {
+  data: Uint8Array;
+  mimeType?: string;
+}
+
+export interface DownloadMediaOptions {
+  maxBytes?: number;                    // Larger files fail with MEDIA_TOO_LARGE, before downloading when the size is known
+}
+
+// Graph API details of a Cloud API phone number
+export interface PhoneNumberDetails {
+  id: string;
+  display_phone_number?: string;
+  verified_name?: string;
+  quality_rating?: string;              // GREEN, YELLOW, RED
+  status?: string;                      // CONNECTED, FLAGGED, RESTRICTED, ...
+  messaging_limit_tier?: string;        // TIER_250, TIER_1K, ..., TIER_UNLIMITED
+}
+
+// A WhatsApp Channel (newsletter) the account can see
+export interface NewsletterInfo {
+  id: string;                           // Newsletter JID (<id>@newsletter)
+  name: string;
+  description?: string;
+  inviteCode?: string;                  // whatsapp.com/channel/<inviteCode>
+  subscribers?: number;
+  role?: string;                        // OWNER, ADMIN, SUBSCRIBER, ...
+}
+
+// Group an invite code points at, as seen before joining
+export interface GroupInviteInfo {
+  id: string;                           // Group JID
+  subject?: string;
+  size?: number;
+}
 
 export interface IWhatsAppClient extends EventEmitter {
   // Lifecycle
@@ -9,9 +61,56 @@ export interface IWhatsAppClient extends EventEmitter {
   // Messaging
   sendMessage(message: WhatsAppMessage): Promise<any>;
 
+  // Download media received on this account by its inbound media id
+  downloadMedia?(mediaId: string, options?: DownloadMediaOptions): Promise<DownloadedMedia>;
+
+  // Post a message payload the typed API does not model yet (Cloud API only)
+  sendRaw?(payload: Record<string, unknown>, phoneNumberId?: string): Promise<SendMessageResponse>;
+
+  // Send many messages through the Graph batch endpoint (Cloud API only)
+  sendBatch?(messages: WhatsAppMessage[]): Promise<BatchResult<SendMessageResponse>[]>;
+
+  // Read receipts and typing indicator. Cloud API ties the indicator to the message being answered.
+  markRead?(message: InboundMessageRef): Promise<void>;
+  sendTyping?(message: InboundMessageRef): Promise<void>;
+
   // Webhooks (Cloud API only - optional for Baileys)
   verifyWebhook?(token: string): Promise<boolean>;
 
+  // Whether phone numbers are registered on WhatsApp
+  checkContacts?(users: string[]): Promise<ContactCheckResult[]>;
+
+  // Blocklist (returns wa_ids / JIDs as the backend reports them)
+  blockUsers?(users: string[]): Promise<string[]>;
+  unblockUsers?(users: string[]): Promise<string[]>;
+  listBlockedUsers?(): Promise<string[]>;
+
+  // Message templates of the business account (Cloud API only)
+  listTemplates?(): Promise<MessageTemplateRecord[]>;
+
+  // Products of the business catalog (Cloud API only)
+  listProducts?(): Promise<ProductCatalogPage>;
+
+  // Quality rating and messaging tier of the sending number (Cloud API only)
+  getPhoneNumber?(): Promise<PhoneNumberDetails>;
+
+  // Look up the group behind a chat.whatsapp.com invite code (Baileys only)
+  getGroupInviteInfo?(code: string): Promise<GroupInviteInfo>;
+
+  // WhatsApp Channels (Baileys only; posts go through sendMessage to the newsletter JID)
+  getNewsletter?(id: string): Promise<NewsletterInfo>;
+  createNewsletter?(name: string, description?: string): Promise<NewsletterInfo>;
+
+  // Calling API (Cloud API only)
+  manageCall?(action: CallAction, options: CallActionOptions): Promise<any>;
+
+  // Instrumentation (HTTP-based clients record API latency and spans)
+  useMetrics?(metrics: WhatsAppMetrics): void;
+  useTracer?(tracer: TracerLike): void;
+
+  // Replace the HTTP stack, e.g. with fetch or a test double (HTTP-based clients only)
+  useTransport?(transport: HttpTransport): void;
+
   // Status
   getConnectionStatus(): ConnectionStatus;
 }
@@ -0,0 +1,100 @@
+import axios, {
+  AxiosError,
+  AxiosHeaders,
+  type AxiosAdapter,
+  type AxiosResponse,
+  type InternalAxiosRequestConfig,
+} from 'axios';
+
+export interface HttpRequest {
+  method: string;                       // GET, POST, DELETE
+  url: string;                          // Absolute, query string included
+  headers: Record<string, string>;
+  body?: string | Uint8Array | FormData;      // FormData for media uploads; the transport sets the boundary
+  responseType: 'text' | 'binary';      // Media downloads want bytes, everything else text
+  timeoutMs?: number;
+  signal?: AbortSignal;
+}
+
+export interface HttpResponse {
+  status: number;
+  headers: Record<string, string>;      // Lower-case names
+  body: string | Uint8Array;
+}
+
+// Sends the Cloud API client's HTTP requests; errors are only for requests that got no response
+export interface HttpTransport {
+  request(request: HttpRequest): Promise<HttpResponse>;
+}
+
+// Transport over the global fetch (Node 18+, browsers, edge runtimes)
+export const fetchTransport: HttpTransport = {
+  async request(request) {
+    const headers = { ...request.headers };
+    if (typeof FormData !== 'undefined' && request.body instanceof FormData) {
+      // fetch writes the multipart header itself, boundary included
+      for (const name of Object.keys(headers)) {
+        if (name.toLowerCase() === 'content-type') delete headers[name];
+      }
+    }
+    const response = await fetch(request.url, {
+      method: request.method,
+      headers,
+      body: request.body as BodyInit | undefined,
+      signal: request.signal ?? (request.timeoutMs ? AbortSignal.timeout(request.timeoutMs) : undefined),
+    });
+    return {
+      status: response.status,
+      headers: Object.fromEntries([...response.headers].map(([name, value]) => [name.toLowerCase(), value])),
+      body: request.responseType === 'binary' ? new Uint8Array(await response.arrayBuffer()) : await response.text(),
+    };
+  },
+};
+
+/**
+ * Axios adapter that hands requests to `transport`. Axios still builds URLs, serializes bodies,
+ * parses responses and raises errors for non-2xx statuses, so the client behaves the same whatever
+ * sends the bytes.
+ */
+export function transportAdapter(transport: HttpTransport): AxiosAdapter {
+  return async (config) => {
+    const headers: Record<string, string> = {};
+    for (const [name, value] of Object.entries(AxiosHeaders.from(config.headers).toJSON())) {
+      if (value !== undefined && value !== null && value !== false) headers[name] = String(value);
+    }
+    const responseType = config.responseType === 'arraybuffer' ? 'binary' : 'text';
+    const response = await transport.request({
+      method: (config.method ?? 'get').toUpperCase(),
+      url: axios.getUri(config),
+      headers,
+      ...(config.data !== undefined && config.data !== null ? { body: config.data } : {}),
+      responseType,
+      ...(config.timeout ? { timeoutMs: config.timeout } : {}),
+      ...(config.signal ? { signal: config.signal as AbortSignal } : {}),
+    });
+    const { body } = response;
+    // Axios's own adapters hand over Buffers for binary responses and strings otherwise
+    const bytes = () => (typeof body === 'string' ? Buffer.from(body) : Buffer.from(body));
+    const data = responseType === 'binary' ? bytes() : typeof body === 'string' ? body : bytes().toString('utf8');
+    return settleResponse(config, {
+      data,
+      status: response.status,
+      statusText: '',
+      headers: response.headers,
+      config,
+    });
+  };
+}
+
+// Resolve or reject the way axios's own adapters do, so callers see the same errors as live
+export function settleResponse(config: InternalAxiosRequestConfig, response: AxiosResponse): AxiosResponse {
+  const validate = config.validateStatus;
+  if (!validate || validate(response.status)) return response;
+  throw new AxiosError(
+    `Request failed with status code ${response.status}`,
+    response.status < 500 ? AxiosError.ERR_BAD_REQUEST : AxiosError.ERR_BAD_RESPONSE,
+    config,
+    undefined,
+    response
+  );
+}
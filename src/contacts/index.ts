@@ -0,0 +1,12 @@
+export { ContactValidityCache } from './validity';
+export type { ContactCheckResult } from './validity';
+export { ContactTags, InMemoryContactTagStore, RuntimeContactTagStore } from './tags';
+export type { ContactTagStore, AudienceSelector } from './tags';
+export {
+  ContactLanguages,
+  InMemoryContactLanguageStore,
+  RuntimeContactLanguageStore,
+  matchTemplateLanguage,
+  sameLanguage,
+} from './language';
+export type { ContactLanguage, ContactLanguageStore, LanguageDetector, LanguageSource } from './language';
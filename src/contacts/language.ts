@@ -0,0 +1,171 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { UnifiedMessage } from '../types';
+import { systemClock, type Clock } from '../utils/clock';
+import { LruCache } from '../utils/lru';
+import { normalizeWhatsAppTarget } from '../utils/normalize';
+
+// Where a contact's language came from; a source only replaces one of equal or lower rank
+export type LanguageSource = 'detected' | 'template' | 'explicit';
+
+export interface ContactLanguage {
+  language: string;                     // Template language code, e.g. es, pt_BR
+  source: LanguageSource;
+  updatedAt: number;
+}
+
+// Guesses the language of a message, e.g. by wrapping a language identification library
+export type LanguageDetector = (text: string) => string | undefined | Promise<string | undefined>;
+
+// Persists languages per wa_id
+export interface ContactLanguageStore {
+  get(waId: string): Promise<ContactLanguage | undefined>;
+  set(waId: string, language: ContactLanguage | undefined): Promise<void>;
+}
+
+export class InMemoryContactLanguageStore implements ContactLanguageStore {
+  private languages = new Map<string, ContactLanguage>();
+
+  async get(waId: string): Promise<ContactLanguage | undefined> {
+    return this.languages.get(waId);
+  }
+
+  async set(waId: string, language: ContactLanguage | undefined): Promise<void> {
+    if (language) this.languages.set(waId, language);
+    else this.languages.delete(waId);
+  }
+}
+
+// Keeps each contact's language under its own runtime cache key, so concurrent updates for
+// different contacts can't overwrite each other; writes for one contact run in order
+export class RuntimeContactLanguageStore implements ContactLanguageStore {
+  // Languages stored as one map, first shared and then per account, read until the contact's next write
+  private static readonly LEGACY_KEY = 'whatsapp-contact-languages';
+  private writes = new Map<string, Promise<void>>();
+
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string
+  ) {}
+
+  async get(waId: string): Promise<ContactLanguage | undefined> {
+    const stored = await this.runtime.getCache<ContactLanguage | null>(this.key(waId));
+    if (stored !== undefined) return stored ?? undefined;
+    const legacy =
+      (await this.runtime.getCache<Record<string, ContactLanguage>>(`${RuntimeContactLanguageStore.LEGACY_KEY}-${this.accountId}`)) ??
+      (await this.runtime.getCache<Record<string, ContactLanguage>>(RuntimeContactLanguageStore.LEGACY_KEY));
+    return legacy?.[waId];
+  }
+
+  set(waId: string, language: ContactLanguage | undefined): Promise<void> {
+    const write = (this.writes.get(waId) ?? Promise.resolve())
+      .catch(() => {})
+      .then(async () => {
+        // null rather than a deleted key, so a cleared language doesn't fall back to the legacy map
+        await this.runtime.setCache(this.key(waId), language ?? null);
+      });
+    this.writes.set(waId, write);
+    const settled = () => {
+      if (this.writes.get(waId) === write) this.writes.delete(waId);
+    };
+    write.then(settled, settled);
+    return write;
+  }
+
+  private key(waId: string): string {
+    return `whatsapp-contact-language-${this.accountId}-${waId}`;
+  }
+}
+
+const RANK: Record<LanguageSource, number> = { detected: 0, template: 1, explicit: 2 };
+const MAX_TRACKED_TEMPLATES = 10_000;
+// Shorter texts say too little about their language to be worth detecting
+const MIN_DETECTABLE_LENGTH = 12;
+
+const languageKey = (code: string) => code.replace('-', '_').toLowerCase();
+const baseLanguage = (code: string) => languageKey(code).split('_')[0];
+
+// Whether two language codes name the same language, ignoring region (en_US and en-GB do)
+export function sameLanguage(a: string, b: string): boolean {
+  return baseLanguage(a) === baseLanguage(b);
+}
+
+/**
+ * Pick the variant of a template to send in `preferred`: the same code, else the bare language
+ * (es for es_MX), else any regional variant of it (pt_BR for pt).
+ */
+export function matchTemplateLanguage(preferred: string, available: string[]): string | undefined {
+  const wanted = languageKey(preferred);
+  const base = baseLanguage(preferred);
+  return (
+    available.find((code) => languageKey(code) === wanted) ??
+    available.find((code) => languageKey(code) === base) ??
+    available.find((code) => baseLanguage(code) === base)
+  );
+}
+
+/**
+ * The language each contact is likely to read: set explicitly, learned from the language of a
+ * template they reply to, or detected from what they write. Template sends are remembered by
+ * message id, since a reply only carries the id of the message it answers.
+ */
+export class ContactLanguages {
+  private store: ContactLanguageStore;
+  private detector?: LanguageDetector;
+  private templateSends: LruCache<string>;
+
+  constructor(store?: ContactLanguageStore, private readonly clock: Clock = systemClock) {
+    this.store = store ?? new InMemoryContactLanguageStore();
+    this.templateSends = new LruCache({ maxChats: MAX_TRACKED_TEMPLATES, clock });
+  }
+
+  useStore(store: ContactLanguageStore): void {
+    this.store = store;
+  }
+
+  useDetector(detector: LanguageDetector | undefined): void {
+    this.detector = detector;
+  }
+
+  trackTemplate(messageId: string, language: string): void {
+    if (messageId) this.templateSends.set(messageId, language);
+  }
+
+  async get(contact: string): Promise<ContactLanguage | undefined> {
+    return this.store.get(this.key(contact));
+  }
+
+  // Returns the contact's language afterwards, which stays as it was when outranked
+  async set(contact: string, language: string, source: LanguageSource = 'explicit'): Promise<ContactLanguage> {
+    const waId = this.key(contact);
+    const current = await this.store.get(waId);
+    if (current && RANK[current.source] > RANK[source]) return current;
+    // Most inbound messages confirm what is already known; don't rewrite it each time
+    if (current && current.language === language && current.source === source) return current;
+    const next = { language, source, updatedAt: this.clock.now() };
+    await this.store.set(waId, next);
+    return next;
+  }
+
+  async clear(contact: string): Promise<void> {
+    await this.store.set(this.key(contact), undefined);
+  }
+
+  // Learn from an inbound message; returns the language it suggested, if any
+  async observe(message: UnifiedMessage): Promise<string | undefined> {
+    const template = message.replyTo ? this.templateSends.get(message.replyTo) : undefined;
+    if (template) {
+      await this.set(message.from, template, 'template');
+      return template;
+    }
+    if (!this.detector || message.type !== 'text' || message.content.length < MIN_DETECTABLE_LENGTH) {
+      return undefined;
+    }
+    const detected = await this.detector(message.content);
+    if (detected) await this.set(message.from, detected, 'detected');
+    return detected;
+  }
+
+  private key(contact: string): string {
+    return normalizeWhatsAppTarget(contact)?.id ?? contact;
+  }
+}
@@ -0,0 +1,162 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import { normalizeWhatsAppTarget } from '../utils/normalize';
+
+// Persists labels per wa_id
+export interface ContactTagStore {
+  get(waId: string): Promise<string[]>;
+  set(waId: string, tags: string[]): Promise<void>;
+  entries(): Promise<Array<[string, string[]]>>;
+}
+
+export class InMemoryContactTagStore implements ContactTagStore {
+  private tags = new Map<string, string[]>();
+
+  async get(waId: string): Promise<string[]> {
+    return this.tags.get(waId) ?? [];
+  }
+
+  async set(waId: string, tags: string[]): Promise<void> {
+    if (tags.length === 0) this.tags.delete(waId);
+    else this.tags.set(waId, tags);
+  }
+
+  async entries(): Promise<Array<[string, string[]]>> {
+    return [...this.tags.entries()];
+  }
+}
+
+// Keeps each contact's tags under its own runtime cache key, plus an index of tagged contacts so
+// queries don't need a key scan. Writes for one contact run in order, as do index updates.
+export class RuntimeContactTagStore implements ContactTagStore {
+  // Tags stored as one map, first shared and then per account, read until the contact's next write
+  private static readonly LEGACY_KEY = 'whatsapp-contact-tags';
+  private writes = new Map<string, Promise<void>>();
+  private indexWrite: Promise<void> = Promise.resolve();
+
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string
+  ) {}
+
+  async get(waId: string): Promise<string[]> {
+    return (await this.runtime.getCache<string[]>(this.key(waId))) ?? (await this.legacy())[waId] ?? [];
+  }
+
+  set(waId: string, tags: string[]): Promise<void> {
+    const write = (this.writes.get(waId) ?? Promise.resolve())
+      .catch(() => {})
+      .then(async () => {
+        // An empty list rather than a deleted key, so removed tags don't fall back to the legacy map
+        await this.runtime.setCache(this.key(waId), tags);
+        await this.index(waId, tags.length > 0);
+      });
+    this.writes.set(waId, write);
+    const settled = () => {
+      if (this.writes.get(waId) === write) this.writes.delete(waId);
+    };
+    write.then(settled, settled);
+    return write;
+  }
+
+  async entries(): Promise<Array<[string, string[]]>> {
+    const entries = await Promise.all(
+      (await this.loadIndex()).map(async (waId): Promise<[string, string[]]> => [waId, await this.get(waId)])
+    );
+    return entries.filter(([, tags]) => tags.length > 0);
+  }
+
+  private index(waId: string, tagged: boolean): Promise<void> {
+    this.indexWrite = this.indexWrite
+      .catch(() => {})
+      .then(async () => {
+        const index = await this.loadIndex();
+        if (index.includes(waId) === tagged) return;
+        await this.runtime.setCache(this.indexKey, tagged ? [...index, waId] : index.filter((id) => id !== waId));
+      });
+    return this.indexWrite;
+  }
+
+  private async loadIndex(): Promise<string[]> {
+    return (await this.runtime.getCache<string[]>(this.indexKey)) ?? Object.keys(await this.legacy());
+  }
+
+  private async legacy(): Promise<Record<string, string[]>> {
+    return (
+      (await this.runtime.getCache<Record<string, string[]>>(`${RuntimeContactTagStore.LEGACY_KEY}-${this.accountId}`)) ??
+      (await this.runtime.getCache<Record<string, string[]>>(RuntimeContactTagStore.LEGACY_KEY)) ??
+      {}
+    );
+  }
+
+  private key(waId: string): string {
+    return `whatsapp-contact-tag-${this.accountId}-${waId}`;
+  }
+
+  private get indexKey(): string {
+    return `whatsapp-contact-tag-index-${this.accountId}`;
+  }
+}
+
+// Selects broadcast recipients by tag
+export interface AudienceSelector {
+  tags: string[];
+  match?: 'any' | 'all';     // Default any
+  exclude?: string[];        // Contacts with any of these tags are left out
+}
+
+export class ContactTags {
+  private store: ContactTagStore;
+
+  constructor(store?: ContactTagStore) {
+    this.store = store ?? new InMemoryContactTagStore();
+  }
+
+  useStore(store: ContactTagStore): void {
+    this.store = store;
+  }
+
+  async add(contact: string, tags: string[]): Promise<string[]> {
+    const waId = this.key(contact);
+    const current = await this.store.get(waId);
+    const next = [...new Set([...current, ...tags.map(normalizeTag)])];
+    await this.store.set(waId, next);
+    return next;
+  }
+
+  async remove(contact: string, tags: string[]): Promise<string[]> {
+    const waId = this.key(contact);
+    const removed = new Set(tags.map(normalizeTag));
+    const next = (await this.store.get(waId)).filter((t) => !removed.has(t));
+    await this.store.set(waId, next);
+    return next;
+  }
+
+  async get(contact: string): Promise<string[]> {
+    return this.store.get(this.key(contact));
+  }
+
+  async hasTag(contact: string, tag: string): Promise<boolean> {
+    return (await this.get(contact)).includes(normalizeTag(tag));
+  }
+
+  // wa_ids matching the selector
+  async select(selector: AudienceSelector): Promise<string[]> {
+    const wanted = selector.tags.map(normalizeTag);
+    const excluded = (selector.exclude ?? []).map(normalizeTag);
+    const matches = selector.match === 'all'
+      ? (tags: string[]) => wanted.every((t) => tags.includes(t))
+      : (tags: string[]) => wanted.some((t) => tags.includes(t));
+
+    return (await this.store.entries())
+      .filter(([, tags]) => matches(tags) && !excluded.some((t) => tags.includes(t)))
+      .map(([waId]) => waId);
+  }
+
+  private key(contact: string): string {
+    return normalizeWhatsAppTarget(contact)?.id ?? contact;
+  }
+}
+
+function normalizeTag(tag: string): string {
+  return tag.trim().toLowerCase();
+}
@@ -0,0 +1,37 @@
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface ContactCheckResult {
+  input: string;
+  onWhatsApp: boolean;
+  waId?: string;             // Canonical wa_id (Cloud API) or JID (Baileys) when on WhatsApp
+}
+
+const DEFAULT_TTL_MS = 24 * 60 * 60 * 1000;
+
+// Remembers which numbers are (not) on WhatsApp so sends to invalid numbers fail fast
+export class ContactValidityCache {
+  private entries = new Map<string, { result: ContactCheckResult; expiresAt: number }>();
+
+  constructor(
+    private ttlMs = DEFAULT_TTL_MS,
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  get(key: string, now = this.clock.now()): ContactCheckResult | undefined {
+    const entry = this.entries.get(key);
+    if (!entry) return undefined;
+    if (entry.expiresAt <= now) {
+      this.entries.delete(key);
+      return undefined;
+    }
+    return entry.result;
+  }
+
+  set(key: string, result: ContactCheckResult, now = this.clock.now()): void {
+    this.entries.set(key, { result, expiresAt: now + this.ttlMs });
+  }
+
+  clear(): void {
+    this.entries.clear();
+  }
+}
@@ -0,0 +1,103 @@
+export class WhatsAppError extends Error {
+  readonly code: string;
+
+  constructor(code: string, message: string, options?: { cause?: unknown }) {
+    super(message, options);
+    this.name = 'WhatsAppError';
+    this.code = code;
+  }
+
+  // Lossless conversion for errors from clients and dependencies: WhatsAppErrors pass through,
+  // Graph API responses become GraphApiError, anything else is wrapped and kept as `cause`
+  static from(error: unknown, code = 'UNKNOWN'): WhatsAppError {
+    if (error instanceof WhatsAppError) return error;
+    const graph = GraphApiError.fromResponse(error);
+    if (graph) return graph;
+    return new WhatsAppError(code, error instanceof Error ? error.message : String(error), { cause: error });
+  }
+}
+
+export class UnsupportedGroupOperationError extends WhatsAppError {
+  readonly operation: string;
+  readonly groupId: string;
+
+  constructor(operation: string, groupId: string) {
+    super(
+      'UNSUPPORTED_GROUP_OPERATION',
+      `Operation "${operation}" is not supported for group ${groupId}`
+    );
+    this.name = 'UnsupportedGroupOperationError';
+    this.operation = operation;
+    this.groupId = groupId;
+  }
+}
+
+export class NotOnWhatsAppError extends WhatsAppError {
+  readonly recipient: string;
+
+  constructor(recipient: string) {
+    super('NOT_ON_WHATSAPP', `${recipient} is not a WhatsApp user`);
+    this.name = 'NotOnWhatsAppError';
+    this.recipient = recipient;
+  }
+}
+
+// An account's circuit breaker is open after repeated send failures
+export class CircuitOpenError extends WhatsAppError {
+  readonly accountId: string;
+  readonly retryInMs: number;
+
+  constructor(accountId: string, retryInMs: number) {
+    super('CIRCUIT_OPEN', `Sends from account ${accountId} are paused after repeated failures; retry in ${Math.ceil(retryInMs / 1000)}s`);
+    this.name = 'CircuitOpenError';
+    this.accountId = accountId;
+    this.retryInMs = retryInMs;
+  }
+}
+
+export class InteractiveLimitError extends WhatsAppError {
+  readonly field: string;
+  readonly limit: number;
+  readonly actual: number;
+
+  constructor(field: string, limit: number, actual: number, unit = 'characters') {
+    super('INTERACTIVE_LIMIT', `${field} has ${actual} ${unit}; WhatsApp allows at most ${limit}`);
+    this.name = 'InteractiveLimitError';
+    this.field = field;
+    this.limit = limit;
+    this.actual = actual;
+  }
+}
+
+// Error body returned by the Graph API: { error: { message, code, error_subcode, fbtrace_id } }
+export class GraphApiError extends WhatsAppError {
+  readonly status?: number;
+  readonly graphCode?: number;
+  readonly subcode?: number;
+  readonly fbtraceId?: string;
+
+  constructor(
+    message: string,
+    details: { status?: number; code?: number; subcode?: number; fbtraceId?: string; cause?: unknown } = {}
+  ) {
+    super('GRAPH_API_ERROR', message, { cause: details.cause });
+    this.name = 'GraphApiError';
+    this.status = details.status;
+    this.graphCode = details.code;
+    this.subcode = details.subcode;
+    this.fbtraceId = details.fbtraceId;
+  }
+
+  // Wrap an axios error; returns undefined when there is no Graph error body
+  static fromResponse(error: any): GraphApiError | undefined {
+    const body = error?.response?.data?.error;
+    if (!body) return undefined;
+    return new GraphApiError(body.message ?? 'Graph API request failed', {
+      status: error.response.status,
+      code: body.code,
+      subcode: body.error_subcode,
+      fbtraceId: body.fbtrace_id,
+      cause: error,
+    });
+  }
+}
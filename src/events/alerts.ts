@@ -0,0 +1,107 @@
+import { buildTemplate } from '../templates/builder';
+import type { WhatsAppMessage } from '../types';
+import type { EventSink } from './sinks';
+import type { WhatsAppEvent, WhatsAppEventKind } from './types';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface AdminAlertConfig {
+  numbers: string[];                    // Operators alerted on WhatsApp
+  // Approved template with one body parameter, so alerts also arrive outside the 24h service window
+  template?: { name: string; language?: string };
+  failedSendThreshold?: number;         // Alert when an account's failed send log reaches this size (default 20)
+  cooldownMs?: number;                  // Repeat the same alert for an account at most this often (default 15 min)
+}
+
+export type AlertSender = (message: WhatsAppMessage, event: WhatsAppEvent) => Promise<unknown>;
+
+export const ALERT_KINDS: WhatsAppEventKind[] = [
+  'auth_failed',
+  'account_degraded',
+  'circuit_opened',
+  'send_failed',
+  'webhook_rejected',
+];
+
+const DEFAULT_FAILED_SEND_THRESHOLD = 20;
+const DEFAULT_COOLDOWN_MS = 15 * 60 * 1000;
+
+// Template parameters may not contain newlines, tabs or runs of spaces
+function templateSafe(text: string): string {
+  return text.replace(/\s+/g, ' ').trim();
+}
+
+// Text of the alert for a critical event, with a key that groups repeats; undefined for anything else
+export function alertFor(
+  event: WhatsAppEvent,
+  failedSendThreshold = DEFAULT_FAILED_SEND_THRESHOLD
+): { key: string; text: string } | undefined {
+  const account = `WhatsApp account ${event.accountId}`;
+  switch (event.kind) {
+    case 'auth_failed':
+      return { key: 'auth', text: `${account} failed to authenticate: ${event.reason}` };
+    case 'account_degraded': {
+      const token = event.problems.find((p) => p.code === 'INVALID_ACCESS_TOKEN');
+      return token ? { key: 'auth', text: `${account} failed to authenticate: ${token.message}` } : undefined;
+    }
+    case 'circuit_opened':
+      return {
+        key: 'circuit',
+        text: `${account} stopped sending after ${event.failures} failures in a row: ${event.error}`,
+      };
+    case 'send_failed':
+      return event.failedSends >= failedSendThreshold
+        ? { key: 'failed_sends', text: `${account} has ${event.failedSends} failed sends waiting for replay` }
+        : undefined;
+    case 'webhook_rejected':
+      return { key: 'signature', text: `${account} rejected a webhook with an invalid signature` };
+    default:
+      return undefined;
+  }
+}
+
+/**
+ * Sends a WhatsApp message to every admin number when a critical event occurs: authentication
+ * failures, an open circuit breaker, a growing failed send log or webhook signature failures.
+ * The same alert for the same account is repeated at most once per cooldown.
+ */
+export class AdminAlertSink implements EventSink {
+  readonly kinds = ALERT_KINDS;
+  private lastSent = new Map<string, number>();
+
+  constructor(
+    private readonly config: AdminAlertConfig,
+    private readonly sendMessage: AlertSender,
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  async send(event: WhatsAppEvent): Promise<void> {
+    const alert = alertFor(event, this.config.failedSendThreshold);
+    if (!alert) return;
+
+    const key = `${event.accountId}:${alert.key}`;
+    const now = this.clock.now();
+    const last = this.lastSent.get(key);
+    if (last !== undefined && now - last < (this.config.cooldownMs ?? DEFAULT_COOLDOWN_MS)) return;
+    this.lastSent.set(key, now);
+
+    const errors: unknown[] = [];
+    for (const to of this.config.numbers) {
+      try {
+        await this.sendMessage(this.toMessage(to, alert.text), event);
+      } catch (error) {
+        errors.push(error);
+      }
+    }
+    if (errors.length > 0) throw errors[0];
+  }
+
+  private toMessage(to: string, text: string): WhatsAppMessage {
+    const { template } = this.config;
+    if (!template) return { type: 'text', to, content: text };
+    return {
+      type: 'template',
+      to,
+      content: buildTemplate(template.name, template.language ?? 'en_US', { body: [templateSafe(text)] }),
+    };
+  }
+}
@@ -0,0 +1,63 @@
+import { logger } from '@elizaos/core';
+import type { WhatsAppEvent, WhatsAppEventKind, WhatsAppEventOf } from './types';
+
+export type WhatsAppEventListener<E extends WhatsAppEvent = WhatsAppEvent> = (
+  event: E
+) => void | Promise<void>;
+
+interface Subscription {
+  listener: WhatsAppEventListener;
+  kinds?: ReadonlySet<WhatsAppEventKind>;
+}
+
+// Fan-out of typed plugin events; every subscriber sees every event it asked for
+export class EventBus {
+  private subscriptions = new Set<Subscription>();
+
+  // Subscribe to all events, or only the listed kinds. Returns an unsubscribe function.
+  subscribe(listener: WhatsAppEventListener): () => void;
+  subscribe<K extends WhatsAppEventKind>(
+    kinds: K[],
+    listener: WhatsAppEventListener<WhatsAppEventOf<K>>
+  ): () => void;
+  subscribe(
+    kindsOrListener: WhatsAppEventKind[] | WhatsAppEventListener,
+    maybeListener?: WhatsAppEventListener<never>
+  ): () => void {
+    const subscription: Subscription =
+      typeof kindsOrListener === 'function'
+        ? { listener: kindsOrListener }
+        : { listener: maybeListener as WhatsAppEventListener, kinds: new Set(kindsOrListener) };
+
+    this.subscriptions.add(subscription);
+    return () => {
+      this.subscriptions.delete(subscription);
+    };
+  }
+
+  // Deliver to every matching subscriber; one failing listener does not affect the others
+  publish(event: WhatsAppEvent): void {
+    void this.deliver(event);
+  }
+
+  // Like publish, but resolves once async subscribers have finished
+  async deliver(event: WhatsAppEvent): Promise<void> {
+    const pending: Promise<void>[] = [];
+    for (const { listener, kinds } of [...this.subscriptions]) {
+      if (kinds && !kinds.has(event.kind)) continue;
+      try {
+        const result = listener(event);
+        if (result instanceof Promise) {
+          pending.push(result.catch((error: Error) => logger.error('[WhatsApp] Event listener failed:', error.message)));
+        }
+      } catch (error) {
+        logger.error('[WhatsApp] Event listener failed:', (error as Error).message);
+      }
+    }
+    await Promise.all(pending);
+  }
+
+  get size(): number {
+    return this.subscriptions.size;
+  }
+}
@@ -0,0 +1,73 @@
+import { createHmac } from 'crypto';
+import axios from 'axios';
+import type { EventSink } from './sinks';
+import type { WhatsAppEvent, WhatsAppEventKind } from './types';
+
+// Structural subset of axios used for delivery, so tests and callers can supply their own
+export interface HttpPoster {
+  post(url: string, body: string, config: { headers: Record<string, string>; timeout?: number }): Promise<unknown>;
+}
+
+export interface WebhookForwarderConfig {
+  url: string;
+  secret?: string;                      // Signs the body with HMAC-SHA256 when set
+  kinds?: WhatsAppEventKind[];          // Default: message_received, status_changed
+  maxRetries?: number;                  // Default 3
+  backoffMs?: number;                   // Initial delay, doubled per retry. Default 500
+  timeoutMs?: number;                   // Default 10000
+}
+
+export const SIGNATURE_HEADER = 'X-WhatsApp-Signature';
+export const DEFAULT_FORWARD_KINDS: WhatsAppEventKind[] = ['message_received', 'status_changed'];
+
+// "sha256=<hex>" over "<timestamp>.<body>", so receivers can reject replayed requests
+export function signPayload(secret: string, timestamp: string, body: string): string {
+  return `sha256=${createHmac('sha256', secret).update(`${timestamp}.${body}`).digest('hex')}`;
+}
+
+// Network errors, 429 and 5xx are worth retrying; other client errors are not
+function isRetryable(error: unknown): boolean {
+  const status = (error as { response?: { status?: number } }).response?.status;
+  return status === undefined || status === 429 || status >= 500;
+}
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+export class WebhookForwarder implements EventSink {
+  readonly kinds: WhatsAppEventKind[];
+
+  constructor(
+    private readonly config: WebhookForwarderConfig,
+    private readonly http: HttpPoster = axios
+  ) {
+    this.kinds = config.kinds ?? DEFAULT_FORWARD_KINDS;
+  }
+
+  async send(event: WhatsAppEvent): Promise<void> {
+    const body = JSON.stringify(event);
+    const timestamp = String(Math.floor(Date.now() / 1000));
+    const headers: Record<string, string> = {
+      'Content-Type': 'application/json',
+      'X-WhatsApp-Event': event.kind,
+      'X-WhatsApp-Timestamp': timestamp,
+    };
+    if (this.config.secret) {
+      headers[SIGNATURE_HEADER] = signPayload(this.config.secret, timestamp, body);
+    }
+
+    const maxRetries = this.config.maxRetries ?? 3;
+    const backoffMs = this.config.backoffMs ?? 500;
+    for (let attempt = 0; ; attempt++) {
+      try {
+        await this.http.post(this.config.url, body, {
+          headers,
+          timeout: this.config.timeoutMs ?? 10_000,
+        });
+        return;
+      } catch (error) {
+        if (attempt >= maxRetries || !isRetryable(error)) throw error;
+        await sleep(backoffMs * 2 ** attempt);
+      }
+    }
+  }
+}
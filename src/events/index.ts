@@ -0,0 +1,25 @@
+export { EventBus } from './bus';
+export type { WhatsAppEventListener } from './bus';
+export type {
+  WhatsAppEvent,
+  WhatsAppEventKind,
+  WhatsAppEventOf,
+  MessageStatusUpdate,
+  MessagePricing,
+  TemplateStatusUpdate,
+} from './types';
+export { attachSink, createKafkaSink, createNatsSink, jsonSerializer } from './sinks';
+export type {
+  EventSink,
+  EventSerializer,
+  KafkaProducerLike,
+  KafkaSinkOptions,
+  NatsConnectionLike,
+  NatsSinkOptions,
+} from './sinks';
+export { WebhookForwarder, signPayload, SIGNATURE_HEADER, DEFAULT_FORWARD_KINDS } from './forwarder';
+export type { WebhookForwarderConfig, HttpPoster } from './forwarder';
+export { WhatsAppEventType, runtimeEventType } from './runtime';
+export type { WhatsAppEventTypeName, WhatsAppRuntimeEventPayload } from './runtime';
+export { AdminAlertSink, ALERT_KINDS, alertFor } from './alerts';
+export type { AdminAlertConfig, AlertSender } from './alerts';
@@ -0,0 +1,39 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { WhatsAppEvent, WhatsAppEventKind } from './types';
+
+// Runtime event names for bus events, so other plugins can handle them from their `events` map
+export const WhatsAppEventType = {
+  MESSAGE_RECEIVED: 'WHATSAPP_MESSAGE_RECEIVED',
+  MESSAGE_SENT: 'WHATSAPP_MESSAGE_SENT',
+  MESSAGE_ECHOED: 'WHATSAPP_MESSAGE_ECHOED',
+  MESSAGE_UNSUPPORTED: 'WHATSAPP_MESSAGE_UNSUPPORTED',
+  TEMPLATE_BUTTON_CLICKED: 'WHATSAPP_TEMPLATE_BUTTON_CLICKED',
+  STATUS_CHANGED: 'WHATSAPP_STATUS_CHANGED',
+  PAYMENT_STATUS_CHANGED: 'WHATSAPP_PAYMENT_STATUS_CHANGED',
+  PAIRING_REQUESTED: 'WHATSAPP_PAIRING_REQUESTED',
+  CONNECTION_CHANGED: 'WHATSAPP_CONNECTION_CHANGED',
+  TEMPLATE_STATUS_CHANGED: 'WHATSAPP_TEMPLATE_STATUS_CHANGED',
+  TEMPLATE_PACING_CHANGED: 'WHATSAPP_TEMPLATE_PACING_CHANGED',
+  CALL: 'WHATSAPP_CALL',
+  POLL_RESULT: 'WHATSAPP_POLL_RESULT',
+  CIRCUIT_OPENED: 'WHATSAPP_CIRCUIT_OPENED',
+  CIRCUIT_CLOSED: 'WHATSAPP_CIRCUIT_CLOSED',
+  ACCOUNT_DEGRADED: 'WHATSAPP_ACCOUNT_DEGRADED',
+  ACCOUNT_RECOVERED: 'WHATSAPP_ACCOUNT_RECOVERED',
+  SEND_FAILED: 'WHATSAPP_SEND_FAILED',
+  AUTH_FAILED: 'WHATSAPP_AUTH_FAILED',
+  WEBHOOK_REJECTED: 'WHATSAPP_WEBHOOK_REJECTED',
+} as const;
+
+export type WhatsAppEventTypeName = (typeof WhatsAppEventType)[keyof typeof WhatsAppEventType];
+
+export function runtimeEventType(kind: WhatsAppEventKind): WhatsAppEventTypeName {
+  return `WHATSAPP_${kind.toUpperCase()}` as WhatsAppEventTypeName;
+}
+
+// Payload handed to runtime event handlers
+export interface WhatsAppRuntimeEventPayload<E extends WhatsAppEvent = WhatsAppEvent> {
+  runtime: IAgentRuntime;
+  source: 'whatsapp';
+  event: E;
+}
@@ -0,0 +1,78 @@
+import type { EventBus } from './bus';
+import type { WhatsAppEvent, WhatsAppEventKind } from './types';
+
+export type EventSerializer = (event: WhatsAppEvent) => string | Uint8Array;
+
+// Destination for bus events, e.g. a message broker topic
+export interface EventSink {
+  send(event: WhatsAppEvent): Promise<void>;
+}
+
+// Structural subset of a kafkajs Producer
+export interface KafkaProducerLike {
+  send(record: {
+    topic: string;
+    messages: Array<{ key?: string; value: string | Buffer; headers?: Record<string, string> }>;
+  }): Promise<unknown>;
+}
+
+// Structural subset of a nats.js NatsConnection
+export interface NatsConnectionLike {
+  publish(subject: string, data?: Uint8Array): void;
+}
+
+export interface KafkaSinkOptions {
+  topic?: string | ((event: WhatsAppEvent) => string);   // Default "whatsapp-events"
+  key?: (event: WhatsAppEvent) => string | undefined;    // Default: the account id
+  serialize?: EventSerializer;                           // Default: JSON
+}
+
+export interface NatsSinkOptions {
+  subject?: string | ((event: WhatsAppEvent) => string); // Default "whatsapp.<accountId>.<kind>"
+  serialize?: EventSerializer;                           // Default: JSON
+}
+
+export const jsonSerializer: EventSerializer = (event) => JSON.stringify(event);
+
+function resolve(option: string | ((event: WhatsAppEvent) => string), event: WhatsAppEvent): string {
+  return typeof option === 'function' ? option(event) : option;
+}
+
+export function createKafkaSink(producer: KafkaProducerLike, options: KafkaSinkOptions = {}): EventSink {
+  const serialize = options.serialize ?? jsonSerializer;
+  return {
+    async send(event) {
+      const value = serialize(event);
+      await producer.send({
+        topic: resolve(options.topic ?? 'whatsapp-events', event),
+        messages: [
+          {
+            key: options.key ? options.key(event) : event.accountId,
+            value: typeof value === 'string' ? value : Buffer.from(value),
+            headers: { kind: event.kind },
+          },
+        ],
+      });
+    },
+  };
+}
+
+export function createNatsSink(connection: NatsConnectionLike, options: NatsSinkOptions = {}): EventSink {
+  const serialize = options.serialize ?? jsonSerializer;
+  const encoder = new TextEncoder();
+  return {
+    async send(event) {
+      const value = serialize(event);
+      connection.publish(
+        resolve(options.subject ?? ((e) => `whatsapp.${e.accountId}.${e.kind}`), event),
+        typeof value === 'string' ? encoder.encode(value) : value
+      );
+    },
+  };
+}
+
+// Forward bus events (all, or only the listed kinds) to a sink. Returns an unsubscribe function.
+export function attachSink(bus: EventBus, sink: EventSink, kinds?: WhatsAppEventKind[]): () => void {
+  const forward = (event: WhatsAppEvent) => sink.send(event);
+  return kinds ? bus.subscribe(kinds, forward) : bus.subscribe(forward);
+}
@@ -0,0 +1,73 @@
+import type { HealthProblem } from '../accounts/health';
+import type { CallEvent } from '../calls';
+import type { PaymentStatusUpdate } from '../payments';
+import type { PollResult } from '../polls';
+import type { TemplatePacingUpdate } from '../templates/pacing';
+import type {
+  ConnectionStatus,
+  MessageEcho,
+  QRCodeData,
+  TemplateButtonReply,
+  UnifiedMessage,
+  UnsupportedMessage,
+  WhatsAppMessage,
+} from '../types';
+
+export interface MessageStatusUpdate {
+  messageId: string;                    // wamid of the outbound message
+  status: string;                       // sent, delivered, read, failed
+  recipient: string;
+  timestamp: number;
+  pricing?: MessagePricing;             // Cloud API: how the message is charged
+  conversation?: {
+    id: string;
+    origin?: string;                    // marketing, utility, authentication, service, ...
+    expiresAt?: number;                 // Seconds since epoch
+  };
+}
+
+export interface MessagePricing {
+  billable: boolean;
+  category: string;                     // marketing, utility, authentication, service, referral_conversion
+  model?: string;                       // CBP (per conversation) or PMP (per message)
+}
+
+export interface TemplateStatusUpdate {
+  templateId: string;
+  name: string;
+  language?: string;
+  status: string;                       // APPROVED, REJECTED, PAUSED, DISABLED, ...
+  reason?: string;
+}
+
+interface EventBase {
+  accountId: string;
+}
+
+export type WhatsAppEvent = EventBase &
+  (
+    | { kind: 'message_received'; message: UnifiedMessage }
+    | { kind: 'message_sent'; message: WhatsAppMessage; messageId: string }
+    | { kind: 'message_echoed'; message: MessageEcho }
+    | { kind: 'message_unsupported'; message: UnsupportedMessage }
+    | { kind: 'template_button_clicked'; message: UnifiedMessage; button: TemplateButtonReply }
+    | { kind: 'status_changed'; status: MessageStatusUpdate }
+    | { kind: 'payment_status_changed'; payment: PaymentStatusUpdate }
+    | { kind: 'pairing_requested'; qr: QRCodeData }
+    | { kind: 'connection_changed'; status: ConnectionStatus }
+    | { kind: 'template_status_changed'; template: TemplateStatusUpdate }
+    | { kind: 'template_pacing_changed'; pacing: TemplatePacingUpdate }
+    | { kind: 'call'; call: CallEvent }
+    | { kind: 'poll_result'; result: PollResult }
+    | { kind: 'circuit_opened'; failures: number; retryInMs: number; error: string }
+    | { kind: 'circuit_closed' }
+    | { kind: 'account_degraded'; problems: HealthProblem[] }
+    | { kind: 'account_recovered' }
+    | { kind: 'send_failed'; message: WhatsAppMessage; error: { code: string; message: string }; failedSends: number }
+    | { kind: 'auth_failed'; reason: string }
+    | { kind: 'webhook_rejected'; reason: 'signature' }
+  );
+
+export type WhatsAppEventKind = WhatsAppEvent['kind'];
+
+export type WhatsAppEventOf<K extends WhatsAppEventKind> = Extract<WhatsAppEvent, { kind: K }>;
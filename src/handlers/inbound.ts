@@ -0,0 +1,127 @@
+import { EventEmitter } from "events";
+import { WhatsAppError } from "../errors";
+
+// What happens to new items once `capacity` are waiting: wait until there is room, discard the
+// oldest waiting item, or refuse the whole notification
+export type InboundOverflow = "wait" | "drop_oldest" | "reject";
+
+export interface InboundConfig {
+    workers?: number;           // Webhook messages and statuses handled at once (default 8)
+    capacity?: number;          // Items waiting for a free worker before the overflow policy applies (default 100)
+    overflow?: InboundOverflow; // Default "wait"
+}
+
+export interface InboundItem {
+    key?: string;               // Items with the same key run one at a time, in order
+    run: () => Promise<void>;
+}
+
+interface Job extends InboundItem {
+    queuedAt: number;
+    resolve: () => void;
+    reject: (error: unknown) => void;
+}
+
+const DEFAULT_WORKERS = 8;
+const DEFAULT_CAPACITY = 100;
+
+/**
+ * Bounded channel feeding a fixed pool of workers. Items with the same key run one at a time and
+ * in order, so one sender's messages are not reordered. Emits "depth" with the number of waiting
+ * items whenever it changes, "processed" with `{ waitMs, runMs }` after each item, and "dropped"
+ * with a count when the overflow policy discards or refuses items.
+ */
+export class InboundChannel extends EventEmitter {
+    private queue: Job[] = [];
+    private active = 0;
+    private busyKeys = new Set<string>();
+    private waiting: Array<() => void> = [];
+    readonly workers: number;
+    readonly capacity: number;
+    readonly overflow: InboundOverflow;
+
+    constructor(config: InboundConfig = {}) {
+        super();
+        this.workers = Math.max(1, config.workers ?? DEFAULT_WORKERS);
+        this.capacity = Math.max(1, config.capacity ?? DEFAULT_CAPACITY);
+        this.overflow = config.overflow ?? "wait";
+    }
+
+    // Items waiting for a worker
+    get depth(): number {
+        return this.queue.length;
+    }
+
+    // Items being handled
+    get running(): number {
+        return this.active;
+    }
+
+    // Handle a batch of items under the overflow policy; rejects with the first failure once all are done
+    async process(items: InboundItem[]): Promise<void> {
+        const room = this.capacity - this.queue.length + (this.workers - this.active);
+        if (this.overflow === "reject" && items.length > room) {
+            this.emit("dropped", items.length);
+            throw new WhatsAppError("INBOUND_FULL", `Inbound queue is full (${this.queue.length} waiting)`);
+        }
+
+        // Failures are collected as they happen, so none goes unhandled while later items wait for room
+        const pending: Promise<void>[] = [];
+        const errors: unknown[] = [];
+        for (const item of items) {
+            if (this.overflow === "wait") await this.ready();
+            pending.push(this.push(item.run, item.key).catch((error) => void errors.push(error)));
+        }
+        await Promise.all(pending);
+        if (errors.length > 0) throw errors[0];
+    }
+
+    // Resolves once the channel has room for another item
+    async ready(): Promise<void> {
+        while (this.queue.length >= this.capacity) {
+            await new Promise<void>((resolve) => this.waiting.push(resolve));
+        }
+    }
+
+    // Queue an item; resolves or rejects with its handler, or resolves unrun if it is dropped
+    push(run: () => Promise<void>, key?: string): Promise<void> {
+        if (this.overflow === "drop_oldest") {
+            while (this.queue.length >= this.capacity) {
+                this.queue.shift()?.resolve();
+                this.emit("dropped", 1);
+            }
+        }
+        const done = new Promise<void>((resolve, reject) =>
+            this.queue.push({ key, run, queuedAt: performance.now(), resolve, reject })
+        );
+        this.pump();
+        this.emit("depth", this.queue.length);
+        return done;
+    }
+
+    private pump(): void {
+        while (this.active < this.workers) {
+            const index = this.queue.findIndex((job) => job.key === undefined || !this.busyKeys.has(job.key));
+            if (index < 0) return;
+            const [job] = this.queue.splice(index, 1);
+            this.waiting.shift()?.();
+            this.start(job);
+        }
+    }
+
+    private start(job: Job): void {
+        this.active++;
+        if (job.key !== undefined) this.busyKeys.add(job.key);
+        const started = performance.now();
+        Promise.resolve()
+            .then(job.run)
+            .then(job.resolve, job.reject)
+            .finally(() => {
+                this.active--;
+                if (job.key !== undefined) this.busyKeys.delete(job.key);
+                this.emit("processed", { waitMs: started - job.queuedAt, runMs: performance.now() - started });
+                this.pump();
+                this.emit("depth", this.queue.length);
+            });
+    }
+}
@@ -1,2 +1,3 @@
+export * from "./inbound";
 export * from "./message.handler";
 export * from "./webhook.handler";
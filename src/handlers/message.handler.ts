@@ -1,5 +1,6 @@
 import type { IWhatsAppClient } from "../clients/interface";
 import type { WhatsAppMessage } from "../types";
+import { WhatsAppError } from "../errors";
 
 export class MessageHandler {
     constructor(private client: IWhatsAppClient) {}
@@ -10,12 +11,18 @@ export class MessageHandler {
             // Cloud API returns { data: ... }, Baileys returns the response directly
             return response?.data ?? response;
         } catch (error: unknown) {
+            // Typed errors already carry a precise message for callers to branch on
+            if (error instanceof WhatsAppError) {
+                throw error;
+            }
             if (error instanceof Error) {
-                throw new Error(
-                    `Failed to send WhatsApp message: ${error.message}`
+                throw new WhatsAppError(
+                    "SEND_FAILED",
+                    `Failed to send WhatsApp message: ${error.message}`,
+                    { cause: error }
                 );
             }
-            throw new Error("Failed to send WhatsApp message");
+            throw new WhatsAppError("SEND_FAILED", "Failed to send WhatsApp message", { cause: error });
         }
     }
 }
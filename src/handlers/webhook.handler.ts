@@ -1,45 +1,217 @@
+import { EventEmitter } from "events";
+import { logger } from "@elizaos/core";
 import type { IWhatsAppClient } from "../clients/interface";
-import type { WhatsAppWebhookEvent } from "../types";
+import type { MessageEcho, UnifiedMessage, UnsupportedMessage, WhatsAppWebhookEvent } from "../types";
+import { parseCallEvents } from "../calls";
+import type { MessageStatusUpdate, TemplateStatusUpdate } from "../events";
+import { redact, type RedactOptions } from "../utils/redact";
+import { WhatsAppError } from "../errors";
+import { formatAddress, parseAddressSubmission } from "../interactive/address";
+import { parsePaymentStatus } from "../payments";
+import { formatLocation } from "../location";
+import { InboundChannel, type InboundConfig, type InboundItem } from "./inbound";
 
-export class WebhookHandler {
-    constructor(private client: IWhatsAppClient) {}
+type WebhookMessage = NonNullable<
+    WhatsAppWebhookEvent["entry"][number]["changes"][number]["value"]["messages"]
+>[number];
+
+type WebhookEcho = NonNullable<
+    WhatsAppWebhookEvent["entry"][number]["changes"][number]["value"]["message_echoes"]
+>[number];
+
+type WebhookContact = NonNullable<
+    WhatsAppWebhookEvent["entry"][number]["changes"][number]["value"]["contacts"]
+>[number];
+
+const MEDIA_TYPES = ["image", "audio", "video", "document"] as const;
+
+// Message types toUnified understands; anything else is dispatched as "unsupported"
+const SUPPORTED_TYPES = new Set<string>(["text", ...MEDIA_TYPES, "location", "interactive", "button", "reaction"]);
+
+function unifiedType(message: WebhookMessage, mediaType?: (typeof MEDIA_TYPES)[number]): UnifiedMessage["type"] {
+    const { interactive } = message;
+    if (interactive?.button_reply || interactive?.list_reply || interactive?.nfm_reply) return "interactive";
+    if (message.button) return "button";
+    if (message.reaction) return "reaction";
+    if (message.location) return "location";
+    return mediaType ?? "text";
+}
+
+type TemplateStatusValue = WhatsAppWebhookEvent["entry"][number]["changes"][number]["value"];
+
+export class WebhookHandler extends EventEmitter {
+    readonly inbound: InboundChannel;
+
+    constructor(
+        private client: IWhatsAppClient,
+        private logOptions: RedactOptions = {},
+        inbound: InboundConfig = {}
+    ) {
+        super();
+        this.inbound = new InboundChannel(inbound);
+    }
 
     async handle(event: WhatsAppWebhookEvent): Promise<void> {
         try {
-            // Process messages
-            if (event.entry?.[0]?.changes?.[0]?.value?.messages) {
-                const messages = event.entry[0].changes[0].value.messages;
-                for (const message of messages) {
-                    await this.handleMessage(message);
+            // Process messages and status updates concurrently, keeping each chat in order
+            const items: InboundItem[] = [];
+            for (const entry of event.entry ?? []) {
+                for (const change of entry.changes ?? []) {
+                    const { messages, contacts, statuses, message_echoes } = change.value ?? {};
+                    for (const message of messages ?? []) {
+                        items.push({ key: message.from, run: () => this.handleMessage(message, contacts) });
+                    }
+                    for (const status of statuses ?? []) {
+                        items.push({ key: status.recipient_id, run: () => this.handleStatus(status) });
+                    }
+                    for (const echo of message_echoes ?? []) {
+                        items.push({ key: echo.to, run: () => this.handleEcho(echo) });
+                    }
                 }
             }
+            await this.inbound.process(items);
+
+            // Process Calling API events
+            for (const call of parseCallEvents(event)) {
+                this.emit("call", call);
+            }
 
-            // Process status updates
-            if (event.entry?.[0]?.changes?.[0]?.value?.statuses) {
-                const statuses = event.entry[0].changes[0].value.statuses;
-                for (const status of statuses) {
-                    await this.handleStatus(status);
+            // Process template review outcomes
+            for (const entry of event.entry ?? []) {
+                for (const change of entry.changes ?? []) {
+                    if (change.field === "message_template_status_update") {
+                        this.emit("templateStatus", this.toTemplateStatus(change.value));
+                    }
                 }
             }
         } catch (error: unknown) {
             if (error instanceof Error) {
-                throw new Error(
-                    `Failed to send WhatsApp message: ${error.message}`
+                throw new WhatsAppError(
+                    "WEBHOOK_FAILED",
+                    `Failed to process WhatsApp webhook: ${error.message}`,
+                    { cause: error }
                 );
             }
-            throw new Error("Failed to send WhatsApp message");
+            throw new WhatsAppError("WEBHOOK_FAILED", "Failed to process WhatsApp webhook", { cause: error });
         }
     }
 
-    private async handleMessage(message: any): Promise<void> {
-        // Implement message handling logic
-        // This could emit events or trigger callbacks based on your framework's needs
-        console.log("Received message:", message);
+    private async handleMessage(message: any, contacts?: WebhookContact[]): Promise<void> {
+        console.log("Received message:", redact(message, this.logOptions));
+        if (!SUPPORTED_TYPES.has(message.type)) {
+            await this.dispatch("unsupported", this.toUnsupported(message, contacts));
+            return;
+        }
+        await this.dispatch("message", this.toUnified(message, contacts));
+    }
+
+    // Coexistence: keep the chat's transcript complete when a person replies from the Business app
+    private async handleEcho(echo: WebhookEcho): Promise<void> {
+        logger.debug("[WhatsApp] Received message echo:", redact(echo, this.logOptions));
+        const message: MessageEcho = { ...this.toUnified(echo), to: echo.to };
+        await this.dispatch("echo", message);
+    }
+
+    // Like emit, but waits for listeners that return a promise
+    private async dispatch(name: string, payload: unknown): Promise<void> {
+        await Promise.all(this.listeners(name).map((listener) => listener.call(this, payload)));
+    }
+
+    private toUnsupported(message: WebhookMessage, contacts?: WebhookContact[]): UnsupportedMessage {
+        const senderName = contacts?.find((c) => c.wa_id === message.from)?.profile?.name;
+        const error = message.errors?.[0];
+        const reason = error?.error_data?.details ?? error?.message ?? error?.title;
+        return {
+            id: message.id,
+            from: message.from,
+            timestamp: Number(message.timestamp ?? 0),
+            type: message.type,
+            ...(senderName ? { senderName } : {}),
+            ...(reason ? { reason } : {}),
+        };
+    }
+
+    private toUnified(message: WebhookMessage, contacts?: WebhookContact[]): UnifiedMessage {
+        const reply =
+            message.interactive?.button_reply ?? message.interactive?.list_reply;
+        const mediaType = MEDIA_TYPES.find((type) => message.type === type && message[type]);
+        const media = mediaType ? message[mediaType] : undefined;
+        const senderName = contacts?.find((c) => c.wa_id === message.from)?.profile?.name;
+        const nfm = message.interactive?.nfm_reply;
+        const address = nfm ? parseAddressSubmission(nfm) : null;
+        return {
+            id: message.id,
+            from: message.from,
+            timestamp: Number(message.timestamp ?? 0),
+            type: unifiedType(message, mediaType),
+            content:
+                reply?.title ??
+                (address ? formatAddress(address) : undefined) ??
+                message.button?.text ??
+                (message.location ? formatLocation(message.location) : undefined) ??
+                message.text?.body ??
+                media?.caption ??
+                "",
+            ...(reply ? { replyId: reply.id } : {}),
+            ...(message.location ? { location: message.location } : {}),
+            ...(message.button
+                ? { button: { text: message.button.text, payload: message.button.payload ?? message.button.text } }
+                : {}),
+            ...(message.context?.id ? { replyTo: message.context.id } : {}),
+            ...(address ? { address } : {}),
+            ...(senderName ? { senderName } : {}),
+            ...(message.reaction
+                ? { reaction: { messageId: message.reaction.message_id, emoji: message.reaction.emoji ?? "" } }
+                : {}),
+            ...(media
+                ? {
+                      media: {
+                          id: media.id,
+                          mimeType: media.mime_type,
+                          caption: media.caption,
+                          ...(message.document?.filename ? { fileName: message.document.filename } : {}),
+                      },
+                  }
+                : {}),
+        };
     }
 
     private async handleStatus(status: any): Promise<void> {
-        // Implement status update handling logic
-        // This could emit events or trigger callbacks based on your framework's needs
-        console.log("Received status update:", status);
+        console.log("Received status update:", redact(status, this.logOptions));
+        const payment = parsePaymentStatus(status);
+        if (payment) {
+            await this.dispatch("payment", payment);
+            return;
+        }
+        const { conversation, pricing } = status;
+        const update: MessageStatusUpdate = {
+            messageId: status.id,
+            status: status.status,
+            recipient: status.recipient_id,
+            timestamp: Number(status.timestamp ?? 0),
+            ...(pricing
+                ? { pricing: { billable: pricing.billable !== false, category: pricing.category, model: pricing.pricing_model } }
+                : {}),
+            ...(conversation?.id
+                ? {
+                      conversation: {
+                          id: conversation.id,
+                          origin: conversation.origin?.type,
+                          expiresAt: conversation.expiration_timestamp ? Number(conversation.expiration_timestamp) : undefined,
+                      },
+                  }
+                : {}),
+        };
+        await this.dispatch("status", update);
+    }
+
+    private toTemplateStatus(value: TemplateStatusValue): TemplateStatusUpdate {
+        return {
+            templateId: String(value.message_template_id ?? ""),
+            name: value.message_template_name ?? "",
+            status: value.event ?? "",
+            ...(value.message_template_language ? { language: value.message_template_language } : {}),
+            ...(value.reason && value.reason !== "NONE" ? { reason: value.reason } : {}),
+        };
     }
 }
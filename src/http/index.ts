@@ -0,0 +1,12 @@
+export {
+  WEBHOOK_PATH,
+  webhookRoutes,
+  verifyWebhookRequest,
+  receiveWebhookRequest,
+} from './webhook';
+export type { WebhookTarget, WebhookResult } from './webhook';
+export { WebhookLog, RuntimeWebhookPayloadStore } from './webhook-log';
+export type { StoredWebhook, WebhookLogConfig, WebhookPayloadStore } from './webhook-log';
+export { mountRoutes } from './mount';
+export { createLambdaWebhookHandler } from './lambda';
+export type { ApiGatewayEvent, ApiGatewayResult } from './lambda';
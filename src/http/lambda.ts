@@ -0,0 +1,66 @@
+import { receiveWebhookRequest, verifyWebhookRequest, type WebhookTarget } from './webhook';
+import { SIGNATURE_HEADER } from './signature';
+
+// The parts of API Gateway REST (v1) and HTTP API (v2) proxy events the webhook needs
+export interface ApiGatewayEvent {
+  httpMethod?: string;
+  requestContext?: { http?: { method?: string } };
+  queryStringParameters?: Record<string, string | undefined> | null;
+  headers?: Record<string, string | undefined> | null;
+  body?: string | null;
+  isBase64Encoded?: boolean;
+}
+
+export interface ApiGatewayResult {
+  statusCode: number;
+  headers?: Record<string, string>;
+  body: string;
+}
+
+type TargetSource = WebhookTarget | (() => WebhookTarget | Promise<WebhookTarget>);
+
+function rawBody(event: ApiGatewayEvent): string | undefined {
+  if (!event.body) return undefined;
+  return event.isBase64Encoded ? Buffer.from(event.body, 'base64').toString('utf8') : event.body;
+}
+
+// REST API events keep header case as sent; HTTP API events lower-case them
+function header(event: ApiGatewayEvent, name: string): string | undefined {
+  const entry = Object.entries(event.headers ?? {}).find(([key]) => key.toLowerCase() === name);
+  return entry?.[1];
+}
+
+/**
+ * Lambda handler for API Gateway proxy integrations. Pass a plugin or a factory that builds
+ * one; the factory runs once per container, so cold starts pay for setup only once.
+ */
+export function createLambdaWebhookHandler(source: TargetSource) {
+  let target: Promise<WebhookTarget> | undefined;
+  const resolve = () => (target ??= Promise.resolve(typeof source === 'function' ? source() : source));
+
+  return async (event: ApiGatewayEvent): Promise<ApiGatewayResult> => {
+    const method = event.httpMethod ?? event.requestContext?.http?.method;
+
+    if (method === 'GET') {
+      const result = await verifyWebhookRequest(await resolve(), event.queryStringParameters ?? undefined);
+      return { statusCode: result.status, headers: { 'Content-Type': 'text/plain' }, body: result.body ?? '' };
+    }
+
+    if (method === 'POST') {
+      const raw = rawBody(event);
+      let body: unknown;
+      try {
+        body = raw ? JSON.parse(raw) : undefined;
+      } catch {
+        return { statusCode: 400, body: '' };
+      }
+      const result = await receiveWebhookRequest(await resolve(), body, {
+        rawBody: raw,
+        signature: header(event, SIGNATURE_HEADER),
+      });
+      return { statusCode: result.status, body: '' };
+    }
+
+    return { statusCode: 405, body: '' };
+  };
+}
@@ -0,0 +1,124 @@
+import type { IncomingMessage, ServerResponse } from 'http';
+import type { IAgentRuntime, Route } from '@elizaos/core';
+
+type RouteHandler = NonNullable<Route['handler']>;
+
+// Express/connect requests may already carry parsed fields; plain node requests do not
+type MountRequest = IncomingMessage & {
+  body?: unknown;
+  rawBody?: string | Buffer;      // Kept for webhook signature checks
+  query?: Record<string, unknown>;
+  params?: Record<string, string>;
+};
+
+type Next = (error?: unknown) => void;
+
+interface CompiledRoute {
+  method: string;
+  pattern: RegExp;
+  keys: string[];
+  handler: RouteHandler;
+}
+
+function compile(route: Route, prefix: string): CompiledRoute | null {
+  if (!route.handler || route.type === 'STATIC') return null;
+
+  const keys: string[] = [];
+  const source = `${prefix}${route.path}`
+    .split('/')
+    .map((segment) => {
+      if (!segment.startsWith(':')) return segment.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+      keys.push(segment.slice(1));
+      return '([^/]+)';
+    })
+    .join('/');
+
+  return { method: route.type, pattern: new RegExp(`^${source}/?$`), keys, handler: route.handler };
+}
+
+async function readJson(req: MountRequest): Promise<{ body: unknown; rawBody?: string | Buffer }> {
+  if (req.body !== undefined) return { body: req.body, rawBody: req.rawBody };
+
+  const chunks: Buffer[] = [];
+  for await (const chunk of req) {
+    chunks.push(chunk as Buffer);
+  }
+  const raw = Buffer.concat(chunks).toString('utf8');
+  return { body: raw ? JSON.parse(raw) : undefined, rawBody: raw };
+}
+
+// Minimal express-style response over a node ServerResponse
+function wrap(res: ServerResponse) {
+  const wrapped = {
+    status(code: number) {
+      res.statusCode = code;
+      return wrapped;
+    },
+    json(data: unknown) {
+      res.setHeader('Content-Type', 'application/json');
+      res.end(JSON.stringify(data));
+      return wrapped;
+    },
+    send(data: unknown) {
+      res.end(typeof data === 'string' || Buffer.isBuffer(data) ? data : JSON.stringify(data));
+      return wrapped;
+    },
+    end() {
+      res.end();
+      return wrapped;
+    },
+    setHeader(name: string, value: string) {
+      res.setHeader(name, value);
+      return wrapped;
+    },
+    get headersSent() {
+      return res.headersSent;
+    },
+  };
+  return wrapped;
+}
+
+/**
+ * Serve plugin routes (webhook, admin) from an existing HTTP server.
+ * Works as express/connect middleware or as a plain `http.createServer` listener.
+ */
+export function mountRoutes(routes: Route[], runtime: IAgentRuntime, prefix = '') {
+  const compiled = routes.map((route) => compile(route, prefix)).filter((r): r is CompiledRoute => !!r);
+
+  return async (req: MountRequest, res: ServerResponse, next?: Next): Promise<void> => {
+    const url = new URL(req.url ?? '/', 'http://localhost');
+
+    for (const route of compiled) {
+      if (route.method !== req.method) continue;
+      const match = route.pattern.exec(url.pathname);
+      if (!match) continue;
+
+      let parsed: { body: unknown; rawBody?: string | Buffer };
+      try {
+        parsed = req.method === 'GET' ? { body: req.body } : await readJson(req);
+      } catch {
+        res.statusCode = 400;
+        res.end();
+        return;
+      }
+
+      const request = Object.assign(req, {
+        params: Object.fromEntries(route.keys.map((key, i) => [key, match[i + 1]])),
+        query: req.query ?? Object.fromEntries(url.searchParams),
+        ...parsed,
+      });
+      try {
+        await route.handler(request as never, wrap(res) as never, runtime);
+      } catch (error) {
+        if (next) return next(error);
+        res.statusCode = 500;
+        res.end();
+      }
+      return;
+    }
+
+    if (next) return next();
+    res.statusCode = 404;
+    res.end();
+  };
+}
@@ -0,0 +1,11 @@
+import { createHmac, timingSafeEqual } from 'crypto';
+
+export const SIGNATURE_HEADER = 'x-hub-signature-256';
+
+// Check Meta's X-Hub-Signature-256 header ("sha256=<hex hmac of the raw body>") against an app secret
+export function isValidSignature(rawBody: string | Uint8Array, header: string | undefined, secret: string): boolean {
+  const match = /^sha256=([0-9a-f]{64})$/i.exec(header?.trim() ?? '');
+  if (!match) return false;
+  const expected = createHmac('sha256', secret).update(rawBody).digest();
+  return timingSafeEqual(Buffer.from(match[1], 'hex'), expected);
+}
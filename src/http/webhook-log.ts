@@ -0,0 +1,158 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface WebhookLogConfig {
+  maxEntries?: number;                  // Most recent notifications kept (default 500)
+  maxAgeMs?: number;                    // Older notifications are dropped (default 7 days)
+}
+
+// A webhook notification as it arrived, kept so it can be fed through the pipeline again
+export interface StoredWebhook {
+  id: string;
+  receivedAt: number;                   // Milliseconds since epoch
+  accountId?: string;                   // Account the notification was routed to, when known
+  body: string;                         // Raw request body
+}
+
+// Where stored notifications are kept between restarts; each one is written once, when it arrives
+export interface WebhookPayloadStore {
+  load(): Promise<StoredWebhook[]>;
+  add(entry: StoredWebhook): Promise<void>;
+  remove(ids: string[]): Promise<void>;
+}
+
+// Stored notifications in the agent's runtime cache: one key per body, plus an index of their ids
+export class RuntimeWebhookPayloadStore implements WebhookPayloadStore {
+  private readonly indexKey = 'whatsapp-webhook-log-index';
+  // Every body under one key, as older versions kept them
+  private readonly legacyKey = 'whatsapp-webhook-log';
+
+  constructor(private runtime: IAgentRuntime) {}
+
+  async load(): Promise<StoredWebhook[]> {
+    const ids = await this.runtime.getCache<string[]>(this.indexKey);
+    if (!ids) return this.migrate();
+    const entries = await Promise.all(ids.map((id) => this.runtime.getCache<StoredWebhook>(this.key(id))));
+    return entries.filter((entry): entry is StoredWebhook => !!entry);
+  }
+
+  async add(entry: StoredWebhook): Promise<void> {
+    await this.runtime.setCache(this.key(entry.id), entry);
+    const ids = (await this.runtime.getCache<string[]>(this.indexKey)) ?? [];
+    await this.runtime.setCache(this.indexKey, [...ids, entry.id]);
+  }
+
+  async remove(ids: string[]): Promise<void> {
+    const removed = new Set(ids);
+    const index = (await this.runtime.getCache<string[]>(this.indexKey)) ?? [];
+    await this.runtime.setCache(this.indexKey, index.filter((id) => !removed.has(id)));
+    await Promise.all(ids.map((id) => this.runtime.deleteCache(this.key(id))));
+  }
+
+  // Move bodies saved under the single legacy key to a key each
+  private async migrate(): Promise<StoredWebhook[]> {
+    const entries = (await this.runtime.getCache<StoredWebhook[]>(this.legacyKey)) ?? [];
+    await Promise.all(entries.map((entry) => this.runtime.setCache(this.key(entry.id), entry)));
+    await this.runtime.setCache(this.indexKey, entries.map((entry) => entry.id));
+    if (entries.length > 0) await this.runtime.deleteCache(this.legacyKey);
+    return entries;
+  }
+
+  private key(id: string): string {
+    return `whatsapp-webhook-log-${id}`;
+  }
+}
+
+const DEFAULT_MAX_ENTRIES = 500;
+const DEFAULT_MAX_AGE_MS = 7 * 24 * 60 * 60 * 1000;
+
+/**
+ * Raw bodies of recent webhook notifications, oldest first, bounded by count and age. Only
+ * notifications that passed signature checks are recorded. With a store, each entry is saved as
+ * it is recorded and deleted once dropped, and the entries are loaded again on start.
+ */
+export class WebhookLog {
+  private entries = new Map<string, StoredWebhook>();
+  private sequence = 0;
+  private store?: WebhookPayloadStore;
+  private saving: Promise<void> = Promise.resolve();
+  private readonly maxEntries: number;
+  private readonly maxAgeMs: number;
+
+  constructor(
+    config: WebhookLogConfig = {},
+    private readonly clock: Clock = systemClock
+  ) {
+    this.maxEntries = Math.max(1, config.maxEntries ?? DEFAULT_MAX_ENTRIES);
+    this.maxAgeMs = config.maxAgeMs ?? DEFAULT_MAX_AGE_MS;
+  }
+
+  // Persist entries to `store`, first restoring the ones it already holds
+  async useStore(store: WebhookPayloadStore): Promise<void> {
+    this.store = store;
+    const stored = await store.load();
+    const unsaved = [...this.entries.values()];
+    this.entries = new Map([...stored, ...unsaved].map((entry) => [entry.id, entry]));
+    for (const entry of unsaved) this.persist((s) => s.add(entry));
+    this.trim();
+  }
+
+  record(body: string, accountId?: string): StoredWebhook {
+    const receivedAt = this.clock.now();
+    const entry: StoredWebhook = {
+      id: `webhook-${receivedAt}-${++this.sequence}`,
+      receivedAt,
+      ...(accountId ? { accountId } : {}),
+      body,
+    };
+    this.entries.set(entry.id, entry);
+    this.persist((store) => store.add(entry));
+    this.trim();
+    return entry;
+  }
+
+  list(): StoredWebhook[] {
+    this.trim();
+    return [...this.entries.values()];
+  }
+
+  get(id: string): StoredWebhook | undefined {
+    this.trim();
+    return this.entries.get(id);
+  }
+
+  remove(id: string): boolean {
+    const removed = this.entries.delete(id);
+    if (removed) this.persist((store) => store.remove([id]));
+    return removed;
+  }
+
+  get size(): number {
+    this.trim();
+    return this.entries.size;
+  }
+
+  // Resolves once every change so far has been saved
+  flush(): Promise<void> {
+    return this.saving;
+  }
+
+  private trim(): void {
+    const cutoff = this.clock.now() - this.maxAgeMs;
+    const dropped: string[] = [];
+    // Map keeps insertion order, so the first entries are the oldest
+    for (const [id, entry] of this.entries) {
+      if (this.entries.size <= this.maxEntries && entry.receivedAt > cutoff) break;
+      this.entries.delete(id);
+      dropped.push(id);
+    }
+    if (dropped.length > 0) this.persist((store) => store.remove(dropped));
+  }
+
+  // Queue a store change after the ones before it, so the index is never written concurrently
+  private persist(change: (store: WebhookPayloadStore) => Promise<void>): void {
+    const store = this.store;
+    if (!store) return;
+    this.saving = this.saving.then(() => change(store)).catch(() => {});
+  }
+}
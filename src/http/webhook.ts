@@ -0,0 +1,117 @@
+import type { Route } from '@elizaos/core';
+import { logger } from '@elizaos/core';
+import type { WhatsAppWebhookEvent } from '../types';
+import { WhatsAppConnectorService } from '../service';
+import { SIGNATURE_HEADER } from './signature';
+
+export const WEBHOOK_PATH = '/whatsapp/webhook';
+
+// Anything that can answer Meta's webhook calls: the plugin itself or the connector service
+export interface WebhookTarget {
+  verifyWebhook(token: string, accountId?: string): Promise<boolean>;
+  handleWebhook(event: WhatsAppWebhookEvent): Promise<void>;
+  // False rejects the notification; targets without app secrets accept everything
+  verifySignature?(event: WhatsAppWebhookEvent, rawBody?: string, signature?: string): Promise<boolean> | boolean;
+  // Keep an authentic notification before it is handled, so it can be replayed later
+  recordWebhook?(event: WhatsAppWebhookEvent, rawBody?: string): void;
+}
+
+// The exact bytes Meta signed and the X-Hub-Signature-256 header
+export interface SignedRequest {
+  rawBody?: string;
+  signature?: string;
+}
+
+export interface WebhookResult {
+  status: number;
+  body?: string;
+}
+
+type Query = Record<string, unknown> | undefined;
+
+function first(value: unknown): string | undefined {
+  return Array.isArray(value) ? value[0] : typeof value === 'string' ? value : undefined;
+}
+
+// Answer the GET subscription handshake: echo hub.challenge when the verify token matches
+// (the token of `accountId` when the callback URL names one, otherwise any account's)
+export async function verifyWebhookRequest(
+  target: WebhookTarget,
+  query: Query,
+  accountId?: string
+): Promise<WebhookResult> {
+  const mode = first(query?.['hub.mode']);
+  const token = first(query?.['hub.verify_token']);
+  const challenge = first(query?.['hub.challenge']);
+
+  if (mode === 'subscribe' && token && challenge && (await target.verifyWebhook(token, accountId))) {
+    return { status: 200, body: challenge };
+  }
+  return { status: 403 };
+}
+
+// Accept a POSTed notification. Meta is answered as soon as it is verified and recorded: handling
+// awaits the agent's turn, and a slow turn would otherwise make Meta time out and redeliver it
+export async function receiveWebhookRequest(
+  target: WebhookTarget,
+  body: unknown,
+  request: SignedRequest = {}
+): Promise<WebhookResult> {
+  const event = body as WhatsAppWebhookEvent | undefined;
+  if (!event || !Array.isArray(event.entry)) {
+    return { status: 400 };
+  }
+  if (target.verifySignature && !(await target.verifySignature(event, request.rawBody, request.signature))) {
+    logger.warn('[WhatsApp] Rejected webhook notification with a missing or invalid signature');
+    return { status: 401 };
+  }
+  try {
+    target.recordWebhook?.(event, request.rawBody);
+  } catch (error) {
+    logger.error('[WhatsApp] Could not record webhook notification:', (error as Error).message);
+  }
+  void Promise.resolve()
+    .then(() => target.handleWebhook(event))
+    .catch((error) => logger.error('[WhatsApp] Webhook processing failed:', (error as Error).message));
+  return { status: 200 };
+}
+
+type RouteHandler = NonNullable<Route['handler']>;
+type Request = Parameters<RouteHandler>[0];
+
+function connector(runtime: Parameters<RouteHandler>[2]) {
+  return runtime.getService<WhatsAppConnectorService>(WhatsAppConnectorService.serviceType);
+}
+
+// Signatures cover the bytes Meta sent, so the server must keep the unparsed body as req.rawBody
+function signedRequest(req: Request): SignedRequest {
+  const { rawBody } = req as { rawBody?: string | Uint8Array };
+  const signature = (req.headers as Record<string, string | string[] | undefined> | undefined)?.[SIGNATURE_HEADER];
+  return {
+    rawBody: typeof rawBody === 'string' || !rawBody ? rawBody : Buffer.from(rawBody).toString('utf8'),
+    signature: first(signature),
+  };
+}
+
+const verifyHandler: RouteHandler = async (req, res, runtime) => {
+  const service = connector(runtime);
+  const accountId = (req.params as Record<string, string> | undefined)?.accountId;
+  const result = service
+    ? await verifyWebhookRequest(service, req.query as Query, accountId)
+    : { status: 503 };
+  res.status(result.status).send(result.body ?? '');
+};
+
+const receiveHandler: RouteHandler = async (req, res, runtime) => {
+  const service = connector(runtime);
+  const result = service ? await receiveWebhookRequest(service, req.body, signedRequest(req)) : { status: 503 };
+  res.status(result.status).send('');
+};
+
+// One callback URL for every account, or /whatsapp/webhook/:accountId per Meta app
+export const webhookRoutes: Route[] = [
+  { type: 'GET', path: WEBHOOK_PATH, handler: verifyHandler },
+  { type: 'POST', path: WEBHOOK_PATH, handler: receiveHandler },
+  { type: 'GET', path: `${WEBHOOK_PATH}/:accountId`, handler: verifyHandler },
+  { type: 'POST', path: `${WEBHOOK_PATH}/:accountId`, handler: receiveHandler },
+];
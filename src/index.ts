@@ -2,54 +2,1281 @@ import { EventEmitter } from 'events';
 import type { Plugin } from "@elizaos/core";
 import type { IWhatsAppClient } from "./clients/interface";
 import { ClientFactory } from "./clients/factory";
-import type { WhatsAppConfig, WhatsAppMessage, WhatsAppWebhookEvent } from "./types";
+import type { HttpTransport } from "./clients/transport";
+import type {
+    MessageEcho,
+    SendMessageResponse,
+    UnifiedMedia,
+    UnifiedMessage,
+    UnsupportedMessage,
+    WhatsAppConfig,
+    WhatsAppInteractive,
+    WhatsAppMedia,
+    WhatsAppMessage,
+    WhatsAppTemplate,
+    WhatsAppWebhookEvent,
+} from "./types";
 import { MessageHandler, WebhookHandler } from "./handlers";
+import { InMemoryMenuSessionStore, MenuEngine, type MenuStep } from "./menus";
+import {
+    buildCallPermissionRequest,
+    type CallAction,
+    type CallActionOptions,
+    type CallSession,
+} from "./calls";
 import { WhatsAppConnectorService } from "./service";
+import { whatsappActions } from "./actions";
+import { whatsappProviders } from "./providers";
+import { adminRoutes } from "./admin";
+import { webhookRoutes } from "./http";
+import {
+    ContactLanguages,
+    ContactTags,
+    ContactValidityCache,
+    matchTemplateLanguage,
+    type AudienceSelector,
+    type ContactCheckResult,
+} from "./contacts";
+import { NotOnWhatsAppError, WhatsAppError } from "./errors";
+import { isValidSignature } from "./http/signature";
+import type { BatchResult } from "./clients/batch";
+import { canonicalChatId, normalizeWhatsAppTarget } from "./utils/normalize";
+import { detectAuthMethod } from "./utils/config-detector";
+import { InMemoryMessageArchive, type ArchivedMessage, type MessageArchive } from "./archive";
+import { noopMetrics, type WhatsAppMetrics } from "./metrics";
+import { withSpan, type TracerLike } from "./tracing";
+import {
+    CircuitBreaker,
+    ConversationWindow,
+    DEFAULT_MESSAGING_LIMIT,
+    DeliveryLedger,
+    FailedSendLog,
+    failoverReason,
+    isServiceWindowClosed,
+    isTimeoutError,
+    isTransientError,
+    messagingTierLimit,
+    QuietHours,
+    retryDelay,
+    sendAttempt,
+    type FailoverReason,
+    type SendAttempt,
+    type SentMessage,
+} from "./outbox";
+import { EventBus, type MessageStatusUpdate } from "./events";
+import { OutboundPipeline, type OutboundMiddleware, type ReplyPart } from "./middleware/outbound";
+import { groupMediaLimit } from "./middleware/group-limits";
+import { appendReplyFooter, replyFooterFor, replyFooterLength } from "./middleware/reply-footer";
+import { ContentPolicy, enforcePolicy, type OutboundPolicy } from "./middleware/policy";
+import { groupTextLimit } from "./utils/group-config";
+import { enrichLocation, formatLocation, type Geocoder } from "./location";
+import {
+    translateInbound,
+    translateOutbound,
+    type TranslationOptions,
+    type Translator,
+} from "./middleware/translation";
+import { ReactionTally, ReactionWorkflows, type MessageReactions } from "./reactions";
+import { checkHealth, HealthMonitor, type HealthCheckConfig, type HealthProblem } from "./accounts/health";
+import { isTokenRejected } from "./accounts/doctor";
+import { PollManager, type PollDefinition } from "./polls";
+import { TemplateCatalog, TemplatePacing, type TemplatePacingUpdate } from "./templates";
+import { ProductCatalog, type ProductCatalogPage } from "./catalog";
+import { ButtonCallbackRegistry, InMemoryPromptStore, InteractivePrompts, type ButtonCallback } from "./interactive";
+import { maskPhone } from "./utils/redact";
+import { systemClock, type Clock } from "./utils/clock";
+import { LruCache, type LruOptions } from "./utils/lru";
+import type { AccountStateSnapshot } from "./state";
+import { typingDelay } from "./pacing";
+import type { GroupInviteInfo, InboundMessageRef, NewsletterInfo } from "./clients/interface";
+import { parseGroupInviteLink, resolveGroupInviteKeys } from "./utils/invite-link";
+
+// Plain-text rendering of an outbound message for the archive
+function describeContent(message: WhatsAppMessage): string {
+    switch (message.type) {
+        case "text":
+            return message.content;
+        case "interactive":
+            return message.content.body.text;
+        case "location": {
+            const location = message.content;
+            return `[location] ${location.name ?? `${location.latitude},${location.longitude}`}`;
+        }
+        case "media": {
+            const media = message.content;
+            return media.caption ? `[${media.kind}] ${media.caption}` : `[${media.kind}]`;
+        }
+        case "template":
+            return `[template: ${message.content.name}]`;
+        case "reaction":
+            return `[reaction] ${message.content.emoji}`;
+    }
+}
+
+export interface SendOptions {
+    failover?: boolean;         // Reroute to the fallback account when this one can't send (default true)
+    localize?: boolean;         // Send templates in the recipient's known language when approved (default true)
+    replyTo?: string;           // Quote this message
+    previewUrl?: boolean;       // Show a preview of the first link in a text (Cloud API)
+    idempotencyKey?: string;    // Lets a timed-out send be retried; the service also sends each key only once a day
+    bypassQueue?: boolean;      // Fail fast while the circuit breaker is open rather than wait in its queue
+    templateFallback?: WhatsAppTemplate; // Sent instead once the recipient's 24-hour service window has closed
+    reply?: ReplyPart;          // Marks the agent's reply to a chat and where this message falls in it (the last gets the reply footer)
+}
+
+// Carry the options that shape the message itself onto it
+function withDelivery(message: WhatsAppMessage, { replyTo, previewUrl }: SendOptions): WhatsAppMessage {
+    if (!replyTo && !previewUrl) return message;
+    return { ...message, ...(replyTo ? { replyTo } : {}), ...(previewUrl ? { previewUrl } : {}) };
+}
+
+// How long a looked-up messaging tier is trusted; Meta raises tiers over days
+const TIER_CACHE_MS = 60 * 60 * 1000;
+
+// Inbound media remembered for forwarding
+const RECEIVED_MEDIA_LIMIT = 200;
+
+// Inbound message ids remembered, and for how long, so redelivered webhooks are handled once
+const INBOUND_ID_LIMIT = 10_000;
+const INBOUND_ID_TTL_MS = 24 * 60 * 60 * 1000;
+
+// The agent's own messages remembered by id, so a reaction finds the message it is on
+const SENT_MESSAGE_LIMIT = 10_000;
+
+// A chat gets the unsupported-message notice at most once per interval, however many stickers it sends
+const UNSUPPORTED_NOTICE_INTERVAL_MS = 60 * 60 * 1000;
+
+// Unsupported types that are notifications rather than something the person wrote
+const SILENT_UNSUPPORTED_TYPES = new Set(["system", "request_welcome"]);
+
+type MediaKind = WhatsAppMedia["kind"];
+
+function isMediaKind(type: UnifiedMessage["type"]): type is MediaKind {
+    return type === "image" || type === "video" || type === "audio" || type === "document";
+}
 
 export class WhatsAppPlugin extends EventEmitter implements Plugin {
     private client: IWhatsAppClient;
     private messageHandler: MessageHandler;
     private webhookHandler: WebhookHandler;
+    private config: WhatsAppConfig;
+    private contacts: ContactValidityCache;
+    private archive: MessageArchive;
+    private metrics: WhatsAppMetrics = noopMetrics;
+    private tracer?: TracerLike;
+    private inFlight = 0;
+    private queuedReplies = 0;
+    private fallback?: WhatsAppPlugin;
+    private conversations: ConversationWindow;
+    private tier?: { limit?: number; checkedAt: number };
+    private lastWebhookAt?: number;
+    private health?: HealthMonitor;
+    private replaying = new Map<WhatsAppMessage, string>();
+    private receivedIds: LruCache<true>;
+    private sentMessages: LruCache<ArchivedMessage>;
+    private unsupportedNotices: LruCache<number>;
+    private receivedMedia = new Map<string, Omit<WhatsAppMedia, "id" | "url" | "data">>();
+    private geocoder?: Geocoder;
+    private policy?: OutboundMiddleware;
+    private translation?: { translator: Translator; agentLanguage: string; inbound: boolean; removeOutbound?: () => void };
+
+    readonly menus: MenuEngine;
+    readonly prompts: InteractivePrompts;
+    readonly tags: ContactTags;
+    readonly languages: ContactLanguages;
+    readonly failedSends: FailedSendLog;
+    readonly deliveries: DeliveryLedger;
+    readonly events = new EventBus();
+    readonly outbound = new OutboundPipeline();
+    readonly reactions = new ReactionWorkflows();
+    readonly reactionTally = new ReactionTally();
+    readonly polls: PollManager;
+    readonly templates: TemplateCatalog;
+    readonly templatePacing: TemplatePacing;
+    readonly products: ProductCatalog;
+    readonly buttonCallbacks: ButtonCallbackRegistry;
+    readonly circuit?: CircuitBreaker;
+    readonly quietHours?: QuietHours;
 
     name: string;
     description: string;
 
-    constructor(config: WhatsAppConfig) {
+    // `clock` is the source of time for expiry, windows and timers; tests can pass a ManualClock
+    constructor(config: WhatsAppConfig, readonly clock: Clock = systemClock) {
         super();
         this.name = "WhatsApp Plugin";
         this.description = "WhatsApp integration supporting both Cloud API and Baileys";
 
-        this.client = ClientFactory.create(config);
+        this.config = config;
+        this.client = ClientFactory.create(config, clock);
+        this.contacts = new ContactValidityCache(config.contactCacheTtlMs, clock);
+        this.archive = new InMemoryMessageArchive({}, clock, this.chatStateLimits("archive"));
+        this.conversations = new ConversationWindow(clock);
+        if (config.groups) this.outbound.use(groupMediaLimit(config.groups));
+        if (config.replyFooter || Object.values(config.groups ?? {}).some((g) => g.replyFooter)) {
+            this.outbound.use(appendReplyFooter(config.replyFooter, config.groups));
+        }
+        if (config.contentPolicy || Object.values(config.groups ?? {}).some((g) => g.contentPolicy)) {
+            this.usePolicy(new ContentPolicy(config.contentPolicy, config.groups));
+        }
+        this.receivedIds = new LruCache({ maxChats: INBOUND_ID_LIMIT, maxIdleMs: INBOUND_ID_TTL_MS, clock });
+        this.sentMessages = new LruCache({ maxChats: SENT_MESSAGE_LIMIT, clock });
+        this.unsupportedNotices = new LruCache({ maxIdleMs: UNSUPPORTED_NOTICE_INTERVAL_MS, clock });
+        this.failedSends = new FailedSendLog(undefined, clock);
+        this.deliveries = new DeliveryLedger(clock);
+        this.polls = new PollManager(clock);
+        this.buttonCallbacks = new ButtonCallbackRegistry(clock);
         this.messageHandler = new MessageHandler(this.client);
-        this.webhookHandler = new WebhookHandler(this.client);
+        this.webhookHandler = new WebhookHandler(
+            this.client,
+            { logMessageContent: config.logMessageContent },
+            config.inbound
+        );
+        this.menus = new MenuEngine(new InMemoryMenuSessionStore(this.chatStateLimits("menu_sessions")), clock);
+        this.prompts = new InteractivePrompts(new InMemoryPromptStore(this.chatStateLimits("prompts")), clock);
+        this.tags = new ContactTags();
+        this.languages = new ContactLanguages(undefined, clock);
+        if (config.quietHours) this.quietHours = new QuietHours(config.quietHours);
+        this.templatePacing = new TemplatePacing(clock);
+        this.templates = new TemplateCatalog(async () => {
+            const records = (await this.client.listTemplates?.()) ?? [];
+            for (const pacing of this.templatePacing.observeCatalog(records)) this.publishPacing(pacing);
+            return records;
+        }, undefined, clock);
+        this.products = new ProductCatalog(() => this.fetchProducts(), undefined, clock);
 
-        // Forward client events
-        this.client.on('message', (msg) => this.emit('message', msg));
-        this.client.on('qr', (qr) => this.emit('qr', qr));
+        // Forward client events, and publish the typed ones on the event bus
+        const accountId = this.accountId;
+        if (config.circuitBreaker !== false) {
+            this.circuit = new CircuitBreaker(accountId, config.circuitBreaker, clock);
+            this.circuit.on('open', ({ failures, retryInMs, error }) => {
+                this.emit('circuitOpen', { failures, retryInMs, error });
+                this.events.publish({
+                    kind: 'circuit_opened',
+                    accountId,
+                    failures,
+                    retryInMs,
+                    error: error instanceof Error ? error.message : String(error),
+                });
+            });
+            this.circuit.on('close', () => {
+                this.emit('circuitClose');
+                this.events.publish({ kind: 'circuit_closed', accountId });
+            });
+        }
+        this.client.on('message', (msg) => this.receive(msg));
+        this.client.on('unsupported', (msg) => this.receiveUnsupported(msg));
+        this.client.on('status', (status) => this.receiveStatus(status));
+        this.client.on('qr', (qr) => {
+            this.emit('qr', qr);
+            this.events.publish({ kind: 'pairing_requested', accountId, qr });
+        });
         this.client.on('ready', () => this.emit('ready'));
-        this.client.on('connection', (status) => this.emit('connection', status));
-        this.client.on('error', (err) => this.emit('error', err));
+        this.client.on('connection', (status) => {
+            this.emit('connection', status);
+            this.events.publish({ kind: 'connection_changed', accountId, status });
+        });
+        this.client.on('error', (err) => {
+            this.emit('error', err);
+            if (err?.code === 'LOGGED_OUT') {
+                this.events.publish({ kind: 'auth_failed', accountId, reason: err.message });
+            }
+        });
+        this.webhookHandler.on('message', (msg) => this.receive(msg));
+        this.webhookHandler.on('unsupported', (msg) => this.receiveUnsupported(msg));
+        this.webhookHandler.on('status', (status) => this.receiveStatus(status));
+        this.webhookHandler.on('echo', (message) => this.receiveEcho(message));
+        this.webhookHandler.on('payment', (payment) => {
+            this.emit('payment', payment);
+            return this.events.deliver({ kind: 'payment_status_changed', accountId, payment });
+        });
+        this.webhookHandler.on('templateStatus', (template) => {
+            this.templates.invalidate();
+            this.emit('templateStatus', template);
+            this.events.publish({ kind: 'template_status_changed', accountId, template });
+            const pacing = this.templatePacing.observeTemplate(template);
+            if (pacing) this.publishPacing(pacing);
+        });
+        this.webhookHandler.inbound.on('depth', (depth) => this.metrics.setInboundQueueDepth?.(accountId, depth));
+        this.webhookHandler.inbound.on('processed', ({ waitMs, runMs }) => {
+            this.metrics.observeInboundWait?.(accountId, waitMs / 1000);
+            this.metrics.observeInboundProcessing?.(accountId, runMs / 1000);
+        });
+        this.webhookHandler.inbound.on('dropped', (count) => this.metrics.inboundDropped?.(accountId, count));
+        this.webhookHandler.on('call', (call) => {
+            this.emit('call', call);
+            this.events.publish({ kind: 'call', accountId, call });
+        });
+        this.menus.on('completed', (result) => this.emit('menuCompleted', result));
+        this.polls.on('result', (result) => {
+            this.emit('pollResult', result);
+            this.events.publish({ kind: 'poll_result', accountId, result });
+        });
+    }
+
+    get accountId(): string {
+        return this.config.accountId ?? 'default';
     }
 
     async start(): Promise<void> {
         await this.client.start();
+        await this.resolveGroupInvites();
+        this.lastWebhookAt ??= this.clock.now();
     }
 
     async stop(): Promise<void> {
+        this.polls.clear();
+        this.circuit?.clear();
+        this.health?.stop();
         await this.client.stop();
     }
 
-    async sendMessage(message: WhatsAppMessage): Promise<any> {
-        return this.messageHandler.send(message);
+    // Baileys accounts have no catalog
+    private async fetchProducts(): Promise<ProductCatalogPage> {
+        return (await this.client.listProducts?.()) ?? { catalogId: "", products: [] };
+    }
+
+    // Group settings may be keyed by invite link; swap those for the group JIDs they point at
+    private async resolveGroupInvites(): Promise<void> {
+        const { groups } = this.config;
+        if (!groups || !Object.keys(groups).some(parseGroupInviteLink)) return;
+        const { failed } = await resolveGroupInviteKeys(groups, async (code) => (await this.getGroupInviteInfo(code)).id);
+        for (const { link, error } of failed) {
+            this.emit("error", new WhatsAppError("INVALID_GROUP_INVITE", `Group invite ${link} could not be resolved; its settings are ignored`, { cause: error }));
+        }
+    }
+
+    // Group behind an invite link or code, e.g. to check it against the configured groups before joining
+    async getGroupInviteInfo(linkOrCode: string): Promise<GroupInviteInfo> {
+        if (!this.client.getGroupInviteInfo) {
+            throw new WhatsAppError("UNSUPPORTED", "Group invite links can only be resolved by Baileys accounts");
+        }
+        return this.client.getGroupInviteInfo(parseGroupInviteLink(linkOrCode) ?? linkOrCode);
+    }
+
+    // Check token, number status and webhook delivery periodically, publishing account_degraded / account_recovered
+    monitorHealth(config: HealthCheckConfig = {}): HealthMonitor {
+        this.health?.stop();
+        const monitor = new HealthMonitor(() => this.checkHealth(config), config.intervalMs, this.clock);
+        const accountId = this.accountId;
+        monitor.on('degraded', (problems: HealthProblem[]) => {
+            this.emit('degraded', problems);
+            this.events.publish({ kind: 'account_degraded', accountId, problems });
+        });
+        monitor.on('recovered', () => {
+            this.emit('recovered');
+            this.events.publish({ kind: 'account_recovered', accountId });
+        });
+        monitor.on('error', (err) => this.emit('error', err));
+        monitor.start();
+        this.health = monitor;
+        return monitor;
+    }
+
+    // Problems with this account right now; empty when healthy
+    checkHealth(config: HealthCheckConfig = {}): Promise<HealthProblem[]> {
+        return checkHealth(
+            {
+                connection: this.getConnectionStatus(),
+                phoneNumber: this.client.getPhoneNumber?.bind(this.client),
+                lastWebhookAt: this.lastWebhookAt,
+            },
+            config,
+            this.clock.now()
+        );
+    }
+
+    useMetrics(metrics: WhatsAppMetrics): void {
+        this.metrics = metrics;
+        this.client.useMetrics?.(metrics);
+    }
+
+    // Chat-state limits for one per-chat store, with its evictions counted under `store`
+    chatStateLimits(store: string): LruOptions {
+        return {
+            ...this.config.chatState,
+            clock: this.clock,
+            onEvict: (_chat, reason) => this.metrics.chatStateEvicted?.(this.accountId, store, reason),
+        };
+    }
+
+    useTracer(tracer: TracerLike): void {
+        this.tracer = tracer;
+        this.client.useTracer?.(tracer);
+    }
+
+    // Send Graph API requests through `transport`; Baileys accounts keep their own socket
+    useTransport(transport: HttpTransport): void {
+        this.client.useTransport?.(transport);
+    }
+
+    async sendMessage(message: WhatsAppMessage, options: SendOptions = {}): Promise<any> {
+        const attributes = {
+            "whatsapp.account": this.accountId,
+            "whatsapp.recipient": maskPhone(message.to),
+            "whatsapp.message_type": message.type,
+        };
+        this.inFlight++;
+        this.emit('pendingSends', this.pendingSends);
+        try {
+            const outgoing = await this.prepare(message, options);
+            return await withSpan(this.tracer, "whatsapp.send", attributes, async (span) => {
+                const response = await this.send(outgoing, options);
+                // Awaited so a prompt and the message that follows it are remembered in order
+                if (!response?.templateFallback) await this.rememberPrompt(outgoing).catch((error) => this.emit("error", error));
+                const wamid = response?.messages?.[0]?.id ?? response?.key?.id;
+                if (wamid) span?.setAttribute("whatsapp.wamid", wamid);
+                return response;
+            });
+        } finally {
+            this.inFlight--;
+            this.emit('pendingSends', this.pendingSends);
+        }
+    }
+
+    // The message as it goes out: through the outbound middleware, localized, then checked by the policy
+    private async prepare(message: WhatsAppMessage, options: SendOptions): Promise<WhatsAppMessage> {
+        const rewritten = await this.outbound.run(withDelivery(message, options), {
+            accountId: this.accountId,
+            reply: options.reply,
+        });
+        const localized = await this.localize(rewritten, options);
+        return this.policy ? this.policy(localized, { accountId: this.accountId }) : localized;
+    }
+
+    // Remember the buttons or list a sent message offered, and the menu state that sent them; any
+    // other message to the chat clears what was offered before
+    private async rememberPrompt(message: WhatsAppMessage): Promise<void> {
+        const session = message.type === "interactive" ? await this.menus.getSession(message.to) : undefined;
+        await this.prompts.offer(message, session && { menuId: session.menuId, stateId: session.stateId });
+    }
+
+    // Longest text to put in one message to `to`, after any group textChunkLimit
+    textLimit(to: string): number {
+        return groupTextLimit(this.config.groups, to);
+    }
+
+    // Longest text to put in one message of an agent reply to `to`, leaving room for the reply footer
+    replyTextLimit(to: string): number {
+        return this.textLimit(to) - replyFooterLength(this.config.replyFooter, this.config.groups, to);
+    }
+
+    // Footer that ends agent replies to `to`, if any
+    replyFooter(to: string): string | undefined {
+        return replyFooterFor(this.config.replyFooter, this.config.groups, to);
+    }
+
+    // A send's result as one message of a MessageHandle, tracked by the account that sent it
+    sentMessage(response: any): SentMessage {
+        const via = response?.failover && this.fallback ? this.fallback : this;
+        const id = response?.messages?.[0]?.id ?? response?.key?.id ?? "";
+        return { id, accountId: via.accountId, ledger: via.deliveries, response };
+    }
+
+    private localize(message: WhatsAppMessage, options: SendOptions): Promise<WhatsAppMessage> {
+        return options.localize === false ? Promise.resolve(message) : this.localizeTemplate(message);
+    }
+
+    // Switch a template to the variant in the recipient's language, when one is approved
+    private async localizeTemplate(message: WhatsAppMessage): Promise<WhatsAppMessage> {
+        if (message.type !== 'template' || !this.client.listTemplates) return message;
+        try {
+            const known = await this.languages.get(message.to);
+            if (!known) return message;
+            const variants = (await this.templates.approved())
+                .filter((t) => t.name === message.content.name)
+                .map((t) => t.language);
+            const code = matchTemplateLanguage(known.language, variants);
+            if (!code || code === message.content.language.code) return message;
+            return { ...message, content: { ...message.content, language: { ...message.content.language, code } } };
+        } catch (error) {
+            this.emit('error', error);
+            return message;
+        }
+    }
+
+    // Sends in progress plus reply messages still waiting their turn
+    get pendingSends(): number {
+        return this.inFlight + this.queuedReplies;
+    }
+
+    private async send(message: WhatsAppMessage, options: SendOptions): Promise<any> {
+        const fallback = options.failover === false ? undefined : this.fallback;
+        await this.checkRecipient(message);
+        if (fallback && this.circuit && this.circuit.retryInMs() > 0) {
+            return this.failover(fallback, message, "circuit_open");
+        }
+        const earlier: SendAttempt[] = [];
+        try {
+            // The breaker sees the send once, however many tries it took
+            const response = await this.guarded(
+                () => this.sendWithRetry(message, options, earlier),
+                options.bypassQueue ? { wait: false } : {}
+            );
+            await this.recordSent(message, response?.messages?.[0]?.id ?? response?.key?.id ?? '');
+            return response;
+        } catch (error) {
+            const template = options.templateFallback;
+            if (template && message.type !== "template" && isServiceWindowClosed(error)) {
+                // Only a template may reopen the conversation
+                const reopen = await this.localize(
+                    withDelivery({ type: "template", to: message.to, content: template }, options),
+                    options
+                );
+                const response = await this.send(reopen, { ...options, templateFallback: undefined });
+                return { ...response, templateFallback: true };
+            }
+            const reason = fallback ? failoverReason(error) : undefined;
+            if (fallback && reason) return this.failover(fallback, message, reason);
+            this.recordFailed(message, error, earlier);
+            throw error;
+        }
+    }
+
+    // Try a send up to `sendRetry.attempts` times, noting each failed try in `earlier`. A timeout
+    // may have reached Meta anyway, so it is only tried again when an idempotency key was given.
+    private async sendWithRetry(message: WhatsAppMessage, options: SendOptions, earlier: SendAttempt[]): Promise<any> {
+        const retry = this.config.sendRetry ?? {};
+        for (;;) {
+            try {
+                return await this.messageHandler.send(message);
+            } catch (error) {
+                const retryable = isTransientError(error) && (!isTimeoutError(error) || !!options.idempotencyKey);
+                if (earlier.length + 1 >= (retry.attempts ?? 3) || !retryable) throw error;
+                earlier.push(sendAttempt(error, this.clock.now()));
+                await this.sleep(retryDelay(retry, earlier.length - 1));
+            }
+        }
+    }
+
+    private sleep(ms: number): Promise<void> {
+        return new Promise((resolve) => this.clock.setTimeout(resolve, ms));
+    }
+
+    // Account that sends in this one's place when it is rate-limited, circuit-open or flagged
+    useFallback(plugin: WhatsAppPlugin | undefined): void {
+        this.fallback = plugin;
+    }
+
+    // Send from the fallback account, noting the substitution on the result. The message already
+    // went through this account's middleware and policy, so it is not rewritten a second time.
+    private async failover(fallback: WhatsAppPlugin, message: WhatsAppMessage, reason: FailoverReason): Promise<any> {
+        const response = await fallback.send(message, { failover: false });
+        return Object.assign(response ?? {}, { failover: { from: this.accountId, to: fallback.accountId, reason } });
+    }
+
+    // Route a client call through the account's circuit breaker, when it has one
+    private guarded<T>(call: () => Promise<T>, options: { wait?: boolean } = {}): Promise<T> {
+        return this.circuit ? this.circuit.run(call, options) : call();
+    }
+
+    // With verifyRecipients, refuse to send to numbers known not to be on WhatsApp
+    private async checkRecipient(message: WhatsAppMessage): Promise<void> {
+        const target = normalizeWhatsAppTarget(message.to);
+        if (this.config.verifyRecipients && target?.kind === 'user') {
+            const contact = await this.checkContact(message.to);
+            if (!contact.onWhatsApp) {
+                throw new NotOnWhatsAppError(message.to);
+            }
+        }
+    }
+
+    private async recordSent(message: WhatsAppMessage, messageId: string): Promise<void> {
+        if (message.type === 'template') {
+            // Only templates open business-initiated conversations; replies inside a service window don't count
+            this.conversations.record(canonicalChatId(message.to));
+            this.templatePacing.track(messageId, message.content.name);
+            this.languages.trackTemplate(messageId, message.content.language.code);
+        }
+        this.metrics.messageSent(message.type, this.accountId);
+        this.events.publish({ kind: 'message_sent', accountId: this.accountId, message, messageId });
+        const sent: ArchivedMessage = {
+            id: messageId,
+            chatId: canonicalChatId(message.to),
+            direction: 'outbound',
+            sender: 'agent',
+            type: message.type,
+            text: describeContent(message),
+            timestamp: this.clock.now(),
+        };
+        if (messageId) this.sentMessages.set(messageId, sent);
+        await this.archiveMessage(sent);
+    }
+
+    private recordFailed(message: WhatsAppMessage, error: unknown, earlier: SendAttempt[] = []): void {
+        this.metrics.messageFailed(
+            message.type,
+            this.accountId,
+            (error as { code?: string }).code ?? 'SEND_FAILED'
+        );
+        const target = normalizeWhatsAppTarget(message.to);
+        if (error instanceof NotOnWhatsAppError) {
+            if (target) this.contacts.set(target.id, { input: message.to, onWhatsApp: false });
+        } else {
+            // A failed replay adds to the entry being replayed rather than dead-lettering the message twice
+            const replayed = this.replaying.get(message);
+            if (!replayed || !this.failedSends.recordAttempt(replayed, error, earlier)) {
+                this.failedSends.record(message, error, earlier);
+            }
+        }
+
+        const { code = 'SEND_FAILED', message: text = String(error) } = error as { code?: string; message?: string };
+        this.events.publish({
+            kind: 'send_failed',
+            accountId: this.accountId,
+            message,
+            error: { code, message: text },
+            failedSends: this.failedSends.size,
+        });
+        if (isTokenRejected(error)) {
+            this.events.publish({ kind: 'auth_failed', accountId: this.accountId, reason: text });
+        }
+    }
+
+    /**
+     * Send many messages at once. The Cloud API coalesces them into batch calls of up to 50;
+     * other clients send one by one. Each message succeeds or fails on its own.
+     */
+    async sendBatch(messages: WhatsAppMessage[]): Promise<BatchResult<SendMessageResponse>[]> {
+        const results: BatchResult<SendMessageResponse>[] = [];
+        if (!this.client.sendBatch) {
+            for (const message of messages) {
+                try {
+                    results.push({ ok: true, status: 200, data: await this.sendMessage(message) });
+                } catch (error) {
+                    results.push({ ok: false, error: WhatsAppError.from(error) });
+                }
+            }
+            return results;
+        }
+
+        // Batched messages get the same middleware, localization and policy as single sends;
+        // a message that fails them (or the recipient check) is reported without being sent
+        const prepared = await Promise.all(
+            messages.map((message) => this.prepare(message, {}).then((outgoing) => ({ outgoing }), (error: unknown) => ({ error })))
+        );
+        const checked = await Promise.all(
+            prepared.map((p) =>
+                "error" in p ? p.error : this.checkRecipient(p.outgoing).then(() => null, (error: unknown) => error)
+            )
+        );
+        const outgoing = prepared.map((p, i) => ("outgoing" in p ? p.outgoing : messages[i]));
+        // Refused before sending (e.g. by the policy), so there is nothing to replay
+        const refused = prepared.map((p) => "error" in p);
+        const sendable = outgoing.filter((_, i) => !checked[i]);
+        const sendBatch = this.client.sendBatch.bind(this.client);
+        const attributes = { "whatsapp.account": this.accountId, "whatsapp.batch_size": sendable.length };
+        const sent = sendable.length
+            ? await this.guarded(() =>
+                  withSpan(this.tracer, "whatsapp.send_batch", attributes, () => sendBatch(sendable))
+              )
+            : [];
+
+        for (const [i, message] of outgoing.entries()) {
+            const result: BatchResult<SendMessageResponse> = checked[i]
+                ? { ok: false, error: WhatsAppError.from(checked[i]) }
+                : (sent.shift() as BatchResult<SendMessageResponse>);
+            if (result.ok) {
+                await this.recordSent(message, result.data?.messages?.[0]?.id ?? '');
+            } else if (!refused[i]) {
+                this.recordFailed(message, result.error);
+            }
+            results.push(result);
+        }
+        return results;
+    }
+
+    // Resend a dead-lettered message; it leaves the queue once sent, a repeat failure joins its history
+    async replayFailedSend(id: string): Promise<any> {
+        const entry = this.failedSends.get(id);
+        if (!entry) {
+            throw new WhatsAppError("NOT_FOUND", `No failed send with id ${id}`);
+        }
+        this.replaying.set(entry.message, id);
+        try {
+            const response = await this.sendMessage(entry.message);
+            this.failedSends.remove(id);
+            return response;
+        } finally {
+            this.replaying.delete(entry.message);
+        }
+    }
+
+    // Drop a dead-lettered message without sending it
+    discardFailedSend(id: string): boolean {
+        return this.failedSends.remove(id);
+    }
+
+    useArchive(archive: MessageArchive): void {
+        this.archive = archive;
+    }
+
+    // In-process state of this account; stores that persist elsewhere are left out
+    exportState(): AccountStateSnapshot {
+        const menuSessions = this.menus.snapshotSessions();
+        const prompts = this.prompts.snapshot();
+        const archive = this.archive.snapshot?.();
+        return {
+            conversations: this.conversations.snapshot(),
+            ...(archive ? { archive } : {}),
+            ...(menuSessions ? { menuSessions } : {}),
+            ...(prompts ? { prompts } : {}),
+            ...(this.lastWebhookAt !== undefined ? { lastWebhookAt: this.lastWebhookAt } : {}),
+        };
+    }
+
+    importState(state: AccountStateSnapshot): void {
+        this.conversations.restore(state.conversations ?? []);
+        if (state.archive) this.archive.restore?.(state.archive);
+        if (state.menuSessions) this.menus.restoreSessions(state.menuSessions);
+        if (state.prompts) this.prompts.restore(state.prompts);
+        if (state.lastWebhookAt !== undefined) this.lastWebhookAt = state.lastWebhookAt;
+    }
+
+    private rememberMedia(kind: MediaKind, media: UnifiedMedia): void {
+        this.receivedMedia.set(media.id, {
+            kind,
+            ...(media.mimeType ? { mimeType: media.mimeType } : {}),
+            ...(media.caption ? { caption: media.caption } : {}),
+            ...(media.fileName ? { fileName: media.fileName } : {}),
+        });
+        if (this.receivedMedia.size > RECEIVED_MEDIA_LIMIT) {
+            this.receivedMedia.delete(this.receivedMedia.keys().next().value as string);
+        }
+    }
+
+    // Re-send media received on this account, optionally from another account (`via`).
+    // Cloud API media ids only work on the number that received them; otherwise the file is
+    // downloaded here and uploaded again by the sending account.
+    async forwardMedia(mediaId: string, to: string, via: WhatsAppPlugin = this): Promise<any> {
+        const received = this.receivedMedia.get(mediaId);
+        if (!received) {
+            throw new WhatsAppError("NOT_FOUND", `No received media with id ${mediaId}`);
+        }
+
+        if (via === this && detectAuthMethod(this.config) === "cloudapi") {
+            return this.sendMessage({ type: "media", to, content: { ...received, id: mediaId } });
+        }
+        if (!this.client.downloadMedia) {
+            throw new WhatsAppError("UNSUPPORTED", `Account ${this.accountId} cannot download media`);
+        }
+        const { data, mimeType } = await this.client.downloadMedia(mediaId);
+        return via.sendMessage({
+            type: "media",
+            to,
+            content: { ...received, mimeType: mimeType ?? received.mimeType, data },
+        });
+    }
+
+    // Resolve the coordinates of shared locations into places; undefined turns it off
+    useGeocoder(geocoder: Geocoder | undefined): void {
+        this.geocoder = geocoder;
+    }
+
+    // Translate between each contact's stored language and the agent's; undefined turns it off
+    useTranslator(translator: Translator | undefined, options: TranslationOptions = {}): void {
+        this.translation?.removeOutbound?.();
+        this.translation = undefined;
+        if (!translator) return;
+        const agentLanguage = options.agentLanguage ?? "en";
+        this.translation = {
+            translator,
+            agentLanguage,
+            inbound: options.inbound !== false,
+            removeOutbound:
+                options.outbound === false
+                    ? undefined
+                    : this.outbound.use(translateOutbound(translator, this.languages, agentLanguage)),
+        };
+    }
+
+    // Check every message with `policy` after the outbound middleware has run; undefined turns it off
+    usePolicy(policy: OutboundPolicy | undefined): void {
+        this.policy = policy ? enforcePolicy(policy) : undefined;
+    }
+
+    // An inbound image as a data: URI when inlineImageMaxBytes is set and the image is no larger
+    async inlineImage(msg: UnifiedMessage): Promise<string | undefined> {
+        const maxBytes = this.config.inlineImageMaxBytes;
+        if (!maxBytes || msg.type !== "image" || !msg.media || !this.client.downloadMedia) return undefined;
+        try {
+            const { data, mimeType } = await this.client.downloadMedia(msg.media.id, { maxBytes });
+            const type = mimeType ?? msg.media.mimeType ?? "image/jpeg";
+            return `data:${type};base64,${Buffer.from(data).toString("base64")}`;
+        } catch (error) {
+            if (error instanceof WhatsAppError && error.code === "MEDIA_TOO_LARGE") return undefined;
+            throw error;
+        }
+    }
+
+    // Whether sends go through the Cloud API, where free-form messages need an open service window
+    get usesCloudApi(): boolean {
+        return detectAuthMethod(this.config) === "cloudapi";
+    }
+
+    // Last messages exchanged with a chat, oldest first
+    async getRecentMessages(chat: string, limit: number): Promise<ArchivedMessage[]> {
+        return this.archive.recent(canonicalChatId(chat), limit);
+    }
+
+    // Whether this is the first delivery of an inbound message; Meta redelivers notifications it
+    // thinks were missed, and a redelivery must not reach the agent a second time
+    private firstDelivery(id: string): boolean {
+        if (!id) return true;
+        if (this.receivedIds.get(id)) return false;
+        this.receivedIds.set(id, true);
+        return true;
+    }
+
+    // Resolves once subscribers have handled the message, so webhook processing can bound concurrency
+    private async receive(received: UnifiedMessage): Promise<void> {
+        if (!this.firstDelivery(received.id)) return;
+        this.metrics.messageReceived(received.type, this.accountId);
+        if (received.media && isMediaKind(received.type)) {
+            this.rememberMedia(received.type, received.media);
+        }
+        const msg = await this.locate(received);
+        if (!this.translation?.inbound && !msg.reaction && !msg.participant) {
+            // Nothing waits on the sender's language without a translator, so learn it in the background
+            this.languages.observe(msg).catch((error) => this.emit('error', error));
+        }
+        if (msg.reaction) {
+            // Reactions drive workflows instead of being archived as conversation turns
+            void this.handleReaction(msg);
+        } else {
+            void this.archiveMessage({
+                id: msg.id,
+                chatId: canonicalChatId(msg.from),
+                direction: 'inbound',
+                sender: msg.participant ?? msg.from,
+                type: msg.type,
+                text: msg.content,
+                timestamp: msg.timestamp ? msg.timestamp * 1000 : this.clock.now(),
+            });
+        }
+        this.emit('message', msg);
+        if (msg.button) {
+            // Template quick replies also reach subscribers as their own event, apart from interactive replies
+            this.emit('templateButton', msg);
+            this.events.publish({ kind: 'template_button_clicked', accountId: this.accountId, message: msg, button: msg.button });
+        }
+        return this.events.deliver({ kind: 'message_received', accountId: this.accountId, message: msg });
+    }
+
+    // Describe a shared location by the place it is near, so the agent needn't interpret coordinates
+    private async locate(msg: UnifiedMessage): Promise<UnifiedMessage> {
+        if (!msg.location || !this.geocoder) return msg;
+        try {
+            const location = await enrichLocation(msg.location, this.geocoder);
+            return location === msg.location ? msg : { ...msg, location, content: formatLocation(location) };
+        } catch (error) {
+            this.emit('error', error);
+            return msg;
+        }
+    }
+
+    // A typed answer to the options last offered in the chat ("2", "the first one") reads as if
+    // the option was tapped; a tapped reply consumes the prompt as well. Call it once the message
+    // is known to be handled (the ElizaOS service does so after its sender checks), so dropped
+    // messages leave the prompt for the answer that counts.
+    async interpretAnswer(msg: UnifiedMessage): Promise<UnifiedMessage> {
+        try {
+            if (msg.replyId) {
+                await this.prompts.clear(msg.from);
+                return msg;
+            }
+            if (msg.type !== "text" || !msg.content) return msg;
+            const option = await this.prompts.interpret(msg.from, msg.content);
+            return option ? { ...msg, replyId: option.id, replyTitle: option.title } : msg;
+        } catch (error) {
+            this.emit("error", error);
+            return msg;
+        }
+    }
+
+    // With an inbound translator, learn the sender's language and add the text in the agent's
+    // language. It is a model call, so the message handler makes it (the ElizaOS service does
+    // before handing the message to the agent) rather than the webhook path.
+    async translateReceived(msg: UnifiedMessage): Promise<UnifiedMessage> {
+        const translation = this.translation?.inbound ? this.translation : undefined;
+        if (!translation || msg.reaction || msg.participant) return msg;
+        try {
+            await this.languages.observe(msg);
+            const translated = await translateInbound(msg, translation.translator, this.languages, translation.agentLanguage);
+            return translated ? { ...msg, translation: translated } : msg;
+        } catch (error) {
+            // An untranslated message beats a lost one
+            this.emit('error', error);
+            return msg;
+        }
+    }
+
+    // Stickers, locations and other types the agent can't read; optionally tell the sender so
+    private async receiveUnsupported(msg: UnsupportedMessage): Promise<void> {
+        if (!this.firstDelivery(msg.id)) return;
+        this.metrics.messageReceived('unsupported', this.accountId);
+        const notice = this.config.unsupportedMessageReply;
+        if (notice && !msg.participant && !SILENT_UNSUPPORTED_TYPES.has(msg.type) && this.noticeDue(msg.from)) {
+            this.sendMessage({ type: 'text', to: msg.from, content: notice }).catch((error) => this.emit('error', error));
+        }
+        this.emit('unsupported', msg);
+        return this.events.deliver({ kind: 'message_unsupported', accountId: this.accountId, message: msg });
+    }
+
+    private noticeDue(from: string): boolean {
+        const chatId = canonicalChatId(from);
+        const now = this.clock.now();
+        const noticedAt = this.unsupportedNotices.get(chatId);
+        if (noticedAt !== undefined && now - noticedAt < UNSUPPORTED_NOTICE_INTERVAL_MS) return false;
+        this.unsupportedNotices.set(chatId, now);
+        return true;
+    }
+
+    private receiveStatus(status: MessageStatusUpdate): Promise<void> {
+        this.deliveries.record(status);
+        const pacing = this.templatePacing.observeStatus(status);
+        if (pacing) this.publishPacing(pacing);
+        this.emit('status', status);
+        return this.events.deliver({ kind: 'status_changed', accountId: this.accountId, status });
+    }
+
+    // Messages sent by people from the Business app join the transcript as outbound turns. Echoes
+    // are never templates, so they don't use up messaging tier capacity.
+    private receiveEcho(message: MessageEcho): Promise<void> {
+        void this.archiveMessage({
+            id: message.id,
+            chatId: canonicalChatId(message.to),
+            direction: 'outbound',
+            sender: 'business',
+            type: message.type,
+            text: message.content,
+            timestamp: message.timestamp ? message.timestamp * 1000 : this.clock.now(),
+        });
+        this.emit('echo', message);
+        return this.events.deliver({ kind: 'message_echoed', accountId: this.accountId, message });
+    }
+
+    // Archive failures must never break sending or receiving
+    private async archiveMessage(entry: ArchivedMessage): Promise<void> {
+        try {
+            await this.archive.append(entry);
+        } catch (error) {
+            this.emit('error', error);
+        }
+    }
+
+    private publishPacing(pacing: TemplatePacingUpdate): void {
+        this.emit('templatePacing', pacing);
+        this.events.publish({ kind: 'template_pacing_changed', accountId: this.accountId, pacing });
+    }
+
+    // Conversations this number can still open today: its messaging limit less the chats sent a template in the last 24h
+    async remainingCapacity(now = this.clock.now()): Promise<number> {
+        return Math.max(0, (await this.messagingLimit(now)) - this.conversations.count(now));
+    }
+
+    // Conversations this number may open per rolling 24 hours: messagingLimit, else its Meta tier
+    async messagingLimit(now = this.clock.now()): Promise<number> {
+        return this.config.messagingLimit ?? (await this.tierLimit(now)) ?? DEFAULT_MESSAGING_LIMIT;
+    }
+
+    // Messaging tier of a Cloud API number, looked up at most once an hour
+    private async tierLimit(now: number): Promise<number | undefined> {
+        if (!this.client.getPhoneNumber) return undefined;
+        if (!this.tier || now - this.tier.checkedAt > TIER_CACHE_MS) {
+            let limit: number | undefined;
+            try {
+                limit = messagingTierLimit((await this.client.getPhoneNumber()).messaging_limit_tier);
+            } catch (error) {
+                this.emit('error', error);
+            }
+            this.tier = { limit, checkedAt: now };
+        }
+        return this.tier.limit;
+    }
+
+    // Send the same message to every contact matching a tag selector, one at a time
+    async broadcast(
+        selector: AudienceSelector,
+        message: Omit<WhatsAppMessage, 'to'>
+    ): Promise<Array<{ to: string; ok: boolean; error?: string }>> {
+        const results: Array<{ to: string; ok: boolean; error?: string }> = [];
+        for (const to of await this.tags.select(selector)) {
+            try {
+                await this.sendMessage({ ...message, to } as WhatsAppMessage);
+                results.push({ to, ok: true });
+            } catch (error) {
+                results.push({ to, ok: false, error: (error as Error).message });
+            }
+        }
+        return results;
+    }
+
+    // Current reactions on one of the agent's messages, with counts by emoji
+    getReactions(messageId: string): MessageReactions {
+        return this.reactionTally.get(messageId);
+    }
+
+    // Tally reactions to the agent's archived messages and run workflows for added ones
+    private async handleReaction(msg: UnifiedMessage): Promise<void> {
+        const reaction = msg.reaction;
+        if (!reaction) return;
+
+        const chatId = canonicalChatId(msg.from);
+        try {
+            const message = await this.sentMessage(chatId, reaction.messageId);
+            if (!message) return;
+            this.reactionTally.record(message.id, msg.participant ?? msg.from, reaction.emoji);
+            if (!reaction.emoji || !this.reactions.has(reaction.emoji)) return;
+            await this.reactions.dispatch({
+                emoji: reaction.emoji,
+                reactor: msg.participant ?? msg.from,
+                chatId,
+                accountId: this.accountId,
+                message,
+            });
+        } catch (error) {
+            this.emit('error', error);
+        }
+    }
+
+    // One of the agent's messages by id; the chat's archive is only read for one sent before this
+    // process remembered it, e.g. before a restart
+    private async sentMessage(chatId: string, messageId: string): Promise<ArchivedMessage | undefined> {
+        const remembered = this.sentMessages.get(messageId);
+        if (remembered) return remembered.chatId === chatId ? remembered : undefined;
+        const history = await this.archive.recent(chatId, Number.MAX_SAFE_INTEGER);
+        return history.find((m) => m.id === messageId && m.direction === 'outbound');
+    }
+
+    // Read receipts and typing are best effort: unsupported clients and failures are ignored
+    async markRead(message: InboundMessageRef): Promise<void> {
+        await this.client.markRead?.(message).catch(() => undefined);
+    }
+
+    async sendTyping(message: InboundMessageRef): Promise<void> {
+        await this.client.sendTyping?.(message).catch(() => undefined);
+    }
+
+    // Answer an inbound message, simulating typing first when the account is humanized
+    async sendReply(inbound: InboundMessageRef, messages: WhatsAppMessage[]): Promise<void> {
+        const humanize = this.config.humanize;
+        const part = (index: number): SendOptions => ({ reply: { index, count: messages.length } });
+        if (!humanize || humanize.enabled === false) {
+            for (const [index, message] of messages.entries()) await this.sendMessage(message, part(index));
+            return;
+        }
+
+        let queued = messages.length;
+        this.queuedReplies += queued;
+        this.emit('pendingSends', this.pendingSends);
+        try {
+            if (humanize.markRead !== false) await this.markRead(inbound);
+            for (const [index, message] of messages.entries()) {
+                await this.sendTyping(inbound);
+                await this.sleep(typingDelay(describeContent(message), humanize));
+                queued--;
+                this.queuedReplies--;
+                await this.sendMessage(message, part(index));
+            }
+        } finally {
+            this.queuedReplies -= queued;
+            if (queued > 0) this.emit('pendingSends', this.pendingSends);
+        }
+    }
+
+    // Whether a number is on WhatsApp; results are cached per normalized number
+    async checkContact(user: string): Promise<ContactCheckResult> {
+        const key = normalizeWhatsAppTarget(user)?.id ?? user;
+        const cached = this.contacts.get(key);
+        if (cached) return cached;
+
+        if (!this.client.checkContacts) {
+            throw new WhatsAppError("UNSUPPORTED", "This client can't check whether numbers are on WhatsApp");
+        }
+        const [result] = await this.client.checkContacts([user]);
+        this.contacts.set(key, result);
+        return result;
+    }
+
+    async startMenu(to: string, menuId: string): Promise<any> {
+        return this.sendMessage(await this.menus.start(menuId, to));
+    }
+
+    // Open a poll and send it to every recipient; the result is emitted when the window closes
+    async startPoll(
+        definition: PollDefinition
+    ): Promise<{ pollId: string; results: Array<{ to: string; ok: boolean; error?: string }> }> {
+        const { pollId, messages } = this.polls.open(definition);
+        const results: Array<{ to: string; ok: boolean; error?: string }> = [];
+        for (const message of messages) {
+            try {
+                await this.sendMessage(message);
+                results.push({ to: message.to, ok: true });
+            } catch (error) {
+                results.push({ to: message.to, ok: false, error: (error as Error).message });
+            }
+        }
+        return { pollId, results };
+    }
+
+    // Record an interactive reply as a poll vote; returns false when it is not a vote
+    handlePollReply(msg: UnifiedMessage): boolean {
+        if (!msg.replyId) return false;
+        return this.polls.vote(msg.participant ?? msg.from, msg.replyId, msg.from);
+    }
+
+    // Post a Cloud API message payload as-is, for message types this plugin does not model yet
+    async sendRaw(payload: Record<string, unknown>): Promise<SendMessageResponse> {
+        if (!this.client.sendRaw) {
+            throw new WhatsAppError("UNSUPPORTED", "Raw payloads are only supported by the Cloud API");
+        }
+        const sendRaw = this.client.sendRaw.bind(this.client);
+        const attributes = { "whatsapp.account": this.accountId, "whatsapp.message_type": String(payload.type ?? "raw") };
+        return this.guarded(() => withSpan(this.tracer, "whatsapp.send_raw", attributes, () => sendRaw(payload)));
+    }
+
+    // Send buttons or a list and remember what to do when each option is chosen
+    async sendInteractive(
+        to: string,
+        content: WhatsAppInteractive,
+        callbacks: Record<string, ButtonCallback>,
+        context: Record<string, unknown> = {},
+        ttlMs?: number
+    ): Promise<any> {
+        this.buttonCallbacks.register(to, callbacks, context, ttlMs);
+        return this.sendMessage({ type: "interactive", to, content });
+    }
+
+    // Run the callback registered for a button reply; returns false when there is none.
+    // Callbacks naming an action need `runAction`, which the ElizaOS service provides.
+    async handleButtonReply(
+        msg: UnifiedMessage,
+        runAction?: (action: string, options: Record<string, unknown>, context: Record<string, unknown>) => Promise<void>
+    ): Promise<boolean> {
+        if (!msg.replyId) return false;
+        const resolved = this.buttonCallbacks.resolve(msg.from, msg.replyId);
+        if (!resolved) return false;
+
+        const { callback, context } = resolved;
+        if (typeof callback === "function") {
+            await callback({ reply: msg, context, accountId: this.accountId });
+        } else if (runAction) {
+            await runAction(callback.action, callback.options ?? {}, context);
+        } else {
+            throw new WhatsAppError("UNSUPPORTED", `Button "${msg.replyId}" maps to action ${callback.action}, but no action runner is available`);
+        }
+        return true;
+    }
+
+    // Advance a running menu with an interactive reply, sending the next prompt if any
+    async handleMenuReply(msg: UnifiedMessage): Promise<MenuStep | null> {
+        if (!msg.replyId) return null;
+        const step = await this.menus.handleReply(msg.from, msg.replyId);
+        if (step?.message) {
+            await this.sendMessage(step.message);
+        }
+        return step;
+    }
+
+    async blockUsers(users: string[]): Promise<string[]> {
+        if (!this.client.blockUsers) {
+            throw new WhatsAppError("UNSUPPORTED", "This client can't manage the blocklist");
+        }
+        return this.client.blockUsers(users);
+    }
+
+    async unblockUsers(users: string[]): Promise<string[]> {
+        if (!this.client.unblockUsers) {
+            throw new WhatsAppError("UNSUPPORTED", "This client can't manage the blocklist");
+        }
+        return this.client.unblockUsers(users);
+    }
+
+    async listBlockedUsers(): Promise<string[]> {
+        if (!this.client.listBlockedUsers) {
+            throw new WhatsAppError("UNSUPPORTED", "This client can't manage the blocklist");
+        }
+        return this.client.listBlockedUsers();
+    }
+
+    // Details of a WhatsApp Channel (Baileys accounts only)
+    async getNewsletter(id: string): Promise<NewsletterInfo> {
+        if (!this.client.getNewsletter) {
+            throw new WhatsAppError("UNSUPPORTED", "WhatsApp Channels are only supported by Baileys accounts");
+        }
+        return this.client.getNewsletter(id);
+    }
+
+    // Create a channel owned by this account; post to it with sendMessage({ to: info.id, ... })
+    async createNewsletter(name: string, description?: string): Promise<NewsletterInfo> {
+        if (!this.client.createNewsletter) {
+            throw new WhatsAppError("UNSUPPORTED", "WhatsApp Channels are only supported by Baileys accounts");
+        }
+        return this.client.createNewsletter(name, description);
+    }
+
+    async requestCallPermission(to: string, body: string): Promise<any> {
+        return this.sendMessage(buildCallPermissionRequest(to, body));
+    }
+
+    async initiateCall(to: string, sdpOffer: string): Promise<any> {
+        return this.callAction('connect', { to, session: { sdpType: 'offer', sdp: sdpOffer } });
+    }
+
+    async acceptCall(callId: string, session: CallSession, preAccept = false): Promise<any> {
+        return this.callAction(preAccept ? 'pre_accept' : 'accept', { callId, session });
+    }
+
+    async rejectCall(callId: string): Promise<any> {
+        return this.callAction('reject', { callId });
+    }
+
+    async terminateCall(callId: string): Promise<any> {
+        return this.callAction('terminate', { callId });
+    }
+
+    private async callAction(action: CallAction, options: CallActionOptions): Promise<any> {
+        if (!this.client.manageCall) {
+            throw new WhatsAppError("UNSUPPORTED", 'Calling is not supported by this client implementation');
+        }
+        const response = await this.client.manageCall(action, options);
+        return response?.data ?? response;
     }
 
     async handleWebhook(event: WhatsAppWebhookEvent): Promise<void> {
-        return this.webhookHandler.handle(event);
+        const attributes = {
+            "whatsapp.account": this.accountId,
+            "whatsapp.entry_count": event.entry?.length ?? 0,
+        };
+        this.lastWebhookAt = this.clock.now();
+        return withSpan(this.tracer, "whatsapp.webhook", attributes, async () => {
+            const started = performance.now();
+            try {
+                return await this.webhookHandler.handle(event);
+            } finally {
+                this.metrics.observeWebhookProcessing((performance.now() - started) / 1000);
+            }
+        });
+    }
+
+    // Webhook notifications must be signed with the app secret when one is configured
+    verifySignature(_event: WhatsAppWebhookEvent, rawBody?: string, signature?: string): boolean {
+        const valid = this.signatureMatches(rawBody, signature);
+        if (!valid) this.events.publish({ kind: 'webhook_rejected', accountId: this.accountId, reason: 'signature' });
+        return valid;
+    }
+
+    // Whether a webhook body was signed with this account's app secret; true when it has none
+    signatureMatches(rawBody?: string, signature?: string): boolean {
+        const secret = "appSecret" in this.config ? this.config.appSecret : undefined;
+        if (!secret) return true;
+        return rawBody !== undefined && isValidSignature(rawBody, signature, secret);
     }
 
     async verifyWebhook(token: string): Promise<boolean> {
         if (!this.client.verifyWebhook) {
-            throw new Error('verifyWebhook is not supported by this client implementation');
+            throw new WhatsAppError("UNSUPPORTED", 'verifyWebhook is not supported by this client implementation');
         }
         return this.client.verifyWebhook(token);
     }
@@ -60,13 +1287,63 @@ export class WhatsAppPlugin extends EventEmitter implements Plugin {
 }
 
 export * from "./types";
+export * from "./errors";
+export * from "./utils/normalize";
+export * from "./utils/clock";
+export * from "./utils/lru";
+export * from "./utils/invite-link";
 export { ClientFactory } from "./clients/factory";
+export type { DownloadedMedia, GroupInviteInfo, InboundMessageRef, NewsletterInfo, PhoneNumberDetails } from "./clients/interface";
+export { MAX_BATCH_SIZE } from "./clients/batch";
+export type { BatchRequest, BatchResult } from "./clients/batch";
+export { fromCloudPayload, toCloudPayload } from "./clients/cloud-payload";
+export { fetchTransport, transportAdapter } from "./clients/transport";
+export type { HttpRequest, HttpResponse, HttpTransport } from "./clients/transport";
+export { fixtureAdapter } from "./clients/fixtures";
+export type { FixtureConfig, FixtureMode } from "./clients/fixtures";
 export { WhatsAppConnectorService } from "./service";
+export type { ChatState, ServiceSendOptions } from "./service";
+export { validateDeployment, checkHealth, HealthMonitor } from "./accounts";
+export type {
+    DeploymentIssue,
+    DeploymentReport,
+    ValidateDeploymentOptions,
+    HealthCheckConfig,
+    HealthProblem,
+} from "./accounts";
+export * from "./menus";
+export * from "./calls";
+export * from "./payments";
+export * from "./actions";
+export * from "./middleware";
+export * from "./location";
+export * from "./contacts";
+export * from "./archive";
+export * from "./providers";
+export * from "./metrics";
+export * from "./tracing";
+export * from "./outbox";
+export * from "./admin";
+export * from "./http";
+export * from "./events";
+export * from "./pacing";
+export * from "./reactions";
+export * from "./polls";
+export * from "./canned";
+export * from "./templates";
+export * from "./catalog";
+export * from "./billing";
+export * from "./otp";
+export * from "./interactive";
+export * from "./state";
 
 const whatsappPlugin: Plugin = {
   name: "whatsapp",
   description: "WhatsApp connector for ElizaOS — supports Baileys (QR code) and Cloud API",
   services: [WhatsAppConnectorService],
+  actions: whatsappActions,
+  providers: whatsappProviders,
+  routes: [...webhookRoutes, ...adminRoutes],
 };
 
 export default whatsappPlugin;
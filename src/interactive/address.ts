@@ -0,0 +1,64 @@
+import type { AddressSubmission } from '../types';
+
+// Countries where WhatsApp offers address messages
+export const ADDRESS_MESSAGE_COUNTRIES = ['IN', 'SG'] as const;
+
+function field(values: Record<string, string>, ...keys: string[]): string | undefined {
+  for (const key of keys) {
+    if (values[key]) return values[key];
+  }
+  return undefined;
+}
+
+/**
+ * Typed address from the `response_json` of an address_message reply, or null when it is not one
+ * (e.g. a flow submission) or does not parse.
+ */
+export function parseAddressSubmission(reply: { name?: string; response_json: string }): AddressSubmission | null {
+  if (reply.name !== 'address_message') return null;
+
+  let response: { saved_address_id?: string; values?: Record<string, unknown> };
+  try {
+    response = JSON.parse(reply.response_json);
+  } catch {
+    return null;
+  }
+  if (!response || typeof response !== 'object') return null;
+  const values = Object.fromEntries(
+    Object.entries(response.values ?? {}).filter((entry): entry is [string, string] => typeof entry[1] === 'string')
+  );
+
+  const fields: Omit<AddressSubmission, 'savedAddressId' | 'values'> = {
+    name: field(values, 'name'),
+    phoneNumber: field(values, 'phone_number'),
+    postalCode: field(values, 'in_pin_code', 'sg_post_code'),
+    houseNumber: field(values, 'house_number', 'unit_number'),
+    buildingName: field(values, 'building_name'),
+    address: field(values, 'address'),
+    landmarkArea: field(values, 'landmark_area'),
+    city: field(values, 'city'),
+    state: field(values, 'state'),
+  };
+  return {
+    ...(response.saved_address_id ? { savedAddressId: response.saved_address_id } : {}),
+    // Leave out fields the user did not fill in
+    ...Object.fromEntries(Object.entries(fields).filter(([, value]) => value !== undefined)),
+    values,
+  };
+}
+
+// One line for transcripts and the agent: "Ada, 12 Main Rd, Goregaon, Mumbai, Maharashtra 400063"
+export function formatAddress(address: AddressSubmission): string {
+  const locality = [address.state, address.postalCode].filter(Boolean).join(' ');
+  return [
+    address.name,
+    address.houseNumber,
+    address.buildingName,
+    address.address,
+    address.landmarkArea,
+    address.city,
+    locality,
+  ]
+    .filter(Boolean)
+    .join(', ');
+}
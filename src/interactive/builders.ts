@@ -0,0 +1,275 @@
+import { InteractiveLimitError, WhatsAppError } from '../errors';
+import type { WhatsAppAddressParameters, WhatsAppFlowParameters, WhatsAppInteractive } from '../types';
+import { ADDRESS_MESSAGE_COUNTRIES } from './address';
+
+// Cloud API limits for interactive messages
+export const INTERACTIVE_LIMITS = {
+  bodyText: 1024,
+  headerText: 60,
+  footerText: 60,
+  buttons: 3,
+  buttonTitle: 20,
+  buttonId: 256,
+  listButtonText: 20,
+  sections: 10,
+  rows: 10,                             // Across all sections
+  sectionTitle: 24,
+  rowTitle: 24,
+  rowDescription: 72,
+  rowId: 200,
+  ctaText: 20,
+} as const;
+
+type ListRow = NonNullable<NonNullable<WhatsAppInteractive['action']['sections']>[number]['rows']>[number];
+type ListSection = { title?: string; rows: ListRow[] };
+
+function checkLength(field: string, value: string, limit: number): string {
+  if (!value) {
+    throw new WhatsAppError('INTERACTIVE_LIMIT', `${field} cannot be empty`);
+  }
+  // Limits count characters as the user sees them, not UTF-16 code units
+  const length = [...value].length;
+  if (length > limit) throw new InteractiveLimitError(field, limit, length);
+  return value;
+}
+
+function checkCount(field: string, count: number, limit: number): void {
+  if (count > limit) throw new InteractiveLimitError(field, limit, count, 'entries');
+}
+
+abstract class InteractiveBuilder<T> {
+  protected headerText?: string;
+  protected footerText?: string;
+
+  constructor(protected bodyText: string) {
+    checkLength('Body', bodyText, INTERACTIVE_LIMITS.bodyText);
+  }
+
+  header(text: string): T {
+    this.headerText = checkLength('Header', text, INTERACTIVE_LIMITS.headerText);
+    return this as unknown as T;
+  }
+
+  footer(text: string): T {
+    this.footerText = checkLength('Footer', text, INTERACTIVE_LIMITS.footerText);
+    return this as unknown as T;
+  }
+
+  protected frame(): Pick<WhatsAppInteractive, 'header' | 'body' | 'footer'> {
+    return {
+      ...(this.headerText ? { header: { type: 'text', text: this.headerText } } : {}),
+      body: { text: this.bodyText },
+      ...(this.footerText ? { footer: { text: this.footerText } } : {}),
+    };
+  }
+}
+
+// Rows of one list section
+export class ListSectionBuilder {
+  private rows: ListRow[] = [];
+
+  constructor(private title?: string) {
+    if (title !== undefined) checkLength('Section title', title, INTERACTIVE_LIMITS.sectionTitle);
+  }
+
+  row(id: string, title: string, description?: string): this {
+    checkLength(`Row "${id}" id`, id, INTERACTIVE_LIMITS.rowId);
+    checkLength(`Row "${id}" title`, title, INTERACTIVE_LIMITS.rowTitle);
+    if (description !== undefined) {
+      checkLength(`Row "${id}" description`, description, INTERACTIVE_LIMITS.rowDescription);
+    }
+    this.rows.push({ id, title, ...(description ? { description } : {}) });
+    return this;
+  }
+
+  build(): ListSection {
+    return { ...(this.title ? { title: this.title } : {}), rows: [...this.rows] };
+  }
+}
+
+// new ListMessageBuilder('Pick a slot').button('Slots').section('Today', (s) => s.row('9', '9:00')).build()
+export class ListMessageBuilder extends InteractiveBuilder<ListMessageBuilder> {
+  private buttonText = 'Choose';
+  private sections: ListSection[] = [];
+
+  button(text: string): this {
+    this.buttonText = checkLength('List button', text, INTERACTIVE_LIMITS.listButtonText);
+    return this;
+  }
+
+  section(title: string | undefined, rows: (section: ListSectionBuilder) => ListSectionBuilder): this {
+    const section = rows(new ListSectionBuilder(title)).build();
+    if (section.rows.length === 0) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', `Section "${title ?? ''}" has no rows`);
+    }
+    checkCount('List sections', this.sections.length + 1, INTERACTIVE_LIMITS.sections);
+    checkCount('List rows', this.rowCount() + section.rows.length, INTERACTIVE_LIMITS.rows);
+
+    const ids = new Set(this.sections.flatMap((s) => s.rows.map((r) => r.id)));
+    const duplicate = section.rows.find((r, i) => ids.has(r.id) || section.rows.findIndex((o) => o.id === r.id) !== i);
+    if (duplicate) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', `Row id "${duplicate.id}" is used more than once`);
+    }
+    this.sections.push(section);
+    return this;
+  }
+
+  build(): WhatsAppInteractive {
+    if (this.sections.length === 0) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', 'A list needs at least one section');
+    }
+    // Section titles are required once a list has more than one section
+    if (this.sections.length > 1 && this.sections.some((s) => !s.title)) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', 'Every section needs a title when a list has several');
+    }
+    return {
+      type: 'list',
+      ...this.frame(),
+      action: { button: this.buttonText, sections: this.sections.map((s) => ({ ...s, rows: [...s.rows] })) },
+    };
+  }
+
+  private rowCount(): number {
+    return this.sections.reduce((n, s) => n + s.rows.length, 0);
+  }
+}
+
+// new ButtonMessageBuilder('Confirm?').button('yes', 'Yes').button('no', 'No').build()
+export class ButtonMessageBuilder extends InteractiveBuilder<ButtonMessageBuilder> {
+  private buttons: Array<{ id: string; title: string }> = [];
+
+  button(id: string, title: string): this {
+    checkLength(`Button "${id}" id`, id, INTERACTIVE_LIMITS.buttonId);
+    checkLength(`Button "${id}" title`, title, INTERACTIVE_LIMITS.buttonTitle);
+    checkCount('Reply buttons', this.buttons.length + 1, INTERACTIVE_LIMITS.buttons);
+    if (this.buttons.some((b) => b.id === id)) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', `Button id "${id}" is used more than once`);
+    }
+    this.buttons.push({ id, title });
+    return this;
+  }
+
+  build(): WhatsAppInteractive {
+    if (this.buttons.length === 0) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', 'A button message needs at least one button');
+    }
+    return {
+      type: 'button',
+      ...this.frame(),
+      action: { buttons: this.buttons.map((reply) => ({ type: 'reply' as const, reply: { ...reply } })) },
+    };
+  }
+}
+
+// new CtaUrlMessageBuilder('Track your order').link('Track', 'https://example.com/track/42').build()
+export class CtaUrlMessageBuilder extends InteractiveBuilder<CtaUrlMessageBuilder> {
+  private displayText?: string;
+  private url?: string;
+
+  link(displayText: string, url: string): this {
+    this.displayText = checkLength('Button text', displayText, INTERACTIVE_LIMITS.ctaText);
+    if (!/^https?:\/\//.test(url)) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', `Button url "${url}" must be http or https`);
+    }
+    this.url = url;
+    return this;
+  }
+
+  build(): WhatsAppInteractive {
+    if (!this.displayText || !this.url) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', 'A CTA message needs a link');
+    }
+    return {
+      type: 'cta_url',
+      ...this.frame(),
+      action: { name: 'cta_url', parameters: { display_text: this.displayText, url: this.url } },
+    };
+  }
+}
+
+// new FlowMessageBuilder('Book a table', { flowId: '123', cta: 'Book' }).navigate('DATE').build()
+export class FlowMessageBuilder extends InteractiveBuilder<FlowMessageBuilder> {
+  private parameters: WhatsAppFlowParameters;
+
+  constructor(
+    bodyText: string,
+    flow: { flowId?: string; flowName?: string; cta: string; token?: string; draft?: boolean }
+  ) {
+    super(bodyText);
+    if (!flow.flowId === !flow.flowName) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', 'A flow message needs exactly one of flowId or flowName');
+    }
+    this.parameters = {
+      flow_message_version: '3',
+      ...(flow.flowId ? { flow_id: flow.flowId } : { flow_name: flow.flowName }),
+      flow_cta: checkLength('Flow button text', flow.cta, INTERACTIVE_LIMITS.ctaText),
+      ...(flow.token ? { flow_token: flow.token } : {}),
+      ...(flow.draft ? { mode: 'draft' as const } : {}),
+    };
+  }
+
+  // Open the flow on a given screen instead of asking the endpoint for the first one
+  navigate(screen: string, data?: Record<string, unknown>): this {
+    this.parameters = {
+      ...this.parameters,
+      flow_action: 'navigate',
+      flow_action_payload: { screen, ...(data ? { data } : {}) },
+    };
+    return this;
+  }
+
+  build(): WhatsAppInteractive {
+    return {
+      type: 'flow',
+      ...this.frame(),
+      action: { name: 'flow', parameters: { ...this.parameters } },
+    };
+  }
+}
+
+// new AddressMessageBuilder('Where should we deliver?', 'IN').prefill({ name: 'Ada' }).build()
+export class AddressMessageBuilder extends InteractiveBuilder<AddressMessageBuilder> {
+  private parameters: WhatsAppAddressParameters;
+
+  constructor(bodyText: string, country: string) {
+    super(bodyText);
+    const code = country.toUpperCase();
+    if (!(ADDRESS_MESSAGE_COUNTRIES as readonly string[]).includes(code)) {
+      throw new WhatsAppError(
+        'INTERACTIVE_LIMIT',
+        `Address messages are only available in ${ADDRESS_MESSAGE_COUNTRIES.join(', ')}, not ${country}`
+      );
+    }
+    this.parameters = { country: code };
+  }
+
+  // Fields filled in for the user, e.g. { name, phone_number, in_pin_code }
+  prefill(values: Record<string, string>): this {
+    this.parameters = { ...this.parameters, values: { ...this.parameters.values, ...values } };
+    return this;
+  }
+
+  // Addresses the user can pick instead of typing one; the chosen id comes back as savedAddressId
+  savedAddress(id: string, value: Record<string, string>): this {
+    if (this.parameters.saved_addresses?.some((a) => a.id === id)) {
+      throw new WhatsAppError('INTERACTIVE_LIMIT', `Saved address id "${id}" is used more than once`);
+    }
+    const saved = [...(this.parameters.saved_addresses ?? []), { id, value: { ...value } }];
+    this.parameters = { ...this.parameters, saved_addresses: saved };
+    return this;
+  }
+
+  // Ask again after a submission failed validation, with a message per field
+  errors(validationErrors: Record<string, string>): this {
+    this.parameters = { ...this.parameters, validation_errors: { ...validationErrors } };
+    return this;
+  }
+
+  build(): WhatsAppInteractive {
+    return {
+      type: 'address_message',
+      ...this.frame(),
+      action: { name: 'address_message', parameters: { ...this.parameters } },
+    };
+  }
+}
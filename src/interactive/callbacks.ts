@@ -0,0 +1,68 @@
+import type { UnifiedMessage } from '../types';
+import { canonicalChatId } from '../utils/normalize';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface ButtonReplyContext {
+  reply: UnifiedMessage;                // The inbound reply; `replyId` is the button pressed
+  context: Record<string, unknown>;     // Whatever was registered with the buttons
+  accountId: string;
+}
+
+// Run a function, or hand the reply to a runtime action by name with extra options
+export type ButtonCallback =
+  | ((reply: ButtonReplyContext) => void | Promise<void>)
+  | { action: string; options?: Record<string, unknown> };
+
+interface PendingButtons {
+  callbacks: Record<string, ButtonCallback>;
+  context: Record<string, unknown>;
+  expiresAt: number;
+}
+
+const DEFAULT_TTL_MS = 24 * 60 * 60 * 1000;
+
+// Callbacks for buttons sent to a chat. The first reply to a message consumes all of its buttons.
+export class ButtonCallbackRegistry {
+  private pending = new Map<string, PendingButtons>();
+
+  constructor(private readonly clock: Clock = systemClock) {}
+
+  register(
+    chat: string,
+    callbacks: Record<string, ButtonCallback>,
+    context: Record<string, unknown> = {},
+    ttlMs = DEFAULT_TTL_MS
+  ): void {
+    this.prune();
+    const entry: PendingButtons = { callbacks, context, expiresAt: this.clock.now() + ttlMs };
+    for (const id of Object.keys(callbacks)) {
+      this.pending.set(this.key(chat, id), entry);
+    }
+  }
+
+  // Take the callback for a reply, or undefined when the button was not registered (or expired)
+  resolve(chat: string, replyId: string): { callback: ButtonCallback; context: Record<string, unknown> } | undefined {
+    const entry = this.pending.get(this.key(chat, replyId));
+    if (!entry) return undefined;
+
+    for (const id of Object.keys(entry.callbacks)) {
+      this.pending.delete(this.key(chat, id));
+    }
+    if (entry.expiresAt <= this.clock.now()) return undefined;
+    return { callback: entry.callbacks[replyId], context: entry.context };
+  }
+
+  get size(): number {
+    return this.pending.size;
+  }
+
+  private prune(now = this.clock.now()): void {
+    for (const [key, entry] of this.pending) {
+      if (entry.expiresAt <= now) this.pending.delete(key);
+    }
+  }
+
+  private key(chat: string, replyId: string): string {
+    return `${canonicalChatId(chat)}:${replyId}`;
+  }
+}
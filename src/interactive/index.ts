@@ -0,0 +1,20 @@
+export {
+  AddressMessageBuilder,
+  ButtonMessageBuilder,
+  CtaUrlMessageBuilder,
+  FlowMessageBuilder,
+  INTERACTIVE_LIMITS,
+  ListMessageBuilder,
+  ListSectionBuilder,
+} from './builders';
+export { ADDRESS_MESSAGE_COUNTRIES, formatAddress, parseAddressSubmission } from './address';
+export { ButtonCallbackRegistry } from './callbacks';
+export type { ButtonCallback, ButtonReplyContext } from './callbacks';
+export {
+  InMemoryPromptStore,
+  InteractivePrompts,
+  RuntimePromptStore,
+  matchOfferedOption,
+  offeredOptions,
+} from './prompts';
+export type { OfferedOption, PendingPrompt, PromptStore } from './prompts';
@@ -0,0 +1,222 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { WhatsAppInteractive, WhatsAppMessage } from '../types';
+import { canonicalChatId } from '../utils/normalize';
+import { systemClock, type Clock } from '../utils/clock';
+import { LruCache, StoredChatBounds, type LruOptions } from '../utils/lru';
+
+export interface OfferedOption {
+  id: string;
+  title: string;
+  description?: string;
+}
+
+// The buttons or list rows last offered in a chat, and the menu state that offered them
+export interface PendingPrompt {
+  chatId: string;                       // canonicalChatId() of the chat
+  kind: 'buttons' | 'list';
+  options: OfferedOption[];             // In the order shown
+  menu?: { menuId: string; stateId: string };
+  offeredAt: number;
+}
+
+// Persists the pending prompt of each chat
+export interface PromptStore {
+  get(chatId: string): Promise<PendingPrompt | undefined>;
+  set(prompt: PendingPrompt): Promise<void>;
+  delete(chatId: string): Promise<void>;
+  // Implemented by stores held in process memory, so prompts can move to a new process
+  snapshot?(): PendingPrompt[];
+  restore?(prompts: PendingPrompt[]): void;
+}
+
+// Prompts in process memory; `limits` bounds how many chats and for how long idle ones are kept
+export class InMemoryPromptStore implements PromptStore {
+  private prompts: LruCache<PendingPrompt>;
+
+  constructor(limits: LruOptions = {}) {
+    this.prompts = new LruCache(limits);
+  }
+
+  async get(chatId: string): Promise<PendingPrompt | undefined> {
+    return this.prompts.get(chatId);
+  }
+
+  async set(prompt: PendingPrompt): Promise<void> {
+    this.prompts.set(prompt.chatId, prompt);
+  }
+
+  async delete(chatId: string): Promise<void> {
+    this.prompts.delete(chatId);
+  }
+
+  snapshot(): PendingPrompt[] {
+    return this.prompts.snapshot().map(([, prompt]) => prompt);
+  }
+
+  restore(prompts: PendingPrompt[]): void {
+    this.prompts.restore(prompts.map((prompt) => [prompt.chatId, prompt, prompt.offeredAt]));
+  }
+}
+
+// Stores prompts in the agent runtime cache so they survive restarts, within the same chat-state
+// limits as the in-memory store
+export class RuntimePromptStore implements PromptStore {
+  private bounds: StoredChatBounds;
+
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string,
+    limits: LruOptions = {}
+  ) {
+    this.bounds = new StoredChatBounds(limits, (chatId) => this.runtime.deleteCache(this.key(chatId)));
+  }
+
+  async get(chatId: string): Promise<PendingPrompt | undefined> {
+    const prompt = (await this.runtime.getCache<PendingPrompt>(this.key(chatId))) ?? undefined;
+    if (!prompt) return undefined;
+    if (this.bounds.isIdle(prompt.offeredAt)) {
+      await this.delete(chatId);
+      return undefined;
+    }
+    this.bounds.touch(chatId);
+    return prompt;
+  }
+
+  async set(prompt: PendingPrompt): Promise<void> {
+    await this.runtime.setCache(this.key(prompt.chatId), prompt);
+    this.bounds.touch(prompt.chatId);
+  }
+
+  async delete(chatId: string): Promise<void> {
+    this.bounds.forget(chatId);
+    await this.runtime.deleteCache(this.key(chatId));
+  }
+
+  private key(chatId: string): string {
+    return `whatsapp-prompt-${this.accountId}-${chatId}`;
+  }
+}
+
+// The reply buttons or list rows of an interactive message; undefined for other kinds
+export function offeredOptions(content: WhatsAppInteractive): Pick<PendingPrompt, 'kind' | 'options'> | undefined {
+  if (content.type === 'button') {
+    const options = (content.action.buttons ?? []).map((b) => ({ id: b.reply.id, title: b.reply.title }));
+    return options.length > 0 ? { kind: 'buttons', options } : undefined;
+  }
+  if (content.type === 'list') {
+    const options = (content.action.sections ?? []).flatMap((s) =>
+      (s.rows ?? []).map((r) => ({ id: r.id, title: r.title, ...(r.description ? { description: r.description } : {}) }))
+    );
+    return options.length > 0 ? { kind: 'list', options } : undefined;
+  }
+  return undefined;
+}
+
+const ORDINALS = ['first', 'second', 'third', 'fourth', 'fifth', 'sixth', 'seventh', 'eighth', 'ninth', 'tenth'];
+const NUMBERS = ['one', 'two', 'three', 'four', 'five', 'six', 'seven', 'eight', 'nine', 'ten'];
+
+// Words around a choice that don't change it: "the first one", "option 2", "#3", "number two please"
+const LEADING_FILLER = /^(?:(?:the|option|number|no\.)\s+|#\s*)/;
+const TRAILING_FILLER = /\s+(?:one|option|please)$/;
+
+function trimAnswer(text: string): string {
+  return text.toLowerCase().trim().replace(/[\s.!?,;:)]+$/, '');
+}
+
+function stripFiller(answer: string): string {
+  let current = answer;
+  for (let previous = ''; previous !== current; ) {
+    previous = current;
+    current = current.replace(LEADING_FILLER, '').replace(TRAILING_FILLER, '').trim();
+  }
+  return current;
+}
+
+// Position an answer names: "2", "2nd", "second", "two", "last"; undefined when it names none
+function answerIndex(answer: string, count: number): number | undefined {
+  if (answer === 'last') return count - 1;
+  const digits = /^(\d+)(?:st|nd|rd|th)?$/.exec(answer);
+  if (digits) return Number(digits[1]) - 1;
+  const ordinal = ORDINALS.indexOf(answer);
+  if (ordinal >= 0) return ordinal;
+  const number = NUMBERS.indexOf(answer);
+  return number >= 0 ? number : undefined;
+}
+
+// The option a typed answer picks: by position ("2", "the first one", "last") or by its title or id
+export function matchOfferedOption(text: string, options: OfferedOption[]): OfferedOption | undefined {
+  const typed = trimAnswer(text);
+  if (!typed) return undefined;
+  const answer = stripFiller(typed) || typed;
+  const byName = options.find((o) => [typed, answer].some((a) => trimAnswer(o.title) === a || o.id.toLowerCase() === a));
+  if (byName) return byName;
+  const index = answerIndex(answer, options.length);
+  return index !== undefined ? options[index] : undefined;
+}
+
+const DEFAULT_TTL_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * Remembers the buttons or list last offered in each chat, so a typed answer such as "2" or "the
+ * first one" can be read as if the option had been tapped. A newer prompt replaces the chat's
+ * previous one, any other message sent to the chat clears it, and an answer (tapped or typed)
+ * consumes it.
+ */
+export class InteractivePrompts {
+  private store: PromptStore;
+
+  constructor(
+    store?: PromptStore,
+    private readonly clock: Clock = systemClock,
+    private readonly ttlMs = DEFAULT_TTL_MS
+  ) {
+    this.store = store ?? new InMemoryPromptStore({ clock });
+  }
+
+  useStore(store: PromptStore): void {
+    this.store = store;
+  }
+
+  // Prompts of an in-memory store; undefined when the store keeps them elsewhere
+  snapshot(): PendingPrompt[] | undefined {
+    return this.store.snapshot?.();
+  }
+
+  restore(prompts: PendingPrompt[]): void {
+    this.store.restore?.(prompts);
+  }
+
+  // Remember the options of a message just sent. Any other message to the chat asks something new
+  // or moves on, so it clears the prompt: a later "2" must not pick from options no longer in view.
+  async offer(message: WhatsAppMessage, menu?: PendingPrompt['menu']): Promise<void> {
+    const offered = message.type === 'interactive' ? offeredOptions(message.content) : undefined;
+    if (!offered) {
+      await this.clear(message.to);
+      return;
+    }
+    await this.store.set({
+      chatId: canonicalChatId(message.to),
+      ...offered,
+      ...(menu ? { menu } : {}),
+      offeredAt: this.clock.now(),
+    });
+  }
+
+  // The chat's pending prompt, unless it has expired
+  async get(chat: string): Promise<PendingPrompt | undefined> {
+    const prompt = await this.store.get(canonicalChatId(chat));
+    return prompt && prompt.offeredAt + this.ttlMs > this.clock.now() ? prompt : undefined;
+  }
+
+  // The option a typed answer picks, consuming the prompt; undefined (and the prompt kept) when none matches
+  async interpret(chat: string, text: string): Promise<OfferedOption | undefined> {
+    const prompt = await this.get(chat);
+    const option = prompt && matchOfferedOption(text, prompt.options);
+    if (option) await this.clear(chat);
+    return option;
+  }
+
+  async clear(chat: string): Promise<void> {
+    await this.store.delete(canonicalChatId(chat));
+  }
+}
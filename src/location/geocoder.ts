@@ -0,0 +1,25 @@
+import type { InboundLocation } from '../types';
+
+// Turns coordinates into a place people recognize, e.g. by wrapping a reverse geocoding API
+export interface Geocoder {
+  reverse(latitude: number, longitude: number): Promise<string | undefined>;
+}
+
+const COORDINATE_DIGITS = 5;
+
+// What the agent reads for a shared location: the resolved place when known, else the pin's label
+// or, failing that, its coordinates
+export function formatLocation(location: InboundLocation): string {
+  const label = [location.name, location.address].filter(Boolean).join(', ');
+  if (location.place) {
+    return `Shared a location near ${location.place}${label ? ` (${label})` : ''}`;
+  }
+  const coordinates = `${location.latitude.toFixed(COORDINATE_DIGITS)}, ${location.longitude.toFixed(COORDINATE_DIGITS)}`;
+  return `Shared a location: ${label || coordinates}`;
+}
+
+// The location with `place` filled in by `geocoder`; unchanged when it finds nothing
+export async function enrichLocation(location: InboundLocation, geocoder: Geocoder): Promise<InboundLocation> {
+  const place = await geocoder.reverse(location.latitude, location.longitude);
+  return place ? { ...location, place } : location;
+}
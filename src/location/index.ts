@@ -0,0 +1,2 @@
+export { enrichLocation, formatLocation } from './geocoder';
+export type { Geocoder } from './geocoder';
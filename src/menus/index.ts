@@ -0,0 +1,4 @@
+export { MenuEngine } from './state-machine';
+export type { MenuDefinition, MenuState, MenuOption, MenuResult, MenuStep } from './state-machine';
+export { InMemoryMenuSessionStore, RuntimeMenuSessionStore } from './store';
+export type { MenuSession, MenuSessionStore } from './store';
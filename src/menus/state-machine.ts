@@ -0,0 +1,197 @@
+import { EventEmitter } from 'events';
+import type { WhatsAppInteractive, WhatsAppMessage } from '../types';
+import { InMemoryMenuSessionStore, type MenuSession, type MenuSessionStore } from './store';
+import { ButtonMessageBuilder, ListMessageBuilder } from '../interactive';
+import { WhatsAppError } from '../errors';
+import { systemClock, type Clock } from '../utils/clock';
+
+// WhatsApp limits for reply buttons and list rows
+const MAX_BUTTONS = 3;
+const MAX_LIST_ROWS = 10;
+
+export interface MenuOption {
+  id: string;
+  title: string;
+  description?: string;
+  next?: string;            // Next state id; omit to finish the menu
+}
+
+export interface MenuState {
+  id: string;
+  prompt: string;
+  kind?: 'buttons' | 'list'; // Defaults to buttons when the options fit
+  field?: string;            // Result key for the answer, defaults to the state id
+  listButtonText?: string;
+  options: MenuOption[];
+}
+
+export interface MenuDefinition {
+  id: string;
+  initial: string;
+  states: MenuState[];
+}
+
+export interface MenuResult {
+  menuId: string;
+  userId: string;
+  answers: Record<string, { id: string; title: string }>;
+  startedAt: number;
+  completedAt: number;
+}
+
+export interface MenuStep {
+  message?: WhatsAppMessage;
+  result?: MenuResult;
+}
+
+export class MenuEngine extends EventEmitter {
+  private menus = new Map<string, MenuDefinition>();
+  private store: MenuSessionStore;
+
+  constructor(
+    store?: MenuSessionStore,
+    private readonly clock: Clock = systemClock
+  ) {
+    super();
+    this.store = store ?? new InMemoryMenuSessionStore({ clock });
+  }
+
+  useStore(store: MenuSessionStore): void {
+    this.store = store;
+  }
+
+  // Sessions of an in-memory store; undefined when the store keeps them elsewhere
+  snapshotSessions(): MenuSession[] | undefined {
+    return this.store.snapshot?.();
+  }
+
+  restoreSessions(sessions: MenuSession[]): void {
+    this.store.restore?.(sessions);
+  }
+
+  register(menu: MenuDefinition): void {
+    const ids = new Set(menu.states.map((s) => s.id));
+    if (!ids.has(menu.initial)) {
+      throw new WhatsAppError('INVALID_MENU', `Menu "${menu.id}" initial state "${menu.initial}" is not defined`);
+    }
+
+    for (const state of menu.states) {
+      if (state.options.length === 0) {
+        throw new WhatsAppError('INVALID_MENU', `Menu "${menu.id}" state "${state.id}" has no options`);
+      }
+      const limit = this.kindOf(state) === 'buttons' ? MAX_BUTTONS : MAX_LIST_ROWS;
+      if (state.options.length > limit) {
+        throw new WhatsAppError(
+          'INVALID_MENU',
+          `Menu "${menu.id}" state "${state.id}" has ${state.options.length} options (max ${limit})`
+        );
+      }
+      for (const option of state.options) {
+        if (option.next && !ids.has(option.next)) {
+          throw new WhatsAppError(
+            'INVALID_MENU',
+            `Menu "${menu.id}" option "${option.id}" points to unknown state "${option.next}"`
+          );
+        }
+      }
+      // Surface title and text limits now rather than when the prompt is sent
+      this.render('', state);
+    }
+
+    this.menus.set(menu.id, menu);
+  }
+
+  // Start (or restart) a menu for a user and return the first prompt
+  async start(menuId: string, userId: string): Promise<WhatsAppMessage> {
+    const menu = this.menus.get(menuId);
+    if (!menu) {
+      throw new WhatsAppError('NOT_FOUND', `Unknown menu "${menuId}"`);
+    }
+
+    const now = this.clock.now();
+    await this.store.set({
+      menuId,
+      userId,
+      stateId: menu.initial,
+      answers: {},
+      startedAt: now,
+      updatedAt: now,
+    });
+
+    return this.render(userId, this.state(menu, menu.initial));
+  }
+
+  // Advance the user's menu with a reply id. Returns null when the reply is not for a menu.
+  async handleReply(userId: string, replyId: string): Promise<MenuStep | null> {
+    const session = await this.store.get(userId);
+    if (!session) return null;
+
+    const menu = this.menus.get(session.menuId);
+    if (!menu) {
+      await this.store.delete(userId);
+      return null;
+    }
+
+    const state = this.state(menu, session.stateId);
+    const option = state.options.find((o) => o.id === replyId);
+    if (!option) return null;
+
+    session.answers[state.field ?? state.id] = { id: option.id, title: option.title };
+    session.updatedAt = this.clock.now();
+
+    if (option.next) {
+      session.stateId = option.next;
+      await this.store.set(session);
+      return { message: this.render(userId, this.state(menu, option.next)) };
+    }
+
+    await this.store.delete(userId);
+    const result = this.complete(session);
+    this.emit('completed', result);
+    return { result };
+  }
+
+  async cancel(userId: string): Promise<void> {
+    await this.store.delete(userId);
+  }
+
+  async getSession(userId: string): Promise<MenuSession | undefined> {
+    return this.store.get(userId);
+  }
+
+  private complete(session: MenuSession): MenuResult {
+    return {
+      menuId: session.menuId,
+      userId: session.userId,
+      answers: session.answers,
+      startedAt: session.startedAt,
+      completedAt: session.updatedAt,
+    };
+  }
+
+  private state(menu: MenuDefinition, stateId: string): MenuState {
+    const state = menu.states.find((s) => s.id === stateId);
+    if (!state) {
+      throw new WhatsAppError('INVALID_MENU', `Menu "${menu.id}" has no state "${stateId}"`);
+    }
+    return state;
+  }
+
+  private kindOf(state: MenuState): 'buttons' | 'list' {
+    return state.kind ?? (state.options.length <= MAX_BUTTONS ? 'buttons' : 'list');
+  }
+
+  private render(to: string, state: MenuState): WhatsAppMessage {
+    const content: WhatsAppInteractive =
+      this.kindOf(state) === 'buttons'
+        ? state.options
+            .reduce((b, o) => b.button(o.id, o.title), new ButtonMessageBuilder(state.prompt))
+            .build()
+        : new ListMessageBuilder(state.prompt)
+            .button(state.listButtonText ?? 'Choose')
+            .section(undefined, (s) => state.options.reduce((r, o) => r.row(o.id, o.title, o.description), s))
+            .build();
+
+    return { type: 'interactive', to, content };
+  }
+}
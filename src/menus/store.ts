@@ -0,0 +1,89 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import { LruCache, StoredChatBounds, type LruOptions } from '../utils/lru';
+
+export interface MenuSession {
+  menuId: string;
+  userId: string;
+  stateId: string;
+  answers: Record<string, { id: string; title: string }>;
+  startedAt: number;
+  updatedAt: number;
+}
+
+// Persists each user's position in a running menu
+export interface MenuSessionStore {
+  get(userId: string): Promise<MenuSession | undefined>;
+  set(session: MenuSession): Promise<void>;
+  delete(userId: string): Promise<void>;
+  // Implemented by stores held in process memory, so sessions can move to a new process
+  snapshot?(): MenuSession[];
+  restore?(sessions: MenuSession[]): void;
+}
+
+// Sessions in process memory; `limits` bounds how many users and for how long idle ones are kept
+export class InMemoryMenuSessionStore implements MenuSessionStore {
+  private sessions: LruCache<MenuSession>;
+
+  constructor(limits: LruOptions = {}) {
+    this.sessions = new LruCache(limits);
+  }
+
+  async get(userId: string): Promise<MenuSession | undefined> {
+    return this.sessions.get(userId);
+  }
+
+  async set(session: MenuSession): Promise<void> {
+    this.sessions.set(session.userId, session);
+  }
+
+  async delete(userId: string): Promise<void> {
+    this.sessions.delete(userId);
+  }
+
+  snapshot(): MenuSession[] {
+    return this.sessions.snapshot().map(([, session]) => session);
+  }
+
+  restore(sessions: MenuSession[]): void {
+    this.sessions.restore(sessions.map((session) => [session.userId, session, session.updatedAt]));
+  }
+}
+
+// Stores sessions in the agent runtime cache so they survive restarts, apart for each account and
+// within the same chat-state limits as the in-memory store
+export class RuntimeMenuSessionStore implements MenuSessionStore {
+  private bounds: StoredChatBounds;
+
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string,
+    limits: LruOptions = {}
+  ) {
+    this.bounds = new StoredChatBounds(limits, (userId) => this.runtime.deleteCache(this.key(userId)));
+  }
+
+  async get(userId: string): Promise<MenuSession | undefined> {
+    const session = (await this.runtime.getCache<MenuSession>(this.key(userId))) ?? undefined;
+    if (!session) return undefined;
+    if (this.bounds.isIdle(session.updatedAt)) {
+      await this.delete(userId);
+      return undefined;
+    }
+    this.bounds.touch(userId);
+    return session;
+  }
+
+  async set(session: MenuSession): Promise<void> {
+    await this.runtime.setCache(this.key(session.userId), session);
+    this.bounds.touch(session.userId);
+  }
+
+  async delete(userId: string): Promise<void> {
+    this.bounds.forget(userId);
+    await this.runtime.deleteCache(this.key(userId));
+  }
+
+  private key(userId: string): string {
+    return `whatsapp-menu-${this.accountId}-${userId}`;
+  }
+}
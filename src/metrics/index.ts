@@ -0,0 +1,2 @@
+export { noopMetrics, createPrometheusMetrics } from './metrics';
+export type { WhatsAppMetrics, PromClientLike } from './metrics';
@@ -0,0 +1,147 @@
+// Instrumentation hooks; the default implementation records nothing
+export interface WhatsAppMetrics {
+  messageSent(type: string, account: string): void;
+  messageReceived(type: string, account: string): void;
+  messageFailed(type: string, account: string, reason: string): void;
+  observeApiLatency(endpoint: string, seconds: number): void;
+  observeWebhookProcessing(seconds: number): void;
+  setQueueDepth(depth: number): void;
+  setActiveAccounts(count: number): void;
+  // Inbound worker pool; optional so existing implementations keep compiling
+  setInboundQueueDepth?(account: string, depth: number): void;
+  observeInboundWait?(account: string, seconds: number): void;
+  observeInboundProcessing?(account: string, seconds: number): void;
+  inboundDropped?(account: string, count: number): void;
+  chatStateEvicted?(account: string, store: string, reason: string): void;
+}
+
+export const noopMetrics: WhatsAppMetrics = {
+  messageSent: () => {},
+  messageReceived: () => {},
+  messageFailed: () => {},
+  observeApiLatency: () => {},
+  observeWebhookProcessing: () => {},
+  setQueueDepth: () => {},
+  setActiveAccounts: () => {},
+};
+
+// Structural subset of prom-client, so it stays an optional peer dependency
+interface PromMetricConfig {
+  name: string;
+  help: string;
+  labelNames?: string[];
+  buckets?: number[];
+  registers?: unknown[];
+}
+
+export interface PromClientLike {
+  Counter: new (config: PromMetricConfig) => { inc(labels?: Record<string, string>, value?: number): void };
+  Histogram: new (config: PromMetricConfig) => {
+    observe(labels: Record<string, string>, value: number): void;
+  };
+  Gauge: new (config: PromMetricConfig) => {
+    set(value: number): void;
+    set(labels: Record<string, string>, value: number): void;
+  };
+}
+
+const LATENCY_BUCKETS = [0.05, 0.1, 0.25, 0.5, 1, 2.5, 5, 10];
+
+// Registers WhatsApp metrics on the caller's prom-client registry:
+//   createPrometheusMetrics(await import('prom-client'), register)
+export function createPrometheusMetrics(
+  prom: PromClientLike,
+  registry: unknown,
+  prefix = 'whatsapp_'
+): WhatsAppMetrics {
+  const registers = [registry];
+  const sent = new prom.Counter({
+    name: `${prefix}messages_sent_total`,
+    help: 'Messages sent successfully',
+    labelNames: ['type', 'account'],
+    registers,
+  });
+  const received = new prom.Counter({
+    name: `${prefix}messages_received_total`,
+    help: 'Messages received',
+    labelNames: ['type', 'account'],
+    registers,
+  });
+  const failed = new prom.Counter({
+    name: `${prefix}messages_failed_total`,
+    help: 'Messages that failed to send',
+    labelNames: ['type', 'account', 'reason'],
+    registers,
+  });
+  const apiLatency = new prom.Histogram({
+    name: `${prefix}graph_api_request_duration_seconds`,
+    help: 'Graph API request latency',
+    labelNames: ['endpoint'],
+    buckets: LATENCY_BUCKETS,
+    registers,
+  });
+  const webhookDuration = new prom.Histogram({
+    name: `${prefix}webhook_processing_duration_seconds`,
+    help: 'Time spent processing a webhook payload',
+    buckets: LATENCY_BUCKETS,
+    registers,
+  });
+  const queueDepth = new prom.Gauge({
+    name: `${prefix}queue_depth`,
+    help: 'Messages waiting to be sent',
+    registers,
+  });
+  const activeAccounts = new prom.Gauge({
+    name: `${prefix}active_accounts`,
+    help: 'Connected WhatsApp accounts',
+    registers,
+  });
+  const inboundDepth = new prom.Gauge({
+    name: `${prefix}inbound_queue_depth`,
+    help: 'Webhook messages and statuses waiting for a worker',
+    labelNames: ['account'],
+    registers,
+  });
+  const inboundWait = new prom.Histogram({
+    name: `${prefix}inbound_wait_seconds`,
+    help: 'Time a webhook item waited for a worker',
+    labelNames: ['account'],
+    buckets: LATENCY_BUCKETS,
+    registers,
+  });
+  const inboundProcessing = new prom.Histogram({
+    name: `${prefix}inbound_processing_seconds`,
+    help: 'Time spent handling a webhook item',
+    labelNames: ['account'],
+    buckets: LATENCY_BUCKETS,
+    registers,
+  });
+  const inboundDropped = new prom.Counter({
+    name: `${prefix}inbound_dropped_total`,
+    help: 'Webhook items dropped or refused because the inbound queue was full',
+    labelNames: ['account'],
+    registers,
+  });
+
+  const chatStateEvictions = new prom.Counter({
+    name: `${prefix}chat_state_evictions_total`,
+    help: 'In-memory chat state evicted for size or idleness',
+    labelNames: ['account', 'store', 'reason'],
+    registers,
+  });
+
+  return {
+    messageSent: (type, account) => sent.inc({ type, account }),
+    messageReceived: (type, account) => received.inc({ type, account }),
+    messageFailed: (type, account, reason) => failed.inc({ type, account, reason }),
+    observeApiLatency: (endpoint, seconds) => apiLatency.observe({ endpoint }, seconds),
+    observeWebhookProcessing: (seconds) => webhookDuration.observe({}, seconds),
+    setQueueDepth: (depth) => queueDepth.set(depth),
+    setActiveAccounts: (count) => activeAccounts.set(count),
+    setInboundQueueDepth: (account, depth) => inboundDepth.set({ account }, depth),
+    observeInboundWait: (account, seconds) => inboundWait.observe({ account }, seconds),
+    observeInboundProcessing: (account, seconds) => inboundProcessing.observe({ account }, seconds),
+    inboundDropped: (account, count) => inboundDropped.inc({ account }, count),
+    chatStateEvicted: (account, store, reason) => chatStateEvictions.inc({ account, store, reason }),
+  };
+}
@@ -0,0 +1,24 @@
+import { WhatsAppError } from '../errors';
+import type { WhatsAppGroupConfig } from '../types';
+import { resolveWhatsAppGroupConfig } from '../utils/group-config';
+import { isGroupJid } from '../utils/normalize';
+import type { OutboundMiddleware } from './outbound';
+
+const BYTES_PER_MB = 1024 * 1024;
+
+// Refuse media over a group's mediaMaxMb. Only raw bytes can be measured before sending, so media
+// sent by link or uploaded id passes through.
+export function groupMediaLimit(groups: Record<string, WhatsAppGroupConfig> | undefined): OutboundMiddleware {
+  return (message) => {
+    if (message.type !== 'media' || !message.content.data || !isGroupJid(message.to)) return message;
+    const maxMb = resolveWhatsAppGroupConfig(groups, message.to).mediaMaxMb;
+    const size = message.content.data.byteLength;
+    if (maxMb !== undefined && size > maxMb * BYTES_PER_MB) {
+      throw new WhatsAppError(
+        'MEDIA_TOO_LARGE',
+        `${message.content.kind} is ${(size / BYTES_PER_MB).toFixed(1)} MB; ${message.to} accepts at most ${maxMb} MB`
+      );
+    }
+    return message;
+  };
+}
@@ -0,0 +1,18 @@
+export { SpamFilter } from './spam-filter';
+export type {
+  SpamAction,
+  SpamReason,
+  SpamFilterConfig,
+  SpamVerdict,
+  SpamFilterMetrics,
+} from './spam-filter';
+export { SenderRateLimiter, DEFAULT_SLOW_DOWN_NOTICE } from './rate-limiter';
+export type { SenderRateLimitConfig, RateLimitDecision, RateLimitState, SenderWindow } from './rate-limiter';
+export { OutboundPipeline, expandEmojiShortcodes, mapMessageText } from './outbound';
+export type { OutboundContext, OutboundMiddleware, ReplyPart } from './outbound';
+export { groupMediaLimit } from './group-limits';
+export { appendReplyFooter, replyFooterFor, replyFooterLength } from './reply-footer';
+export { ContentPolicy, enforcePolicy, invalidPolicyPatterns } from './policy';
+export type { ContentPolicyConfig, OutboundPolicy, PolicyVerdict } from './policy';
+export { RuntimeModelTranslator, translateInbound, translateOutbound } from './translation';
+export type { TranslationOptions, Translator } from './translation';
@@ -0,0 +1,72 @@
+import type { WhatsAppMessage } from '../types';
+import { expandShortcodes } from '../utils/emoji';
+
+export interface OutboundContext {
+  accountId: string;
+  reply?: ReplyPart;                    // Set when the message is (part of) the agent's reply to a chat
+}
+
+// Position of a message among the messages of one agent reply
+export interface ReplyPart {
+  index: number;                        // 0-based
+  count: number;
+}
+
+// Rewrites a message just before it is sent; return the message unchanged to pass it on
+export type OutboundMiddleware = (
+  message: WhatsAppMessage,
+  context: OutboundContext
+) => WhatsAppMessage | Promise<WhatsAppMessage>;
+
+// Middleware run in registration order on every message an account sends
+export class OutboundPipeline {
+  private middleware: OutboundMiddleware[] = [];
+
+  // Returns a function that removes the middleware again
+  use(middleware: OutboundMiddleware): () => void {
+    this.middleware.push(middleware);
+    return () => {
+      this.middleware = this.middleware.filter((m) => m !== middleware);
+    };
+  }
+
+  get size(): number {
+    return this.middleware.length;
+  }
+
+  async run(message: WhatsAppMessage, context: OutboundContext): Promise<WhatsAppMessage> {
+    let current = message;
+    for (const middleware of this.middleware) {
+      current = await middleware(current, context);
+    }
+    return current;
+  }
+}
+
+// Apply `transform` to the text people read: text bodies, captions and interactive body/footer
+export function mapMessageText(message: WhatsAppMessage, transform: (text: string) => string): WhatsAppMessage {
+  switch (message.type) {
+    case 'text':
+      return { ...message, content: transform(message.content) };
+    case 'media':
+      return message.content.caption
+        ? { ...message, content: { ...message.content, caption: transform(message.content.caption) } }
+        : message;
+    case 'interactive': {
+      const { body, footer } = message.content;
+      return {
+        ...message,
+        content: {
+          ...message.content,
+          body: { ...body, text: transform(body.text) },
+          ...(footer ? { footer: { ...footer, text: transform(footer.text) } } : {}),
+        },
+      };
+    }
+    default:
+      return message;
+  }
+}
+
+// Turn :thumbsup:-style shortcodes into emoji, which WhatsApp would otherwise show literally
+export const expandEmojiShortcodes: OutboundMiddleware = (message) => mapMessageText(message, expandShortcodes);
@@ -0,0 +1,147 @@
+import { logger } from '@elizaos/core';
+import { WhatsAppError } from '../errors';
+import type { WhatsAppGroupConfig, WhatsAppMessage } from '../types';
+import { resolveWhatsAppGroupConfig } from '../utils/group-config';
+import { isGroupJid } from '../utils/normalize';
+import { mapMessageText, type OutboundContext, type OutboundMiddleware } from './outbound';
+
+export type PolicyVerdict =
+  | { action: 'allow' }
+  | { action: 'rewrite'; message: WhatsAppMessage; reasons: string[] }
+  | { action: 'block'; reasons: string[] };
+
+// Decides whether an outgoing message may be sent as is, rewritten, or not at all
+export interface OutboundPolicy {
+  check(message: WhatsAppMessage, context: OutboundContext): PolicyVerdict | Promise<PolicyVerdict>;
+}
+
+export interface ContentPolicyConfig {
+  bannedPhrases?: string[];             // Matched case-insensitively
+  bannedPatterns?: string[];            // Regular expressions, matched case-insensitively
+  maxLinks?: number;                    // Links allowed per message
+  blockPhoneNumbers?: boolean;          // Phone numbers in the text
+  action?: 'block' | 'redact';          // Refuse the message, or cut what breaks the rules (default block)
+  redaction?: string;                   // Replaces redacted text (default "[removed]")
+}
+
+const LINK = /\bhttps?:\/\/\S+|\bwww\.\S+/gi;
+const PHONE_NUMBER = /\+?\d[\d\s().-]{6,}\d/g;
+// Fewer digits than this is more likely a date, amount or order number
+const MIN_PHONE_DIGITS = 9;
+
+function isPhoneNumber(candidate: string): boolean {
+  return candidate.replace(/\D/g, '').length >= MIN_PHONE_DIGITS;
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+// bannedPatterns entries that are not valid regular expressions
+export function invalidPolicyPatterns(config: ContentPolicyConfig | undefined): string[] {
+  return (config?.bannedPatterns ?? []).filter((pattern) => {
+    try {
+      new RegExp(pattern, 'gi');
+      return false;
+    } catch {
+      return true;
+    }
+  });
+}
+
+// Rules with their phrases and patterns compiled once
+interface CompiledRules {
+  config: ContentPolicyConfig;
+  banned: RegExp[];
+}
+
+function compileRules(config: ContentPolicyConfig, scope: string): CompiledRules {
+  const invalid = invalidPolicyPatterns(config);
+  for (const pattern of invalid) {
+    logger.error(`[WhatsApp] Ignoring invalid bannedPatterns entry ${JSON.stringify(pattern)} in the ${scope} content policy`);
+  }
+  const banned = [
+    ...(config.bannedPhrases ?? []).map((phrase) => new RegExp(escapeRegExp(phrase), 'gi')),
+    ...(config.bannedPatterns ?? []).filter((p) => !invalid.includes(p)).map((pattern) => new RegExp(pattern, 'gi')),
+  ];
+  return { config, banned };
+}
+
+// Every text people would read in the message
+function messageTexts(message: WhatsAppMessage): string[] {
+  const texts: string[] = [];
+  mapMessageText(message, (text) => {
+    texts.push(text);
+    return text;
+  });
+  return texts;
+}
+
+/**
+ * Keyword and regex rules for outgoing text: banned phrases and patterns, a cap on links, and no
+ * phone numbers. Rules apply to text bodies, captions and interactive body/footer; templates are
+ * approved by Meta and pass. Group entries in `groups` (`contentPolicy`) override the account rules.
+ * Rules are compiled once; a pattern that is not a valid regular expression is logged and skipped.
+ */
+export class ContentPolicy implements OutboundPolicy {
+  private readonly rules: CompiledRules;
+  private readonly groupRules = new Map<WhatsAppGroupConfig, CompiledRules>();
+
+  constructor(
+    config: ContentPolicyConfig = {},
+    private readonly groups?: Record<string, WhatsAppGroupConfig>
+  ) {
+    this.rules = compileRules(config, 'account');
+    for (const [groupId, group] of Object.entries(groups ?? {})) {
+      if (group.contentPolicy) {
+        this.groupRules.set(group, compileRules({ ...config, ...group.contentPolicy }, `group ${groupId}`));
+      }
+    }
+  }
+
+  check(message: WhatsAppMessage): PolicyVerdict {
+    const { config, banned } = this.rulesFor(message.to);
+    const reasons = new Set<string>();
+    for (const text of messageTexts(message)) {
+      if (banned.some((rule) => text.search(rule) >= 0)) reasons.add('banned_phrase');
+      const links = text.match(LINK)?.length ?? 0;
+      if (config.maxLinks !== undefined && links > config.maxLinks) reasons.add('too_many_links');
+      if (config.blockPhoneNumbers && (text.match(PHONE_NUMBER) ?? []).some(isPhoneNumber)) reasons.add('phone_number');
+    }
+    if (reasons.size === 0) return { action: 'allow' };
+    if (config.action !== 'redact') return { action: 'block', reasons: [...reasons] };
+
+    const redaction = config.redaction ?? '[removed]';
+    const redact = (text: string): string => {
+      let result = banned.reduce((t, rule) => t.replace(rule, redaction), text);
+      if (config.blockPhoneNumbers) {
+        result = result.replace(PHONE_NUMBER, (match) => (isPhoneNumber(match) ? redaction : match));
+      }
+      if (config.maxLinks !== undefined) {
+        let links = 0;
+        result = result.replace(LINK, (link) => (++links > (config.maxLinks ?? 0) ? redaction : link));
+      }
+      return result;
+    };
+    return { action: 'rewrite', message: mapMessageText(message, redact), reasons: [...reasons] };
+  }
+
+  // Account rules, or a group's contentPolicy laid over them
+  private rulesFor(to: string): CompiledRules {
+    if (!isGroupJid(to)) return this.rules;
+    return this.groupRules.get(resolveWhatsAppGroupConfig(this.groups, to)) ?? this.rules;
+  }
+}
+
+// Run `policy` on every message; blocked messages fail with POLICY_VIOLATION instead of being sent
+export function enforcePolicy(policy: OutboundPolicy): OutboundMiddleware {
+  return async (message, context) => {
+    const verdict = await policy.check(message, context);
+    if (verdict.action === 'allow') return message;
+    if (verdict.action === 'rewrite') return verdict.message;
+    throw new WhatsAppError(
+      'POLICY_VIOLATION',
+      `Message to ${message.to} was blocked by the outbound policy: ${verdict.reasons.join(', ')}`
+    );
+  };
+}
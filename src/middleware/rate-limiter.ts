@@ -0,0 +1,83 @@
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface SenderRateLimitConfig {
+  maxMessages: number;         // Messages answered per sender per window
+  windowMs?: number;           // Default 60s
+  notice?: string;             // Sent once when the limit is first exceeded
+}
+
+export type RateLimitDecision = 'allow' | 'notify' | 'suppress';
+
+export interface RateLimitState {
+  limit: number;
+  remaining: number;           // Messages still answered in the current window
+  resetInMs: number;           // Until the oldest counted message leaves the window
+  cooldown: boolean;           // True while the sender is over the limit
+}
+
+export const DEFAULT_SLOW_DOWN_NOTICE =
+  "You're sending messages faster than I can answer. I'll respond again in a minute.";
+
+export interface SenderWindow {
+  timestamps: number[];
+  notified: boolean;
+}
+
+// Sliding-window limiter that lets the agent go quiet for chatty senders
+export class SenderRateLimiter {
+  private windows = new Map<string, SenderWindow>();
+
+  constructor(private readonly clock: Clock = systemClock) {}
+
+  check(key: string, config: SenderRateLimitConfig, now = this.clock.now()): RateLimitDecision {
+    const windowMs = config.windowMs ?? 60_000;
+    if (this.windows.size > 1000) this.prune(windowMs, now);
+
+    const state = this.windows.get(key) ?? { timestamps: [], notified: false };
+    state.timestamps = state.timestamps.filter((t) => t > now - windowMs);
+
+    if (state.timestamps.length < config.maxMessages) {
+      state.timestamps.push(now);
+      state.notified = false;
+      this.windows.set(key, state);
+      return 'allow';
+    }
+
+    this.windows.set(key, state);
+    if (state.notified) return 'suppress';
+    state.notified = true;
+    return 'notify';
+  }
+
+  // Current allowance for a key without counting a message
+  status(key: string, config: SenderRateLimitConfig, now = this.clock.now()): RateLimitState {
+    const windowMs = config.windowMs ?? 60_000;
+    const recent = (this.windows.get(key)?.timestamps ?? []).filter((t) => t > now - windowMs);
+    const remaining = Math.max(0, config.maxMessages - recent.length);
+    return {
+      limit: config.maxMessages,
+      remaining,
+      resetInMs: recent.length > 0 ? recent[0] + windowMs - now : 0,
+      cooldown: remaining === 0,
+    };
+  }
+
+  reset(key: string): void {
+    this.windows.delete(key);
+  }
+
+  snapshot(): Array<[string, SenderWindow]> {
+    return [...this.windows].map(([key, state]) => [key, { ...state, timestamps: [...state.timestamps] }]);
+  }
+
+  restore(windows: Array<[string, SenderWindow]>): void {
+    this.windows = new Map(windows);
+  }
+
+  // Drop senders whose window has fully elapsed
+  prune(windowMs = 60_000, now = this.clock.now()): void {
+    for (const [key, state] of this.windows) {
+      if (state.timestamps.every((t) => t <= now - windowMs)) this.windows.delete(key);
+    }
+  }
+}
@@ -0,0 +1,44 @@
+import type { WhatsAppGroupConfig } from '../types';
+import { resolveWhatsAppGroupConfig } from '../utils/group-config';
+import { isGroupJid } from '../utils/normalize';
+import type { OutboundMiddleware } from './outbound';
+
+// Blank line between the reply and its footer
+const SEPARATOR = '\n\n';
+
+// Footer for messages to `to`: a group's replyFooter, else the account's; empty means none
+export function replyFooterFor(
+  footer: string | undefined,
+  groups: Record<string, WhatsAppGroupConfig> | undefined,
+  to: string
+): string | undefined {
+  const group = isGroupJid(to) ? resolveWhatsAppGroupConfig(groups, to).replyFooter : undefined;
+  const text = (group ?? footer)?.trim();
+  return text || undefined;
+}
+
+// Characters a footer adds to the message it is appended to
+export function replyFooterLength(
+  footer: string | undefined,
+  groups: Record<string, WhatsAppGroupConfig> | undefined,
+  to: string
+): number {
+  const text = replyFooterFor(footer, groups, to);
+  return text ? text.length + SEPARATOR.length : 0;
+}
+
+// Append the footer to the agent's text replies. A reply split across messages carries it on its
+// last part only; other sends (alerts, OTP codes, broadcasts, API calls) never do. Streamed replies
+// learn their last part late and add the footer themselves.
+export function appendReplyFooter(
+  footer: string | undefined,
+  groups?: Record<string, WhatsAppGroupConfig>
+): OutboundMiddleware {
+  return (message, context) => {
+    if (message.type !== 'text') return message;
+    const reply = context.reply;
+    if (!reply || reply.index < reply.count - 1) return message;
+    const text = replyFooterFor(footer, groups, message.to);
+    return text ? { ...message, content: `${message.content}${SEPARATOR}${text}` } : message;
+  };
+}
@@ -0,0 +1,116 @@
+import type { UnifiedMessage } from '../types';
+import { systemClock } from '../utils/clock';
+
+export type SpamAction = 'drop' | 'flag' | 'block';
+export type SpamReason = 'flood' | 'duplicate' | 'banned_keyword' | 'link_spam';
+
+export interface SpamFilterConfig {
+  enabled?: boolean;               // Default true
+  action?: SpamAction;             // What to do with offending messages (default: drop)
+  floodMaxMessages?: number;       // Messages allowed per sender per window (default: 10)
+  floodWindowMs?: number;          // Default 60s
+  duplicateThreshold?: number;     // Identical messages per window before flagging (default: 3)
+  bannedKeywords?: string[];
+  maxLinks?: number;               // Links allowed in one message (default: 3)
+}
+
+export interface SpamVerdict {
+  action: 'allow' | SpamAction;
+  reasons: SpamReason[];
+}
+
+export interface SpamFilterMetrics {
+  inspected: number;
+  allowed: number;
+  dropped: number;
+  flagged: number;
+  blocked: number;
+  byReason: Record<SpamReason, number>;
+}
+
+const LINK_PATTERN = /\b(?:https?:\/\/|www\.)\S+/gi;
+
+export class SpamFilter {
+  private config: Required<Omit<SpamFilterConfig, 'bannedKeywords'>> & { bannedKeywords: string[] };
+  private history = new Map<string, Array<{ at: number; content: string }>>();
+  private metrics: SpamFilterMetrics = {
+    inspected: 0,
+    allowed: 0,
+    dropped: 0,
+    flagged: 0,
+    blocked: 0,
+    byReason: { flood: 0, duplicate: 0, banned_keyword: 0, link_spam: 0 },
+  };
+
+  constructor(config: SpamFilterConfig = {}) {
+    this.config = {
+      enabled: config.enabled ?? true,
+      action: config.action ?? 'drop',
+      floodMaxMessages: config.floodMaxMessages ?? 10,
+      floodWindowMs: config.floodWindowMs ?? 60_000,
+      duplicateThreshold: config.duplicateThreshold ?? 3,
+      bannedKeywords: (config.bannedKeywords ?? []).map((k) => k.toLowerCase()),
+      maxLinks: config.maxLinks ?? 3,
+    };
+  }
+
+  inspect(msg: UnifiedMessage, now = systemClock.now()): SpamVerdict {
+    if (!this.config.enabled) return { action: 'allow', reasons: [] };
+    this.metrics.inspected++;
+
+    const sender = msg.participant ?? msg.from;
+    const content = msg.content.trim().toLowerCase();
+    const recent = this.record(sender, content, now);
+    const reasons: SpamReason[] = [];
+
+    if (recent.length > this.config.floodMaxMessages) {
+      reasons.push('flood');
+    }
+    if (content && recent.filter((m) => m.content === content).length >= this.config.duplicateThreshold) {
+      reasons.push('duplicate');
+    }
+    if (this.config.bannedKeywords.some((keyword) => content.includes(keyword))) {
+      reasons.push('banned_keyword');
+    }
+    if ((content.match(LINK_PATTERN) ?? []).length > this.config.maxLinks) {
+      reasons.push('link_spam');
+    }
+
+    for (const reason of reasons) {
+      this.metrics.byReason[reason]++;
+    }
+
+    if (reasons.length === 0) {
+      this.metrics.allowed++;
+      return { action: 'allow', reasons };
+    }
+
+    const action = this.config.action;
+    this.metrics[action === 'drop' ? 'dropped' : action === 'flag' ? 'flagged' : 'blocked']++;
+    return { action, reasons };
+  }
+
+  getMetrics(): SpamFilterMetrics {
+    return { ...this.metrics, byReason: { ...this.metrics.byReason } };
+  }
+
+  // Forget a sender's history, e.g. after they have been blocked
+  reset(sender: string): void {
+    this.history.delete(sender);
+  }
+
+  private record(sender: string, content: string, now: number) {
+    const cutoff = now - this.config.floodWindowMs;
+    const recent = (this.history.get(sender) ?? []).filter((m) => m.at > cutoff);
+    recent.push({ at: now, content });
+    this.history.set(sender, recent);
+
+    // Drop idle senders so the map doesn't grow with every contact ever seen
+    if (this.history.size > 1000) {
+      for (const [key, entries] of this.history) {
+        if (entries[entries.length - 1].at <= cutoff) this.history.delete(key);
+      }
+    }
+    return recent;
+  }
+}
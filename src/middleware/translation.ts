@@ -0,0 +1,77 @@
+import { type IAgentRuntime, ModelType } from '@elizaos/core';
+import { sameLanguage, type ContactLanguages } from '../contacts/language';
+import type { MessageTranslation, UnifiedMessage, WhatsAppMessage } from '../types';
+import { mapMessageText, type OutboundMiddleware } from './outbound';
+
+// Translates text between languages, e.g. by wrapping a translation API or a language model
+export interface Translator {
+  translate(text: string, to: string, from?: string): Promise<string>;
+}
+
+export interface TranslationOptions {
+  agentLanguage?: string;               // Language the agent reads and writes (default en)
+  inbound?: boolean;                    // Translate what contacts write into agentLanguage (default true)
+  outbound?: boolean;                   // Translate what is sent into each contact's language (default true)
+}
+
+// Translate every text `mapMessageText` reaches, concurrently
+async function translateTexts(
+  message: WhatsAppMessage,
+  translate: (text: string) => Promise<string>
+): Promise<WhatsAppMessage> {
+  const texts: string[] = [];
+  mapMessageText(message, (text) => {
+    texts.push(text);
+    return text;
+  });
+  if (texts.length === 0) return message;
+  const translated = await Promise.all(texts.map(translate));
+  let next = 0;
+  return mapMessageText(message, () => translated[next++]);
+}
+
+// Rewrite outgoing text into the recipient's stored language when it isn't the agent's
+export function translateOutbound(
+  translator: Translator,
+  languages: ContactLanguages,
+  agentLanguage = 'en'
+): OutboundMiddleware {
+  return async (message) => {
+    // Templates are already localized by picking the approved variant
+    if (message.type === 'template') return message;
+    const contact = await languages.get(message.to);
+    if (!contact || sameLanguage(contact.language, agentLanguage)) return message;
+    return translateTexts(message, (text) => translator.translate(text, contact.language, agentLanguage));
+  };
+}
+
+// What an inbound message says in the agent's language, when the sender is known to write another
+export async function translateInbound(
+  message: UnifiedMessage,
+  translator: Translator,
+  languages: ContactLanguages,
+  agentLanguage = 'en'
+): Promise<MessageTranslation | undefined> {
+  if (!message.content || message.reaction) return undefined;
+  const contact = await languages.get(message.from);
+  if (!contact || sameLanguage(contact.language, agentLanguage)) return undefined;
+  const text = await translator.translate(message.content, agentLanguage, contact.language);
+  return { text, from: contact.language, to: agentLanguage };
+}
+
+// Translates with the agent's small text model
+export class RuntimeModelTranslator implements Translator {
+  constructor(private runtime: IAgentRuntime) {}
+
+  async translate(text: string, to: string, from?: string): Promise<string> {
+    const prompt = [
+      `Translate the following WhatsApp message ${from ? `from ${from} ` : ''}into ${to}.`,
+      'Keep emoji, formatting (*bold*, _italic_, ~strike~, ```code```), links and numbers as they are.',
+      'Reply with the translation only.',
+      '',
+      text,
+    ].join('\n');
+    const translated = await this.runtime.useModel(ModelType.TEXT_SMALL, { prompt });
+    return typeof translated === 'string' && translated.trim() ? translated.trim() : text;
+  }
+}
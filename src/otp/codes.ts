@@ -0,0 +1,122 @@
+import { createHash, randomInt, timingSafeEqual } from 'crypto';
+import type { IAgentRuntime } from '@elizaos/core';
+import { canonicalChatId } from '../utils/normalize';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface OtpConfig {
+  digits?: number;                      // Default 6
+  ttlMs?: number;                       // Default 10 minutes
+  maxAttempts?: number;                 // Wrong guesses before the code is discarded, default 5
+}
+
+// Only a hash of the code is kept
+export interface PendingOtp {
+  hash: string;
+  expiresAt: number;
+  attempts: number;
+}
+
+export type OtpVerification = 'valid' | 'invalid' | 'expired' | 'missing' | 'locked';
+
+export interface OtpStore {
+  get(recipient: string): Promise<PendingOtp | undefined>;
+  set(recipient: string, otp: PendingOtp): Promise<void>;
+  delete(recipient: string): Promise<void>;
+}
+
+export class InMemoryOtpStore implements OtpStore {
+  private codes = new Map<string, PendingOtp>();
+
+  async get(recipient: string): Promise<PendingOtp | undefined> {
+    return this.codes.get(recipient);
+  }
+
+  async set(recipient: string, otp: PendingOtp): Promise<void> {
+    this.codes.set(recipient, otp);
+  }
+
+  async delete(recipient: string): Promise<void> {
+    this.codes.delete(recipient);
+  }
+}
+
+// Pending codes of one account, so a code sent from one number doesn't verify on another
+export class RuntimeOtpStore implements OtpStore {
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string
+  ) {}
+
+  async get(recipient: string): Promise<PendingOtp | undefined> {
+    return this.runtime.getCache<PendingOtp>(this.key(recipient));
+  }
+
+  async set(recipient: string, otp: PendingOtp): Promise<void> {
+    await this.runtime.setCache(this.key(recipient), otp);
+  }
+
+  async delete(recipient: string): Promise<void> {
+    await this.runtime.deleteCache(this.key(recipient));
+  }
+
+  private key(recipient: string): string {
+    return `whatsapp-otp-${this.accountId}-${recipient}`;
+  }
+}
+
+function hashCode(code: string): string {
+  return createHash('sha256').update(code).digest('hex');
+}
+
+// One-time codes per recipient: issuing a new code replaces the previous one
+export class OtpManager {
+  private store: OtpStore;
+  private config: Required<OtpConfig>;
+
+  constructor(
+    config: OtpConfig = {},
+    store?: OtpStore,
+    private readonly clock: Clock = systemClock
+  ) {
+    this.config = { digits: 6, ttlMs: 10 * 60 * 1000, maxAttempts: 5, ...config };
+    this.store = store ?? new InMemoryOtpStore();
+  }
+
+  useStore(store: OtpStore): void {
+    this.store = store;
+  }
+
+  async issue(recipient: string, now = this.clock.now()): Promise<string> {
+    const code = Array.from({ length: this.config.digits }, () => randomInt(10)).join('');
+    await this.store.set(canonicalChatId(recipient), {
+      hash: hashCode(code),
+      expiresAt: now + this.config.ttlMs,
+      attempts: 0,
+    });
+    return code;
+  }
+
+  async verify(recipient: string, code: string, now = this.clock.now()): Promise<OtpVerification> {
+    const key = canonicalChatId(recipient);
+    const pending = await this.store.get(key);
+    if (!pending) return 'missing';
+    if (pending.expiresAt <= now) {
+      await this.store.delete(key);
+      return 'expired';
+    }
+
+    const matches = timingSafeEqual(Buffer.from(hashCode(code.trim()), 'hex'), Buffer.from(pending.hash, 'hex'));
+    if (matches) {
+      await this.store.delete(key);
+      return 'valid';
+    }
+
+    pending.attempts += 1;
+    if (pending.attempts >= this.config.maxAttempts) {
+      await this.store.delete(key);
+      return 'locked';
+    }
+    await this.store.set(key, pending);
+    return 'invalid';
+  }
+}
@@ -0,0 +1,2 @@
+export { InMemoryOtpStore, OtpManager, RuntimeOtpStore } from './codes';
+export type { OtpConfig, OtpStore, OtpVerification, PendingOtp } from './codes';
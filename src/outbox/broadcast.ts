@@ -0,0 +1,131 @@
+import type { TemplatePace } from '../templates/pacing';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface BroadcastSender {
+  accountId: string;
+  capacity: number;                     // Conversations the number can still open today
+  send(to: string): Promise<unknown>;   // May resolve to `{ accountId }` when another number sent it, e.g. after failover
+  pace?(): TemplatePace;                // Checked before every send, e.g. whether Meta is pacing the template
+  sendAt?(to: string, now: number): number;  // Earliest time to message `to`, e.g. after their quiet hours
+}
+
+export interface BroadcastOptions {
+  paceMs?: number;                      // Pause between two sends from the same number (default 200ms)
+  slowPaceMs?: number;                  // Pause while the sender's pace is 'slow' (default 5s)
+  urgent?: boolean;                     // Send now even to recipients in their quiet hours
+}
+
+export interface BroadcastResult {
+  to: string;
+  accountId?: string;                   // Absent when no number had capacity left
+  ok: boolean;
+  error?: string;
+  deferredUntil?: number;               // When the recipient's quiet hours end; with ok false and no error, not sent yet
+}
+
+// A recipient left for later because of their quiet hours, rather than one that failed
+export function isDeferred(result: BroadcastResult): boolean {
+  return !result.ok && result.error === undefined && result.deferredUntil !== undefined;
+}
+
+export const NO_CAPACITY_ERROR = 'No number has messaging capacity left';
+
+const DEFAULT_PACE_MS = 200;
+const DEFAULT_SLOW_PACE_MS = 5_000;
+
+const sleep = (clock: Clock, ms: number) => new Promise<void>((resolve) => clock.setTimeout(resolve, ms));
+
+/**
+ * Split recipients between senders in proportion to their remaining capacity (largest remainder),
+ * never beyond it. Returns one recipient list per sender, plus those nobody can take.
+ */
+export function allocateRecipients(
+  recipients: string[],
+  capacities: number[]
+): { assigned: string[][]; unassigned: string[] } {
+  const caps = capacities.map((c) => Math.max(0, c));
+  const total = caps.reduce((sum, c) => sum + c, 0);
+  const quotas = caps.map(() => 0);
+
+  if (total > 0) {
+    const n = Math.min(recipients.length, total);
+    // Unlimited numbers, when there are any, share the whole audience between them
+    const unlimited = caps.filter((c) => !Number.isFinite(c)).length;
+    const shares = caps.map((c) => (unlimited ? (Number.isFinite(c) ? 0 : n / unlimited) : (n * c) / total));
+    shares.forEach((share, i) => (quotas[i] = Math.min(caps[i], Math.floor(share))));
+    let left = n - quotas.reduce((sum, q) => sum + q, 0);
+    const byRemainder = shares
+      .map((share, i) => ({ i, rest: share - Math.floor(share) }))
+      .sort((a, b) => b.rest - a.rest);
+    for (const { i } of byRemainder) {
+      if (left === 0) break;
+      if (quotas[i] < caps[i]) {
+        quotas[i]++;
+        left--;
+      }
+    }
+  }
+
+  const assigned: string[][] = [];
+  let next = 0;
+  for (const quota of quotas) {
+    assigned.push(recipients.slice(next, next + quota));
+    next += quota;
+  }
+  return { assigned, unassigned: recipients.slice(next) };
+}
+
+/**
+ * Send to every recipient, spreading them across numbers by remaining capacity. Numbers send in
+ * parallel, each one at a time with `paceMs` between sends, `slowPaceMs` while their pace is slow;
+ * a paused number stops and its remaining recipients fail. Unless `urgent`, recipients in their
+ * quiet hours are not sent to but come back deferred (see `isDeferred`), so the caller isn't held
+ * for hours. Results follow the recipient order, once per recipient.
+ */
+export async function distributeBroadcast(
+  recipients: string[],
+  senders: BroadcastSender[],
+  options: BroadcastOptions = {},
+  clock: Clock = systemClock
+): Promise<BroadcastResult[]> {
+  const paceMs = options.paceMs ?? DEFAULT_PACE_MS;
+  const slowPaceMs = options.slowPaceMs ?? DEFAULT_SLOW_PACE_MS;
+  const audience = [...new Set(recipients)];
+  const { assigned, unassigned } = allocateRecipients(audience, senders.map((s) => s.capacity));
+  const results = new Map<string, BroadcastResult>();
+
+  for (const to of unassigned) {
+    results.set(to, { to, ok: false, error: NO_CAPACITY_ERROR });
+  }
+
+  await Promise.all(
+    senders.map(async (sender, i) => {
+      const start = clock.now();
+      const queue: string[] = [];
+      for (const to of assigned[i]) {
+        const at = options.urgent ? start : sender.sendAt?.(to, start) ?? start;
+        if (at > start) results.set(to, { to, accountId: sender.accountId, ok: false, deferredUntil: at });
+        else queue.push(to);
+      }
+      for (const [n, to] of queue.entries()) {
+        const pace = sender.pace?.() ?? 'normal';
+        if (pace === 'paused') {
+          for (const rest of queue.slice(n)) {
+            results.set(rest, { to: rest, accountId: sender.accountId, ok: false, error: 'Template is paused' });
+          }
+          break;
+        }
+        const wait = pace === 'slow' ? Math.max(paceMs, slowPaceMs) : paceMs;
+        if (n > 0 && wait > 0) await sleep(clock, wait);
+        try {
+          const sent = (await sender.send(to)) as { accountId?: string } | undefined;
+          results.set(to, { to, accountId: sent?.accountId ?? sender.accountId, ok: true });
+        } catch (error) {
+          results.set(to, { to, accountId: sender.accountId, ok: false, error: (error as Error).message });
+        }
+      }
+    })
+  );
+
+  return audience.map((to) => results.get(to) as BroadcastResult);
+}
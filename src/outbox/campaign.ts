@@ -0,0 +1,158 @@
+import { systemClock, type Clock, type ClockTimer } from '../utils/clock';
+import {
+  allocateRecipients,
+  distributeBroadcast,
+  isDeferred,
+  NO_CAPACITY_ERROR,
+  type BroadcastOptions,
+  type BroadcastResult,
+  type BroadcastSender,
+} from './broadcast';
+
+export interface CampaignSenderLimits {
+  accountId: string;
+  capacity: number;                     // Conversations the number can still open today
+  limit: number;                        // Conversations it may open on a fresh day (its messaging tier)
+}
+
+export interface CampaignDay {
+  day: number;                          // 0 is today
+  startsAt: number;                     // Milliseconds since epoch
+  recipients: Record<string, string[]>; // By account id
+}
+
+export interface CampaignPlan {
+  days: CampaignDay[];
+  unscheduled: string[];                // Recipients beyond maxDays of capacity
+}
+
+export interface CampaignOptions extends BroadcastOptions {
+  maxDays?: number;                     // Days a campaign may spread over (default 7)
+}
+
+export interface ScheduledCampaign {
+  plan: CampaignPlan;
+  done: Promise<BroadcastResult[]>;     // Settles after the last day, or once cancelled
+  cancel(): void;
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const DEFAULT_MAX_DAYS = 7;
+// Longest delay a timer takes; Node fires longer ones at once
+const MAX_TIMER_MS = 2 ** 31 - 1;
+
+/**
+ * Spread recipients over days so no number opens more conversations than its tier allows: today
+ * uses what each number has left, every later day its full limit, 24 hours apart.
+ */
+export function planCampaign(
+  recipients: string[],
+  senders: CampaignSenderLimits[],
+  options: { now: number; maxDays?: number }
+): CampaignPlan {
+  const maxDays = options.maxDays ?? DEFAULT_MAX_DAYS;
+  let queue = [...new Set(recipients)];
+  const days: CampaignDay[] = [];
+
+  for (let day = 0; day < maxDays && queue.length > 0; day++) {
+    const capacities = senders.map((s) => (day === 0 ? s.capacity : s.limit));
+    const { assigned, unassigned } = allocateRecipients(queue, capacities);
+    if (unassigned.length === queue.length) {
+      // Nothing left today is fine; no limit at all on a fresh day means nothing ever will be
+      if (day === 0) continue;
+      break;
+    }
+    days.push({
+      day,
+      startsAt: options.now + day * DAY_MS,
+      recipients: Object.fromEntries(
+        senders.flatMap((s, i) => (assigned[i].length > 0 ? [[s.accountId, assigned[i]]] : []))
+      ),
+    });
+    queue = unassigned;
+  }
+
+  return { days, unscheduled: queue };
+}
+
+/**
+ * Send a planned campaign, each day at its start with senders' capacity read afresh. Recipients in
+ * their quiet hours are sent to once those hours end, their results keeping `deferredUntil`.
+ * Recipients a day cannot take after all roll over to the next day; what is left after the last
+ * day, and the plan's unscheduled recipients, fail. Results follow the plan's order.
+ */
+export function runCampaign(
+  plan: CampaignPlan,
+  senders: () => Promise<BroadcastSender[]>,
+  options: BroadcastOptions = {},
+  clock: Clock = systemClock
+): ScheduledCampaign {
+  const results = new Map<string, BroadcastResult>();
+  let timer: ClockTimer | undefined;
+  let wake: (() => void) | undefined;
+  let cancelled = false;
+
+  const wait = (ms: number) =>
+    new Promise<void>((resolve) => {
+      wake = resolve;
+      timer = clock.setTimeout(resolve, ms);
+    });
+
+  // Days past the 24th are further off than one timer reaches, so wait in steps
+  const waitUntil = async (at: number) => {
+    while (!cancelled && at > clock.now()) await wait(Math.min(at - clock.now(), MAX_TIMER_MS));
+  };
+
+  let carried: string[] = [];
+  let deferred: BroadcastResult[] = [];
+
+  const dispatch = async (queue: string[], deferredUntil?: number) => {
+    for (const result of await distributeBroadcast(queue, await senders(), options, clock)) {
+      if (!result.ok && !result.accountId) carried.push(result.to);
+      else if (isDeferred(result)) deferred.push(result);
+      else results.set(result.to, deferredUntil ? { ...result, deferredUntil } : result);
+    }
+  };
+
+  const cancelAll = (queue: string[]) => {
+    for (const to of queue) results.set(to, { to, ok: false, error: 'Campaign cancelled' });
+  };
+
+  const run = async (): Promise<BroadcastResult[]> => {
+    for (const day of plan.days) {
+      const queue = [...carried, ...Object.values(day.recipients).flat()];
+      carried = [];
+      await waitUntil(day.startsAt);
+      if (cancelled) {
+        cancelAll(queue);
+        continue;
+      }
+      await dispatch(queue);
+      // Recipients in their quiet hours go out as each one's hours end
+      while (deferred.length > 0) {
+        const at = Math.min(...deferred.map((r) => r.deferredUntil as number));
+        await waitUntil(at);
+        const due = cancelled ? deferred : deferred.filter((r) => (r.deferredUntil as number) <= at);
+        deferred = deferred.filter((r) => !due.includes(r));
+        if (cancelled) cancelAll(due.map((r) => r.to));
+        else await dispatch(due.map((r) => r.to), at);
+      }
+    }
+    for (const to of [...carried, ...plan.unscheduled]) {
+      results.set(to, { to, ok: false, error: NO_CAPACITY_ERROR });
+    }
+
+    const order = [...plan.days.flatMap((d) => Object.values(d.recipients).flat()), ...plan.unscheduled];
+    return order.map((to) => results.get(to) as BroadcastResult);
+  };
+
+  return {
+    plan,
+    done: run(),
+    cancel: () => {
+      cancelled = true;
+      clock.clearTimeout(timer);
+      wake?.();
+    },
+  };
+}
@@ -0,0 +1,169 @@
+import { EventEmitter } from 'events';
+import { CircuitOpenError, WhatsAppError } from '../errors';
+import { isTokenRejected } from '../accounts/doctor';
+import { systemClock, type Clock, type ClockTimer } from '../utils/clock';
+import { failoverReason } from './failover';
+import { isTransientError } from './retry';
+
+export interface CircuitBreakerConfig {
+  failureThreshold?: number;            // Consecutive failures before the breaker opens (default 5)
+  resetTimeoutMs?: number;              // Time open before a half-open probe is let through (default 30s)
+  queue?: boolean;                      // Hold sends while open instead of failing fast
+  maxQueued?: number;                   // Default 100; sends beyond it fail fast
+}
+
+export type CircuitState = 'closed' | 'open' | 'half_open';
+
+const DEFAULT_FAILURE_THRESHOLD = 5;
+const DEFAULT_RESET_TIMEOUT_MS = 30_000;
+const DEFAULT_MAX_QUEUED = 100;
+
+interface Waiter {
+  resolve: () => void;
+  reject: (error: unknown) => void;
+}
+
+// Only failures of the account itself count: a rejected token, rate limits, server errors and
+// lost connections. Anything caused by the request (bad parameters, unsupported message types,
+// unknown recipients) shows the account is reachable, however often it happens.
+function isAccountFailure(error: unknown): boolean {
+  if (error instanceof WhatsAppError && error.code === 'SEND_FAILED' && error.cause !== undefined) {
+    return isAccountFailure(error.cause);
+  }
+  if (error instanceof WhatsAppError && (error.code === 'NOT_CONNECTED' || error.code === 'LOGGED_OUT')) return true;
+  return isTokenRejected(error) || failoverReason(error) === 'rate_limited' || isTransientError(error);
+}
+
+/**
+ * Stops sending through an account that keeps failing. After `failureThreshold` consecutive
+ * failures the breaker opens and sends fail fast with CircuitOpenError (or wait, with `queue`);
+ * once `resetTimeoutMs` has passed a single probe is let through, and its outcome closes or
+ * reopens the breaker. Emits "open" and "close".
+ */
+export class CircuitBreaker extends EventEmitter {
+  private current: CircuitState = 'closed';
+  private failures = 0;
+  private openedAt = 0;
+  private probing = false;
+  private waiting: Waiter[] = [];
+  private timer?: ClockTimer;
+
+  constructor(
+    private readonly accountId: string,
+    private readonly config: CircuitBreakerConfig = {},
+    private readonly clock: Clock = systemClock
+  ) {
+    super();
+  }
+
+  get state(): CircuitState {
+    return this.current;
+  }
+
+  get consecutiveFailures(): number {
+    return this.failures;
+  }
+
+  get queued(): number {
+    return this.waiting.length;
+  }
+
+  // Milliseconds until the next probe is allowed; 0 unless open
+  retryInMs(now = this.clock.now()): number {
+    if (this.current !== 'open') return 0;
+    return Math.max(0, this.openedAt + this.resetTimeoutMs - now);
+  }
+
+  // `wait: false` fails fast while open even when the breaker queues sends
+  async run<T>(fn: () => Promise<T>, { wait = this.config.queue }: { wait?: boolean } = {}): Promise<T> {
+    const probe = await this.admit(wait === true);
+    try {
+      const result = await fn();
+      this.succeeded();
+      return result;
+    } catch (error) {
+      if (isAccountFailure(error)) {
+        this.failed(error, probe);
+      } else {
+        this.succeeded();
+      }
+      throw error;
+    } finally {
+      if (probe) this.probing = false;
+    }
+  }
+
+  // Close the breaker and release queued sends, e.g. after an operator fixes the account
+  reset(): void {
+    this.failures = 0;
+    if (this.current !== 'closed') this.close();
+  }
+
+  // Reject queued sends and stop the probe timer; used when the account shuts down
+  clear(): void {
+    this.clock.clearTimeout(this.timer);
+    this.timer = undefined;
+    const waiting = this.waiting;
+    this.waiting = [];
+    for (const waiter of waiting) waiter.reject(new CircuitOpenError(this.accountId, this.retryInMs()));
+  }
+
+  // Resolves once the call may go ahead; true when it is the half-open probe
+  private async admit(wait: boolean): Promise<boolean> {
+    for (;;) {
+      if (this.current === 'open' && this.retryInMs() === 0) this.current = 'half_open';
+      if (this.current === 'closed') return false;
+      if (this.current === 'half_open' && !this.probing) {
+        this.probing = true;
+        return true;
+      }
+      if (!wait || this.waiting.length >= (this.config.maxQueued ?? DEFAULT_MAX_QUEUED)) {
+        throw new CircuitOpenError(this.accountId, this.retryInMs());
+      }
+      await new Promise<void>((resolve, reject) => this.waiting.push({ resolve, reject }));
+    }
+  }
+
+  private succeeded(): void {
+    this.failures = 0;
+    if (this.current !== 'closed') this.close();
+  }
+
+  private failed(error: unknown, probe: boolean): void {
+    this.failures++;
+    if (this.current === 'open') return;
+    if (probe || this.failures >= (this.config.failureThreshold ?? DEFAULT_FAILURE_THRESHOLD)) {
+      this.open(error);
+    }
+  }
+
+  private open(error: unknown): void {
+    this.current = 'open';
+    this.openedAt = this.clock.now();
+    if (this.config.queue) {
+      // Wake queued sends when the probe is due, so one of them can make it
+      this.clock.clearTimeout(this.timer);
+      this.timer = this.clock.setTimeout(() => this.wake(), this.resetTimeoutMs);
+      this.timer.unref?.();
+    }
+    this.emit('open', { failures: this.failures, retryInMs: this.resetTimeoutMs, error });
+  }
+
+  private close(): void {
+    this.current = 'closed';
+    this.clock.clearTimeout(this.timer);
+    this.timer = undefined;
+    this.emit('close');
+    this.wake();
+  }
+
+  private wake(): void {
+    const waiting = this.waiting;
+    this.waiting = [];
+    for (const waiter of waiting) waiter.resolve();
+  }
+
+  private get resetTimeoutMs(): number {
+    return this.config.resetTimeoutMs ?? DEFAULT_RESET_TIMEOUT_MS;
+  }
+}
@@ -0,0 +1,134 @@
+import { WhatsAppError } from '../errors';
+import type { MessageStatusUpdate } from '../events/types';
+import { systemClock, type Clock, type ClockTimer } from '../utils/clock';
+import { LruCache, type LruOptions } from '../utils/lru';
+
+export type DeliveryState = 'sent' | 'delivered' | 'read' | 'failed';
+
+// Statuses only move forward; webhooks may deliver them out of order
+const PROGRESS: Record<string, number> = { sent: 1, delivered: 2, read: 3, played: 3 };
+
+const DEFAULT_MAX_MESSAGES = 10_000;
+// Waits give up after a day unless given a timeout; a status may never come, e.g. a lost webhook
+const DEFAULT_WAIT_MS = 24 * 60 * 60 * 1000;
+// Messages waited on at once; the longest-waiting one is given up on to make room
+const MAX_WAITED_MESSAGES = 10_000;
+
+interface Waiter {
+  until: 'delivered' | 'read';
+  resolve: () => void;
+  reject: (error: unknown) => void;
+  timer?: ClockTimer;
+}
+
+/**
+ * Latest delivery status of each sent message, fed by status webhooks (and Baileys receipts), with
+ * waits for a message to reach "delivered" or "read". Only the most recently updated messages are
+ * kept; a status that arrives before the send returns is remembered for the wait that follows.
+ */
+export class DeliveryLedger {
+  private states: LruCache<DeliveryState>;
+  private waiters = new Map<string, Waiter[]>();
+
+  constructor(private readonly clock: Clock = systemClock, options: LruOptions = {}) {
+    this.states = new LruCache({ maxChats: DEFAULT_MAX_MESSAGES, clock, ...options });
+  }
+
+  record(update: MessageStatusUpdate): void {
+    const { messageId, status } = update;
+    const current = this.states.get(messageId);
+    if (current === 'failed') return;
+    if (status === 'failed') {
+      this.states.set(messageId, 'failed');
+    } else if (PROGRESS[status] && (!current || PROGRESS[status] > PROGRESS[current])) {
+      this.states.set(messageId, status === 'played' ? 'read' : (status as DeliveryState));
+    } else {
+      return;
+    }
+    this.settle(messageId);
+  }
+
+  status(messageId: string): DeliveryState | undefined {
+    return this.states.get(messageId);
+  }
+
+  // Resolves once the message reaches `until`; rejects if it fails or `timeoutMs` (default a day) passes first
+  waitFor(messageId: string, until: 'delivered' | 'read', timeoutMs = DEFAULT_WAIT_MS): Promise<void> {
+    if (!messageId) {
+      return Promise.reject(new WhatsAppError('NOT_FOUND', 'The send returned no message id to track'));
+    }
+    return new Promise<void>((resolve, reject) => {
+      const waiter: Waiter = { until, resolve, reject };
+      waiter.timer = this.clock.setTimeout(() => {
+        this.remove(messageId, waiter);
+        reject(new WhatsAppError('DELIVERY_TIMEOUT', `Message ${messageId} was not ${until} within ${timeoutMs}ms`));
+      }, timeoutMs);
+      waiter.timer.unref?.();
+      if (!this.waiters.has(messageId) && this.waiters.size >= MAX_WAITED_MESSAGES) this.giveUpOldest();
+      this.waiters.set(messageId, [...(this.waiters.get(messageId) ?? []), waiter]);
+      this.settle(messageId);
+    });
+  }
+
+  // Resolve or reject the waits the message's current status answers
+  private settle(messageId: string): void {
+    const state = this.states.get(messageId);
+    const waiting = this.waiters.get(messageId);
+    if (!state || !waiting) return;
+    for (const waiter of waiting) {
+      if (state === 'failed') {
+        this.remove(messageId, waiter);
+        waiter.reject(new WhatsAppError('DELIVERY_FAILED', `Message ${messageId} could not be delivered`));
+      } else if (PROGRESS[state] >= PROGRESS[waiter.until]) {
+        this.remove(messageId, waiter);
+        waiter.resolve();
+      }
+    }
+  }
+
+  private giveUpOldest(): void {
+    const [messageId, waiting] = this.waiters.entries().next().value as [string, Waiter[]];
+    for (const waiter of waiting) {
+      this.remove(messageId, waiter);
+      waiter.reject(new WhatsAppError('DELIVERY_TIMEOUT', `Stopped waiting on message ${messageId} to make room for newer waits`));
+    }
+  }
+
+  private remove(messageId: string, waiter: Waiter): void {
+    this.clock.clearTimeout(waiter.timer);
+    const rest = (this.waiters.get(messageId) ?? []).filter((w) => w !== waiter);
+    if (rest.length > 0) this.waiters.set(messageId, rest);
+    else this.waiters.delete(messageId);
+  }
+}
+
+// One message as it went out: its id, the account that sent it, and that account's ledger
+export interface SentMessage {
+  id: string;
+  accountId: string;
+  ledger: DeliveryLedger;
+  response: unknown;
+}
+
+// What a send returns: the messages it produced, and waits on their delivery
+export interface MessageHandle {
+  wamid: string;                        // Id of the first message; '' when nothing was sent
+  accountId: string;                    // Account that sent it, after any failover
+  chunkIds: string[];                   // Every message id, in order, when the content was split
+  responses: unknown[];                 // Raw client responses, in order
+  awaitDelivered(timeoutMs?: number): Promise<void>;  // Every chunk delivered
+  awaitRead(timeoutMs?: number): Promise<void>;       // Every chunk read
+}
+
+export function messageHandle(accountId: string, sent: SentMessage[]): MessageHandle {
+  const waitAll = (until: 'delivered' | 'read', timeoutMs?: number) =>
+    Promise.all(sent.map((s) => s.ledger.waitFor(s.id, until, timeoutMs))).then(() => undefined);
+  return {
+    wamid: sent[0]?.id ?? '',
+    accountId: sent[0]?.accountId ?? accountId,
+    chunkIds: sent.map((s) => s.id),
+    responses: sent.map((s) => s.response),
+    awaitDelivered: (timeoutMs) => waitAll('delivered', timeoutMs),
+    awaitRead: (timeoutMs) => waitAll('read', timeoutMs),
+  };
+}
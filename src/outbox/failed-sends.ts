@@ -0,0 +1,147 @@
+import type { IAgentRuntime } from '@elizaos/core';
+import type { WhatsAppMessage } from '../types';
+import { systemClock, type Clock } from '../utils/clock';
+
+export interface SendAttempt {
+  code: string;
+  message: string;
+  failedAt: number;                     // Milliseconds since epoch
+}
+
+export interface FailedSend {
+  id: string;
+  message: WhatsAppMessage;
+  error: { code: string; message: string };   // Latest failure
+  failedAt: number;                     // Milliseconds since epoch of the latest failure
+  attempts: SendAttempt[];              // Every failed attempt, retries and replays included, oldest first
+}
+
+// Where dead letters are kept between restarts
+export interface DeadLetterStore {
+  load(): Promise<FailedSend[]>;
+  save(entries: FailedSend[]): Promise<void>;
+}
+
+// Dead letters in the agent's runtime cache, one list per account
+export class RuntimeDeadLetterStore implements DeadLetterStore {
+  constructor(
+    private runtime: IAgentRuntime,
+    private accountId: string
+  ) {}
+
+  private get key(): string {
+    return `whatsapp-dead-letters:${this.accountId}`;
+  }
+
+  async load(): Promise<FailedSend[]> {
+    return (await this.runtime.getCache<FailedSend[]>(this.key)) ?? [];
+  }
+
+  async save(entries: FailedSend[]): Promise<void> {
+    await this.runtime.setCache(this.key, entries);
+  }
+}
+
+export function sendAttempt(error: unknown, failedAt = systemClock.now()): SendAttempt {
+  return {
+    code: (error as { code?: string })?.code ?? 'SEND_FAILED',
+    message: (error as Error)?.message ?? String(error),
+    failedAt,
+  };
+}
+
+const DEFAULT_MAX_ENTRIES = 100;
+
+/**
+ * Dead-letter queue: the most recent sends that failed for good, with their original message and
+ * every failed attempt, kept so operators can inspect, replay or discard them. Entries live in
+ * memory and, with a store, are saved after each change and loaded again on start.
+ */
+export class FailedSendLog {
+  private entries = new Map<string, FailedSend>();
+  private sequence = 0;
+  private store?: DeadLetterStore;
+  private saving: Promise<void> = Promise.resolve();
+
+  constructor(
+    private readonly maxEntries = DEFAULT_MAX_ENTRIES,
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  // Persist entries to `store`, first restoring the ones it already holds
+  async useStore(store: DeadLetterStore): Promise<void> {
+    this.store = store;
+    const stored = await store.load();
+    this.entries = new Map([...stored, ...this.entries.values()].map((entry) => [entry.id, entry]));
+    this.trim();
+    this.persist();
+  }
+
+  record(message: WhatsAppMessage, error: unknown, earlier: SendAttempt[] = []): FailedSend {
+    const attempt = sendAttempt(error, this.clock.now());
+    const entry: FailedSend = {
+      id: `failed-${attempt.failedAt}-${++this.sequence}`,
+      message,
+      error: { code: attempt.code, message: attempt.message },
+      failedAt: attempt.failedAt,
+      attempts: [...earlier, attempt],
+    };
+    this.entries.set(entry.id, entry);
+    this.trim();
+    this.persist();
+    return entry;
+  }
+
+  // Add another failure to an existing entry, e.g. after a replay failed
+  recordAttempt(id: string, error: unknown, earlier: SendAttempt[] = []): FailedSend | undefined {
+    const entry = this.entries.get(id);
+    if (!entry) return undefined;
+    const attempt = sendAttempt(error, this.clock.now());
+    entry.attempts.push(...earlier, attempt);
+    entry.error = { code: attempt.code, message: attempt.message };
+    entry.failedAt = attempt.failedAt;
+    // Move it to the end so a fresh failure is not the next one evicted
+    this.entries.delete(id);
+    this.entries.set(id, entry);
+    this.persist();
+    return entry;
+  }
+
+  list(): FailedSend[] {
+    return [...this.entries.values()];
+  }
+
+  get(id: string): FailedSend | undefined {
+    return this.entries.get(id);
+  }
+
+  remove(id: string): boolean {
+    const removed = this.entries.delete(id);
+    if (removed) this.persist();
+    return removed;
+  }
+
+  get size(): number {
+    return this.entries.size;
+  }
+
+  // Resolves once every change so far has been saved
+  flush(): Promise<void> {
+    return this.saving;
+  }
+
+  private trim(): void {
+    // Map keeps insertion order, so the first key is the oldest entry
+    while (this.entries.size > this.maxEntries) {
+      this.entries.delete(this.entries.keys().next().value as string);
+    }
+  }
+
+  // Saves are queued so they land in order; each one writes the whole list, so a failed save
+  // is made good by the next change
+  private persist(): void {
+    const store = this.store;
+    if (!store) return;
+    this.saving = this.saving.then(() => store.save(this.list())).catch(() => {});
+  }
+}
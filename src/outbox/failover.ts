@@ -0,0 +1,34 @@
+import { CircuitOpenError, GraphApiError } from '../errors';
+
+export type FailoverReason = 'rate_limited' | 'circuit_open' | 'flagged';
+
+// Added to a send result when the message went out from the fallback account
+export interface FailoverRecord {
+  from: string;                         // Account the message was meant for
+  to: string;                           // Account that sent it
+  reason: FailoverReason;
+}
+
+// Graph error codes for throughput and pair rate limits
+const RATE_LIMIT_CODES = new Set([4, 80007, 130429, 131056]);
+// Graph error codes for numbers blocked or restricted over quality or policy
+const FLAGGED_CODES = new Set([368, 131031, 131048]);
+
+// Why a failed send should move to the fallback account; undefined when another number would not help
+export function failoverReason(error: unknown): FailoverReason | undefined {
+  if (error instanceof CircuitOpenError) return 'circuit_open';
+  if (!(error instanceof GraphApiError)) return undefined;
+  if (error.status === 429 || (error.graphCode !== undefined && RATE_LIMIT_CODES.has(error.graphCode))) {
+    return 'rate_limited';
+  }
+  if (error.graphCode !== undefined && FLAGGED_CODES.has(error.graphCode)) return 'flagged';
+  return undefined;
+}
+
+// Graph error code for a free-form message sent more than 24 hours after the recipient last wrote
+const SERVICE_WINDOW_CLOSED_CODE = 131047;
+
+// The recipient's customer service window has closed, so only a template will reach them
+export function isServiceWindowClosed(error: unknown): boolean {
+  return error instanceof GraphApiError && error.graphCode === SERVICE_WINDOW_CLOSED_CODE;
+}
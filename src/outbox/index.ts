@@ -0,0 +1,17 @@
+export { FailedSendLog, RuntimeDeadLetterStore, sendAttempt } from './failed-sends';
+export type { DeadLetterStore, FailedSend, SendAttempt } from './failed-sends';
+export { isTimeoutError, isTransientError, retryDelay } from './retry';
+export type { SendRetryConfig } from './retry';
+export { DeliveryLedger, messageHandle } from './delivery';
+export type { DeliveryState, MessageHandle, SentMessage } from './delivery';
+export { CircuitBreaker } from './circuit-breaker';
+export type { CircuitBreakerConfig, CircuitState } from './circuit-breaker';
+export { failoverReason, isServiceWindowClosed } from './failover';
+export type { FailoverReason, FailoverRecord } from './failover';
+export { allocateRecipients, distributeBroadcast, isDeferred, NO_CAPACITY_ERROR } from './broadcast';
+export type { BroadcastOptions, BroadcastResult, BroadcastSender } from './broadcast';
+export { planCampaign, runCampaign } from './campaign';
+export { QuietHours } from './quiet-hours';
+export type { QuietHoursConfig } from './quiet-hours';
+export type { CampaignDay, CampaignOptions, CampaignPlan, CampaignSenderLimits, ScheduledCampaign } from './campaign';
+export { ConversationWindow, DEFAULT_MESSAGING_LIMIT, MESSAGING_TIER_LIMITS, messagingTierLimit } from './tier';
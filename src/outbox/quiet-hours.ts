@@ -0,0 +1,76 @@
+import { WhatsAppError } from '../errors';
+import { normalizeWhatsAppTarget } from '../utils/normalize';
+import { inferTimeZone, localMinutes } from '../utils/timezone';
+
+// Local hours during which non-urgent sends (broadcasts, scheduled campaigns) wait
+export interface QuietHoursConfig {
+  start?: string;                       // HH:MM when quiet hours begin (default 21:00)
+  end?: string;                         // HH:MM when sending may resume (default 08:00)
+  timeZone?: string;                    // For numbers whose zone can't be inferred (default UTC)
+  timeZones?: Record<string, string>;   // Known zones by wa_id, overriding inference
+}
+
+const MINUTE_MS = 60 * 1000;
+const DAY_MINUTES = 24 * 60;
+
+const key = (waId: string) => normalizeWhatsAppTarget(waId)?.id ?? waId;
+
+function parseClockTime(value: string, field: string): number {
+  const [hours, minutes = '0', extra] = value.split(':');
+  const h = Number(hours);
+  const m = Number(minutes);
+  if (extra !== undefined || !Number.isInteger(h) || !Number.isInteger(m) || h < 0 || h > 23 || m < 0 || m > 59) {
+    throw new WhatsAppError('INVALID_CONFIG', `quietHours.${field} must be HH:MM, got "${value}"`);
+  }
+  return h * 60 + m;
+}
+
+// Checked up front, since Intl only rejects an unknown zone once a send asks for it
+function checkTimeZone(timeZone: string, field: string): string {
+  try {
+    new Intl.DateTimeFormat('en-US', { timeZone });
+  } catch {
+    throw new WhatsAppError('INVALID_CONFIG', `quietHours.${field} is not a known time zone, got "${timeZone}"`);
+  }
+  return timeZone;
+}
+
+/**
+ * Decides when a non-urgent message may reach a contact: straight away outside quiet hours, or
+ * when they end in the contact's time zone. Zones come from `timeZones`, else the number's
+ * calling code, else `timeZone`. A window may wrap midnight (21:00-08:00) or not (12:00-14:00).
+ */
+export class QuietHours {
+  private readonly start: number;
+  private readonly end: number;
+  private readonly timeZones: Map<string, string>;
+
+  constructor(private readonly config: QuietHoursConfig = {}) {
+    this.start = parseClockTime(config.start ?? '21:00', 'start');
+    this.end = parseClockTime(config.end ?? '08:00', 'end');
+    if (config.timeZone !== undefined) checkTimeZone(config.timeZone, 'timeZone');
+    this.timeZones = new Map(
+      Object.entries(config.timeZones ?? {}).map(([waId, zone]) => [key(waId), checkTimeZone(zone, `timeZones.${waId}`)])
+    );
+  }
+
+  setTimeZone(waId: string, timeZone: string): void {
+    this.timeZones.set(key(waId), checkTimeZone(timeZone, 'timeZone'));
+  }
+
+  timeZoneOf(waId: string): string {
+    return this.timeZones.get(key(waId)) ?? inferTimeZone(waId) ?? this.config.timeZone ?? 'UTC';
+  }
+
+  // Earliest time at or after `now` when `waId` may be messaged
+  sendAt(waId: string, now: number): number {
+    const local = localMinutes(now, this.timeZoneOf(waId));
+    const quiet = this.start <= this.end
+      ? local >= this.start && local < this.end
+      : local >= this.start || local < this.end;
+    if (!quiet) return now;
+    const wait = (this.end - local + DAY_MINUTES) % DAY_MINUTES;
+    // Round down to the minute so the window opens exactly at `end`
+    return now - (now % MINUTE_MS) + wait * MINUTE_MS;
+  }
+}
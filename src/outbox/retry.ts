@@ -0,0 +1,40 @@
+import { GraphApiError, WhatsAppError } from '../errors';
+
+export interface SendRetryConfig {
+  attempts?: number;                    // Tries per send, the first included (default 3; 1 turns retries off)
+  backoffMs?: number;                   // Delay before the first retry, doubled each time (default 500)
+}
+
+// Graph API codes for temporary server-side failures
+const TRANSIENT_GRAPH_CODES = new Set([1, 2, 131000, 131016]);
+
+// Worth sending again as-is: network failures and temporary Graph API or server errors.
+// Rate limits, open circuits and problems with the message itself are not.
+export function isTransientError(error: unknown): boolean {
+  if (error instanceof GraphApiError) {
+    return (error.status !== undefined && error.status >= 500) || TRANSIENT_GRAPH_CODES.has(error.graphCode ?? 0);
+  }
+  if (error instanceof WhatsAppError) {
+    // The message handler wraps client errors as SEND_FAILED, keeping the original as `cause`
+    return error.code === 'SEND_FAILED' && error.cause !== undefined && isTransientError(error.cause);
+  }
+  const http = error as { isAxiosError?: boolean; response?: { status?: number } } | undefined;
+  if (!http?.isAxiosError) return false;
+  const status = http.response?.status;
+  return status === undefined || status >= 500;
+}
+
+// No answer came back in time, so Meta may have accepted the message all the same
+export function isTimeoutError(error: unknown): boolean {
+  if (error instanceof GraphApiError) return error.status === 504;
+  if (error instanceof WhatsAppError) {
+    return error.code === 'SEND_FAILED' && error.cause !== undefined && isTimeoutError(error.cause);
+  }
+  const http = error as { isAxiosError?: boolean; code?: string; response?: { status?: number } } | undefined;
+  if (!http?.isAxiosError) return false;
+  return http.code === 'ECONNABORTED' || http.code === 'ETIMEDOUT' || http.response?.status === 504;
+}
+
+export function retryDelay(config: SendRetryConfig, retry: number): number {
+  return (config.backoffMs ?? 500) * 2 ** retry;
+}
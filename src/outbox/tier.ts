@@ -0,0 +1,51 @@
+import { systemClock, type Clock } from '../utils/clock';
+
+// Business-initiated conversations a number may open per rolling 24 hours, by Meta messaging tier
+export const MESSAGING_TIER_LIMITS: Record<string, number> = {
+  TIER_50: 50,
+  TIER_250: 250,
+  TIER_1K: 1_000,
+  TIER_10K: 10_000,
+  TIER_100K: 100_000,
+  TIER_UNLIMITED: Number.POSITIVE_INFINITY,
+};
+
+// Used when a number's tier is unknown (Baileys, or the Graph lookup failed): the lowest paid tier
+export const DEFAULT_MESSAGING_LIMIT = 250;
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+export function messagingTierLimit(tier: string | undefined): number | undefined {
+  return tier ? MESSAGING_TIER_LIMITS[tier.toUpperCase()] : undefined;
+}
+
+// Distinct recipients sent a template (a business-initiated conversation) in the last 24 hours, the
+// unit messaging tiers are counted in
+export class ConversationWindow {
+  private lastSent = new Map<string, number>();
+
+  constructor(private readonly clock: Clock = systemClock) {}
+
+  record(recipient: string, now = this.clock.now()): void {
+    // Re-insert so the Map stays ordered oldest first
+    this.lastSent.delete(recipient);
+    this.lastSent.set(recipient, now);
+  }
+
+  // Recipients and when each was last messaged, oldest first
+  snapshot(): Array<[string, number]> {
+    return [...this.lastSent];
+  }
+
+  restore(entries: Array<[string, number]>): void {
+    this.lastSent = new Map([...entries].sort((a, b) => a[1] - b[1]));
+  }
+
+  count(now = this.clock.now()): number {
+    for (const [recipient, sentAt] of this.lastSent) {
+      if (sentAt > now - DAY_MS) break;
+      this.lastSent.delete(recipient);
+    }
+    return this.lastSent.size;
+  }
+}
@@ -0,0 +1,15 @@
+export interface HumanizeConfig {
+  enabled?: boolean;                    // Default true when the config is present
+  msPerChar?: number;                   // Simulated typing speed (default 40ms, ~25 chars/s)
+  minDelayMs?: number;                  // Default 800
+  maxDelayMs?: number;                  // Default 6000
+  markRead?: boolean;                   // Mark the inbound message read before replying (default true)
+}
+
+// How long to "type" a message before sending it
+export function typingDelay(text: string, config: HumanizeConfig = {}): number {
+  const min = config.minDelayMs ?? 800;
+  const max = Math.max(min, config.maxDelayMs ?? 6000);
+  const delay = text.length * (config.msPerChar ?? 40);
+  return Math.min(max, Math.max(min, Math.round(delay)));
+}
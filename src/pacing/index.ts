@@ -0,0 +1,2 @@
+export { typingDelay } from './humanize';
+export type { HumanizeConfig } from './humanize';
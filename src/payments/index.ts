@@ -0,0 +1,2 @@
+export * from './types';
+export { buildOrderDetails, buildOrderStatus, parsePaymentStatus } from './payloads';
@@ -0,0 +1,99 @@
+import { WhatsAppError } from '../errors';
+import type { WhatsAppInteractive, WhatsAppWebhookEvent } from '../types';
+import type {
+  OrderDetailsInput,
+  OrderStatus,
+  PaymentAmount,
+  PaymentStatusUpdate,
+  WhatsAppOrderDetailsParameters,
+} from './types';
+
+type WebhookStatus = NonNullable<WhatsAppWebhookEvent['entry'][number]['changes'][number]['value']['statuses']>[number];
+
+// Order amounts are built from minor units, so every amount shares the same offset
+const MINOR_UNITS = 100;
+
+function amount(value: number): PaymentAmount {
+  if (!Number.isInteger(value) || value < 0) {
+    throw new WhatsAppError('INVALID_ORDER', `Amount ${value} must be a non-negative integer in minor units`);
+  }
+  return { value, offset: MINOR_UNITS };
+}
+
+/**
+ * Interactive "order_details" message asking the user to review and pay (India and Brazil numbers
+ * with payments set up). Totals are computed from the items so they always add up, as Meta
+ * rejects orders whose subtotal or total don't match.
+ */
+export function buildOrderDetails(input: OrderDetailsInput): WhatsAppInteractive {
+  if (!input.referenceId) {
+    throw new WhatsAppError('INVALID_ORDER', 'An order needs a referenceId');
+  }
+  if (input.items.length === 0) {
+    throw new WhatsAppError('INVALID_ORDER', `Order ${input.referenceId} has no items`);
+  }
+
+  const subtotal = input.items.reduce((sum, item) => sum + (item.saleAmount ?? item.amount) * item.quantity, 0);
+  const total = subtotal + (input.tax ?? 0) + (input.shipping ?? 0) - (input.discount ?? 0);
+  const parameters: WhatsAppOrderDetailsParameters = {
+    reference_id: input.referenceId,
+    type: input.goods ?? 'physical-goods',
+    ...(input.paymentConfiguration ? { payment_configuration: input.paymentConfiguration } : {}),
+    currency: input.currency ?? 'INR',
+    total_amount: amount(total),
+    order: {
+      status: 'pending',
+      items: input.items.map((item) => ({
+        retailer_id: item.retailerId,
+        name: item.name,
+        amount: amount(item.amount),
+        quantity: item.quantity,
+        ...(item.saleAmount !== undefined ? { sale_amount: amount(item.saleAmount) } : {}),
+      })),
+      subtotal: amount(subtotal),
+      tax: amount(input.tax ?? 0),
+      ...(input.shipping !== undefined ? { shipping: amount(input.shipping) } : {}),
+      ...(input.discount !== undefined ? { discount: amount(input.discount) } : {}),
+      ...(input.expiresAt !== undefined
+        ? { expiration: { timestamp: String(input.expiresAt), description: 'Order expires' } }
+        : {}),
+    },
+  };
+
+  return {
+    type: 'order_details',
+    body: { text: input.body },
+    ...(input.footer ? { footer: { text: input.footer } } : {}),
+    action: { name: 'review_and_pay', parameters },
+  };
+}
+
+// Interactive "order_status" message telling the user how an order paid for in chat is progressing
+export function buildOrderStatus(referenceId: string, status: OrderStatus, body: string, description?: string): WhatsAppInteractive {
+  return {
+    type: 'order_status',
+    body: { text: body },
+    action: {
+      name: 'review_order',
+      parameters: { reference_id: referenceId, order: { status, ...(description ? { description } : {}) } },
+    },
+  };
+}
+
+// Typed update for a statuses entry of type "payment", or null for message delivery statuses
+export function parsePaymentStatus(status: WebhookStatus): PaymentStatusUpdate | null {
+  if (status.type !== 'payment' || !status.payment) return null;
+  const { payment } = status;
+  const transaction = payment.transaction;
+  return {
+    referenceId: payment.reference_id,
+    status: status.status,
+    recipient: status.recipient_id,
+    timestamp: Number(status.timestamp ?? 0),
+    ...(payment.amount ? { amount: payment.amount } : {}),
+    ...(payment.currency ? { currency: payment.currency } : {}),
+    ...(transaction?.id ? { transactionId: transaction.id } : {}),
+    ...(transaction?.status ? { transactionStatus: transaction.status } : {}),
+    ...(transaction?.error ? { error: { code: transaction.error.code, reason: transaction.error.reason } } : {}),
+  };
+}
@@ -0,0 +1,75 @@
+// Money as the Cloud API sends it: value / offset, e.g. { value: 21000, offset: 100 } is 210.00
+export interface PaymentAmount {
+  value: number;
+  offset: number;
+}
+
+export type OrderGoodsType = 'digital-goods' | 'physical-goods';
+
+export type OrderStatus = 'pending' | 'processing' | 'partially-shipped' | 'shipped' | 'completed' | 'canceled';
+
+export interface OrderItemInput {
+  retailerId: string;                   // Your id for the item
+  name: string;
+  amount: number;                       // Unit price in minor units (paise, centavos)
+  quantity: number;
+  saleAmount?: number;                  // Discounted unit price, minor units
+}
+
+export interface OrderDetailsInput {
+  referenceId: string;                  // Unique per order; payment updates carry it back
+  body: string;
+  items: OrderItemInput[];
+  goods?: OrderGoodsType;               // Default physical-goods
+  currency?: string;                    // Default INR
+  paymentConfiguration?: string;        // Payment configuration name set up in WhatsApp Manager
+  tax?: number;                         // Minor units
+  shipping?: number;
+  discount?: number;
+  expiresAt?: number;                   // Seconds since epoch; the order can't be paid after this
+  footer?: string;
+}
+
+// action.parameters of an "order_details" message
+export interface WhatsAppOrderDetailsParameters {
+  reference_id: string;
+  type: OrderGoodsType;
+  payment_type?: string;
+  payment_configuration?: string;
+  currency: string;
+  total_amount: PaymentAmount;
+  order: {
+    status: 'pending';
+    items: Array<{
+      retailer_id: string;
+      name: string;
+      amount: PaymentAmount;
+      quantity: number;
+      sale_amount?: PaymentAmount;
+    }>;
+    subtotal: PaymentAmount;
+    tax: PaymentAmount;
+    shipping?: PaymentAmount;
+    discount?: PaymentAmount;
+    expiration?: { timestamp: string; description: string };
+  };
+}
+
+// action.parameters of an "order_status" message
+export interface WhatsAppOrderStatusParameters {
+  reference_id: string;
+  order: { status: OrderStatus; description?: string };
+}
+
+// A payment status webhook (statuses entry with type "payment")
+export interface PaymentStatusUpdate {
+  referenceId: string;                  // reference_id of the order_details message
+  status: string;                       // pending, captured, failed
+  recipient: string;
+  timestamp: number;
+  amount?: PaymentAmount;
+  currency?: string;
+  transactionId?: string;
+  transactionStatus?: string;           // success, failure, pending
+  error?: { code?: string; reason?: string };
+}
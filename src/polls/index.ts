@@ -0,0 +1,2 @@
+export { PollManager } from './poll';
+export type { PollDefinition, PollOption, PollResult, PollTally } from './poll';
@@ -0,0 +1,179 @@
+import { EventEmitter } from 'events';
+import type { WhatsAppInteractive, WhatsAppMessage } from '../types';
+import { ButtonMessageBuilder, ListMessageBuilder } from '../interactive';
+import { WhatsAppError } from '../errors';
+import { systemClock, type Clock, type ClockTimer } from '../utils/clock';
+import { canonicalChatId, isGroupJid } from '../utils/normalize';
+
+// WhatsApp limits for reply buttons and list rows
+const MAX_BUTTONS = 3;
+const MAX_LIST_ROWS = 10;
+
+// Reply ids are namespaced so votes can be told apart from menu replies and other polls
+const REPLY_PREFIX = 'poll:';
+
+export interface PollOption {
+  id: string;
+  title: string;
+  description?: string;
+}
+
+export interface PollDefinition {
+  id?: string;                          // Generated when omitted
+  question: string;
+  options: PollOption[];
+  recipients: string[];                 // Phone numbers or group JIDs
+  windowMs: number;                     // Voting window; closes early once every direct recipient voted, unless sent to a group
+  listButtonText?: string;
+}
+
+export interface PollTally {
+  id: string;
+  title: string;
+  votes: number;
+}
+
+export interface PollResult {
+  pollId: string;
+  question: string;
+  tallies: PollTally[];                 // In option order
+  votes: Record<string, string>;        // Voter -> option id; a later vote replaces an earlier one
+  recipients: string[];
+  startedAt: number;
+  closedAt: number;
+}
+
+interface OpenPoll {
+  id: string;
+  definition: PollDefinition;
+  votes: Map<string, string>;
+  direct: Set<string>;                  // Canonical ids of people the poll went to directly
+  groups: Set<string>;                  // Canonical ids of groups, where any member may vote
+  startedAt: number;
+  timer: ClockTimer;
+}
+
+export class PollManager extends EventEmitter {
+  private polls = new Map<string, OpenPoll>();
+  private sequence = 0;
+
+  constructor(private readonly clock: Clock = systemClock) {
+    super();
+  }
+
+  // Open a poll and return the prompt to send to each recipient
+  open(definition: PollDefinition): { pollId: string; messages: WhatsAppMessage[] } {
+    if (definition.options.length < 2) {
+      throw new WhatsAppError('INVALID_POLL', 'A poll needs at least two options');
+    }
+    if (definition.options.length > MAX_LIST_ROWS) {
+      throw new WhatsAppError('INVALID_POLL', `A poll has at most ${MAX_LIST_ROWS} options (got ${definition.options.length})`);
+    }
+    if (definition.options.some((o) => o.id.includes(':'))) {
+      throw new WhatsAppError('INVALID_POLL', 'Poll option ids cannot contain ":"');
+    }
+    if (definition.recipients.length === 0) {
+      throw new WhatsAppError('INVALID_POLL', 'A poll needs at least one recipient');
+    }
+
+    const pollId = definition.id ?? `${this.clock.now().toString(36)}-${++this.sequence}`;
+    if (this.polls.has(pollId)) {
+      throw new WhatsAppError('INVALID_POLL', `Poll "${pollId}" is already open`);
+    }
+
+    const content = this.render(pollId, definition);
+    const timer = this.clock.setTimeout(() => this.close(pollId), definition.windowMs);
+    timer.unref?.();
+    const chats = definition.recipients.map(canonicalChatId);
+    this.polls.set(pollId, {
+      id: pollId,
+      definition,
+      votes: new Map(),
+      direct: new Set(chats.filter((chat) => !isGroupJid(chat))),
+      groups: new Set(chats.filter(isGroupJid)),
+      startedAt: this.clock.now(),
+      timer,
+    });
+
+    return {
+      pollId,
+      messages: definition.recipients.map((to) => ({ type: 'interactive', to, content })),
+    };
+  }
+
+  // Record a vote from an interactive reply in `chat` (the voter's own chat unless given). Returns
+  // false when the reply is not for an open poll or comes from a chat the poll was not sent to.
+  vote(voter: string, replyId: string, chat = voter): boolean {
+    if (!replyId.startsWith(REPLY_PREFIX)) return false;
+
+    const separator = replyId.lastIndexOf(':');
+    const poll = this.polls.get(replyId.slice(REPLY_PREFIX.length, separator));
+    const optionId = replyId.slice(separator + 1);
+    if (!poll || !poll.definition.options.some((o) => o.id === optionId)) return false;
+
+    const from = canonicalChatId(chat);
+    const voterId = canonicalChatId(voter);
+    const allowed = isGroupJid(from) ? poll.groups.has(from) : from === voterId && poll.direct.has(from);
+    if (!allowed) return false;
+
+    poll.votes.set(voterId, optionId);
+    // Group membership is unknown, so only polls sent to people alone can tell everyone voted
+    if (poll.groups.size === 0 && [...poll.direct].every((id) => poll.votes.has(id))) {
+      this.close(poll.id);
+    }
+    return true;
+  }
+
+  // Close a poll before its window ends; returns undefined when it is not open
+  close(pollId: string): PollResult | undefined {
+    const poll = this.polls.get(pollId);
+    if (!poll) return undefined;
+
+    this.clock.clearTimeout(poll.timer);
+    this.polls.delete(pollId);
+
+    const result = this.tally(poll);
+    this.emit('result', result);
+    return result;
+  }
+
+  isOpen(pollId: string): boolean {
+    return this.polls.has(pollId);
+  }
+
+  // Close every open poll without emitting results, e.g. on shutdown
+  clear(): void {
+    for (const poll of this.polls.values()) this.clock.clearTimeout(poll.timer);
+    this.polls.clear();
+  }
+
+  private tally(poll: OpenPoll): PollResult {
+    const counts = new Map<string, number>();
+    for (const optionId of poll.votes.values()) {
+      counts.set(optionId, (counts.get(optionId) ?? 0) + 1);
+    }
+
+    return {
+      pollId: poll.id,
+      question: poll.definition.question,
+      tallies: poll.definition.options.map((o) => ({ id: o.id, title: o.title, votes: counts.get(o.id) ?? 0 })),
+      votes: Object.fromEntries(poll.votes),
+      recipients: poll.definition.recipients,
+      startedAt: poll.startedAt,
+      closedAt: this.clock.now(),
+    };
+  }
+
+  private render(pollId: string, definition: PollDefinition): WhatsAppInteractive {
+    const replyId = (option: PollOption) => `${REPLY_PREFIX}${pollId}:${option.id}`;
+    const { question, options } = definition;
+
+    if (options.length <= MAX_BUTTONS) {
+      return options.reduce((b, o) => b.button(replyId(o), o.title), new ButtonMessageBuilder(question)).build();
+    }
+    return new ListMessageBuilder(question)
+      .button(definition.listButtonText ?? 'Vote')
+      .section(undefined, (s) => options.reduce((r, o) => r.row(replyId(o), o.title, o.description), s))
+      .build();
+  }
+}
@@ -0,0 +1,49 @@
+import { logger, type ProviderResult } from '@elizaos/core';
+import type { WhatsAppProvider } from './provider';
+import type { ProductSummary } from '../catalog';
+
+// Products beyond this are left out of the prompt; the full list is still in `data`
+const MAX_LISTED = 50;
+
+function formatProduct(product: ProductSummary): string {
+  const details = [product.price, product.available ? undefined : 'out of stock'].filter(Boolean).join(', ');
+  return `- ${product.name} (retailer id ${product.retailerId}${details ? `, ${details}` : ''})`;
+}
+
+// Failures the catalog keeps returning until its TTL passes; each is logged once
+const reported = new WeakSet<object>();
+
+// Lists the business catalog so the agent can send product messages with real retailer ids
+export const catalogProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_CATALOG',
+  description: 'Products in the WhatsApp business catalog, with the ids product messages need',
+
+  get: async ({ service, accountId }): Promise<ProviderResult> => {
+    let catalog: { catalogId: string; products: ProductSummary[] };
+    try {
+      catalog = await service.listProducts(accountId);
+    } catch (error) {
+      if (typeof error === 'object' && error !== null) {
+        if (reported.has(error)) return { text: '' };
+        reported.add(error);
+      }
+      logger.warn(`[WhatsApp] Could not list catalog products: ${(error as Error).message}`);
+      return { text: '' };
+    }
+    if (catalog.products.length === 0) {
+      return { text: '' };
+    }
+
+    const listed = catalog.products.slice(0, MAX_LISTED);
+    const more = catalog.products.length - listed.length;
+    return {
+      text: [
+        `# WhatsApp catalog ${catalog.catalogId}`,
+        ...listed.map(formatProduct),
+        ...(more > 0 ? [`(${more} more products not shown)`] : []),
+      ].join('\n'),
+      values: { whatsappCatalogId: catalog.catalogId },
+      data: { whatsappProducts: catalog.products },
+    };
+  },
+};
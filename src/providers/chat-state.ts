@@ -0,0 +1,66 @@
+import { logger, type ProviderResult } from '@elizaos/core';
+import type { ChatState } from '../service';
+import type { WhatsAppProvider } from './provider';
+
+// Longest part of a message shown in the chat summary
+const SNIPPET_LENGTH = 160;
+
+function snippet(text: string): string {
+  const line = text.replace(/\s+/g, ' ').trim();
+  return line.length > SNIPPET_LENGTH ? `${line.slice(0, SNIPPET_LENGTH - 1)}…` : line;
+}
+
+function formatWindow(state: ChatState): string | undefined {
+  if (state.windowOpen === undefined) return undefined;
+  return state.windowOpen && state.windowClosesAt
+    ? `Service window: open until ${new Date(state.windowClosesAt).toISOString()}`
+    : 'Service window: closed; only approved templates reach this contact';
+}
+
+// Tells the agent what kind of chat this is, which account answers it, the contact's language,
+// whether free-form replies can still be sent, and, with WHATSAPP_CHAT_STATE_MESSAGES, the last
+// few messages
+export const chatStateProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_CURRENT_CHAT',
+  description: 'The current WhatsApp chat: user or group, answering account, language and service window',
+
+  get: async ({ runtime, service, chatId, accountId }): Promise<ProviderResult> => {
+    if (!chatId) {
+      return { text: '' };
+    }
+
+    let state: ChatState;
+    try {
+      state = await service.getChatState(chatId, undefined, accountId);
+    } catch (error) {
+      logger.warn(`[WhatsApp] Could not look up chat state: ${(error as Error).message}`);
+      return { text: '' };
+    }
+
+    const lines = [
+      `Chat: ${state.chatId} (${state.kind})`,
+      ...(state.accountId ? [`Answering account: ${state.accountId}`] : []),
+      ...(state.language ? [`Contact language: ${state.language.language} (${state.language.source})`] : []),
+    ];
+    const serviceWindow = formatWindow(state);
+    if (serviceWindow) lines.push(serviceWindow);
+    if (state.recent.length > 0) {
+      lines.push('Last messages:');
+      for (const m of state.recent) {
+        const who = m.direction === 'inbound' ? m.sender : runtime.character.name;
+        lines.push(`- ${who}: ${snippet(m.text)}`);
+      }
+    }
+
+    return {
+      text: `# WhatsApp chat\n${lines.join('\n')}`,
+      values: {
+        whatsappChatId: state.chatId,
+        whatsappChatKind: state.kind,
+        whatsappLanguage: state.language?.language ?? null,
+        whatsappWindowOpen: state.windowOpen ?? null,
+      },
+      data: { whatsappChatState: state },
+    };
+  },
+};
@@ -0,0 +1,26 @@
+import type { ProviderResult } from '@elizaos/core';
+import type { WhatsAppProvider } from './provider';
+
+// Renders the rolling summary of a long chat's older turns, which the recent-messages provider
+// then leaves out, so the agent's context stays bounded however long the conversation runs
+export const conversationSummaryProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_CONVERSATION_SUMMARY',
+  description: 'Summary of earlier WhatsApp messages with the current chat',
+
+  get: async ({ service, chatId, accountId }): Promise<ProviderResult> => {
+    if (!chatId) {
+      return { text: '' };
+    }
+
+    const { summary } = await service.getConversationContext(chatId, accountId);
+    if (!summary) {
+      return { text: '' };
+    }
+
+    return {
+      text: `# Earlier in this WhatsApp conversation\n${summary.text}`,
+      values: { whatsappSummarizedTurns: summary.turns },
+      data: { whatsappConversationSummary: summary },
+    };
+  },
+};
@@ -0,0 +1,39 @@
+import type { Provider } from '@elizaos/core';
+import { catalogProvider as catalog } from './catalog';
+import { chatStateProvider as chatState } from './chat-state';
+import { conversationSummaryProvider as conversationSummary } from './conversation-summary';
+import { rateLimitStatusProvider as rateLimitStatus } from './rate-limit';
+import { recentMessagesProvider as recentMessages } from './recent-messages';
+import { templatesProvider as templates } from './templates';
+import { toRuntimeProvider, type WhatsAppProvider } from './provider';
+
+export { providerContext, toRuntimeProvider } from './provider';
+export type { WhatsAppProvider, WhatsAppProviderContext } from './provider';
+
+// The providers as WhatsAppProviders, to call with a context of your own
+export const whatsappContextProviders: WhatsAppProvider[] = [
+  chatState,
+  conversationSummary,
+  recentMessages,
+  templates,
+  catalog,
+  rateLimitStatus,
+];
+
+// The same providers as the runtime calls them; exported names keep the runtime Provider shape
+export const chatStateProvider: Provider = toRuntimeProvider(chatState);
+export const conversationSummaryProvider: Provider = toRuntimeProvider(conversationSummary);
+export const recentMessagesProvider: Provider = toRuntimeProvider(recentMessages);
+export const templatesProvider: Provider = toRuntimeProvider(templates);
+export const catalogProvider: Provider = toRuntimeProvider(catalog);
+export const rateLimitStatusProvider: Provider = toRuntimeProvider(rateLimitStatus);
+
+// Everything the plugin registers with the runtime
+export const whatsappProviders: Provider[] = [
+  chatStateProvider,
+  conversationSummaryProvider,
+  recentMessagesProvider,
+  templatesProvider,
+  catalogProvider,
+  rateLimitStatusProvider,
+];
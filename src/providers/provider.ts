@@ -0,0 +1,52 @@
+import type { IAgentRuntime, Memory, Provider, ProviderResult, State } from '@elizaos/core';
+import { WhatsAppConnectorService } from '../service';
+
+// What a WhatsApp provider is given: the runtime call, plus the running service and the chat and
+// account the message came through
+export interface WhatsAppProviderContext {
+  runtime: IAgentRuntime;
+  message: Memory;
+  state: State;
+  service: WhatsAppConnectorService;
+  chatId?: string;                      // The chat the message belongs to, when known
+  accountId?: string;                   // The account the message arrived on
+}
+
+// A source of agent context from WhatsApp. Providers only see WhatsApp messages while the
+// connector is running, and return an empty text when they have nothing to add.
+export interface WhatsAppProvider {
+  name: string;
+  description: string;
+  get(context: WhatsAppProviderContext): Promise<ProviderResult>;
+}
+
+// The context for a runtime provider call; undefined when the message isn't from WhatsApp or the
+// connector isn't running
+export function providerContext(
+  runtime: IAgentRuntime,
+  message: Memory,
+  state: State
+): WhatsAppProviderContext | undefined {
+  const service = runtime.getService<WhatsAppConnectorService>(WhatsAppConnectorService.serviceType);
+  if (message.content?.source !== 'whatsapp' || !service) return undefined;
+  return {
+    runtime,
+    message,
+    state,
+    service,
+    chatId: message.content.channelId as string | undefined,
+    accountId: message.content.whatsappAccount as string | undefined,
+  };
+}
+
+// `provider` as the runtime registers it
+export function toRuntimeProvider(provider: WhatsAppProvider): Provider {
+  return {
+    name: provider.name,
+    description: provider.description,
+    get: async (runtime: IAgentRuntime, message: Memory, state: State): Promise<ProviderResult> => {
+      const context = providerContext(runtime, message, state);
+      return context ? provider.get(context) : { text: '' };
+    },
+  };
+}
@@ -0,0 +1,45 @@
+import type { ProviderResult } from '@elizaos/core';
+import type { WhatsAppProvider } from './provider';
+
+// Tells the agent how many replies it has left for this sender, so it can batch when close to the limit
+export const rateLimitStatusProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_RATE_LIMIT_STATUS',
+  description: 'Remaining WhatsApp reply allowance for the current sender and queued outbound messages',
+
+  get: async ({ message, service, chatId, accountId }): Promise<ProviderResult> => {
+    const sender = (message.content.whatsappSender as string | undefined) ?? chatId;
+    if (!chatId || !sender) {
+      return { text: '' };
+    }
+
+    const status = service.getRateLimitStatus(sender, chatId, accountId);
+    const lines: string[] = [];
+    if (status.sender) {
+      const resetIn = Math.ceil(status.sender.resetInMs / 1000);
+      lines.push(
+        status.sender.cooldown
+          ? `Reply limit reached for this sender; replies resume in ${resetIn}s.`
+          : `${status.sender.remaining} of ${status.sender.limit} replies left for this sender` +
+              (resetIn > 0 ? ` (window resets in ${resetIn}s).` : '.')
+      );
+      if (!status.sender.cooldown && status.sender.remaining <= 1) {
+        lines.push('Combine everything into a single message.');
+      }
+    }
+    if (status.pendingSends > 0) {
+      lines.push(`${status.pendingSends} outbound message(s) are still being sent.`);
+    }
+    if (lines.length === 0) {
+      return { text: '', data: { whatsappRateLimit: status } };
+    }
+
+    return {
+      text: `# WhatsApp rate limit\n${lines.join('\n')}`,
+      values: {
+        whatsappRepliesRemaining: status.sender?.remaining ?? null,
+        whatsappPendingSends: status.pendingSends,
+      },
+      data: { whatsappRateLimit: status },
+    };
+  },
+};
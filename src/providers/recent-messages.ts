@@ -0,0 +1,36 @@
+import type { ProviderResult } from '@elizaos/core';
+import type { WhatsAppProvider } from './provider';
+import { formatReactions } from '../reactions/tally';
+
+// Renders the last N WhatsApp turns with the current chat (those after its summary, when it has
+// one) as context for the agent, with the reactions users left on the agent's messages
+export const recentMessagesProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_RECENT_MESSAGES',
+  description: 'Recent WhatsApp messages exchanged with the current chat',
+
+  get: async ({ runtime, service, chatId, accountId }): Promise<ProviderResult> => {
+    if (!chatId) {
+      return { text: '' };
+    }
+
+    // Turns already folded into the conversation summary are left to that provider
+    const { recent: history } = await service.getConversationContext(chatId, accountId);
+    if (history.length === 0) {
+      return { text: '' };
+    }
+
+    const lines = history.map((m) => {
+      const who =
+        m.direction === 'inbound' ? m.sender : m.sender === 'business' ? 'Business (WhatsApp app)' : runtime.character.name;
+      const reactions = m.direction === 'outbound' ? service.getReactions(m.id, accountId) : undefined;
+      const suffix = reactions?.total ? ` (reactions: ${formatReactions(reactions)})` : '';
+      return `[${new Date(m.timestamp).toISOString()}] ${who}: ${m.text}${suffix}`;
+    });
+
+    return {
+      text: `# Recent WhatsApp conversation\n${lines.join('\n')}`,
+      values: { whatsappRecentMessageCount: history.length },
+      data: { whatsappRecentMessages: history },
+    };
+  },
+};
@@ -0,0 +1,38 @@
+import { logger, type ProviderResult } from '@elizaos/core';
+import type { WhatsAppProvider } from './provider';
+import type { TemplateSummary } from '../templates';
+
+function formatTemplate(template: TemplateSummary): string {
+  const details = [template.language, template.category, template.quality && `quality ${template.quality}`]
+    .filter(Boolean)
+    .join(', ');
+  const variables =
+    template.variables.length > 0 ? ` variables: ${template.variables.map((v) => `{{${v}}}`).join(' ')}` : '';
+  const body = template.body ? ` "${template.body}"` : '';
+  return `- ${template.name} (${details})${variables}${body}`;
+}
+
+// Lists the account's approved templates, which are the only way to start a conversation outside the 24h window
+export const templatesProvider: WhatsAppProvider = {
+  name: 'WHATSAPP_TEMPLATES',
+  description: 'Approved WhatsApp message templates the agent can send to start a conversation',
+
+  get: async ({ service, accountId }): Promise<ProviderResult> => {
+    let templates: TemplateSummary[];
+    try {
+      templates = await service.listApprovedTemplates(accountId);
+    } catch (error) {
+      logger.warn(`[WhatsApp] Could not list message templates: ${(error as Error).message}`);
+      return { text: '' };
+    }
+    if (templates.length === 0) {
+      return { text: '' };
+    }
+
+    return {
+      text: `# Approved WhatsApp templates\n${templates.map(formatTemplate).join('\n')}`,
+      values: { whatsappTemplateNames: templates.map((t) => t.name).join(', ') },
+      data: { whatsappTemplates: templates },
+    };
+  },
+};
@@ -0,0 +1,4 @@
+export { ReactionWorkflows } from './workflows';
+export type { ReactionContext, ReactionHandler } from './workflows';
+export { ReactionTally, formatReactions } from './tally';
+export type { MessageReactions } from './tally';
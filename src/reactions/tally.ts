@@ -0,0 +1,52 @@
+import { LruCache, type LruOptions } from '../utils/lru';
+
+export interface MessageReactions {
+  messageId: string;
+  total: number;
+  counts: Record<string, number>;       // By emoji
+  reactors: Record<string, string>;     // Each reactor's current emoji
+}
+
+const DEFAULT_MAX_MESSAGES = 10_000;
+
+// "👍 2, ❤️ 1", most used first
+export function formatReactions(reactions: MessageReactions): string {
+  return Object.entries(reactions.counts)
+    .sort((a, b) => b[1] - a[1])
+    .map(([emoji, count]) => `${emoji} ${count}`)
+    .join(', ');
+}
+
+/**
+ * Current reactions on the agent's messages. WhatsApp allows one reaction per person per message,
+ * so a new emoji replaces the reactor's previous one and an empty emoji removes it. Only the most
+ * recently reacted-to messages are kept.
+ */
+export class ReactionTally {
+  private messages: LruCache<Map<string, string>>;
+
+  constructor(options: LruOptions = {}) {
+    this.messages = new LruCache({ maxChats: DEFAULT_MAX_MESSAGES, ...options });
+  }
+
+  record(messageId: string, reactor: string, emoji: string): MessageReactions {
+    const reactors = this.messages.get(messageId) ?? new Map<string, string>();
+    if (emoji) reactors.set(reactor, emoji);
+    else reactors.delete(reactor);
+    if (reactors.size > 0) this.messages.set(messageId, reactors);
+    else this.messages.delete(messageId);
+    return this.summarize(messageId, reactors);
+  }
+
+  get(messageId: string): MessageReactions {
+    return this.summarize(messageId, this.messages.get(messageId) ?? new Map());
+  }
+
+  private summarize(messageId: string, reactors: Map<string, string>): MessageReactions {
+    const counts: Record<string, number> = {};
+    for (const emoji of reactors.values()) {
+      counts[emoji] = (counts[emoji] ?? 0) + 1;
+    }
+    return { messageId, total: reactors.size, counts, reactors: Object.fromEntries(reactors) };
+  }
+}
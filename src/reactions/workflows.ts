@@ -0,0 +1,54 @@
+import type { ArchivedMessage } from '../archive';
+import { emojiForShortcode } from '../utils/emoji';
+
+export interface ReactionContext {
+  emoji: string;
+  reactor: string;                      // Sender of the reaction (participant in groups)
+  chatId: string;
+  accountId: string;
+  message: ArchivedMessage;             // The agent's message that was reacted to
+}
+
+export type ReactionHandler = (context: ReactionContext) => void | Promise<void>;
+
+// Emoji presentation selector; "❤️" and "❤" should trigger the same workflow
+const VARIATION_SELECTOR = /\uFE0F/g;
+
+// Handlers may be registered by shortcode too: "thumbsup" or ":thumbsup:" for 👍
+function normalizeEmoji(emoji: string): string {
+  const trimmed = emoji.trim();
+  return (emojiForShortcode(trimmed) ?? trimmed).replace(VARIATION_SELECTOR, '');
+}
+
+// Handlers keyed on reaction emoji, run when users react to the agent's own messages
+export class ReactionWorkflows {
+  private handlers = new Map<string, ReactionHandler[]>();
+
+  // Register a handler for one or more emoji. Returns a function that removes it again.
+  on(emoji: string | string[], handler: ReactionHandler): () => void {
+    const keys = (Array.isArray(emoji) ? emoji : [emoji]).map(normalizeEmoji);
+    for (const key of keys) {
+      this.handlers.set(key, [...(this.handlers.get(key) ?? []), handler]);
+    }
+    return () => {
+      for (const key of keys) {
+        const remaining = (this.handlers.get(key) ?? []).filter((h) => h !== handler);
+        if (remaining.length > 0) this.handlers.set(key, remaining);
+        else this.handlers.delete(key);
+      }
+    };
+  }
+
+  has(emoji: string): boolean {
+    return this.handlers.has(normalizeEmoji(emoji));
+  }
+
+  // Run every handler registered for the emoji; returns how many ran
+  async dispatch(context: ReactionContext): Promise<number> {
+    const handlers = this.handlers.get(normalizeEmoji(context.emoji)) ?? [];
+    for (const handler of handlers) {
+      await handler(context);
+    }
+    return handlers.length;
+  }
+}
@@ -1,4 +1,5 @@
 import {
+  type Action,
   type IAgentRuntime,
   type Memory,
   type Content,
@@ -7,19 +8,229 @@ import {
   EventType,
   ChannelType,
   logger,
-  stringToUuid,
 } from "@elizaos/core";
-import { WhatsAppPlugin } from "./index";
-import type { WhatsAppConfig, UnifiedMessage } from "./types";
+import { WhatsAppPlugin, type SendOptions } from "./index";
+import type { InboundMessageRef } from "./clients/interface";
+import type {
+  ConnectionStatus,
+  WhatsAppGroupConfig,
+  WhatsAppInteractive,
+  WhatsAppLocation,
+  WhatsAppMedia,
+  WhatsAppMessage,
+  WhatsAppTemplate,
+  WhatsAppWebhookEvent,
+  UnifiedMessage,
+} from "./types";
+import { checkGroupMessage, resolveWhatsAppGroupConfig } from "./utils/group-config";
+import { canonicalChatId, isGroupJid, normalizeWhatsAppTarget, type WhatsAppTargetKind } from "./utils/normalize";
+import { chunkText, chunkTextWithMarkers } from "./utils/chunk";
+import { systemClock, type Clock } from "./utils/clock";
+import { LruCache } from "./utils/lru";
+import { normalizeReaction } from "./utils/emoji";
+import { WhatsAppError } from "./errors";
+import { maskPhone, redactText } from "./utils/redact";
+import { filterActions } from "./utils/skills";
+import {
+  DEFAULT_SLOW_DOWN_NOTICE,
+  expandEmojiShortcodes,
+  RuntimeModelTranslator,
+  SenderRateLimiter,
+  SpamFilter,
+  type SpamFilterConfig,
+  type TranslationOptions,
+  type SpamFilterMetrics,
+  type SpamReason,
+  type RateLimitState,
+} from "./middleware";
+import {
+  RuntimeContactLanguageStore,
+  RuntimeContactTagStore,
+  type AudienceSelector,
+  type ContactLanguage,
+} from "./contacts";
+import {
+  ConversationSummaries,
+  RuntimeMessageArchive,
+  RuntimeSummaryStore,
+  type ArchivedMessage,
+  type ConversationContext,
+  type Summarizer,
+  type SummaryOptions,
+  type SummaryStore,
+} from "./archive";
+import type { Geocoder } from "./location";
+import type { WhatsAppMetrics } from "./metrics";
+import type { TracerLike } from "./tracing";
+import type { HttpTransport } from "./clients/transport";
+import {
+  distributeBroadcast,
+  isDeferred,
+  planCampaign,
+  runCampaign,
+  type BroadcastOptions,
+  type BroadcastResult,
+  type BroadcastSender,
+  type CampaignOptions,
+  type CampaignPlan,
+  type ScheduledCampaign,
+  type CircuitState,
+  type FailedSend,
+  type MessageHandle,
+  type SentMessage,
+  messageHandle,
+  RuntimeDeadLetterStore,
+} from "./outbox";
+import {
+  AdminAlertSink,
+  attachSink,
+  runtimeEventType,
+  WebhookForwarder,
+  type AdminAlertConfig,
+  type WhatsAppEventOf,
+} from "./events";
+import type { MessageReactions } from "./reactions";
+import { RuntimeMenuSessionStore, type MenuDefinition, type MenuResult } from "./menus";
+import { CannedResponses, RuntimeCannedResponseStore, type CannedResponse } from "./canned";
+import { buildAuthenticationTemplate, type TemplatePace, type TemplateSummary } from "./templates";
+import type { ProductSummary } from "./catalog";
+import { ConversationCostTracker, type ConversationCostReport, type ConversationPricing, type CostRange } from "./billing";
+import { OtpManager, RuntimeOtpStore, type OtpConfig, type OtpStore, type OtpVerification } from "./otp";
+import { readStateSnapshot, STATE_SNAPSHOT_VERSION, type StateSnapshot } from "./state";
+import { RuntimeWebhookPayloadStore, WebhookLog, type StoredWebhook, type WebhookLogConfig } from "./http/webhook-log";
+import { ButtonMessageBuilder, ListMessageBuilder, RuntimePromptStore, type ButtonCallback } from "./interactive";
+import {
+  DEFAULT_ACCOUNT_ID,
+  listEnabledWhatsAppAccounts,
+  resolveWhatsAppAccount,
+  StickyRoutes,
+  validateDeployment,
+  type DeploymentIssue,
+  type DeploymentReport,
+  type HealthCheckConfig,
+  type ResolvedWhatsAppAccount,
+} from "./accounts";
+import { getJsonSetting, getSetting } from "./settings";
+import {
+  streamText,
+  toMemory,
+  toOutboundMessages,
+  whatsappWorldId,
+  type StreamOptions,
+} from "./bridge";
 
 const SOURCE = "whatsapp";
 
-/** Read a setting from runtime (character settings) with fallback to process.env */
-function getSetting(runtime: IAgentRuntime, key: string): string | null {
-  const v = runtime.getSetting(key);
-  if (v !== null && v !== undefined) return String(v);
-  const e = process.env[key];
-  return e !== undefined ? e : null;
+/** Account summary reported to operators */
+export interface AccountStatus {
+  accountId: string;
+  enabled: boolean;
+  connection: ConnectionStatus;
+  failedSends: number;
+  circuit: CircuitState | "disabled";
+}
+
+/** Throttle state reported to the agent; `sender` is absent when no rate limit applies */
+export interface RateLimitStatus {
+  pendingSends: number;
+  sender?: RateLimitState;
+}
+
+/** WHATSAPP_OTP: the authentication template used to deliver codes, plus code policy */
+export interface OtpSettings extends OtpConfig {
+  template: string;
+  language?: string;
+}
+
+/** What the connector knows about one chat, for the agent's context */
+export interface ChatState {
+  chatId: string;
+  kind: WhatsAppTargetKind;
+  accountId?: string;      // Account that answers the chat; absent when none is running
+  language?: ContactLanguage;
+  windowOpen?: boolean;    // The contact wrote within 24 hours, so free-form messages reach them; Cloud API user chats only
+  windowClosesAt?: number; // When the open window closes, in milliseconds since epoch
+  recent: ArchivedMessage[]; // The last messages, oldest first; empty unless asked for
+}
+
+/** A failed send together with the account it was attempted from */
+export type AccountFailedSend = FailedSend & { accountId: string };
+
+/** A running account: its connection, resolved settings, and whether it is paused */
+interface ConnectedAccount {
+  plugin: WhatsAppPlugin;
+  config: ResolvedWhatsAppAccount;
+  enabled: boolean;
+}
+
+/** Group settings resolved for the chat a message arrived in */
+type GroupContext = WhatsAppGroupConfig & { id: string };
+
+/** Gated copies this service put in a runtime's action list, so they aren't wrapped twice */
+const gatedActions = new WeakSet<Action>();
+
+/** Render a completed menu as text the agent can read alongside the structured result */
+function formatMenuResult(result: MenuResult): string {
+  const lines = Object.entries(result.answers).map(([field, answer]) => `${field}: ${answer.title}`);
+  return [`Completed menu "${result.menuId}"`, ...lines].join("\n");
+}
+
+/** Archived turns read when building a chat's context; the archive keeps 200 per chat by default */
+const SUMMARY_LOOKBACK = 200;
+
+/** The customer service window: free-form messages are allowed this long after the contact's last message */
+const SERVICE_WINDOW_MS = 24 * 60 * 60 * 1000;
+
+/** Idempotency keys remembered, and for how long */
+const IDEMPOTENCY_KEY_LIMIT = 10_000;
+const IDEMPOTENCY_KEY_TTL_MS = 24 * 60 * 60 * 1000;
+
+/** Cloud API rejects media captions longer than this */
+const MAX_CAPTION_LENGTH = 1024;
+
+/** A reply button or list row offered by sendButtons / sendList */
+export interface ChoiceOption {
+  id: string;
+  title: string;
+  description?: string;   // List rows only
+}
+
+/** Per-send options, plus the account to send from (routing picks one otherwise) */
+export interface ServiceSendOptions extends SendOptions {
+  accountId?: string;
+}
+
+/** The `send*` helpers take an account id, as they always have, or full send options */
+function sendOptions(options: string | ServiceSendOptions = {}): ServiceSendOptions {
+  return typeof options === "string" ? { accountId: options } : options;
+}
+
+/** Options for the `index`th of several messages sent as one: only the first quotes, and keys stay unique */
+function partOptions(options: SendOptions, index: number): SendOptions {
+  if (index === 0) return options;
+  const key = options.idempotencyKey;
+  return { ...options, replyTo: undefined, idempotencyKey: key ? `${key}:${index}` : undefined };
+}
+
+/** Canonical chat id for a phone number or JID; rejects anything that is neither */
+function recipient(to: string): string {
+  if (!normalizeWhatsAppTarget(to)) {
+    throw new WhatsAppError("INVALID_RECIPIENT", `"${to}" is not a phone number or WhatsApp JID`);
+  }
+  return canonicalChatId(to);
+}
+
+/** WHATSAPP_TRANSLATION is "true" for the defaults or JSON TranslationOptions; unset or "false" is off */
+function translationSetting(runtime: IAgentRuntime): TranslationOptions | undefined {
+  const raw = getSetting(runtime, "WHATSAPP_TRANSLATION");
+  if (!raw || raw === "false") return undefined;
+  if (raw === "true") return {};
+  return getJsonSetting<TranslationOptions>(runtime, "WHATSAPP_TRANSLATION") ?? undefined;
+}
+
+/** Rate limit windows are per account, and per group for group members */
+function rateLimitKey(account: ConnectedAccount, sender: string, groupId?: string): string {
+  return `${account.config.accountId}:${groupId ? `${groupId}:` : ""}${sender}`;
 }
 
 export class WhatsAppConnectorService extends Service {
@@ -27,7 +238,48 @@ export class WhatsAppConnectorService extends Service {
   capabilityDescription =
     "Connects the agent to WhatsApp using Baileys (QR code) or Cloud API";
 
-  private plugin: WhatsAppPlugin | null = null;
+  private accounts = new Map<string, ConnectedAccount>();
+  private spamFilter: SpamFilter | null = null;
+  private rateLimiter = new SenderRateLimiter();
+  private routes = new StickyRoutes();
+  private stickyRouting = true;
+  private defaultAccountId = DEFAULT_ACCOUNT_ID;
+
+  /** Named replies shared by every account */
+  readonly cannedResponses = new CannedResponses();
+
+  private otpSettings?: OtpSettings;
+  /** Codes by the account that sent them, so a code only verifies on the number it came from */
+  private otps = new Map<string, OtpManager>();
+  private otpStore = (_accountId: string): OtpStore | undefined => undefined;
+
+  /** Raw webhook notifications kept for replay, when WHATSAPP_WEBHOOK_LOG is set */
+  private webhookLog?: WebhookLog;
+
+  /** Set once a notification for an account with an app secret arrived without its raw body */
+  private missingRawBody = false;
+
+  /** Charges reported on Cloud API status updates, priced with WHATSAPP_PRICING */
+  private costs = new ConversationCostTracker();
+
+  /** Sends by idempotency key, for every account */
+  private idempotent?: LruCache<Promise<SentMessage>>;
+
+  /** Rolling summaries of long chats by account, once a summarizer is supplied */
+  private summaries = new Map<string, ConversationSummaries>();
+  private summaryOptions: SummaryOptions = {};
+  private summarizer?: Summarizer;
+  /** Where an account's summaries are kept; in memory until initialize picks the runtime cache */
+  private summaryStore = (_accountId: string): SummaryStore | undefined => undefined;
+
+  /** Archive reads in progress by account and chat, so the providers of one turn share a single read */
+  private historyReads = new Map<string, Promise<ArchivedMessage[]>>();
+
+  /** Messages getChatState includes by default, from WHATSAPP_CHAT_STATE_MESSAGES */
+  private chatStateMessages = 0;
+
+  /** Where connector-wide gauges go, once useMetrics is called */
+  private metrics?: WhatsAppMetrics;
 
   static async start(
     runtime: IAgentRuntime
@@ -38,42 +290,829 @@ export class WhatsAppConnectorService extends Service {
   }
 
   async stop(): Promise<void> {
-    if (this.plugin) {
-      await this.plugin.stop();
-      this.plugin = null;
-      logger.info("[WhatsApp] Disconnected");
+    for (const [accountId, account] of this.accounts) {
+      await account.plugin.stop();
+      logger.info(`[WhatsApp] Disconnected account ${accountId}`);
     }
+    this.accounts.clear();
+    this.reportAccounts();
+    this.reportQueueDepth();
   }
 
-  private resolveConfig(): WhatsAppConfig | null {
-    const runtime = this.runtime;
+  /** Add responses from WHATSAPP_CANNED_RESPONSES that are not stored yet, keeping runtime edits */
+  private async seedCannedResponses(): Promise<void> {
+    const configured = getJsonSetting<Record<string, CannedResponse>>(this.runtime, "WHATSAPP_CANNED_RESPONSES") ?? {};
+    for (const [name, response] of Object.entries(configured)) {
+      if (await this.cannedResponses.get(name)) continue;
+      try {
+        await this.cannedResponses.set(name, response);
+      } catch (err) {
+        logger.warn(`[WhatsApp] Ignoring canned response "${name}": ${(err as Error).message}`);
+      }
+    }
+  }
+
+  private resolveMenus(): MenuDefinition[] {
+    return getJsonSetting<MenuDefinition[]>(this.runtime, "WHATSAPP_MENUS") ?? [];
+  }
+
+  /** Whether an action may run for a message, given the account and group skill lists */
+  isActionAllowed(action: Action, message: Memory): boolean {
+    if (message.content?.source !== SOURCE) return true;
+    const accountId = message.content.whatsappAccount as string | undefined;
+    const account = (accountId && this.accounts.get(accountId)) || this.firstAccount();
+    const group = message.content.whatsappGroup as { skills?: string[] } | undefined;
+    return filterActions([action], account?.config, group).length > 0;
+  }
 
-    // Baileys (QR code) preferred when authDir is set
-    const authDir = getSetting(runtime, "WHATSAPP_AUTH_DIR");
-    if (authDir) {
-      return { authDir, printQRInTerminal: true };
-    }
-
-    // Cloud API
-    const accessToken = getSetting(runtime, "WHATSAPP_ACCESS_TOKEN");
-    const phoneNumberId = getSetting(runtime, "WHATSAPP_PHONE_NUMBER_ID");
-    if (accessToken && phoneNumberId) {
-      return {
-        accessToken,
-        phoneNumberId,
-        webhookVerifyToken: getSetting(runtime, "WHATSAPP_WEBHOOK_VERIFY_TOKEN") ?? undefined,
-        businessAccountId: getSetting(runtime, "WHATSAPP_BUSINESS_ID") ?? undefined,
-        apiVersion: getSetting(runtime, "WHATSAPP_API_VERSION") ?? undefined,
+  /**
+   * Swap the runtime's actions for gated copies so the planner only sees skills enabled for the
+   * conversation. Action objects are shared by every agent that loads their plugin, so they are
+   * copied rather than changed.
+   */
+  private gateRuntimeActions(): void {
+    const actions = this.runtime.actions;
+    actions.forEach((action, i) => {
+      if (gatedActions.has(action)) return;
+      const gated: Action = {
+        ...action,
+        validate: async (rt, message, ...rest) => {
+          const service = rt.getService<WhatsAppConnectorService>(WhatsAppConnectorService.serviceType);
+          if (service && !service.isActionAllowed(action, message)) return false;
+          return action.validate(rt, message, ...rest);
+        },
       };
+      gatedActions.add(gated);
+      actions[i] = gated;
+    });
+  }
+
+  /** The accounts' clock, for scheduling work that spans several of them */
+  private get clock(): Clock {
+    return this.firstAccount()?.plugin.clock ?? systemClock;
+  }
+
+  /** WHATSAPP_DEFAULT_ACCOUNT, else the `default` account when configured, otherwise the first one started */
+  private firstAccount(): ConnectedAccount | undefined {
+    return (
+      this.accounts.get(this.defaultAccountId) ??
+      this.accounts.get(DEFAULT_ACCOUNT_ID) ??
+      this.accounts.values().next().value
+    );
+  }
+
+  /** Account sends go out from when neither the caller nor sticky routing picks one */
+  getDefaultAccount(): string | undefined {
+    return this.firstAccount()?.config.accountId;
+  }
+
+  private requireAccount(accountId?: string): ConnectedAccount {
+    const account = accountId ? this.accounts.get(accountId) : this.firstAccount();
+    if (!account) {
+      throw new WhatsAppError("UNKNOWN_ACCOUNT", this.missingAccount(accountId));
     }
+    return account;
+  }
+
+  /** Why `accountId` has no running connection, told apart using the configured accounts */
+  private missingAccount(accountId?: string): string {
+    if (!accountId) return "WhatsApp connector is not running";
+    const configured = resolveWhatsAppAccount(this.runtime, accountId);
+    if (!configured) return `Unknown WhatsApp account ${accountId}`;
+    return configured.enabled
+      ? `WhatsApp account ${accountId} is not running`
+      : `WhatsApp account ${accountId} is disabled in its settings`;
+  }
+
+  private requirePlugin(accountId?: string): WhatsAppPlugin {
+    return this.requireAccount(accountId).plugin;
+  }
 
-    return null;
+  /** Account to send to a chat from: `accountId` when given, else the one the chat last used, else the default */
+  private routeAccount(to: string, accountId?: string): ConnectedAccount {
+    return (!accountId && this.stickyAccount(to)) || this.requireAccount(accountId);
+  }
+
+  /** The enabled account a chat last exchanged messages with, when sticky routing is on */
+  private stickyAccount(to: string): ConnectedAccount | undefined {
+    if (!this.stickyRouting) return undefined;
+    const lastUsed = this.routes.get(to);
+    const account = lastUsed ? this.accounts.get(lastUsed) : undefined;
+    return account?.enabled ? account : undefined;
+  }
+
+  private routePlugin(to: string, accountId?: string): WhatsAppPlugin {
+    return this.routeAccount(to, accountId).plugin;
+  }
+
+  /** Account a chat will be answered from unless a send names another one */
+  getAccountFor(to: string): string {
+    return this.routeAccount(to).config.accountId;
+  }
+
+  /** Send the first prompt of a registered menu to a user */
+  async startMenu(to: string, menuId: string, accountId?: string): Promise<void> {
+    await this.routePlugin(to, accountId).startMenu(to, menuId);
+  }
+
+  /**
+   * Send one message with per-send options: the account, a message to quote, link previews, an
+   * idempotency key, skipping the circuit breaker queue, or a template to send instead once the
+   * recipient's 24-hour window has closed. The `send*` helpers below build the message for you.
+   * Resolves to a MessageHandle with the message ids and waits on delivery and read receipts.
+   */
+  async sendMessage(message: WhatsAppMessage, options: ServiceSendOptions = {}): Promise<MessageHandle> {
+    const { accountId, ...send } = options;
+    const to = recipient(message.to);
+    const plugin = this.routePlugin(to, accountId);
+    return messageHandle(plugin.accountId, [await this.sendOnce(plugin, { ...message, to }, send)]);
+  }
+
+  /** Send text to a user or group, split into several messages when it is over the length limit */
+  async sendText(to: string, text: string, options?: string | ServiceSendOptions): Promise<MessageHandle> {
+    const chat = recipient(to);
+    const { plugin, config } = this.routeAccount(chat, sendOptions(options).accountId);
+    const limit = plugin.textLimit(chat);
+    return this.sendAll(
+      chunkTextWithMarkers(text, limit, config.chunkMarkers).map((chunk): WhatsAppMessage => ({ type: "text", to: chat, content: chunk })),
+      options
+    );
+  }
+
+  /** Send an image, video, audio file or document; caption overflow (and audio captions) follow as text */
+  async sendMedia(to: string, media: WhatsAppMedia, options?: string | ServiceSendOptions): Promise<MessageHandle> {
+    const chat = recipient(to);
+    const { caption, ...file } = media;
+    const text = caption?.trim() ?? "";
+    const shown = media.kind === "audio" ? undefined : chunkText(text, MAX_CAPTION_LENGTH)[0];
+    const overflow = text.slice(shown?.length ?? 0).trim();
+    const { plugin, config } = this.routeAccount(chat, sendOptions(options).accountId);
+    const limit = plugin.textLimit(chat);
+    return this.sendAll(
+      [
+        { type: "media", to: chat, content: shown ? { ...file, caption: shown } : file },
+        ...chunkTextWithMarkers(overflow, limit, config.chunkMarkers).map((chunk): WhatsAppMessage => ({ type: "text", to: chat, content: chunk })),
+      ],
+      options
+    );
+  }
+
+  /** Send up to three reply buttons; replies reach the agent like any other message */
+  async sendButtons(
+    to: string,
+    body: string,
+    options: ChoiceOption[],
+    send?: string | ServiceSendOptions
+  ): Promise<MessageHandle> {
+    const content = options.reduce((b, o) => b.button(o.id, o.title), new ButtonMessageBuilder(body)).build();
+    return this.sendMessage({ type: "interactive", to, content }, sendOptions(send));
+  }
+
+  /** Send a single-section list of up to ten rows behind a button labelled `buttonText` */
+  async sendList(
+    to: string,
+    body: string,
+    buttonText: string,
+    options: ChoiceOption[],
+    send?: string | ServiceSendOptions
+  ): Promise<MessageHandle> {
+    const content = new ListMessageBuilder(body)
+      .button(buttonText)
+      .section(undefined, (s) => options.reduce((r, o) => r.row(o.id, o.title, o.description), s))
+      .build();
+    return this.sendMessage({ type: "interactive", to, content }, sendOptions(send));
+  }
+
+  /** React to a received message with an emoji or shortcode (":thumbsup:"); an empty emoji removes the reaction */
+  async sendReaction(
+    to: string,
+    messageId: string,
+    emoji: string,
+    options?: string | ServiceSendOptions
+  ): Promise<MessageHandle> {
+    return this.sendMessage(
+      { type: "reaction", to, content: { messageId, emoji: normalizeReaction(emoji) } },
+      sendOptions(options)
+    );
+  }
+
+  /** Share a location pin with a user or group */
+  async sendLocation(
+    to: string,
+    location: WhatsAppLocation,
+    options?: string | ServiceSendOptions
+  ): Promise<MessageHandle> {
+    return this.sendMessage({ type: "location", to, content: location }, sendOptions(options));
+  }
+
+  /**
+   * Send messages to one chat in order from one account, stopping at the first failure. When the
+   * first is replaced by its template fallback, the rest are dropped: the template stands in for them all.
+   */
+  private async sendAll(messages: WhatsAppMessage[], options?: string | ServiceSendOptions): Promise<MessageHandle> {
+    const { accountId, ...send } = sendOptions(options);
+    if (messages.length === 0) return messageHandle(accountId ?? "", []);
+    const plugin = this.routePlugin(messages[0].to, accountId);
+    const sent: SentMessage[] = [];
+    for (const [index, message] of messages.entries()) {
+      const part = await this.sendOnce(plugin, message, partOptions(send, index));
+      sent.push(part);
+      if ((part.response as { templateFallback?: boolean } | undefined)?.templateFallback) break;
+    }
+    return messageHandle(plugin.accountId, sent);
+  }
+
+  /**
+   * Send unless a send with the same idempotency key was already made. Keys are shared by all
+   * accounts, so a repeat that routing or failover sends from another number still goes out once,
+   * and resolves to the message the first send produced.
+   */
+  private sendOnce(plugin: WhatsAppPlugin, message: WhatsAppMessage, options: SendOptions): Promise<SentMessage> {
+    const send = () => plugin.sendMessage(message, options).then((response) => plugin.sentMessage(response));
+    const key = options.idempotencyKey;
+    if (!key) return send();
+    this.idempotent ??= new LruCache({ maxChats: IDEMPOTENCY_KEY_LIMIT, maxIdleMs: IDEMPOTENCY_KEY_TTL_MS, clock: this.clock });
+    const earlier = this.idempotent.get(key);
+    if (earlier) return earlier;
+    const sending = send();
+    this.idempotent.set(key, sending);
+    // A failed send may be tried again under the same key
+    sending.catch(() => this.idempotent?.delete(key));
+    return sending;
+  }
+
+  /** Re-send media received on `fromAccount`, sending it from `viaAccount` (the same account by default) */
+  async forwardMedia(mediaId: string, to: string, fromAccount?: string, viaAccount?: string): Promise<unknown> {
+    const source = this.requirePlugin(fromAccount);
+    const via = viaAccount ? this.requirePlugin(viaAccount) : source;
+    return source.forwardMedia(mediaId, to, via);
+  }
+
+  /** Mark an inbound message (and everything before it in the chat) as read */
+  async markRead(message: InboundMessageRef, accountId?: string): Promise<void> {
+    await this.requirePlugin(accountId).markRead(message);
+  }
+
+  /** Show "typing…" in the chat of an inbound message */
+  async sendTyping(message: InboundMessageRef, accountId?: string): Promise<void> {
+    await this.requirePlugin(accountId).sendTyping(message);
+  }
+
+  /** Approved message templates of an account; empty for Baileys accounts */
+  async listApprovedTemplates(accountId?: string): Promise<TemplateSummary[]> {
+    return this.requirePlugin(accountId).templates.approved();
+  }
+
+  /** Products of an account's catalog, with the catalog id product messages need; empty for Baileys accounts */
+  async listProducts(accountId?: string): Promise<{ catalogId: string; products: ProductSummary[] }> {
+    return this.requirePlugin(accountId).products.list();
+  }
+
+  /** Send a fresh one-time code with the WHATSAPP_OTP authentication template */
+  async sendOtp(to: string, accountId?: string): Promise<void> {
+    if (!this.otpSettings?.template) {
+      throw new WhatsAppError("INVALID_CONFIG", "WHATSAPP_OTP must name an authentication template to send codes");
+    }
+    const account = this.routeAccount(to, accountId);
+    const code = await this.otpFor(account).issue(to);
+    const { template, language = "en_US" } = this.otpSettings;
+    await account.plugin.sendMessage({ type: "template", to, content: buildAuthenticationTemplate(template, language, code) });
+  }
+
+  /** Check a code the user typed back to the account that sent it; a valid or exhausted code cannot be used again */
+  async verifyOtp(to: string, code: string, accountId?: string): Promise<OtpVerification> {
+    return this.otpFor(this.routeAccount(to, accountId)).verify(to, code);
+  }
+
+  private otpFor(account: ConnectedAccount): OtpManager {
+    const { accountId } = account.config;
+    let otp = this.otps.get(accountId);
+    if (!otp) {
+      otp = new OtpManager(this.otpSettings, this.otpStore(accountId), account.plugin.clock);
+      this.otps.set(accountId, otp);
+    }
+    return otp;
+  }
+
+  /**
+   * Send buttons or a list whose replies run a callback or action instead of reaching the agent.
+   * `context` (e.g. the order an action was asking about) is passed back with the reply.
+   */
+  async sendInteractive(
+    to: string,
+    content: WhatsAppInteractive,
+    callbacks: Record<string, ButtonCallback>,
+    context?: Record<string, unknown>,
+    accountId?: string
+  ): Promise<unknown> {
+    return this.routePlugin(to, accountId).sendInteractive(to, content, callbacks, context);
+  }
+
+  /** Send a named canned response to a user or group */
+  async sendCannedResponse(name: string, to: string, options?: string | ServiceSendOptions): Promise<MessageHandle> {
+    const message = await this.cannedResponses.toMessage(name, to);
+    return this.sendMessage(message, sendOptions(options));
+  }
+
+  /**
+   * Send one message to many users, spread across the enabled accounts (or `options.accounts`) in
+   * proportion to the conversations each number can still open today, with `paceMs` between sends
+   * from the same number. Recipients beyond the combined capacity are reported as failed. Template
+   * campaigns slow down on numbers where Meta is pacing the template and stop where it is paused.
+   * With `overflow: "reject"`, nothing is sent when the audience exceeds today's capacity.
+   * Recipients in their quiet hours come back deferred and are sent to in the background once
+   * their hours end.
+   */
+  async broadcast(
+    audience: string[] | AudienceSelector,
+    message: Omit<WhatsAppMessage, "to">,
+    options: BroadcastOptions & { accounts?: string[]; overflow?: "fail" | "reject" } = {}
+  ): Promise<BroadcastResult[]> {
+    const recipients = await this.audience(audience, options.accounts);
+    const senders = await this.broadcastSenders(message, options.accounts);
+    if (options.overflow === "reject") {
+      const capacity = senders.reduce((sum, s) => sum + s.capacity, 0);
+      if (new Set(recipients).size > capacity) {
+        throw new WhatsAppError(
+          "MESSAGING_LIMIT",
+          `Broadcast to ${new Set(recipients).size} recipient(s) exceeds today's capacity of ${capacity}`
+        );
+      }
+    }
+    const results = await distributeBroadcast(recipients, senders, options, this.clock);
+    const sent = results.filter((r) => r.ok).length;
+    logger.info(`[WhatsApp] Broadcast sent to ${sent}/${results.length} recipient(s) from ${senders.length} account(s)`);
+    const deferred = results.filter(isDeferred);
+    if (deferred.length > 0) this.sendDeferred(deferred, message, options);
+    return results;
+  }
+
+  // Hand recipients still in their quiet hours to a campaign that sends as each one's hours end
+  private sendDeferred(
+    deferred: BroadcastResult[],
+    message: Omit<WhatsAppMessage, "to">,
+    options: BroadcastOptions & { accounts?: string[] }
+  ): void {
+    const recipients: Record<string, string[]> = {};
+    for (const { to, accountId = "" } of deferred) (recipients[accountId] ??= []).push(to);
+    const plan = { days: [{ day: 0, startsAt: this.clock.now(), recipients }], unscheduled: [] };
+    logger.info(`[WhatsApp] Broadcast to ${deferred.length} recipient(s) deferred until their quiet hours end`);
+    runCampaign(plan, () => this.broadcastSenders(message, options.accounts), options, this.clock).done.then(
+      (results) => {
+        const sent = results.filter((r) => r.ok).length;
+        logger.info(`[WhatsApp] Deferred broadcast sent to ${sent}/${results.length} recipient(s)`);
+      },
+      (error) => logger.error("[WhatsApp] Deferred broadcast failed:", (error as Error).message)
+    );
+  }
+
+  /**
+   * Plan how a broadcast would spread over the numbers' messaging tiers: today what each number has
+   * left, then its full limit on each following day, up to `maxDays`. Nothing is sent.
+   */
+  async planBroadcast(
+    audience: string[] | AudienceSelector,
+    options: { accounts?: string[]; maxDays?: number } = {}
+  ): Promise<CampaignPlan> {
+    const accounts = this.broadcastAccounts(options.accounts);
+    const limits = await Promise.all(
+      accounts.map(async ({ plugin }) => ({
+        accountId: plugin.accountId,
+        capacity: await plugin.remainingCapacity(),
+        limit: await plugin.messagingLimit(),
+      }))
+    );
+    return planCampaign(await this.audience(audience, options.accounts), limits, { now: this.clock.now(), maxDays: options.maxDays });
+  }
+
+  /**
+   * Broadcast to an audience larger than today's capacity by sending what fits now and the rest on
+   * the following days, as `planBroadcast` lays out. The plan is returned straight away; `done`
+   * settles with every recipient's result once the last day has been sent.
+   */
+  async scheduleBroadcast(
+    audience: string[] | AudienceSelector,
+    message: Omit<WhatsAppMessage, "to">,
+    options: CampaignOptions & { accounts?: string[] } = {}
+  ): Promise<ScheduledCampaign> {
+    const plan = await this.planBroadcast(audience, options);
+    const days = plan.days.length;
+    logger.info(
+      `[WhatsApp] Broadcast scheduled over ${days} day(s)` +
+        (plan.unscheduled.length ? `, ${plan.unscheduled.length} recipient(s) beyond capacity` : "")
+    );
+    return runCampaign(plan, () => this.broadcastSenders(message, options.accounts), options, this.clock);
+  }
+
+  // Tags are kept per account, so a selector matches contacts tagged on any of the broadcasting accounts
+  private async audience(audience: string[] | AudienceSelector, accountIds?: string[]): Promise<string[]> {
+    if (Array.isArray(audience)) return audience;
+    const selected = await Promise.all(this.broadcastAccounts(accountIds).map(({ plugin }) => plugin.tags.select(audience)));
+    return [...new Set(selected.flat())];
+  }
+
+  private broadcastAccounts(accountIds?: string[]): ConnectedAccount[] {
+    return accountIds
+      ? accountIds.map((id) => this.requireAccount(id))
+      : [...this.accounts.values()].filter((account) => account.enabled);
+  }
+
+  private async broadcastSenders(
+    message: Omit<WhatsAppMessage, "to">,
+    accountIds?: string[]
+  ): Promise<BroadcastSender[]> {
+    const template = message.type === "template" ? (message.content as WhatsAppTemplate).name : undefined;
+    return Promise.all(
+      this.broadcastAccounts(accountIds).map(async ({ plugin }) => ({
+        accountId: plugin.accountId,
+        capacity: await plugin.remainingCapacity(),
+        send: async (to: string) => plugin.sentMessage(await plugin.sendMessage({ ...message, to: recipient(to) } as WhatsAppMessage)),
+        pace: (): TemplatePace => (template ? plugin.templatePacing.pace(template) : "normal"),
+        sendAt: (to: string, now: number) => plugin.quietHours?.sendAt(to, now) ?? now,
+      }))
+    );
+  }
+
+  /** Send a streamed agent response as it is generated, flushing on sentence boundaries */
+  async streamResponse(
+    to: string,
+    tokens: AsyncIterable<string>,
+    options?: StreamOptions,
+    accountId?: string
+  ): Promise<void> {
+    const plugin = this.routePlugin(to, accountId);
+    const limit = options?.limit ?? plugin.replyTextLimit(to);
+    const footer = plugin.replyFooter(to);
+    const send = (text: string) => plugin.sendMessage({ type: "text", to, content: text });
+    await streamText(tokens, send, { ...options, limit, ...(footer ? { closing: footer } : {}) }, plugin.clock);
+  }
+
+  /** Block users on an account (the default one unless given); returns the ids WhatsApp confirmed */
+  async blockUsers(users: string[], accountId?: string): Promise<string[]> {
+    const plugin = this.requirePlugin(accountId);
+    const blocked = await plugin.blockUsers(users);
+    for (const user of blocked) {
+      await plugin.tags.add(user, ["blocked"]);
+    }
+    logger.info(`[WhatsApp] Blocked ${blocked.length} user(s) on account ${plugin.accountId}`);
+    return blocked;
+  }
+
+  async unblockUsers(users: string[], accountId?: string): Promise<string[]> {
+    const plugin = this.requirePlugin(accountId);
+    const unblocked = await plugin.unblockUsers(users);
+    for (const user of unblocked) {
+      await plugin.tags.remove(user, ["blocked"]);
+    }
+    return unblocked;
+  }
+
+  /** Record send/receive/latency metrics for every account, e.g. from createPrometheusMetrics() */
+  useMetrics(metrics: WhatsAppMetrics): void {
+    this.metrics = metrics;
+    for (const { plugin } of this.accounts.values()) {
+      plugin.useMetrics(metrics);
+    }
+    this.reportAccounts();
+    this.reportQueueDepth();
+  }
+
+  /** Set the active-accounts gauge to the number of enabled accounts */
+  private reportAccounts(): void {
+    const enabled = [...this.accounts.values()].filter((account) => account.enabled).length;
+    this.metrics?.setActiveAccounts(enabled);
+  }
+
+  /** Set the queue-depth gauge to the sends pending across all accounts */
+  private reportQueueDepth(): void {
+    let depth = 0;
+    for (const { plugin } of this.accounts.values()) depth += plugin.pendingSends;
+    this.metrics?.setQueueDepth(depth);
+  }
+
+  /** Emit spans through an OpenTelemetry tracer for every account */
+  useTracer(tracer: TracerLike): void {
+    for (const { plugin } of this.accounts.values()) {
+      plugin.useTracer(tracer);
+    }
+  }
+
+  /** Send every Cloud API account's Graph API requests through `transport`, e.g. fetchTransport */
+  useTransport(transport: HttpTransport): void {
+    for (const { plugin } of this.accounts.values()) {
+      plugin.useTransport(transport);
+    }
+  }
+
+  /** Resolve the coordinates of shared locations into places, on every account */
+  useGeocoder(geocoder: Geocoder | undefined): void {
+    for (const { plugin } of this.accounts.values()) {
+      plugin.useGeocoder(geocoder);
+    }
+  }
+
+  /** Account whose history of a chat the agent sees: `accountId` when given, else the one answering the chat */
+  private historyAccount(chatId: string, accountId?: string): ConnectedAccount | undefined {
+    return (accountId && this.accounts.get(accountId)) || this.stickyAccount(chatId) || this.firstAccount();
+  }
+
+  /** Archived WhatsApp messages for a chat on an account (the one answering the chat unless given), oldest first */
+  async getRecentMessages(chat: string, limit: number, accountId?: string): Promise<ArchivedMessage[]> {
+    const chatId = canonicalChatId(chat);
+    const account = this.historyAccount(chatId, accountId);
+    return account ? account.plugin.getRecentMessages(chatId, limit) : [];
+  }
+
+  /** Condense long chats for the agent's context with `summarizer`; undefined shows recent turns only */
+  useSummarizer(summarizer: Summarizer | undefined): void {
+    this.summarizer = summarizer;
+    for (const summaries of this.summaries.values()) summaries.useSummarizer(summarizer);
+  }
+
+  /**
+   * What the agent should see of a chat: with a summarizer, a rolling summary of older turns and
+   * the turns after it; otherwise the last WHATSAPP_HISTORY_TURNS turns
+   */
+  async getConversationContext(chat: string, accountId?: string): Promise<ConversationContext> {
+    const chatId = canonicalChatId(chat);
+    const account = this.historyAccount(chatId, accountId);
+    if (!account) return { recent: [] };
+    return this.summariesFor(account).context(chatId, await this.chatHistory(account, chatId));
+  }
+
+  private summariesFor(account: ConnectedAccount): ConversationSummaries {
+    const { accountId } = account.config;
+    let summaries = this.summaries.get(accountId);
+    if (!summaries) {
+      summaries = new ConversationSummaries(this.summaryOptions, this.summaryStore(accountId), account.plugin.clock);
+      summaries.useSummarizer(this.summarizer);
+      this.summaries.set(accountId, summaries);
+    }
+    return summaries;
+  }
+
+  // The chat's last SUMMARY_LOOKBACK messages; concurrent callers share one archive read
+  private chatHistory(account: ConnectedAccount, chatId: string): Promise<ArchivedMessage[]> {
+    const key = `${account.config.accountId}:${chatId}`;
+    const running = this.historyReads.get(key);
+    if (running) return running;
+    const reading = account.plugin.getRecentMessages(chatId, SUMMARY_LOOKBACK).finally(() => this.historyReads.delete(key));
+    this.historyReads.set(key, reading);
+    return reading;
+  }
+
+  /**
+   * What is known about a chat, given as a phone number, JID or whatsapp: id: whether it is a user
+   * or group, the account answering it (or `accountId`), the contact's language, whether the 24-hour
+   * service window is open (Cloud API only), and its last `messages` messages
+   * (WHATSAPP_CHAT_STATE_MESSAGES, none by default). Rejects anything that is not a chat address.
+   */
+  async getChatState(chat: string, messages = this.chatStateMessages, accountId?: string): Promise<ChatState> {
+    const chatId = recipient(chat);
+    const kind = normalizeWhatsAppTarget(chatId)?.kind ?? "user";
+    const account = this.historyAccount(chatId, accountId);
+    const history = account ? await this.chatHistory(account, chatId) : [];
+    const state: ChatState = {
+      chatId,
+      kind,
+      accountId: account?.config.accountId,
+      recent: messages > 0 ? history.slice(-messages) : [],
+    };
+    if (kind !== "user" || !account) return state;
+
+    const language = await account.plugin.languages.get(chatId);
+    // Baileys sends as a linked device, which no service window restricts
+    if (!account.plugin.usesCloudApi) return { ...state, language };
+    const lastInbound = history.findLast((m) => m.direction === "inbound");
+    const closesAt = lastInbound ? lastInbound.timestamp + SERVICE_WINDOW_MS : 0;
+    const windowOpen = closesAt > account.plugin.clock.now();
+    return { ...state, language, windowOpen, windowClosesAt: windowOpen ? closesAt : undefined };
+  }
+
+  /** Reactions users currently have on one of the agent's messages, with counts by emoji */
+  getReactions(messageId: string, accountId?: string): MessageReactions {
+    return this.requirePlugin(accountId).getReactions(messageId);
+  }
+
+  /** Tag a contact on an account (the default one unless given); tags are kept per account */
+  async tagContact(contact: string, tags: string[], accountId?: string): Promise<string[]> {
+    return this.requirePlugin(accountId).tags.add(contact, tags);
+  }
+
+  async untagContact(contact: string, tags: string[], accountId?: string): Promise<string[]> {
+    return this.requirePlugin(accountId).tags.remove(contact, tags);
+  }
+
+  /** Language templates are sent to a contact in, e.g. "es" or "pt_BR"; outranks what was learned */
+  async setContactLanguage(contact: string, language: string, accountId?: string): Promise<ContactLanguage> {
+    return this.requirePlugin(accountId).languages.set(contact, language);
+  }
+
+  /** The contact's language on an account and where it came from: explicit, template reply or detected */
+  async getContactLanguage(contact: string, accountId?: string): Promise<ContactLanguage | undefined> {
+    return this.requirePlugin(accountId).languages.get(contact);
+  }
+
+  async listBlockedUsers(accountId?: string): Promise<string[]> {
+    return this.requirePlugin(accountId).listBlockedUsers();
+  }
+
+  /** The account a webhook notification is for: by phone number id, or business account id for WABA-level events */
+  private webhookOwner(event: WhatsAppWebhookEvent): ConnectedAccount | undefined {
+    const phoneNumberId = event.entry?.[0]?.changes?.[0]?.value?.metadata?.phone_number_id;
+    const businessAccountId = event.entry?.[0]?.id;
+    const accounts = [...this.accounts.values()];
+    return (
+      accounts.find((a) => "phoneNumberId" in a.config && a.config.phoneNumberId === phoneNumberId) ??
+      accounts.find((a) => "businessAccountId" in a.config && a.config.businessAccountId === businessAccountId)
+    );
+  }
+
+  /** Process a Cloud API webhook notification on the account owning its phone number id */
+  async handleWebhook(event: WhatsAppWebhookEvent): Promise<void> {
+    await (this.webhookOwner(event) ?? this.requireAccount()).plugin.handleWebhook(event);
+  }
+
+  /** Keep the raw body of an authenticated notification for `replayWebhook`, when recording is on */
+  recordWebhook(event: WhatsAppWebhookEvent, rawBody?: string): void {
+    this.webhookLog?.record(rawBody ?? JSON.stringify(event), this.webhookOwner(event)?.config.accountId);
+  }
+
+  /** Stored webhook notifications, oldest first; empty unless WHATSAPP_WEBHOOK_LOG is set */
+  listStoredWebhooks(): StoredWebhook[] {
+    return this.webhookLog?.list() ?? [];
+  }
+
+  /**
+   * Feed a stored notification through the webhook pipeline again, skipping the signature check it
+   * passed on arrival. Useful for reproducing handler bugs against payloads Meta actually sent.
+   */
+  async replayWebhook(id: string): Promise<void> {
+    const entry = this.webhookLog?.get(id);
+    if (!entry) {
+      throw new WhatsAppError("NOT_FOUND", `No stored webhook with id ${id}`);
+    }
+    let event: WhatsAppWebhookEvent;
+    try {
+      event = JSON.parse(entry.body) as WhatsAppWebhookEvent;
+    } catch (error) {
+      throw new WhatsAppError("INVALID_PAYLOAD", `Stored webhook ${id} is not valid JSON`, { cause: error });
+    }
+    logger.info(`[WhatsApp] Replaying webhook ${id} received at ${new Date(entry.receivedAt).toISOString()}`);
+    await this.handleWebhook(event);
+  }
+
+  /**
+   * Check the verify token Meta sends when subscribing the webhook (always false for Baileys).
+   * With an account id (from a per-account callback URL) only that account's token is accepted.
+   */
+  async verifyWebhook(token: string, accountId?: string): Promise<boolean> {
+    const candidates = accountId ? [this.accounts.get(accountId)].filter(Boolean) : [...this.accounts.values()];
+    for (const { plugin } of candidates as ConnectedAccount[]) {
+      if (await plugin.verifyWebhook(token).catch(() => false)) return true;
+    }
+    return false;
+  }
+
+  /**
+   * Check X-Hub-Signature-256 with the app secret of the account the notification is for. Notifications
+   * no account claims must match one of the configured secrets; without any secret nothing is checked.
+   */
+  verifySignature(event: WhatsAppWebhookEvent, rawBody?: string, signature?: string): boolean {
+    const owner = this.webhookOwner(event);
+    const signers = (owner ? [owner] : [...this.accounts.values()]).filter(
+      (a) => "appSecret" in a.config && a.config.appSecret
+    );
+    if (rawBody === undefined && signers.length > 0 && !this.missingRawBody) {
+      this.missingRawBody = true;
+      logger.error(
+        "[WhatsApp] Webhook request has no raw body, so its signature cannot be checked and every notification is rejected; keep the unparsed body as req.rawBody"
+      );
+    }
+    if (owner) return owner.plugin.verifySignature(event, rawBody, signature);
+
+    if (signers.length === 0 || signers.some((a) => a.plugin.signatureMatches(rawBody, signature))) return true;
+    const [first] = signers;
+    first.plugin.events.publish({ kind: "webhook_rejected", accountId: first.config.accountId, reason: "signature" });
+    return false;
+  }
+
+  /** Accounts managed by this service with their connection state */
+  listAccounts(): AccountStatus[] {
+    return [...this.accounts.entries()].map(([accountId, account]) => ({
+      accountId,
+      enabled: account.enabled,
+      connection: account.plugin.getConnectionStatus(),
+      failedSends: account.plugin.failedSends.size,
+      circuit: account.plugin.circuit?.state ?? "disabled",
+    }));
+  }
+
+  /** Pause or resume an account; a paused account ignores inbound messages and agent replies */
+  setAccountEnabled(accountId: string, enabled: boolean): boolean {
+    const account = this.accounts.get(accountId);
+    if (!account) return false;
+    account.enabled = enabled;
+    this.reportAccounts();
+    logger.info(`[WhatsApp] Account ${accountId} ${enabled ? "enabled" : "paused"}`);
+    return true;
+  }
+
+  /** Group settings currently enforced, keyed by group JID (or "*") */
+  getGroups(accountId?: string): Record<string, WhatsAppGroupConfig> {
+    return this.requireAccount(accountId).config.groups ?? {};
+  }
+
+  /** Replace the sender allowlist of a group without restarting the connector */
+  setGroupAllowFrom(groupId: string, allowFrom: string[], accountId?: string): WhatsAppGroupConfig {
+    const config = this.requireAccount(accountId).config;
+    const groups = (config.groups ??= {});
+    groups[groupId] = { ...groups[groupId], allowFrom };
+    return groups[groupId];
+  }
+
+  /** Check the configuration of every account; `live` also asks the Graph API whether tokens work */
+  async validateDeployment(live = false): Promise<DeploymentReport> {
+    const report = await validateDeployment(this.runtime, { live });
+    if (!this.missingRawBody) return report;
+    const issue: DeploymentIssue = {
+      severity: "warning",
+      code: "MISSING_RAW_BODY",
+      message: "Webhook requests arrive without req.rawBody, so signed notifications are rejected; keep the unparsed body",
+    };
+    return { ...report, issues: [...report.issues, issue] };
+  }
+
+  /**
+   * Serialize in-process state (sticky routes, reply rate windows and each account's conversation
+   * window, in-memory history and menu sessions) so a new deployment can pick up where this one left off
+   */
+  exportState(): StateSnapshot {
+    return {
+      version: STATE_SNAPSHOT_VERSION,
+      exportedAt: this.clock.now(),
+      accounts: Object.fromEntries(
+        [...this.accounts.entries()].map(([accountId, { plugin }]) => [accountId, plugin.exportState()])
+      ),
+      routes: this.routes.snapshot(),
+      rateLimits: this.rateLimiter.snapshot(),
+    };
+  }
+
+  /** Restore state exported by `exportState`, as an object or JSON; accounts not configured here are skipped */
+  importState(input: StateSnapshot | string): void {
+    const snapshot = readStateSnapshot(input);
+    for (const [accountId, state] of Object.entries(snapshot.accounts)) {
+      this.accounts.get(accountId)?.plugin.importState(state);
+    }
+    this.routes.restore(snapshot.routes.filter(([, accountId]) => this.accounts.has(accountId)));
+    this.rateLimiter.restore(snapshot.rateLimits);
+  }
+
+  /** Billable conversations and their estimated cost, by category and account, within `range` (ms since epoch) */
+  getConversationCosts(range: CostRange = {}): ConversationCostReport {
+    return this.costs.summarize(range);
+  }
+
+  /** Sends that exhausted their retries, from every account, with each failed attempt */
+  listDeadLetters(): AccountFailedSend[] {
+    return [...this.accounts.entries()].flatMap(([accountId, { plugin }]) =>
+      plugin.failedSends.list().map((entry) => ({ ...entry, accountId }))
+    );
+  }
+
+  /** Send a dead letter again from its account; it leaves the queue once delivered */
+  async replayDeadLetter(id: string): Promise<unknown> {
+    return this.deadLetterOwner(id).replayFailedSend(id);
+  }
+
+  /** Drop a dead letter without sending it */
+  discardDeadLetter(id: string): void {
+    this.deadLetterOwner(id).discardFailedSend(id);
+  }
+
+  /** @deprecated Use listDeadLetters */
+  listFailedSends(): AccountFailedSend[] {
+    return this.listDeadLetters();
+  }
+
+  /** @deprecated Use replayDeadLetter */
+  async replayFailedSend(id: string): Promise<unknown> {
+    return this.replayDeadLetter(id);
+  }
+
+  private deadLetterOwner(id: string): WhatsAppPlugin {
+    const owner = [...this.accounts.values()].find(({ plugin }) => plugin.failedSends.get(id));
+    if (!owner) {
+      throw new WhatsAppError("NOT_FOUND", `No dead letter with id ${id}`);
+    }
+    return owner.plugin;
   }
 
   private async initialize(): Promise<void> {
     const runtime = this.runtime;
-    const config = this.resolveConfig();
-    if (!config) {
+    const configs = listEnabledWhatsAppAccounts(runtime);
+    if (configs.length === 0) {
       logger.warn(
         "[WhatsApp] No configuration found (set WHATSAPP_AUTH_DIR for Baileys or " +
         "WHATSAPP_ACCESS_TOKEN + WHATSAPP_PHONE_NUMBER_ID for Cloud API) — connector disabled"
@@ -81,56 +1120,448 @@ export class WhatsAppConnectorService extends Service {
       return;
     }
 
-    this.plugin = new WhatsAppPlugin(config);
+    for (const issue of (await validateDeployment(runtime)).issues) {
+      const log = issue.severity === "error" ? logger.error : logger.warn;
+      log(`[WhatsApp] ${issue.accountId ? `Account ${issue.accountId}: ` : ""}${issue.message} (${issue.code})`);
+    }
 
-    this.plugin.on("qr", (qrData: { terminal?: string; dataURL?: string }) => {
-      logger.info("[WhatsApp] Scan the QR code below with your phone:");
-      process.stdout.write("\n" + (qrData.terminal ?? String(qrData)) + "\n\n");
-    });
+    this.otpSettings = getJsonSetting<OtpSettings>(runtime, "WHATSAPP_OTP") ?? undefined;
+    this.otpStore = (accountId) => new RuntimeOtpStore(runtime, accountId);
+    this.otps.clear();
+    this.cannedResponses.useStore(new RuntimeCannedResponseStore(runtime));
+    await this.seedCannedResponses();
 
-    this.plugin.on("ready", () => {
-      logger.info("[WhatsApp] Connected!");
-    });
+    this.costs.usePricing(getJsonSetting<ConversationPricing>(runtime, "WHATSAPP_PRICING") ?? {});
+    this.summaryOptions = {
+      keep: Number(getSetting(runtime, "WHATSAPP_HISTORY_TURNS")) || undefined,
+      threshold: Number(getSetting(runtime, "WHATSAPP_SUMMARY_THRESHOLD")) || undefined,
+    };
+    this.summaryStore = (accountId) => new RuntimeSummaryStore(runtime, accountId);
+    this.summaries.clear();
+    this.chatStateMessages = Number(getSetting(runtime, "WHATSAPP_CHAT_STATE_MESSAGES")) || 0;
 
-    this.plugin.on("connection", (status: string) => {
-      logger.info(`[WhatsApp] Connection status: ${status}`);
-    });
+    const spamConfig = getJsonSetting<SpamFilterConfig>(runtime, "WHATSAPP_SPAM_FILTER");
+    this.spamFilter = spamConfig ? new SpamFilter(spamConfig) : null;
+    this.stickyRouting = getSetting(runtime, "WHATSAPP_STICKY_ROUTING") !== "false";
+    const defaultAccount = getSetting(runtime, "WHATSAPP_DEFAULT_ACCOUNT");
+    if (defaultAccount) {
+      if (configs.some((c) => c.accountId === defaultAccount)) this.defaultAccountId = defaultAccount;
+      else logger.warn(`[WhatsApp] WHATSAPP_DEFAULT_ACCOUNT: ${this.missingAccount(defaultAccount)}; using the default account`);
+    }
 
-    this.plugin.on("error", (err: Error) => {
-      logger.error("[WhatsApp] Error:", err.message);
-    });
+    // WHATSAPP_WEBHOOK_LOG is "true" for the defaults or a JSON WebhookLogConfig
+    const webhookLogSetting = getSetting(runtime, "WHATSAPP_WEBHOOK_LOG");
+    const webhookLog =
+      webhookLogSetting === "true" ? {} : getJsonSetting<WebhookLogConfig>(runtime, "WHATSAPP_WEBHOOK_LOG");
+    if (webhookLog) {
+      this.webhookLog = new WebhookLog(webhookLog);
+      this.webhookLog.useStore(new RuntimeWebhookPayloadStore(runtime)).catch((err) => {
+        logger.warn("[WhatsApp] Could not load stored webhooks:", (err as Error).message);
+      });
+    }
 
-    this.plugin.on("message", async (msg: UnifiedMessage) => {
-      await this.handleIncomingMessage(msg);
-    });
+    for (const config of configs) {
+      const account: ConnectedAccount = { plugin: this.createPlugin(config), config, enabled: true };
+      this.accounts.set(config.accountId, account);
+    }
+
+    // Fallback accounts take over sends an account cannot make
+    for (const [accountId, account] of this.accounts) {
+      const fallbackId = account.config.fallbackAccount;
+      if (!fallbackId) continue;
+      const fallback = fallbackId === accountId ? undefined : this.accounts.get(fallbackId);
+      if (fallback) {
+        account.plugin.useFallback(fallback.plugin);
+      } else {
+        logger.warn(`[WhatsApp] Account ${accountId}: fallback account ${fallbackId} is not running`);
+      }
+    }
 
     // Register send handler so the runtime can route replies back to WhatsApp
     runtime.registerSendHandler(
       SOURCE,
       async (_rt: IAgentRuntime, target: TargetInfo, content: Content): Promise<void> => {
-        if (!this.plugin || !content.text) return;
         const to = target.channelId ?? (target.entityId ? String(target.entityId) : null);
         if (!to) return;
-        await this.plugin.sendMessage({
-          type: "text",
-          to,
-          content: content.text,
-        });
+        // content.whatsappAccount picks the sending account; otherwise the chat's last account answers
+        const accountId = content.whatsappAccount as string | undefined;
+        const account = accountId ? this.accounts.get(accountId) : (this.stickyAccount(to) ?? this.firstAccount());
+        if (!account?.enabled) return;
+        const limit = account.plugin.replyTextLimit(to);
+        const messages = toOutboundMessages(to, content, limit, account.config.chunkMarkers);
+        for (const [index, message] of messages.entries()) {
+          await account.plugin.sendMessage(message, { reply: { index, count: messages.length } });
+        }
+      }
+    );
+
+    for (const [accountId, account] of this.accounts) {
+      try {
+        await account.plugin.start();
+      } catch (err) {
+        logger.error(`[WhatsApp] Account ${accountId} failed to start:`, redactText((err as Error).message));
+      }
+    }
+    this.reportAccounts();
+
+    // WHATSAPP_HEALTH_CHECK is "true" for the defaults or a JSON HealthCheckConfig
+    const healthSetting = getSetting(runtime, "WHATSAPP_HEALTH_CHECK");
+    const health =
+      healthSetting === "true" ? {} : getJsonSetting<HealthCheckConfig>(runtime, "WHATSAPP_HEALTH_CHECK");
+    if (health) {
+      for (const { plugin } of this.accounts.values()) {
+        plugin.monitorHealth(health);
+        plugin.events.subscribe(["account_degraded", "account_recovered"], (event) => this.reportHealth(event, health.notify));
+      }
+    }
+
+    const alerts = getJsonSetting<AdminAlertConfig>(runtime, "WHATSAPP_ALERTS");
+    if (alerts?.numbers?.length) {
+      const sink = new AdminAlertSink(alerts, async (message, event) => {
+        const sender = this.operatorSender(event.accountId);
+        if (!sender) return;
+        // Alerts skip failover so a failing alert cannot bounce between accounts
+        await sender.plugin.sendMessage({ ...message, to: recipient(message.to) }, { failover: false });
+      });
+      for (const { plugin } of this.accounts.values()) {
+        attachSink(plugin.events, sink, sink.kinds);
+      }
+    }
+    logger.info(`[WhatsApp] Connector service started with ${this.accounts.size} account(s)`);
+  }
+
+  /** Account to tell operators about trouble on `accountId` from: another enabled account when there is one */
+  private operatorSender(accountId: string): ConnectedAccount | undefined {
+    const enabled = [...this.accounts.values()].filter((account) => account.enabled);
+    return enabled.find((a) => a.config.accountId !== accountId) ?? this.accounts.get(accountId);
+  }
+
+  /** Log an account health change and tell the operator number, sending from another account when there is one */
+  private async reportHealth(
+    event: WhatsAppEventOf<"account_degraded" | "account_recovered">,
+    notify?: string
+  ): Promise<void> {
+    const text =
+      event.kind === "account_degraded"
+        ? [
+            `WhatsApp account ${event.accountId} is degraded:`,
+            ...event.problems.map((p) => `- ${p.message} (${p.code})`),
+          ].join("\n")
+        : `WhatsApp account ${event.accountId} has recovered`;
+    if (event.kind === "account_degraded") {
+      logger.warn(`[WhatsApp] ${text.replace(/\n/g, " ")}`);
+    } else {
+      logger.info(`[WhatsApp] ${text}`);
+    }
+    if (!notify) return;
+
+    try {
+      const sender = this.operatorSender(event.accountId);
+      await sender?.plugin.sendMessage({ type: "text", to: recipient(notify), content: text });
+    } catch (err) {
+      logger.error("[WhatsApp] Could not send the account health notice:", redactText((err as Error).message));
+    }
+  }
+
+  /** Build the connection for one account and wire its events into the agent */
+  private createPlugin(config: ResolvedWhatsAppAccount): WhatsAppPlugin {
+    const runtime = this.runtime;
+    const label = config.accountId === DEFAULT_ACCOUNT_ID ? "" : ` [${config.accountId}]`;
+
+    const plugin = new WhatsAppPlugin(config);
+    plugin.menus.useStore(new RuntimeMenuSessionStore(runtime, config.accountId, plugin.chatStateLimits("menu_sessions")));
+    plugin.prompts.useStore(new RuntimePromptStore(runtime, config.accountId, plugin.chatStateLimits("prompts")));
+    plugin.tags.useStore(new RuntimeContactTagStore(runtime, config.accountId));
+    if (getSetting(runtime, "WHATSAPP_EMOJI_SHORTCODES") !== "false") {
+      plugin.outbound.use(expandEmojiShortcodes);
+    }
+    plugin.languages.useStore(new RuntimeContactLanguageStore(runtime, config.accountId));
+    const translation = translationSetting(runtime);
+    if (translation) plugin.useTranslator(new RuntimeModelTranslator(runtime), translation);
+    plugin.failedSends.useStore(new RuntimeDeadLetterStore(runtime, config.accountId)).catch((err) => {
+      logger.warn(`[WhatsApp]${label} Could not load dead letters: ${(err as Error).message}`);
+    });
+    plugin.useArchive(
+      new RuntimeMessageArchive(
+        runtime,
+        config.accountId,
+        {
+          maxAgeMs: Number(getSetting(runtime, "WHATSAPP_HISTORY_RETENTION_DAYS") ?? 30) * 86_400_000,
+          maxPerChat: Number(getSetting(runtime, "WHATSAPP_HISTORY_MAX_PER_CHAT") ?? 200),
+        },
+        plugin.clock,
+        plugin.chatStateLimits("archive")
+      )
+    );
+    for (const menu of this.resolveMenus()) {
+      plugin.menus.register(menu);
+    }
+
+    // Re-emit bus events on the runtime so other plugins can react to WhatsApp activity
+    plugin.events.subscribe(async (event) => {
+      await runtime.emitEvent(runtimeEventType(event.kind), { runtime, source: SOURCE, event });
+    });
+
+    const forwardUrl = getSetting(runtime, "WHATSAPP_FORWARD_URL");
+    if (forwardUrl) {
+      const forwarder = new WebhookForwarder({
+        url: forwardUrl,
+        secret: getSetting(runtime, "WHATSAPP_FORWARD_SECRET") ?? undefined,
+      });
+      attachSink(plugin.events, forwarder, forwarder.kinds);
+    }
+
+    plugin.events.subscribe(["pairing_requested"], ({ qr }) => {
+      logger.info(`[WhatsApp]${label} Scan the QR code below with your phone:`);
+      process.stdout.write("\n" + (qr.terminal ?? String(qr)) + "\n\n");
+    });
+
+    plugin.on("ready", () => {
+      logger.info(`[WhatsApp]${label} Connected!`);
+    });
+
+    plugin.events.subscribe(["connection_changed"], ({ status }) => {
+      logger.info(`[WhatsApp]${label} Connection status: ${status}`);
+    });
+
+    plugin.on("error", (err: Error) => {
+      logger.error(`[WhatsApp]${label} Error:`, redactText(err.message));
+    });
+
+    plugin.on("pendingSends", () => this.reportQueueDepth());
+
+    // Remember which number each chat is talking to
+    plugin.events.subscribe(["message_received"], ({ message }) => this.routes.remember(message.from, config.accountId));
+    plugin.events.subscribe(["message_sent", "message_echoed"], ({ message }) =>
+      this.routes.remember(message.to, config.accountId)
+    );
+
+    plugin.events.subscribe(["status_changed"], ({ status }) => this.costs.record(config.accountId, status));
+
+    plugin.events.subscribe(["message_received"], async ({ message }) => {
+      const account = this.accounts.get(config.accountId);
+      if (account) await this.handleIncomingMessage(account, message);
+    });
+
+    return plugin;
+  }
+
+  /** Reply allowance left for a sender in a chat, and how many sends the account has queued */
+  getRateLimitStatus(sender: string, chat: string, accountId?: string): RateLimitStatus {
+    const account = this.requireAccount(accountId);
+    const groupId = isGroupJid(chat) ? chat : undefined;
+    const groupLimit = groupId ? resolveWhatsAppGroupConfig(account.config.groups, groupId).rateLimit : undefined;
+    const limit = groupLimit ?? account.config.rateLimit;
+    return {
+      pendingSends: account.plugin.pendingSends,
+      ...(limit ? { sender: this.rateLimiter.status(rateLimitKey(account, sender, groupId), limit) } : {}),
+    };
+  }
+
+  /** Counts of inbound messages inspected and filtered as spam, or null when the filter is off */
+  getSpamMetrics(): SpamFilterMetrics | null {
+    return this.spamFilter?.getMetrics() ?? null;
+  }
+
+  /** Apply the per-sender cooldown, sending a single "slow down" notice when it first trips */
+  private async allowSender(
+    account: ConnectedAccount,
+    msg: UnifiedMessage,
+    group?: GroupContext
+  ): Promise<boolean> {
+    const limit = group?.rateLimit ?? account.config.rateLimit;
+    if (!limit) return true;
+
+    const sender = msg.participant ?? msg.from;
+    const decision = this.rateLimiter.check(rateLimitKey(account, sender, group?.id), limit);
+    if (decision === "allow") return true;
+
+    if (decision === "notify") {
+      logger.info(`[WhatsApp] Rate limit reached for ${maskPhone(sender)}, pausing replies`);
+      await account.plugin
+        .sendMessage({ type: "text", to: msg.from, content: limit.notice ?? DEFAULT_SLOW_DOWN_NOTICE })
+        .catch((err: Error) => logger.error("[WhatsApp] Failed to send rate limit notice:", err.message));
+    }
+    return false;
+  }
+
+  private async handleIncomingMessage(account: ConnectedAccount, msg: UnifiedMessage): Promise<void> {
+    if (!account.enabled) return;
+
+    const verdict = this.spamFilter?.inspect(msg, account.plugin.clock.now());
+    if (verdict && verdict.action !== "allow") {
+      const sender = msg.participant ?? msg.from;
+      logger.warn(
+        `[WhatsApp] Spam from ${maskPhone(sender)} (${verdict.reasons.join(", ")}): ${verdict.action}`
+      );
+      if (verdict.action === "block") {
+        await this.blockUsers([sender], account.config.accountId).catch((err: Error) =>
+          logger.error("[WhatsApp] Auto-block failed:", err.message)
+        );
+        this.spamFilter?.reset(sender);
+      }
+      if (verdict.action !== "flag") return;
+    }
+
+    let group: GroupContext | undefined;
+    if (isGroupJid(msg.from)) {
+      const groupConfig = resolveWhatsAppGroupConfig(account.config.groups, msg.from);
+      const mentioned =
+        msg.mentioned === true ||
+        msg.content.toLowerCase().includes(`@${this.runtime.character.name.toLowerCase()}`);
+      const gate = checkGroupMessage(groupConfig, msg.participant ?? msg.from, mentioned);
+      // Tapping a poll option can't mention the agent, so votes skip the mention requirement
+      if (!gate.allowed && gate.reason === "not_mentioned" && account.plugin.handlePollReply(msg)) return;
+      if (!gate.allowed) {
+        logger.debug(`[WhatsApp] Ignoring group message in ${maskPhone(msg.from)}: ${gate.reason}`);
+        return;
       }
+      group = { ...groupConfig, id: msg.from };
+    }
+
+    if (!(await this.allowSender(account, msg, group))) return;
+
+    // Only now that the message will be handled may a typed answer use up the chat's pending prompt,
+    // or a translation cost a model call
+    const flagged = verdict?.action === "flag" ? verdict.reasons : undefined;
+    const answer = await account.plugin.interpretAnswer(msg);
+    await this.dispatchIncoming(account, await account.plugin.translateReceived(answer), group, flagged);
+  }
+
+  /** Route an admitted message to polls, button callbacks, a running menu, or else the agent */
+  private async dispatchIncoming(
+    account: ConnectedAccount,
+    msg: UnifiedMessage,
+    group: GroupContext | undefined,
+    spamReasons: SpamReason[] | undefined
+  ): Promise<void> {
+    // Poll votes are tallied by the plugin and never reach the agent
+    if (account.plugin.handlePollReply(msg)) return;
+
+    // Buttons sent with callbacks run them with the context they were sent with
+    try {
+      const handled = await account.plugin.handleButtonReply(msg, (action, options, context) =>
+        this.runButtonAction(account, msg, action, options, context, group)
+      );
+      if (handled) return;
+    } catch (err) {
+      logger.error("[WhatsApp] Button callback failed:", (err as Error).message);
+      return;
+    }
+
+    // Replies to a running menu advance the menu instead of reaching the agent
+    const step = await account.plugin.handleMenuReply(msg);
+    if (step) {
+      if (step.result) {
+        await this.deliverToAgent(
+          account,
+          msg,
+          { text: formatMenuResult(step.result), menuResult: step.result },
+          group
+        );
+      }
+      return;
+    }
+
+    if (!msg.content && !msg.media) return;
+    const text = msg.translation?.text ?? msg.content;
+    await this.deliverToAgent(
+      account,
+      msg,
+      {
+        // A typed answer to offered options names the option it picked, e.g. "2 (Track my order)"
+        text: msg.replyTitle ? `${text} (${msg.replyTitle})` : text,
+        ...(msg.translation
+          ? { whatsappOriginalText: msg.content, whatsappLanguage: msg.translation.from }
+          : {}),
+        ...(msg.location ? { whatsappLocation: msg.location } : {}),
+        ...(spamReasons ? { whatsappSpamReasons: spamReasons } : {}),
+      },
+      group
+    );
+  }
+
+  /** Hand a button reply straight to a runtime action, as if the agent had chosen it */
+  private async runButtonAction(
+    account: ConnectedAccount,
+    msg: UnifiedMessage,
+    name: string,
+    options: Record<string, unknown>,
+    context: Record<string, unknown>,
+    group?: GroupContext
+  ): Promise<void> {
+    const action = this.runtime.actions.find((a) => a.name === name || a.similes?.includes(name));
+    if (!action) {
+      throw new WhatsAppError("NOT_FOUND", `Unknown action ${name} for button "${msg.replyId}"`);
+    }
+
+    const memory = await this.storeInbound(
+      account,
+      msg,
+      { text: msg.content, buttonReply: { id: msg.replyId, context } },
+      group
+    );
+    await action.handler(
+      this.runtime,
+      memory,
+      undefined,
+      { ...options, context, replyId: msg.replyId },
+      this.replyCallback(account, msg)
     );
+  }
 
-    await this.plugin.start();
-    logger.info("[WhatsApp] Connector service started");
+  /** Replies go out from the account the message arrived on */
+  private replyCallback(account: ConnectedAccount, msg: UnifiedMessage): (response: Content) => Promise<Memory[]> {
+    const accountId = account.config.accountId;
+    return async (response: Content): Promise<Memory[]> => {
+      if (!this.accounts.has(accountId)) return [];
+      const limit = account.plugin.replyTextLimit(msg.from);
+      await account.plugin.sendReply(msg, toOutboundMessages(msg.from, response, limit, account.config.chunkMarkers));
+      return [];
+    };
   }
 
-  private async handleIncomingMessage(msg: UnifiedMessage): Promise<void> {
+  private async deliverToAgent(
+    account: ConnectedAccount,
+    msg: UnifiedMessage,
+    content: Content,
+    group?: GroupContext
+  ): Promise<void> {
     const runtime = this.runtime;
-    if (!msg.content || msg.type !== "text") return;
+    const memory = await this.storeInbound(account, msg, content, group);
 
-    // Derive consistent UUIDs from WhatsApp JIDs
-    const entityId = stringToUuid(`whatsapp-entity-${msg.from}`);
-    const roomId = stringToUuid(`whatsapp-room-${msg.from}-${runtime.agentId}`);
-    const worldId = stringToUuid(`whatsapp-world-${runtime.agentId}`);
+    await runtime.emitEvent(EventType.MESSAGE_RECEIVED, {
+      runtime,
+      message: memory,
+      source: SOURCE,
+      ...(group ? { systemPrompt: group.systemPrompt, skills: group.skills } : {}),
+      callback: this.replyCallback(account, msg),
+    });
+  }
+
+  /** Save an inbound message as a runtime memory, creating its world, room and entity on first use */
+  private async storeInbound(
+    account: ConnectedAccount,
+    msg: UnifiedMessage,
+    content: Content,
+    group?: GroupContext
+  ): Promise<Memory> {
+    const runtime = this.runtime;
+    const sender = msg.participant ?? msg.from;
+    const accountId = account.config.accountId;
+
+    // Plugins may register actions after this service starts, so gate lazily
+    this.gateRuntimeActions();
+
+    // Small images travel with the message so vision models need no separate fetch
+    const inlineMedia = await account.plugin.inlineImage(msg).catch((err) => {
+      logger.warn(`[WhatsApp] Could not inline image ${msg.media?.id}:`, (err as Error).message);
+      return undefined;
+    });
+
+    // The room is the chat and the entity is the sender, both derived from WhatsApp JIDs
+    const memory = toMemory(msg, content, { agentId: runtime.agentId, accountId, group, inlineMedia });
+    const worldId = whatsappWorldId(runtime.agentId);
 
     // Ensure the WhatsApp world exists
     await runtime.ensureWorldExists({
@@ -142,46 +1573,17 @@ export class WhatsAppConnectorService extends Service {
 
     // Ensure entity, room, and participant in one call
     await runtime.ensureConnection({
-      entityId,
-      roomId,
+      entityId: memory.entityId,
+      roomId: memory.roomId,
       worldId,
-      userName: msg.from,
-      name: msg.from,
+      userName: sender,
+      name: msg.senderName ?? sender,
       source: SOURCE,
-      type: ChannelType.DM,
+      type: group ? ChannelType.GROUP : ChannelType.DM,
       channelId: msg.from,
     });
 
-    const memory: Memory = {
-      id: stringToUuid(`whatsapp-msg-${msg.id}`),
-      agentId: runtime.agentId,
-      entityId,
-      roomId,
-      content: {
-        text: msg.content,
-        source: SOURCE,
-        channelId: msg.from,
-      },
-      createdAt: msg.timestamp ? msg.timestamp * 1000 : Date.now(),
-      metadata: { type: "message", timestamp: Date.now(), scope: "private" },
-    };
-
     await runtime.createMemory(memory, "messages");
-
-    await runtime.emitEvent(EventType.MESSAGE_RECEIVED, {
-      runtime,
-      message: memory,
-      source: SOURCE,
-      callback: async (response: Content): Promise<Memory[]> => {
-        if (response.text && this.plugin) {
-          await this.plugin.sendMessage({
-            type: "text",
-            to: msg.from,
-            content: response.text,
-          });
-        }
-        return [];
-      },
-    });
+    return memory;
   }
 }
@@ -0,0 +1,45 @@
+import { type IAgentRuntime, logger } from "@elizaos/core";
+
+/** Read a setting from runtime (character settings) with fallback to process.env */
+export function getSetting(runtime: IAgentRuntime, key: string): string | null {
+  const v = runtime.getSetting(key);
+  if (v !== null && v !== undefined) return String(v);
+  const e = process.env[key];
+  return e !== undefined ? e : null;
+}
+
+/** Read a JSON-encoded setting, logging and ignoring malformed values */
+export function getJsonSetting<T>(runtime: IAgentRuntime, key: string): T | null {
+  const raw = getSetting(runtime, key);
+  if (!raw) return null;
+  try {
+    return JSON.parse(raw) as T;
+  } catch (err) {
+    logger.error(`[WhatsApp] ${key} is not valid JSON:`, (err as Error).message);
+    return null;
+  }
+}
+
+const warnedAliases = new Set<string>();
+
+/**
+ * Read a setting that has also been known by older names. The current name wins; each old name
+ * still in use is logged once as deprecated, louder when it disagrees with the current value.
+ */
+export function getAliasedSetting(runtime: IAgentRuntime, key: string, aliases: string[]): string | null {
+  const value = getSetting(runtime, key);
+  let fallback: string | null = null;
+  for (const alias of aliases) {
+    const old = getSetting(runtime, alias);
+    if (old === null) continue;
+    fallback ??= old;
+    if (warnedAliases.has(alias)) continue;
+    warnedAliases.add(alias);
+    if (value !== null && value !== old) {
+      logger.warn(`[WhatsApp] ${alias} is deprecated and ignored because ${key} is set to a different value`);
+    } else {
+      logger.warn(`[WhatsApp] ${alias} is deprecated; rename it to ${key}`);
+    }
+  }
+  return value ?? fallback;
+}
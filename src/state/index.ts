@@ -0,0 +1,2 @@
+export { readStateSnapshot, STATE_SNAPSHOT_VERSION } from './snapshot';
+export type { AccountStateSnapshot, StateSnapshot } from './snapshot';
@@ -0,0 +1,50 @@
+import type { ArchivedMessage } from '../archive';
+import { WhatsAppError } from '../errors';
+import type { PendingPrompt } from '../interactive';
+import type { MenuSession } from '../menus';
+import type { SenderWindow } from '../middleware';
+
+export const STATE_SNAPSHOT_VERSION = 1;
+
+// One account's in-process state
+export interface AccountStateSnapshot {
+  conversations: Array<[string, number]>;               // Recipient and when it was last messaged
+  archive?: Array<[string, ArchivedMessage[], number]>; // Chat, history and last use; only for an in-memory archive
+  menuSessions?: MenuSession[];                         // Only for in-memory menu sessions
+  prompts?: PendingPrompt[];                            // Only for in-memory interactive prompts
+  lastWebhookAt?: number;
+}
+
+/**
+ * State that would otherwise be lost on restart, in a versioned JSON-safe format. Stores backed
+ * by the runtime cache (the service's archive, menu sessions, interactive prompts, OTP codes and dead letters) are not
+ * included; they already outlive the process.
+ */
+export interface StateSnapshot {
+  version: typeof STATE_SNAPSHOT_VERSION;
+  exportedAt: number;                                   // Milliseconds since epoch
+  accounts: Record<string, AccountStateSnapshot>;
+  routes: Array<[string, string]>;                      // Sticky chat to account routes
+  rateLimits: Array<[string, SenderWindow]>;            // Per-sender reply windows
+}
+
+// Accept a snapshot object or its JSON, refusing versions this build does not understand
+export function readStateSnapshot(input: unknown): StateSnapshot {
+  const snapshot = (typeof input === 'string' ? JSON.parse(input) : input) as Partial<StateSnapshot> | null;
+  if (!snapshot || typeof snapshot !== 'object') {
+    throw new WhatsAppError('INVALID_SNAPSHOT', 'State snapshot must be an object');
+  }
+  if (snapshot.version !== STATE_SNAPSHOT_VERSION) {
+    throw new WhatsAppError(
+      'UNSUPPORTED_SNAPSHOT',
+      `State snapshot version ${snapshot.version} is not supported (expected ${STATE_SNAPSHOT_VERSION})`
+    );
+  }
+  return {
+    version: snapshot.version,
+    exportedAt: snapshot.exportedAt ?? 0,
+    accounts: snapshot.accounts ?? {},
+    routes: snapshot.routes ?? [],
+    rateLimits: snapshot.rateLimits ?? [],
+  };
+}
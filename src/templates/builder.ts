@@ -0,0 +1,155 @@
+import type {
+  WhatsAppCarouselCard,
+  WhatsAppTemplate,
+  WhatsAppTemplateComponent,
+  WhatsAppTemplateParameter,
+} from '../types';
+import { WhatsAppError } from '../errors';
+
+// Positional values fill {{1}}, {{2}}, ...; a map fills named placeholders such as {{first_name}}
+export type TemplateValues = string[] | Record<string, string>;
+
+export interface TemplateInput {
+  header?: TemplateValues;
+  body?: TemplateValues;
+}
+
+// Meta only accepts lowercase letters, digits and underscores in parameter names
+const PARAMETER_NAME = /^[a-z_][a-z0-9_]*$/;
+
+export function templateParameters(values: TemplateValues): WhatsAppTemplateParameter[] {
+  if (Array.isArray(values)) {
+    return values.map((text, i) => {
+      if (!text) throw new WhatsAppError('INVALID_TEMPLATE', `Template parameter {{${i + 1}}} is empty`);
+      return { type: 'text', text };
+    });
+  }
+
+  return Object.entries(values).map(([name, text]) => {
+    if (!PARAMETER_NAME.test(name)) {
+      throw new WhatsAppError('INVALID_TEMPLATE', `Invalid template parameter name "${name}" (use lowercase letters, digits and _)`);
+    }
+    if (!text) throw new WhatsAppError('INVALID_TEMPLATE', `Template parameter {{${name}}} is empty`);
+    return { type: 'text', parameter_name: name, text };
+  });
+}
+
+// Build the template payload for sendMessage from header/body values
+export function buildTemplate(name: string, language: string, input: TemplateInput = {}): WhatsAppTemplate {
+  const components: WhatsAppTemplateComponent[] = [];
+  for (const type of ['header', 'body'] as const) {
+    const values = input[type];
+    const parameters = values ? templateParameters(values) : [];
+    if (parameters.length > 0) components.push({ type, parameters });
+  }
+
+  return {
+    name,
+    language: { code: language },
+    ...(components.length > 0 ? { components } : {}),
+  };
+}
+
+// Carousel limits from the Cloud API
+const MIN_CARDS = 2;
+const MAX_CARDS = 10;
+const MAX_CARD_BUTTONS = 2;
+
+export interface CarouselCardInput {
+  header: { kind: 'image' | 'video'; url?: string; id?: string };
+  body?: TemplateValues;
+  buttons?: Array<{ kind: 'quick_reply'; payload: string } | { kind: 'url'; text: string }>;
+}
+
+export interface CarouselInput {
+  body?: TemplateValues;                // Message bubble above the cards
+  cards: CarouselCardInput[];
+}
+
+function cardComponents(card: CarouselCardInput, index: number): WhatsAppTemplateComponent[] {
+  const { header } = card;
+  if (!header?.url && !header?.id) {
+    throw new WhatsAppError('INVALID_TEMPLATE', `Carousel card ${index} needs a header ${header?.kind ?? 'image or video'} url or id`);
+  }
+  const media = header.id ? { id: header.id } : { link: header.url };
+  const components: WhatsAppTemplateComponent[] = [
+    { type: 'header', parameters: [{ type: header.kind, [header.kind]: media }] },
+  ];
+
+  const body = card.body ? templateParameters(card.body) : [];
+  if (body.length > 0) components.push({ type: 'body', parameters: body });
+
+  (card.buttons ?? []).forEach((button, buttonIndex) => {
+    const value = button.kind === 'quick_reply' ? button.payload : button.text;
+    if (!value) {
+      const missing = button.kind === 'quick_reply' ? 'payload' : 'url suffix';
+      throw new WhatsAppError('INVALID_TEMPLATE', `Carousel card ${index} button ${buttonIndex} needs a ${missing}`);
+    }
+    components.push({
+      type: 'button',
+      sub_type: button.kind,
+      index: buttonIndex,
+      parameters: [button.kind === 'quick_reply' ? { type: 'payload', payload: value } : { type: 'text', text: value }],
+    });
+  });
+  return components;
+}
+
+// Cards must share one layout: same header format and the same buttons in the same order
+function validateCards(cards: CarouselCardInput[]): void {
+  if (cards.length < MIN_CARDS || cards.length > MAX_CARDS) {
+    throw new WhatsAppError('INVALID_TEMPLATE', `A carousel needs ${MIN_CARDS}-${MAX_CARDS} cards (got ${cards.length})`);
+  }
+
+  const layout = (card: CarouselCardInput) =>
+    [card.header?.kind, ...(card.buttons ?? []).map((b) => b.kind)].join(',');
+  const expected = layout(cards[0]);
+  cards.forEach((card, index) => {
+    if ((card.buttons?.length ?? 0) > MAX_CARD_BUTTONS) {
+      throw new WhatsAppError('INVALID_TEMPLATE', `Carousel card ${index} has ${card.buttons?.length} buttons (max ${MAX_CARD_BUTTONS})`);
+    }
+    if (layout(card) !== expected) {
+      throw new WhatsAppError(
+        'INVALID_TEMPLATE',
+        `Carousel card ${index} has header/buttons "${layout(card)}" but card 0 has "${expected}"`
+      );
+    }
+  });
+}
+
+// Build a carousel template: an optional message body followed by media cards
+export function buildCarouselTemplate(name: string, language: string, input: CarouselInput): WhatsAppTemplate {
+  validateCards(input.cards);
+
+  const cards: WhatsAppCarouselCard[] = input.cards.map((card, index) => ({
+    card_index: index,
+    components: cardComponents(card, index),
+  }));
+  const body = input.body ? templateParameters(input.body) : [];
+
+  return {
+    name,
+    language: { code: language },
+    components: [
+      ...(body.length > 0 ? [{ type: 'body', parameters: body }] : []),
+      { type: 'carousel', cards },
+    ],
+  };
+}
+
+// Authentication templates take the code twice: in the body and in the copy-code / one-tap button
+const MAX_OTP_LENGTH = 15;
+
+export function buildAuthenticationTemplate(name: string, language: string, code: string): WhatsAppTemplate {
+  if (!code || code.length > MAX_OTP_LENGTH) {
+    throw new WhatsAppError('INVALID_TEMPLATE', `Authentication codes must be 1-${MAX_OTP_LENGTH} characters`);
+  }
+  return {
+    name,
+    language: { code: language },
+    components: [
+      { type: 'body', parameters: [{ type: 'text', text: code }] },
+      { type: 'button', sub_type: 'url', index: 0, parameters: [{ type: 'text', text: code }] },
+    ],
+  };
+}
@@ -0,0 +1,86 @@
+import { systemClock, type Clock } from '../utils/clock';
+
+// Message template as returned by GET /{waba-id}/message_templates
+export interface MessageTemplateRecord {
+  id: string;
+  name: string;
+  language: string;
+  status: string;                       // APPROVED, PENDING, REJECTED, PAUSED, DISABLED
+  category: string;                     // MARKETING, UTILITY, AUTHENTICATION
+  quality_score?: { score: string };    // GREEN, YELLOW, RED, UNKNOWN
+  components?: Array<{ type: string; format?: string; text?: string }>;
+}
+
+export interface TemplateSummary {
+  name: string;
+  language: string;
+  category: string;
+  status: string;
+  quality?: string;
+  variables: string[];                  // Positional ("1", "2") or named parameters, in order of appearance
+  body?: string;
+}
+
+const DEFAULT_TTL_MS = 10 * 60 * 1000;
+const VARIABLE = /\{\{\s*([\w.]+)\s*\}\}/g;
+
+// Distinct {{n}} / {{name}} placeholders across header and body
+export function templateVariables(components: MessageTemplateRecord['components'] = []): string[] {
+  const found = new Set<string>();
+  for (const component of components) {
+    for (const match of component.text?.matchAll(VARIABLE) ?? []) {
+      found.add(match[1]);
+    }
+  }
+  return [...found];
+}
+
+export function toTemplateSummary(record: MessageTemplateRecord): TemplateSummary {
+  const body = record.components?.find((c) => c.type === 'BODY')?.text;
+  return {
+    name: record.name,
+    language: record.language,
+    category: record.category,
+    status: record.status,
+    ...(record.quality_score?.score ? { quality: record.quality_score.score } : {}),
+    variables: templateVariables(record.components),
+    ...(body ? { body } : {}),
+  };
+}
+
+// Caches the account's templates; they change rarely but are read for every agent turn
+export class TemplateCatalog {
+  private cached?: { templates: TemplateSummary[]; expiresAt: number };
+  private pending?: Promise<TemplateSummary[]>;
+
+  constructor(
+    private fetch: () => Promise<MessageTemplateRecord[]>,
+    private ttlMs = DEFAULT_TTL_MS,
+    private readonly clock: Clock = systemClock
+  ) {}
+
+  async list(): Promise<TemplateSummary[]> {
+    if (this.cached && this.cached.expiresAt > this.clock.now()) {
+      return this.cached.templates;
+    }
+    this.pending ??= this.fetch()
+      .then((records) => {
+        const templates = records.map(toTemplateSummary);
+        this.cached = { templates, expiresAt: this.clock.now() + this.ttlMs };
+        return templates;
+      })
+      .finally(() => {
+        this.pending = undefined;
+      });
+    return this.pending;
+  }
+
+  async approved(): Promise<TemplateSummary[]> {
+    return (await this.list()).filter((t) => t.status === 'APPROVED');
+  }
+
+  // Drop the cache, e.g. when a template status webhook arrives
+  invalidate(): void {
+    this.cached = undefined;
+  }
+}
@@ -0,0 +1,11 @@
+export { TemplateCatalog, templateVariables, toTemplateSummary } from './catalog';
+export type { MessageTemplateRecord, TemplateSummary } from './catalog';
+export { HELD_STATUS, TemplatePacing } from './pacing';
+export type { TemplatePace, TemplatePacingUpdate } from './pacing';
+export {
+  buildAuthenticationTemplate,
+  buildCarouselTemplate,
+  buildTemplate,
+  templateParameters,
+} from './builder';
+export type { CarouselCardInput, CarouselInput, TemplateInput, TemplateValues } from './builder';
@@ -0,0 +1,91 @@
+import type { MessageStatusUpdate, TemplateStatusUpdate } from '../events/types';
+import { systemClock, type Clock } from '../utils/clock';
+import { LruCache } from '../utils/lru';
+import type { TemplateSummary } from './catalog';
+
+// How campaigns using a template should proceed
+export type TemplatePace = 'normal' | 'slow' | 'paused';
+
+export interface TemplatePacingUpdate {
+  name: string;
+  pace: TemplatePace;
+  reason: string;                       // held_for_quality_assessment, PAUSED, DISABLED, APPROVED, expired
+}
+
+// Status Meta reports while it holds a paced marketing template's sends to gauge early feedback
+export const HELD_STATUS = 'held_for_quality_assessment';
+
+const PAUSED_TEMPLATE_STATUSES = new Set(['PAUSED', 'DISABLED']);
+const DEFAULT_HOLD_MS = 60 * 60 * 1000;
+const MAX_TRACKED_SENDS = 10_000;
+
+/**
+ * Which templates Meta is pacing or has paused. Held statuses slow a template down for `holdMs`
+ * after the last one; paused or disabled templates stay paused until they are approved again.
+ * Statuses don't name the template, so template sends are remembered by message id.
+ */
+export class TemplatePacing {
+  private sends: LruCache<string>;
+  private signals = new Map<string, { pace: TemplatePace; reason: string; until?: number }>();
+
+  constructor(
+    private readonly clock: Clock = systemClock,
+    private readonly holdMs = DEFAULT_HOLD_MS
+  ) {
+    this.sends = new LruCache({ maxChats: MAX_TRACKED_SENDS, clock });
+  }
+
+  track(messageId: string, template: string): void {
+    if (messageId) this.sends.set(messageId, template);
+  }
+
+  // Returns the change when a held status slows a template that was running normally
+  observeStatus(status: MessageStatusUpdate): TemplatePacingUpdate | undefined {
+    if (status.status !== HELD_STATUS) return undefined;
+    const name = this.sends.get(status.messageId);
+    if (!name) return undefined;
+    const current = this.pace(name);
+    if (current === 'paused') return undefined;
+    this.signals.set(name, { pace: 'slow', reason: HELD_STATUS, until: this.clock.now() + this.holdMs });
+    return current === 'slow' ? undefined : { name, pace: 'slow', reason: HELD_STATUS };
+  }
+
+  observeTemplate(update: Pick<TemplateStatusUpdate, 'name' | 'status'>): TemplatePacingUpdate | undefined {
+    const status = update.status.toUpperCase();
+    if (PAUSED_TEMPLATE_STATUSES.has(status)) return this.set(update.name, 'paused', status);
+    // A paced template is still approved, so approval only lifts a pause
+    if (status === 'APPROVED' && this.pace(update.name) === 'paused') {
+      return this.set(update.name, 'normal', status);
+    }
+    return undefined;
+  }
+
+  // Reconcile with a fresh template list, which may show pauses whose webhook was missed
+  observeCatalog(templates: Array<Pick<TemplateSummary, 'name' | 'status'>>): TemplatePacingUpdate[] {
+    return templates.flatMap((template) => this.observeTemplate(template) ?? []);
+  }
+
+  pace(name: string, now = this.clock.now()): TemplatePace {
+    const signal = this.signals.get(name);
+    if (!signal) return 'normal';
+    if (signal.until !== undefined && signal.until <= now) {
+      this.signals.delete(name);
+      return 'normal';
+    }
+    return signal.pace;
+  }
+
+  // Templates currently slowed or paused
+  list(now = this.clock.now()): TemplatePacingUpdate[] {
+    return [...this.signals.entries()].flatMap(([name, { pace, reason }]) =>
+      this.pace(name, now) === 'normal' ? [] : [{ name, pace, reason }]
+    );
+  }
+
+  private set(name: string, pace: TemplatePace, reason: string): TemplatePacingUpdate | undefined {
+    const previous = this.pace(name);
+    if (pace === 'normal') this.signals.delete(name);
+    else this.signals.set(name, { pace, reason });
+    return previous === pace ? undefined : { name, pace, reason };
+  }
+}
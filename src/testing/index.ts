@@ -0,0 +1,3 @@
+// Test helpers, published separately as "@elizaos/plugin-whatsapp/testing" so they stay out of the main bundle
+export { signWebhookBody, WebhookEventBuilder } from './webhook-events';
+export type { FixtureMessageOptions, WebhookAccount, WebhookMediaKind, WebhookStatus } from './webhook-events';
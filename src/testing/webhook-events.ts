@@ -0,0 +1,204 @@
+import { createHmac } from 'crypto';
+import type { WebhookMedia, WhatsAppWebhookEvent } from '../types';
+
+type ChangeValue = WhatsAppWebhookEvent['entry'][number]['changes'][number]['value'];
+type WebhookMessage = NonNullable<ChangeValue['messages']>[number];
+
+export type WebhookStatus = 'sent' | 'delivered' | 'read' | 'failed';
+export type WebhookMediaKind = 'image' | 'audio' | 'video' | 'document';
+
+export interface WebhookAccount {
+  phoneNumberId?: string;               // Default "PHONE_NUMBER_ID"
+  displayPhoneNumber?: string;          // Default "15550000000"
+  businessAccountId?: string;           // Default "WABA_ID"
+}
+
+export interface FixtureMessageOptions {
+  id?: string;                          // Default: a generated wamid
+  timestamp?: number;                   // Seconds since epoch; default now
+  name?: string;                        // Sender profile name, added to `contacts`
+}
+
+/**
+ * Builds Cloud API webhook notifications for tests, so handlers can be exercised without
+ * hand-writing nested JSON. Messages and statuses land in one change, as Meta batches them:
+ *
+ *   const event = WebhookEventBuilder.textMessage('15551234567', 'Hi').status('wamid.1', 'read').build();
+ */
+export class WebhookEventBuilder {
+  private messages: WebhookMessage[] = [];
+  private contacts: NonNullable<ChangeValue['contacts']> = [];
+  private statuses: NonNullable<ChangeValue['statuses']> = [];
+  private echoes: NonNullable<ChangeValue['message_echoes']> = [];
+  private templateChanges: ChangeValue[] = [];
+  private sequence = 0;
+
+  constructor(private readonly account: WebhookAccount = {}) {}
+
+  static textMessage(from: string, body: string, options?: FixtureMessageOptions): WebhookEventBuilder {
+    return new WebhookEventBuilder().textMessage(from, body, options);
+  }
+
+  static interactiveButtonReply(
+    from: string,
+    id: string,
+    title: string,
+    options?: FixtureMessageOptions
+  ): WebhookEventBuilder {
+    return new WebhookEventBuilder().interactiveButtonReply(from, id, title, options);
+  }
+
+  static status(messageId: string, status: WebhookStatus, recipient?: string): WebhookEventBuilder {
+    return new WebhookEventBuilder().status(messageId, status, recipient);
+  }
+
+  textMessage(from: string, body: string, options: FixtureMessageOptions = {}): this {
+    return this.message(from, { type: 'text', text: { body } }, options);
+  }
+
+  interactiveButtonReply(from: string, id: string, title: string, options: FixtureMessageOptions = {}): this {
+    const interactive = { type: 'button_reply', button_reply: { id, title } };
+    return this.message(from, { type: 'interactive', interactive }, options);
+  }
+
+  interactiveListReply(
+    from: string,
+    id: string,
+    title: string,
+    options: FixtureMessageOptions & { description?: string } = {}
+  ): this {
+    const { description, ...rest } = options;
+    const interactive = { type: 'list_reply', list_reply: { id, title, ...(description ? { description } : {}) } };
+    return this.message(from, { type: 'interactive', interactive }, rest);
+  }
+
+  // Tap on a template's quick-reply button; `templateMessageId` is the template message it answers
+  templateButtonReply(
+    from: string,
+    templateMessageId: string,
+    text: string,
+    payload?: string,
+    options: FixtureMessageOptions = {}
+  ): this {
+    const button = { text, ...(payload !== undefined ? { payload } : {}) };
+    const context = { from: this.account.displayPhoneNumber ?? '15550000000', id: templateMessageId };
+    return this.message(from, { type: 'button', button, context }, options);
+  }
+
+  reaction(from: string, messageId: string, emoji: string, options: FixtureMessageOptions = {}): this {
+    return this.message(from, { type: 'reaction', reaction: { message_id: messageId, emoji } }, options);
+  }
+
+  media(
+    from: string,
+    kind: WebhookMediaKind,
+    media: Partial<WebhookMedia> & { filename?: string } = {},
+    options: FixtureMessageOptions = {}
+  ): this {
+    const { filename, ...rest } = media;
+    const payload = {
+      ...rest,
+      id: rest.id ?? `media-${++this.sequence}`,
+      ...(kind === 'document' && filename ? { filename } : {}),
+    };
+    return this.message(from, { type: kind, [kind]: payload } as Partial<WebhookMessage> & { type: string }, options);
+  }
+
+  status(messageId: string, status: WebhookStatus, recipient = '15551234567', timestamp = nowSeconds()): this {
+    this.statuses.push({ id: messageId, status, timestamp: String(timestamp), recipient_id: recipient });
+    return this;
+  }
+
+  // A text the business sent to `to` from the WhatsApp Business app (smb_message_echoes change)
+  messageEcho(to: string, body: string, options: Omit<FixtureMessageOptions, 'name'> = {}): this {
+    this.echoes.push({
+      from: this.account.displayPhoneNumber ?? '15550000000',
+      to,
+      id: options.id ?? `wamid.fixture.${++this.sequence}`,
+      timestamp: String(options.timestamp ?? nowSeconds()),
+      type: 'text',
+      text: { body },
+    });
+    return this;
+  }
+
+  // A message_template_status_update change, e.g. templateStatus('123', 'order_update', 'REJECTED', 'INCORRECT_CATEGORY')
+  templateStatus(templateId: string, name: string, event: string, reason?: string, language = 'en_US'): this {
+    this.templateChanges.push({
+      ...this.metadata(),
+      event,
+      message_template_id: templateId,
+      message_template_name: name,
+      message_template_language: language,
+      ...(reason ? { reason } : {}),
+    });
+    return this;
+  }
+
+  build(): WhatsAppWebhookEvent {
+    const changes: WhatsAppWebhookEvent['entry'][number]['changes'] = [];
+    if (this.messages.length > 0 || this.statuses.length > 0) {
+      changes.push({
+        field: 'messages',
+        value: {
+          ...this.metadata(),
+          ...(this.contacts.length > 0 ? { contacts: [...this.contacts] } : {}),
+          ...(this.messages.length > 0 ? { messages: [...this.messages] } : {}),
+          ...(this.statuses.length > 0 ? { statuses: [...this.statuses] } : {}),
+        },
+      });
+    }
+    if (this.echoes.length > 0) {
+      changes.push({ field: 'smb_message_echoes', value: { ...this.metadata(), message_echoes: [...this.echoes] } });
+    }
+    for (const value of this.templateChanges) {
+      changes.push({ field: 'message_template_status_update', value });
+    }
+    return {
+      object: 'whatsapp_business_account',
+      entry: [{ id: this.account.businessAccountId ?? 'WABA_ID', changes }],
+    };
+  }
+
+  // The notification as Meta sends it: JSON body and its X-Hub-Signature-256 for `appSecret`
+  buildSigned(appSecret: string): { body: string; signature: string } {
+    const body = JSON.stringify(this.build());
+    return { body, signature: signWebhookBody(body, appSecret) };
+  }
+
+  private message(
+    from: string,
+    content: Partial<WebhookMessage> & { type: string },
+    options: FixtureMessageOptions
+  ): this {
+    this.messages.push({
+      from,
+      id: options.id ?? `wamid.fixture.${++this.sequence}`,
+      timestamp: String(options.timestamp ?? nowSeconds()),
+      ...content,
+    });
+    if (options.name && !this.contacts.some((c) => c.wa_id === from)) {
+      this.contacts.push({ wa_id: from, profile: { name: options.name } });
+    }
+    return this;
+  }
+
+  private metadata(): Pick<ChangeValue, 'messaging_product' | 'metadata'> {
+    return {
+      messaging_product: 'whatsapp',
+      metadata: {
+        display_phone_number: this.account.displayPhoneNumber ?? '15550000000',
+        phone_number_id: this.account.phoneNumberId ?? 'PHONE_NUMBER_ID',
+      },
+    };
+  }
+}
+
+// X-Hub-Signature-256 header value for a raw webhook body
+export function signWebhookBody(body: string, appSecret: string): string {
+  return `sha256=${createHmac('sha256', appSecret).update(body).digest('hex')}`;
+}
+
+function nowSeconds(): number {
+  return Math.floor(Date.now() / 1000);
+}
@@ -0,0 +1,2 @@
+export { withSpan } from './tracing';
+export type { TracerLike, SpanLike, SpanAttributes } from './tracing';
@@ -0,0 +1,48 @@
+// Structural subset of @opentelemetry/api, so it stays an optional peer dependency
+export interface SpanLike {
+  setAttribute(key: string, value: string | number | boolean): unknown;
+  setStatus(status: { code: number; message?: string }): unknown;
+  recordException(exception: Error): unknown;
+  end(): void;
+}
+
+export interface TracerLike {
+  startActiveSpan<T>(
+    name: string,
+    options: { attributes?: Record<string, string | number | boolean> },
+    fn: (span: SpanLike) => T
+  ): T;
+}
+
+export type SpanAttributes = Record<string, string | number | boolean | undefined>;
+
+// SpanStatusCode.ERROR in @opentelemetry/api
+const STATUS_ERROR = 2;
+
+function defined(attributes: SpanAttributes): Record<string, string | number | boolean> {
+  return Object.fromEntries(
+    Object.entries(attributes).filter(([, v]) => v !== undefined)
+  ) as Record<string, string | number | boolean>;
+}
+
+// Run fn inside a span when a tracer is configured; errors are recorded and rethrown
+export async function withSpan<T>(
+  tracer: TracerLike | undefined,
+  name: string,
+  attributes: SpanAttributes,
+  fn: (span?: SpanLike) => Promise<T>
+): Promise<T> {
+  if (!tracer) return fn();
+
+  return tracer.startActiveSpan(name, { attributes: defined(attributes) }, async (span) => {
+    try {
+      return await fn(span);
+    } catch (error) {
+      span.recordException(error as Error);
+      span.setStatus({ code: STATUS_ERROR, message: (error as Error).message });
+      throw error;
+    } finally {
+      span.end();
+    }
+  });
+}
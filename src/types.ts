@@ -1,26 +1,209 @@
+import type { SenderRateLimitConfig } from "./middleware/rate-limiter";
+import type { ContentPolicyConfig } from "./middleware/policy";
+import type { HumanizeConfig } from "./pacing/humanize";
+import type { CircuitBreakerConfig } from "./outbox/circuit-breaker";
+import type { SendRetryConfig } from "./outbox/retry";
+import type { QuietHoursConfig } from "./outbox/quiet-hours";
+import type { InboundConfig } from "./handlers/inbound";
+import type { ChatStateLimits } from "./utils/lru";
+import type { ChunkMarkers } from "./utils/chunk";
+import type { FixtureConfig } from "./clients/fixtures";
+import type { PaymentAmount, WhatsAppOrderDetailsParameters, WhatsAppOrderStatusParameters } from "./payments/types";
+
 // Discriminated union for config
 export type WhatsAppConfig = BaileysConfig | CloudAPIConfig;
 
-export interface BaileysConfig {
+// Per-group runtime settings, keyed by group JID (or "*" for all groups)
+export interface WhatsAppGroupConfig {
+    enabled?: boolean;          // Default true
+    allowFrom?: string[];       // Sender phone numbers/JIDs allowed to trigger the agent
+    requireMention?: boolean;   // Only respond when the agent is @mentioned
+    systemPrompt?: string;      // Extra instructions for conversations in this group
+    skills?: string[];
+    rateLimit?: SenderRateLimitConfig;  // Overrides the account limit inside this group
+    textChunkLimit?: number;    // Split agent text into messages of at most this many characters (default 4096)
+    mediaMaxMb?: number;        // Refuse to send media over this size to the group
+    contentPolicy?: ContentPolicyConfig;  // Overrides account content rules inside this group
+    replyFooter?: string;       // Overrides the account reply footer inside this group; "" sends none
+}
+
+// Settings shared by both connection methods
+export interface WhatsAppAccountOptions {
+    accountId?: string;             // Label for logs and metrics (default: "default")
+    groups?: Record<string, WhatsAppGroupConfig>;
+    skills?: string[];              // Actions the agent may use on this account (default: all)
+    rateLimit?: SenderRateLimitConfig;
+    verifyRecipients?: boolean;     // Check numbers are on WhatsApp before sending (cached)
+    contactCacheTtlMs?: number;     // Default 24h
+    logMessageContent?: boolean;    // Include message bodies in logs (default: redacted)
+    humanize?: HumanizeConfig;      // Typing simulation before agent replies (default: off)
+    circuitBreaker?: CircuitBreakerConfig | false;  // Fail fast after repeated send failures (default: on)
+    fallbackAccount?: string;       // Account that sends instead when this one is rate-limited, circuit-open or flagged
+    messagingLimit?: number;        // Conversations per 24h for broadcasts; default: the number's Meta tier, else 250
+    quietHours?: QuietHoursConfig;  // Local hours when broadcasts wait for recipients (default: none)
+    unsupportedMessageReply?: string; // Sent back to direct chats when a message type can't be read (default: none)
+    inlineImageMaxBytes?: number;   // Inbound images up to this size reach the agent as data: URIs (default: off)
+    sendRetry?: SendRetryConfig;    // Retries of network and temporary server errors before a send is dead-lettered
+    inbound?: InboundConfig;        // Concurrency and queue capacity for webhook processing
+    chatState?: ChatStateLimits;    // Bounds on per-chat history, menu sessions and prompts
+    chunkMarkers?: ChunkMarkers;    // Numbering or a continuation mark on replies split across messages (default: none)
+    contentPolicy?: ContentPolicyConfig;  // Banned phrases, link and phone number rules for outgoing text
+    replyFooter?: string;           // Disclaimer appended to the last message of each text reply
+}
+
+export interface BaileysConfig extends WhatsAppAccountOptions {
     authMethod?: 'baileys';
     authDir: string;
     sessionPath?: string;
     printQRInTerminal?: boolean;
 }
 
-export interface CloudAPIConfig {
+export interface CloudAPIConfig extends WhatsAppAccountOptions {
     authMethod?: 'cloudapi';
     accessToken: string;
     phoneNumberId: string;
     webhookVerifyToken?: string;
     businessAccountId?: string;
+    catalogId?: string;             // Product catalog for product messages; default: the first one on the business account
     apiVersion?: string;
+    appSecret?: string;             // Meta app secret; webhook notifications must then carry a valid X-Hub-Signature-256
+    fixtures?: FixtureConfig;       // Record Graph API exchanges to files, or replay them offline
 }
 
-export interface WhatsAppMessage {
-    type: "text" | "template";
-    to: string;
-    content: string | WhatsAppTemplate;
+// Tagged by `type`, so the content shape always follows from the message type
+export type WhatsAppMessage = (
+    | { type: "text"; to: string; content: string }
+    | { type: "template"; to: string; content: WhatsAppTemplate }
+    | { type: "interactive"; to: string; content: WhatsAppInteractive }
+    | { type: "media"; to: string; content: WhatsAppMedia }
+    | { type: "location"; to: string; content: WhatsAppLocation }
+    | { type: "reaction"; to: string; content: WhatsAppReaction }
+) & MessageDelivery;
+
+// How any message is delivered, whatever its type
+export interface MessageDelivery {
+    replyTo?: string;      // Message id to quote
+    previewUrl?: boolean;  // Render a preview of the first link in a text (Cloud API)
+}
+
+export type WhatsAppMessageType = WhatsAppMessage["type"];
+
+export interface WhatsAppReaction {
+    messageId: string;     // Message being reacted to
+    emoji: string;         // Empty string removes an earlier reaction
+}
+
+export interface WhatsAppLocation {
+    latitude: number;      // -90 to 90
+    longitude: number;     // -180 to 180
+    name?: string;
+    address?: string;
+}
+
+// A location someone shared; `place` is added by a Geocoder when one is configured
+export interface InboundLocation extends WhatsAppLocation {
+    url?: string;
+    place?: string;        // Human-readable place near the coordinates
+}
+
+// Response of POST /{phone-number-id}/messages
+export interface SendMessageResponse {
+    messaging_product: "whatsapp";
+    contacts?: Array<{ input: string; wa_id: string }>;
+    messages: Array<{ id: string; message_status?: string }>;
+}
+
+export interface WhatsAppMedia {
+    kind: "image" | "video" | "audio" | "document";
+    url?: string;          // Public link; Baileys needs this or data
+    id?: string;           // Uploaded Cloud API media id
+    caption?: string;      // Not shown for audio
+    fileName?: string;     // Documents only
+    mimeType?: string;
+    data?: Uint8Array;     // Raw bytes; Cloud API uploads them before sending
+}
+
+export interface WhatsAppInteractive {
+    type:
+        | "button"
+        | "list"
+        | "cta_url"
+        | "flow"
+        | "call_permission_request"
+        | "address_message"
+        | "order_details"
+        | "order_status"
+        | "product"
+        | "product_list";
+    header?: {
+        type: "text";
+        text: string;
+    };
+    body: {
+        text: string;
+    };
+    footer?: {
+        text: string;
+    };
+    action: {
+        name?: string;
+        buttons?: Array<{
+            type: "reply";
+            reply: {
+                id: string;
+                title: string;
+            };
+        }>;
+        button?: string;
+        sections?: Array<{
+            title?: string;
+            rows?: Array<{              // List messages
+                id: string;
+                title: string;
+                description?: string;
+            }>;
+            product_items?: Array<{     // Product list messages
+                product_retailer_id: string;
+            }>;
+        }>;
+        catalog_id?: string;            // Product and product list messages
+        product_retailer_id?: string;   // Product messages
+        parameters?:
+            | WhatsAppCtaUrlParameters
+            | WhatsAppFlowParameters
+            | WhatsAppAddressParameters
+            | WhatsAppOrderDetailsParameters
+            | WhatsAppOrderStatusParameters;
+    };
+}
+
+// action.parameters of a "cta_url" message: one button that opens a link
+export interface WhatsAppCtaUrlParameters {
+    display_text: string;
+    url: string;
+}
+
+// action.parameters of a "flow" message
+export interface WhatsAppFlowParameters {
+    flow_message_version: "3";
+    flow_id?: string;
+    flow_name?: string;               // Alternative to flow_id
+    flow_cta: string;
+    flow_token?: string;              // Echoed back in the flow completion webhook
+    mode?: "draft" | "published";
+    flow_action?: "navigate" | "data_exchange";
+    flow_action_payload?: {
+        screen: string;
+        data?: Record<string, unknown>;
+    };
+}
+
+// action.parameters of an "address_message" (India and Singapore numbers only)
+export interface WhatsAppAddressParameters {
+    country: string;                  // ISO 3166-1 alpha-2, e.g. "IN"
+    values?: Record<string, string>;  // Prefilled fields: name, phone_number, in_pin_code, city, ...
+    saved_addresses?: Array<{ id: string; value: Record<string, string> }>;
+    validation_errors?: Record<string, string>;  // Shown next to fields when asking again
 }
 
 export interface WhatsAppTemplate {
@@ -28,13 +211,36 @@ export interface WhatsAppTemplate {
     language: {
         code: string;
     };
-    components?: Array<{
-        type: string;
-        parameters: Array<{
-            type: string;
-            text?: string;
-        }>;
-    }>;
+    components?: WhatsAppTemplateComponent[];
+}
+
+export interface WhatsAppTemplateComponent {
+    type: string;                     // header, body, button, carousel
+    sub_type?: string;                // Buttons: quick_reply, url
+    index?: number;                   // Buttons: position in the template
+    parameters?: WhatsAppTemplateParameter[];
+    cards?: WhatsAppCarouselCard[];   // Carousel only
+}
+
+export interface WhatsAppTemplateParameter {
+    type: string;                     // text, payload, image, video
+    text?: string;
+    parameter_name?: string;          // Templates created with named parameters ({{first_name}})
+    payload?: string;
+    image?: { link?: string; id?: string };
+    video?: { link?: string; id?: string };
+}
+
+export interface WhatsAppCarouselCard {
+    card_index: number;
+    components: WhatsAppTemplateComponent[];
+}
+
+export interface WebhookMedia {
+    id: string;
+    mime_type?: string;
+    sha256?: string;
+    caption?: string;
 }
 
 export interface WhatsAppWebhookEvent {
@@ -48,11 +254,39 @@ export interface WhatsAppWebhookEvent {
                     display_phone_number: string;
                     phone_number_id: string;
                 };
+                contacts?: Array<{
+                    wa_id: string;
+                    profile?: {
+                        name?: string;
+                    };
+                }>;
                 statuses?: Array<{
                     id: string;
                     status: string;
                     timestamp: string;
                     recipient_id: string;
+                    type?: string;            // "payment" for payment status updates
+                    conversation?: {
+                        id: string;
+                        origin?: { type: string };
+                        expiration_timestamp?: string;
+                    };
+                    pricing?: {
+                        billable?: boolean;
+                        pricing_model?: string;
+                        category: string;
+                    };
+                    payment?: {
+                        reference_id: string;
+                        amount?: PaymentAmount;
+                        currency?: string;
+                        transaction?: {
+                            id?: string;
+                            type?: string;
+                            status?: string;
+                            error?: { code?: string; reason?: string };
+                        };
+                    };
                 }>;
                 messages?: Array<{
                     from: string;
@@ -61,8 +295,95 @@ export interface WhatsAppWebhookEvent {
                     text?: {
                         body: string;
                     };
+                    reaction?: {
+                        message_id: string;
+                        emoji?: string;
+                    };
+                    // Set on replies, quick-reply button taps and forwarded messages
+                    context?: {
+                        from?: string;
+                        id?: string;
+                    };
+                    image?: WebhookMedia;
+                    audio?: WebhookMedia;
+                    video?: WebhookMedia;
+                    document?: WebhookMedia & { filename?: string };
+                    location?: {
+                        latitude: number;
+                        longitude: number;
+                        name?: string;
+                        address?: string;
+                        url?: string;
+                    };
+                    // Tap on a template's quick-reply button; context.id is the template message
+                    button?: {
+                        payload?: string;
+                        text: string;
+                    };
+                    // On type "unsupported": why WhatsApp couldn't deliver the content
+                    errors?: Array<{
+                        code: number;
+                        title: string;
+                        message?: string;
+                        error_data?: { details?: string };
+                    }>;
+                    interactive?: {
+                        type: string;
+                        button_reply?: {
+                            id: string;
+                            title: string;
+                        };
+                        list_reply?: {
+                            id: string;
+                            title: string;
+                            description?: string;
+                        };
+                        // Flow and address_message submissions
+                        nfm_reply?: {
+                            name?: string;
+                            body?: string;
+                            response_json: string;
+                        };
+                    };
+                    type: string;
+                }>;
+                // smb_message_echoes: messages the business sent from the WhatsApp Business app
+                message_echoes?: Array<{
+                    from: string;
+                    to: string;
+                    id: string;
+                    timestamp: string;
                     type: string;
+                    text?: {
+                        body: string;
+                    };
+                    image?: WebhookMedia;
+                    audio?: WebhookMedia;
+                    video?: WebhookMedia;
+                    document?: WebhookMedia & { filename?: string };
+                }>;
+                calls?: Array<{
+                    id: string;
+                    from: string;
+                    to: string;
+                    event: string;
+                    timestamp: string;
+                    direction?: string;
+                    session?: {
+                        sdp_type: string;
+                        sdp: string;
+                    };
+                    status?: string;
+                    start_time?: string;
+                    end_time?: string;
+                    duration?: number;
                 }>;
+                // message_template_status_update notifications
+                event?: string;
+                message_template_id?: number | string;
+                message_template_name?: string;
+                message_template_language?: string;
+                reason?: string;
             };
             field: string;
         }>;
@@ -78,11 +399,81 @@ export interface QRCodeData {
 
 export type ConnectionStatus = 'connecting' | 'open' | 'close';
 
+export interface UnifiedMedia {
+    id: string;            // Cloud API media id, or the Baileys message id
+    mimeType?: string;
+    caption?: string;
+    fileName?: string;
+    url?: string;          // Direct URL when the transport provides one (Baileys)
+}
+
 // Unified message format (works for both)
 export interface UnifiedMessage {
     id: string;
     from: string;
     timestamp: number;
-    type: 'text' | 'image' | 'audio' | 'video' | 'document';
+    type: 'text' | 'image' | 'audio' | 'video' | 'document' | 'location' | 'interactive' | 'button' | 'reaction';
     content: string;
+    replyId?: string;      // Selected button/list row id for interactive replies
+    replyTitle?: string;   // Title of the option a typed answer ("2", "the first one") picked; replyId holds its id
+    replyTo?: string;      // Id of the message this one quotes or answers
+    participant?: string;  // Sender JID when the message was posted in a group
+    mentioned?: boolean;   // True when the message @mentions this account
+    senderName?: string;   // Profile/push name shown in WhatsApp
+    media?: UnifiedMedia;  // Set for image, audio, video and document messages
+    reaction?: {           // Set for reactions; an empty emoji removes the reaction
+        messageId: string;
+        emoji: string;
+    };
+    address?: AddressSubmission;  // Set when the user submits an address_message
+    button?: TemplateButtonReply; // Set when the user taps a template's quick-reply button
+    location?: InboundLocation;   // Set when the user shares a location
+    translation?: MessageTranslation; // Set when `content` was translated for the agent
+}
+
+// `content` in the agent's language; the message keeps what the contact actually wrote
+export interface MessageTranslation {
+    text: string;
+    from: string;                     // Contact's language
+    to: string;                       // Agent's language
+}
+
+// Quick-reply button tapped on a template message. Unlike interactive button replies these carry the
+// payload given when the template was sent, and `replyTo` on the message is the template's id.
+export interface TemplateButtonReply {
+    text: string;                     // Button label
+    payload: string;                  // Payload set on send; the label when none was set
+}
+
+// Address a user entered (or picked) in reply to an address_message
+export interface AddressSubmission {
+    savedAddressId?: string;          // Set when one of the offered saved addresses was chosen
+    name?: string;
+    phoneNumber?: string;
+    postalCode?: string;              // in_pin_code or sg_post_code
+    houseNumber?: string;
+    buildingName?: string;
+    address?: string;
+    landmarkArea?: string;
+    city?: string;
+    state?: string;
+    values: Record<string, string>;   // Every submitted field as sent by WhatsApp
+}
+
+// A message whose type the plugin can't represent (stickers, contacts, live locations, "unsupported", ...).
+// It is reported instead of reaching the agent as an empty text.
+export interface UnsupportedMessage {
+    id: string;
+    from: string;
+    timestamp: number;
+    type: string;          // Type as the transport named it, e.g. "sticker" or "unsupported"
+    participant?: string;  // Sender JID when the message was posted in a group
+    senderName?: string;
+    reason?: string;       // WhatsApp's explanation, when it gives one
+}
+
+// A message the business sent from the WhatsApp Business app on a number shared with the Cloud API
+// (coexistence); `from` is the business number and `to` the customer
+export interface MessageEcho extends UnifiedMessage {
+    to: string;
 }
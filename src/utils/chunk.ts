@@ -0,0 +1,72 @@
+// Cloud API rejects text bodies longer than this
+export const MAX_TEXT_LENGTH = 4096;
+
+const SEPARATORS = ['\n\n', '\n', ' '];
+
+function isWhitespace(text: string, index: number): boolean {
+  return /\s/.test(text.charAt(index));
+}
+
+// Where to end the chunk starting at `start`. Prefer breaking between paragraphs, then lines,
+// then words; only split mid-word as a last resort
+function breakPoint(text: string, start: number, limit: number): number {
+  const end = start + limit;
+  if (isWhitespace(text, end)) return end;
+
+  // Search only this chunk's window; lastIndexOf on the whole text would scan back through
+  // everything before it whenever the window has no separator
+  const window = text.slice(start, end);
+  const min = Math.floor(limit / 2);
+  for (const separator of SEPARATORS) {
+    const index = window.lastIndexOf(separator);
+    if (index >= min) return start + index;
+  }
+
+  // Keep surrogate pairs (emoji) intact on a hard cut
+  const code = text.charCodeAt(end - 1);
+  return code >= 0xd800 && code <= 0xdbff ? end - 1 : end;
+}
+
+// Split text into pieces that each fit in a single WhatsApp message. Works on offsets into the
+// text, so each character is copied once however long an LLM reply gets.
+export function chunkText(text: string, limit = MAX_TEXT_LENGTH): string[] {
+  const chunks: string[] = [];
+  const source = text.trim();
+  let start = 0;
+
+  while (source.length - start > limit) {
+    const cut = breakPoint(source, start, limit);
+    const chunk = source.slice(start, cut).trimEnd();
+    if (chunk) chunks.push(chunk);
+    start = cut;
+    while (start < source.length && isWhitespace(source, start)) start++;
+  }
+  if (start < source.length) chunks.push(source.slice(start));
+
+  return chunks;
+}
+
+// Marks on the parts of a reply split across messages, so readers know more is coming
+export interface ChunkMarkers {
+  numbering?: boolean;                  // Append " (1/3)" to each part
+  continuation?: string;                // Appended to every part but the last, e.g. " …"
+}
+
+function marker(markers: ChunkMarkers, index: number, total: number): string {
+  const more = index < total - 1 ? (markers.continuation ?? '') : '';
+  return `${more}${markers.numbering ? ` (${index + 1}/${total})` : ''}`;
+}
+
+// chunkText with markers on each part when there is more than one. Room for the markers is kept
+// within `limit`, so a marked part still fits in one message; parts too short for them go unmarked.
+export function chunkTextWithMarkers(text: string, limit = MAX_TEXT_LENGTH, markers: ChunkMarkers = {}): string[] {
+  let reserved = 0;
+  for (;;) {
+    const chunks = chunkText(text, limit - reserved);
+    if (chunks.length <= 1 || (!markers.numbering && !markers.continuation)) return chunks;
+    const needed = Math.max(...chunks.map((_, i) => marker(markers, i, chunks.length).length));
+    if (needed <= reserved) return chunks.map((chunk, i) => chunk + marker(markers, i, chunks.length));
+    if (needed >= limit / 2) return chunkText(text, limit);
+    reserved = needed;
+  }
+}
@@ -0,0 +1,66 @@
+// Source of time for expiry, windows and timers, so tests can control it
+
+export interface ClockTimer {
+  unref?(): void;
+}
+
+export interface Clock {
+  now(): number;                        // Milliseconds since epoch
+  setTimeout(callback: () => void, ms: number): ClockTimer;
+  clearTimeout(timer: ClockTimer | undefined): void;
+}
+
+export const systemClock: Clock = {
+  now: () => Date.now(),
+  setTimeout: (callback, ms) => setTimeout(callback, ms),
+  clearTimeout: (timer) => clearTimeout(timer as ReturnType<typeof setTimeout> | undefined),
+};
+
+interface ScheduledTimer extends ClockTimer {
+  at: number;
+  callback: () => void;
+}
+
+/**
+ * Clock that only moves when told to. Timers fire, in order, as `advance` or `set` passes their
+ * due time, so schedules such as poll windows and circuit breaker probes can be simulated.
+ */
+export class ManualClock implements Clock {
+  private timers = new Set<ScheduledTimer>();
+
+  constructor(private time = 0) {}
+
+  now(): number {
+    return this.time;
+  }
+
+  setTimeout(callback: () => void, ms: number): ClockTimer {
+    const timer: ScheduledTimer = { at: this.time + Math.max(0, ms), callback };
+    this.timers.add(timer);
+    return timer;
+  }
+
+  clearTimeout(timer: ClockTimer | undefined): void {
+    if (timer) this.timers.delete(timer as ScheduledTimer);
+  }
+
+  advance(ms: number): void {
+    this.set(this.time + ms);
+  }
+
+  set(time: number): void {
+    for (;;) {
+      const due = [...this.timers].filter((t) => t.at <= time).sort((a, b) => a.at - b.at)[0];
+      if (!due) break;
+      this.timers.delete(due);
+      this.time = Math.max(this.time, due.at);
+      due.callback();
+    }
+    this.time = Math.max(this.time, time);
+  }
+
+  // Timers not yet fired
+  get pending(): number {
+    return this.timers.size;
+  }
+}
@@ -1,10 +1,12 @@
 import type { WhatsAppConfig } from '../types';
+import { WhatsAppError } from '../errors';
 
 export function detectAuthMethod(config: any): 'baileys' | 'cloudapi' {
   // Explicit method specified
   if (config.authMethod) {
     if (config.authMethod !== 'baileys' && config.authMethod !== 'cloudapi') {
-      throw new Error(
+      throw new WhatsAppError(
+        'INVALID_CONFIG',
         `Invalid authMethod: "${config.authMethod}". Must be either "baileys" or "cloudapi".`
       );
     }
@@ -20,7 +22,8 @@ export function detectAuthMethod(config: any): 'baileys' | 'cloudapi' {
     return 'cloudapi';
   }
 
-  throw new Error(
+  throw new WhatsAppError(
+    'INVALID_CONFIG',
     'Cannot detect auth method. Provide either:\n' +
     '  - authDir (for Baileys QR code)\n' +
     '  - accessToken + phoneNumberId (for Cloud API)'
@@ -0,0 +1,166 @@
+import { WhatsAppError } from '../errors';
+
+// GitHub/Slack-style shortcodes language models tend to write instead of the emoji itself
+export const EMOJI_SHORTCODES: Record<string, string> = {
+  '+1': '👍',
+  '-1': '👎',
+  '100': '💯',
+  alarm_clock: '⏰',
+  angry: '😠',
+  airplane: '✈️',
+  arrow_down: '⬇️',
+  arrow_left: '⬅️',
+  arrow_right: '➡️',
+  arrow_up: '⬆️',
+  balloon: '🎈',
+  bell: '🔔',
+  birthday: '🎂',
+  blush: '😊',
+  books: '📚',
+  broken_heart: '💔',
+  bulb: '💡',
+  calendar: '📅',
+  camera: '📷',
+  car: '🚗',
+  check: '✔️',
+  clap: '👏',
+  coffee: '☕',
+  confused: '😕',
+  cry: '😢',
+  eyes: '👀',
+  fire: '🔥',
+  flushed: '😳',
+  gift: '🎁',
+  grin: '😁',
+  grinning: '😀',
+  heart: '❤️',
+  heart_eyes: '😍',
+  heavy_check_mark: '✔️',
+  hourglass: '⌛',
+  house: '🏠',
+  hugs: '🤗',
+  hushed: '😯',
+  info: 'ℹ️',
+  information_source: 'ℹ️',
+  innocent: '😇',
+  joy: '😂',
+  key: '🔑',
+  kiss: '😘',
+  laughing: '😆',
+  link: '🔗',
+  lock: '🔒',
+  mag: '🔍',
+  memo: '📝',
+  money_with_wings: '💸',
+  moneybag: '💰',
+  muscle: '💪',
+  no_entry: '⛔',
+  ok: '🆗',
+  ok_hand: '👌',
+  open_mouth: '😮',
+  package: '📦',
+  partying_face: '🥳',
+  pensive: '😔',
+  phone: '☎️',
+  point_down: '👇',
+  point_left: '👈',
+  point_right: '👉',
+  point_up: '☝️',
+  pray: '🙏',
+  question: '❓',
+  rage: '😡',
+  raised_hands: '🙌',
+  relaxed: '☺️',
+  relieved: '😌',
+  rocket: '🚀',
+  rofl: '🤣',
+  rose: '🌹',
+  scream: '😱',
+  see_no_evil: '🙈',
+  shopping_cart: '🛒',
+  shrug: '🤷',
+  slightly_smiling_face: '🙂',
+  smile: '😄',
+  smiley: '😃',
+  smirk: '😏',
+  sob: '😭',
+  sparkles: '✨',
+  star: '⭐',
+  star_struck: '🤩',
+  sunglasses: '😎',
+  sunny: '☀️',
+  sweat_smile: '😅',
+  tada: '🎉',
+  thinking: '🤔',
+  thumbsdown: '👎',
+  thumbsup: '👍',
+  truck: '🚚',
+  unamused: '😒',
+  upside_down_face: '🙃',
+  warning: '⚠️',
+  wave: '👋',
+  white_check_mark: '✅',
+  wink: '😉',
+  worried: '😟',
+  x: '❌',
+  yum: '😋',
+  zap: '⚡',
+};
+
+const SHORTCODE = /:([a-z0-9_+-]{1,40}):/gi;
+// Code spans and fences are left as written
+const CODE = /(```[\s\S]*?```|`[^`\n]*`)/;
+
+export function emojiForShortcode(shortcode: string): string | undefined {
+  const name = shortcode.startsWith(':') && shortcode.endsWith(':') ? shortcode.slice(1, -1) : shortcode;
+  const key = name.toLowerCase();
+  return Object.hasOwn(EMOJI_SHORTCODES, key) ? EMOJI_SHORTCODES[key] : undefined;
+}
+
+// Replace known :shortcodes: with emoji, outside code; unknown ones (and times like 10:30:00) stay
+export function expandShortcodes(text: string): string {
+  if (!text.includes(':')) return text;
+  return text
+    .split(CODE)
+    .map((part, i) => (i % 2 === 1 ? part : part.replace(SHORTCODE, (match, name: string) => emojiForShortcode(name) ?? match)))
+    .join('');
+}
+
+// Keycaps (1️⃣), flags (🇧🇷) and pictographs with skin tones, joiners or tags (👍🏽, 👩‍💻)
+const KEYCAP = /^[#*0-9]\uFE0F?\u20E3$/u;
+const FLAG = /^\p{Regional_Indicator}{2}$/u;
+const PICTOGRAPH_SEQUENCE = /^(?:\p{Extended_Pictographic}|\p{Emoji_Modifier}|\p{Emoji_Component})+$/u;
+const PICTOGRAPH = /\p{Extended_Pictographic}/u;
+
+const segmenter = typeof Intl.Segmenter === 'function' ? new Intl.Segmenter('en', { granularity: 'grapheme' }) : undefined;
+
+// User-perceived characters; without Intl.Segmenter a whole string counts as one
+export function graphemes(text: string): string[] {
+  return segmenter ? Array.from(segmenter.segment(text), (s) => s.segment) : [text];
+}
+
+// Whether `text` is exactly one emoji, judged by grapheme cluster and Unicode emoji properties
+export function isSingleEmoji(text: string): boolean {
+  const clusters = graphemes(text);
+  if (clusters.length !== 1) return false;
+  const [cluster] = clusters;
+  return (
+    KEYCAP.test(cluster) ||
+    FLAG.test(cluster) ||
+    (PICTOGRAPH_SEQUENCE.test(cluster) && PICTOGRAPH.test(cluster))
+  );
+}
+
+/**
+ * Reaction emoji as WhatsApp expects it: trimmed, with a shortcode (":thumbsup:" or "thumbsup")
+ * turned into its emoji, and exactly one emoji. An empty string removes a reaction and is kept.
+ */
+export function normalizeReaction(input: string): string {
+  const emoji = input.trim();
+  if (!emoji) return '';
+  const normalized = emojiForShortcode(emoji) ?? emoji;
+  if (!isSingleEmoji(normalized)) {
+    throw new WhatsAppError('INVALID_REACTION', `"${emoji}" is not a single emoji or known shortcode`);
+  }
+  return normalized;
+}
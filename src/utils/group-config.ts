@@ -0,0 +1,62 @@
+import type { WhatsAppGroupConfig } from '../types';
+import { MAX_TEXT_LENGTH } from './chunk';
+import { canonicalChatId, isGroupJid, normalizeWhatsAppTarget } from './normalize';
+
+const WILDCARD = '*';
+
+// Look up the config for a group by JID, falling back to the "*" entry
+export function resolveWhatsAppGroupConfig(
+  groups: Record<string, WhatsAppGroupConfig> | undefined,
+  groupJid: string
+): WhatsAppGroupConfig {
+  if (!groups) return {};
+
+  const groupId = normalizeWhatsAppTarget(groupJid)?.id;
+  for (const [key, config] of Object.entries(groups)) {
+    if (key !== WILDCARD && normalizeWhatsAppTarget(key)?.id === groupId) {
+      return config;
+    }
+  }
+  return groups[WILDCARD] ?? {};
+}
+
+// Longest text message to send to `to`: the group's textChunkLimit, never over the WhatsApp limit
+export function groupTextLimit(groups: Record<string, WhatsAppGroupConfig> | undefined, to: string): number {
+  const limit = isGroupJid(to) ? resolveWhatsAppGroupConfig(groups, to).textChunkLimit : undefined;
+  return limit && limit > 0 ? Math.min(limit, MAX_TEXT_LENGTH) : MAX_TEXT_LENGTH;
+}
+
+export type GroupGateResult =
+  | { allowed: true }
+  | { allowed: false; reason: 'disabled' | 'sender_not_allowed' | 'not_mentioned' };
+
+// Decide whether a group message should reach the agent
+export function checkGroupMessage(
+  config: WhatsAppGroupConfig,
+  sender: string,
+  mentioned: boolean
+): GroupGateResult {
+  if (config.enabled === false) {
+    return { allowed: false, reason: 'disabled' };
+  }
+
+  if (config.allowFrom && config.allowFrom.length > 0) {
+    const senderId = normalizeWhatsAppTarget(sender)?.id;
+    const senderChat = canonicalChatId(sender);
+    const allowed = config.allowFrom.some((entry) => {
+      if (entry === WILDCARD) return true;
+      // Ids that aren't phone numbers, such as @lid JIDs, must match the sender as written
+      const entryId = normalizeWhatsAppTarget(entry)?.id;
+      return entryId ? entryId === senderId : canonicalChatId(entry) === senderChat;
+    });
+    if (!allowed) {
+      return { allowed: false, reason: 'sender_not_allowed' };
+    }
+  }
+
+  if (config.requireMention && !mentioned) {
+    return { allowed: false, reason: 'not_mentioned' };
+  }
+
+  return { allowed: true };
+}
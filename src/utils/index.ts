@@ -1 +1,10 @@
 export * from "./validators";
+export * from "./normalize";
+export * from "./skills";
+export * from "./redact";
+export * from "./chunk";
+export * from "./clock";
+export * from "./lru";
+export * from "./invite-link";
+export * from "./timezone";
+export * from "./emoji";
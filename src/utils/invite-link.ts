@@ -0,0 +1,68 @@
+import type { WhatsAppGroupConfig } from '../types';
+
+const INVITE_HOST = 'chat.whatsapp.com';
+
+// Invite codes are 20-24 base62 characters
+function isInviteCode(code: string): boolean {
+  if (code.length < 20 || code.length > 24) return false;
+  for (let i = 0; i < code.length; i++) {
+    const c = code.charCodeAt(i);
+    const alphanumeric = (c >= 0x30 && c <= 0x39) || (c >= 0x41 && c <= 0x5a) || (c >= 0x61 && c <= 0x7a);
+    if (!alphanumeric) return false;
+  }
+  return true;
+}
+
+// Invite code of a chat.whatsapp.com link (with or without scheme, /invite/ or query string), or null
+export function parseGroupInviteLink(link: string): string | null {
+  let rest = link.trim();
+  const scheme = rest.indexOf('://');
+  if (scheme !== -1) rest = rest.slice(scheme + 3);
+  const slash = rest.indexOf('/');
+  if (slash === -1 || rest.slice(0, slash).toLowerCase() !== INVITE_HOST) return null;
+
+  let path = rest.slice(slash + 1);
+  const end = path.search(/[?#]/);
+  if (end !== -1) path = path.slice(0, end);
+  if (path.startsWith('invite/')) path = path.slice('invite/'.length);
+  if (path.endsWith('/')) path = path.slice(0, -1);
+  return isInviteCode(path) ? path : null;
+}
+
+export function isGroupInviteLink(link: string): boolean {
+  return parseGroupInviteLink(link) !== null;
+}
+
+export function groupInviteLink(code: string): string {
+  return `https://${INVITE_HOST}/${code}`;
+}
+
+export interface ResolvedGroupInvites {
+  resolved: Record<string, string>;     // Invite link -> group JID
+  failed: Array<{ link: string; error: unknown }>;
+}
+
+/**
+ * Rewrite group config keys given as invite links to the group JIDs they point at, in place, so
+ * operators can paste links into WHATSAPP_GROUPS. `resolve` looks up an invite code (Baileys can;
+ * the Cloud API cannot). Keys that fail to resolve are left as they are and never match a chat.
+ */
+export async function resolveGroupInviteKeys(
+  groups: Record<string, WhatsAppGroupConfig>,
+  resolve: (code: string) => Promise<string>
+): Promise<ResolvedGroupInvites> {
+  const result: ResolvedGroupInvites = { resolved: {}, failed: [] };
+  for (const link of Object.keys(groups)) {
+    const code = parseGroupInviteLink(link);
+    if (!code) continue;
+    try {
+      const jid = await resolve(code);
+      groups[jid] = { ...groups[link], ...groups[jid] };
+      delete groups[link];
+      result.resolved[link] = jid;
+    } catch (error) {
+      result.failed.push({ link, error });
+    }
+  }
+  return result;
+}
@@ -0,0 +1,123 @@
+import { systemClock, type Clock } from './clock';
+
+// Bounds for per-chat state, so it doesn't grow with every contact ever seen
+export interface ChatStateLimits {
+  maxChats?: number;                    // Least recently used chats beyond this are evicted (default 10,000)
+  maxIdleMs?: number;                   // Chats untouched this long are evicted (default: never)
+}
+
+export type EvictionReason = 'size' | 'idle';
+
+export interface LruOptions extends ChatStateLimits {
+  clock?: Clock;
+  onEvict?: (key: string, reason: EvictionReason) => void;
+}
+
+const DEFAULT_MAX_CHATS = 10_000;
+
+/**
+ * Map that evicts the least recently used key beyond `maxChats` and keys idle for `maxIdleMs`.
+ * Reads and writes both count as use. Entries stay ordered by last use, so idle ones are always
+ * at the front and expiring them never scans the whole map.
+ */
+export class LruCache<V> {
+  private entries = new Map<string, { value: V; usedAt: number }>();
+  private readonly maxChats: number;
+  private readonly clock: Clock;
+  readonly evictions: Record<EvictionReason, number> = { size: 0, idle: 0 };
+
+  constructor(private readonly options: LruOptions = {}) {
+    this.maxChats = Math.max(1, options.maxChats ?? DEFAULT_MAX_CHATS);
+    this.clock = options.clock ?? systemClock;
+  }
+
+  get(key: string): V | undefined {
+    this.expire();
+    const entry = this.entries.get(key);
+    if (!entry) return undefined;
+    this.entries.delete(key);
+    this.entries.set(key, { value: entry.value, usedAt: this.clock.now() });
+    return entry.value;
+  }
+
+  set(key: string, value: V): void {
+    this.expire();
+    this.entries.delete(key);
+    this.entries.set(key, { value, usedAt: this.clock.now() });
+    while (this.entries.size > this.maxChats) {
+      this.evict(this.entries.keys().next().value as string, 'size');
+    }
+  }
+
+  delete(key: string): boolean {
+    return this.entries.delete(key);
+  }
+
+  get size(): number {
+    this.expire();
+    return this.entries.size;
+  }
+
+  // Entries with their last use, least recently used first
+  snapshot(): Array<[string, V, number]> {
+    this.expire();
+    return [...this.entries].map(([key, { value, usedAt }]) => [key, value, usedAt]);
+  }
+
+  // Replace the contents, keeping each entry's last use so idle limits still apply
+  restore(entries: Array<[string, V, number]>): void {
+    const sorted = [...entries].sort((a, b) => a[2] - b[2]).slice(-this.maxChats);
+    this.entries = new Map(sorted.map(([key, value, usedAt]) => [key, { value, usedAt }]));
+    this.expire();
+  }
+
+  // Drop idle entries now instead of on the next access
+  expire(now = this.clock.now()): void {
+    const { maxIdleMs } = this.options;
+    if (maxIdleMs === undefined) return;
+    for (const [key, entry] of this.entries) {
+      if (now - entry.usedAt < maxIdleMs) break;
+      this.evict(key, 'idle');
+    }
+  }
+
+  private evict(key: string, reason: EvictionReason): void {
+    this.entries.delete(key);
+    this.evictions[reason]++;
+    this.options.onEvict?.(key, reason);
+  }
+}
+
+/**
+ * Applies chat-state limits to state kept outside the process, such as the runtime cache. Chats are
+ * tracked as they are used and `drop` deletes the stored state of each one evicted; state left from
+ * before a restart isn't tracked yet, so readers check `isIdle` against its last activity.
+ */
+export class StoredChatBounds {
+  private chats: LruCache<true>;
+  private readonly clock: Clock;
+
+  constructor(private readonly options: LruOptions, drop: (chatId: string) => Promise<unknown>) {
+    this.clock = options.clock ?? systemClock;
+    this.chats = new LruCache({
+      ...options,
+      onEvict: (chatId, reason) => {
+        drop(chatId).catch(() => {});
+        options.onEvict?.(chatId, reason);
+      },
+    });
+  }
+
+  touch(chatId: string): void {
+    this.chats.set(chatId, true);
+  }
+
+  forget(chatId: string): void {
+    this.chats.delete(chatId);
+  }
+
+  isIdle(lastActive: number): boolean {
+    const { maxIdleMs } = this.options;
+    return maxIdleMs !== undefined && this.clock.now() - lastActive >= maxIdleMs;
+  }
+}
@@ -0,0 +1,131 @@
+import { LruCache } from './lru';
+
+const USER_JID_SUFFIXES = ['@s.whatsapp.net', '@c.us'];
+const GROUP_JID_SUFFIX = '@g.us';
+const NEWSLETTER_JID_SUFFIX = '@newsletter';
+const WHATSAPP_PREFIX = 'whatsapp:';
+
+export type WhatsAppTargetKind = 'user' | 'group' | 'newsletter';
+
+export interface WhatsAppTarget {
+  kind: WhatsAppTargetKind;
+  id: string;   // Phone digits for users, group or channel id (without suffix) otherwise
+}
+
+// These run on every inbound and outbound message, so they scan characters by hand instead of
+// building regexes, and normalized targets are memoized
+const TARGET_CACHE_SIZE = 1024;
+const targetCache = new LruCache<WhatsAppTarget | null>({ maxChats: TARGET_CACHE_SIZE });
+
+function startsWithIgnoreCase(text: string, prefix: string): boolean {
+  return text.length >= prefix.length && text.slice(0, prefix.length).toLowerCase() === prefix;
+}
+
+function endsWithIgnoreCase(text: string, suffix: string): boolean {
+  return text.length >= suffix.length && text.slice(-suffix.length).toLowerCase() === suffix;
+}
+
+function stripPrefix(target: string): string {
+  const t = target.trim();
+  return startsWithIgnoreCase(t, WHATSAPP_PREFIX) ? t.slice(WHATSAPP_PREFIX.length) : t;
+}
+
+// Whitespace as /\s/ matches it, including the narrow and thin spaces phone apps format numbers with
+function isWhitespace(code: number): boolean {
+  return code === 0x20 || (code >= 0x09 && code <= 0x0d) || code === 0xa0 || code === 0x1680
+    || (code >= 0x2000 && code <= 0x200a) || code === 0x2028 || code === 0x2029 || code === 0x202f
+    || code === 0x205f || code === 0x3000 || code === 0xfeff;
+}
+
+// Separators people type inside phone numbers: whitespace, ( ) + - .
+function isPhoneSeparator(code: number): boolean {
+  return isWhitespace(code) || code === 0x28 || code === 0x29 || code === 0x2b || code === 0x2d || code === 0x2e;
+}
+
+export function isGroupJid(target: string): boolean {
+  return endsWithIgnoreCase(stripPrefix(target), GROUP_JID_SUFFIX);
+}
+
+// WhatsApp Channels are addressed as <id>@newsletter
+export function isNewsletterJid(target: string): boolean {
+  return endsWithIgnoreCase(stripPrefix(target), NEWSLETTER_JID_SUFFIX);
+}
+
+export function isUserJid(target: string): boolean {
+  const t = stripPrefix(target);
+  return USER_JID_SUFFIXES.some((suffix) => endsWithIgnoreCase(t, suffix));
+}
+
+// Strip formatting from a phone number; returns null unless 7-15 digits remain (E.164 range)
+export function normalizePhoneDigits(phone: string): string | null {
+  const source = stripPrefix(phone);
+  let digits = '';
+  for (let i = 0; i < source.length; i++) {
+    const code = source.charCodeAt(i);
+    if (code >= 0x30 && code <= 0x39) {
+      if (digits.length === 15) return null;
+      digits += source[i];
+    } else if (!isPhoneSeparator(code)) {
+      return null;
+    }
+  }
+  return digits.length >= 7 ? digits : null;
+}
+
+export function normalizeE164(phone: string): string | null {
+  const digits = normalizePhoneDigits(phone);
+  return digits ? `+${digits}` : null;
+}
+
+function parseTarget(target: string): WhatsAppTarget | null {
+  const t = stripPrefix(target);
+
+  if (endsWithIgnoreCase(t, GROUP_JID_SUFFIX)) {
+    const id = t.slice(0, -GROUP_JID_SUFFIX.length);
+    return id ? { kind: 'group', id } : null;
+  }
+
+  if (endsWithIgnoreCase(t, NEWSLETTER_JID_SUFFIX)) {
+    const id = t.slice(0, -NEWSLETTER_JID_SUFFIX.length);
+    return id ? { kind: 'newsletter', id } : null;
+  }
+
+  const at = t.indexOf('@');
+  if (at !== -1 && !isUserJid(t)) return null;
+  const user = at === -1 ? t : t.slice(0, at);
+  const colon = user.indexOf(':');   // Drop device suffix (123:4@s.whatsapp.net)
+  const digits = normalizePhoneDigits(colon === -1 ? user : user.slice(0, colon));
+  return digits ? { kind: 'user', id: digits } : null;
+}
+
+// Classify a phone number, JID, or whatsapp:-prefixed id as a user or group target
+export function normalizeWhatsAppTarget(target: string): WhatsAppTarget | null {
+  let normalized = targetCache.get(target);
+  if (normalized === undefined) {
+    normalized = parseTarget(target);
+    targetCache.set(target, normalized);
+  }
+  // Hand out a copy so callers can't corrupt the cached entry
+  return normalized && { ...normalized };
+}
+
+// Stable key for a chat: phone digits for users, the JID for groups and channels
+export function canonicalChatId(target: string): string {
+  const normalized = normalizeWhatsAppTarget(target);
+  if (!normalized) return stripPrefix(target);
+  return normalized.kind === 'user' ? normalized.id : toJid(target);
+}
+
+// Baileys addresses chats by JID
+export function toJid(target: string): string {
+  const normalized = normalizeWhatsAppTarget(target);
+  if (!normalized) return stripPrefix(target);
+  switch (normalized.kind) {
+    case 'group':
+      return `${normalized.id}${GROUP_JID_SUFFIX}`;
+    case 'newsletter':
+      return `${normalized.id}${NEWSLETTER_JID_SUFFIX}`;
+    default:
+      return `${normalized.id}@s.whatsapp.net`;
+  }
+}
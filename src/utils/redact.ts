@@ -0,0 +1,70 @@
+// Masks phone numbers, credentials, and (unless enabled) message bodies before logging
+
+export interface RedactOptions {
+  logMessageContent?: boolean;
+}
+
+// ITU calling codes: 1 and 7 are single digit, these prefixes are two digits, the rest three
+const TWO_DIGIT_CODES = new Set([
+  '20', '27', '30', '31', '32', '33', '34', '36', '39', '40', '41', '43', '44', '45', '46', '47',
+  '48', '49', '51', '52', '53', '54', '55', '56', '57', '58', '60', '61', '62', '63', '64', '65',
+  '66', '81', '82', '84', '86', '90', '91', '92', '93', '94', '95', '98',
+]);
+
+const PHONE_FIELDS = new Set(['from', 'to', 'wa_id', 'recipient_id', 'participant', 'remoteJid', 'input']);
+const CONTENT_FIELDS = new Set(['body', 'text', 'caption', 'content', 'conversation']);
+const SECRET_FIELDS = new Set(['access_token', 'accessToken', 'authorization', 'Authorization', 'token']);
+
+const PHONE_PATTERN = /\+?\d{7,15}/g;
+const TOKEN_PATTERN = /\b(?:EAA[A-Za-z0-9]{20,}|Bearer\s+[A-Za-z0-9._-]{10,})/g;
+
+function countryCodeLength(digits: string): number {
+  if (digits.startsWith('1') || digits.startsWith('7')) return 1;
+  return TWO_DIGIT_CODES.has(digits.slice(0, 2)) ? 2 : 3;
+}
+
+// "+14155550123" -> "+1*******23"; JID suffixes are kept
+export function maskPhone(value: string): string {
+  const [user, domain] = value.split('@');
+  const digits = user.replace(/\D/g, '');
+  if (digits.length < 7) return value;
+
+  const keep = countryCodeLength(digits);
+  const masked = `${user.startsWith('+') ? '+' : ''}${digits.slice(0, keep)}${'*'.repeat(
+    digits.length - keep - 2
+  )}${digits.slice(-2)}`;
+  return domain ? `${masked}@${domain}` : masked;
+}
+
+export function redactText(text: string): string {
+  return text
+    .replace(TOKEN_PATTERN, '[redacted token]')
+    .replace(PHONE_PATTERN, (match) => maskPhone(match));
+}
+
+// Deep-copies a value with PII masked; safe to hand to console or logger
+export function redact(value: unknown, options: RedactOptions = {}, key?: string): unknown {
+  if (typeof value === 'string') {
+    if (key && SECRET_FIELDS.has(key)) return '[redacted]';
+    if (key && CONTENT_FIELDS.has(key) && !options.logMessageContent) {
+      return `[${value.length} chars]`;
+    }
+    if (key && PHONE_FIELDS.has(key)) return maskPhone(value);
+    // Other structured fields (ids, timestamps) keep their digits; free text is fully masked
+    return key ? value.replace(TOKEN_PATTERN, '[redacted token]') : redactText(value);
+  }
+
+  if (Array.isArray(value)) {
+    return value.map((item) => redact(item, options, key));
+  }
+
+  if (value && typeof value === 'object') {
+    const result: Record<string, unknown> = {};
+    for (const [k, v] of Object.entries(value)) {
+      result[k] = redact(v, options, k);
+    }
+    return result;
+  }
+
+  return value;
+}
@@ -0,0 +1,31 @@
+import type { Action } from '@elizaos/core';
+
+interface SkillScope {
+  skills?: string[];
+}
+
+function permits(scope: SkillScope | undefined, skill: string): boolean {
+  if (!scope?.skills) return true;   // No list means every skill is allowed
+  const name = skill.toUpperCase();
+  return scope.skills.some((s) => s === '*' || s.toUpperCase() === name);
+}
+
+// A skill must be allowed by the account and, for group chats, by the group as well
+export function isSkillEnabled(
+  account: SkillScope | undefined,
+  group: SkillScope | undefined,
+  skill: string
+): boolean {
+  return permits(account, skill) && permits(group, skill);
+}
+
+// Actions available in a conversation; an action matches its name or any simile
+export function filterActions<T extends Pick<Action, 'name' | 'similes'>>(
+  actions: T[],
+  account: SkillScope | undefined,
+  group: SkillScope | undefined
+): T[] {
+  return actions.filter((action) =>
+    [action.name, ...(action.similes ?? [])].some((name) => isSkillEnabled(account, group, name))
+  );
+}
@@ -0,0 +1,85 @@
+// Time zone of the most populous region behind each country calling code. Numbers only say which
+// country they belong to, so countries spanning several zones get their main one.
+export const CALLING_CODE_TIME_ZONES: Record<string, string> = {
+  '1': 'America/New_York',
+  '7': 'Europe/Moscow',
+  '20': 'Africa/Cairo',
+  '27': 'Africa/Johannesburg',
+  '30': 'Europe/Athens',
+  '31': 'Europe/Amsterdam',
+  '32': 'Europe/Brussels',
+  '33': 'Europe/Paris',
+  '34': 'Europe/Madrid',
+  '36': 'Europe/Budapest',
+  '39': 'Europe/Rome',
+  '40': 'Europe/Bucharest',
+  '41': 'Europe/Zurich',
+  '43': 'Europe/Vienna',
+  '44': 'Europe/London',
+  '45': 'Europe/Copenhagen',
+  '46': 'Europe/Stockholm',
+  '47': 'Europe/Oslo',
+  '48': 'Europe/Warsaw',
+  '49': 'Europe/Berlin',
+  '51': 'America/Lima',
+  '52': 'America/Mexico_City',
+  '54': 'America/Argentina/Buenos_Aires',
+  '55': 'America/Sao_Paulo',
+  '56': 'America/Santiago',
+  '57': 'America/Bogota',
+  '58': 'America/Caracas',
+  '60': 'Asia/Kuala_Lumpur',
+  '61': 'Australia/Sydney',
+  '62': 'Asia/Jakarta',
+  '63': 'Asia/Manila',
+  '64': 'Pacific/Auckland',
+  '65': 'Asia/Singapore',
+  '66': 'Asia/Bangkok',
+  '81': 'Asia/Tokyo',
+  '82': 'Asia/Seoul',
+  '84': 'Asia/Ho_Chi_Minh',
+  '86': 'Asia/Shanghai',
+  '90': 'Europe/Istanbul',
+  '91': 'Asia/Kolkata',
+  '92': 'Asia/Karachi',
+  '212': 'Africa/Casablanca',
+  '233': 'Africa/Accra',
+  '234': 'Africa/Lagos',
+  '254': 'Africa/Nairobi',
+  '351': 'Europe/Lisbon',
+  '353': 'Europe/Dublin',
+  '380': 'Europe/Kyiv',
+  '593': 'America/Guayaquil',
+  '880': 'Asia/Dhaka',
+  '966': 'Asia/Riyadh',
+  '971': 'Asia/Dubai',
+  '972': 'Asia/Jerusalem',
+};
+
+// Time zone for a phone number or wa_id, by its longest known calling code prefix
+export function inferTimeZone(phone: string): string | undefined {
+  let digits = '';
+  for (const char of phone) {
+    if (char === '@') break;
+    if (char >= '0' && char <= '9') digits += char;
+  }
+  for (let length = 3; length > 0; length--) {
+    const zone = CALLING_CODE_TIME_ZONES[digits.slice(0, length)];
+    if (zone && digits.length > length) return zone;
+  }
+  return undefined;
+}
+
+const formatters = new Map<string, Intl.DateTimeFormat>();
+
+// Minutes past local midnight at `time` in `timeZone`
+export function localMinutes(time: number, timeZone: string): number {
+  let format = formatters.get(timeZone);
+  if (!format) {
+    format = new Intl.DateTimeFormat('en-US', { timeZone, hour: 'numeric', minute: 'numeric', hourCycle: 'h23' });
+    formatters.set(timeZone, format);
+  }
+  const parts = format.formatToParts(new Date(time));
+  const part = (type: string) => Number(parts.find((p) => p.type === type)?.value ?? 0);
+  return part('hour') * 60 + part('minute');
+}
@@ -1,39 +1,45 @@
 import type { WhatsAppMessage, WhatsAppTemplate, WhatsAppConfig } from "../types";
+import { WhatsAppError } from "../errors";
+import { normalizeReaction } from "./emoji";
 
 export function validateConfig(config: WhatsAppConfig): void {
     if (!config.accessToken) {
-        throw new Error("WhatsApp access token is required");
+        throw new WhatsAppError("INVALID_CONFIG", "WhatsApp access token is required");
     }
     if (!config.phoneNumberId) {
-        throw new Error("WhatsApp phone number ID is required");
+        throw new WhatsAppError("INVALID_CONFIG", "WhatsApp phone number ID is required");
     }
 }
 
 export function validateMessage(message: WhatsAppMessage): void {
     if (!message.to) {
-        throw new Error("Recipient phone number is required");
+        throw new WhatsAppError("INVALID_MESSAGE", "Recipient phone number is required");
     }
 
     if (!message.type) {
-        throw new Error("Message type is required");
+        throw new WhatsAppError("INVALID_MESSAGE", "Message type is required");
     }
 
     if (!message.content) {
-        throw new Error("Message content is required");
+        throw new WhatsAppError("INVALID_MESSAGE", "Message content is required");
     }
 
     if (message.type === "template") {
-        validateTemplate(message.content as WhatsAppTemplate);
+        validateTemplate(message.content);
+    }
+
+    if (message.type === "reaction") {
+        normalizeReaction(message.content.emoji);
     }
 }
 
 export function validateTemplate(template: WhatsAppTemplate): void {
     if (!template.name) {
-        throw new Error("Template name is required");
+        throw new WhatsAppError("INVALID_TEMPLATE", "Template name is required");
     }
 
     if (!template.language || !template.language.code) {
-        throw new Error("Template language code is required");
+        throw new WhatsAppError("INVALID_TEMPLATE", "Template language code is required");
     }
 }
 
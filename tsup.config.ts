@@ -1,7 +1,7 @@
 import { defineConfig } from "tsup";
 
 export default defineConfig({
-    entry: ["src/index.ts"],
+    entry: ["src/index.ts", "src/testing/index.ts", "src/browser/index.ts", "src/cli/bin.ts"],
     outDir: "dist",
     sourcemap: true,
     clean: true,